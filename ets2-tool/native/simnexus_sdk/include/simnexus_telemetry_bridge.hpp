@@ -1,5 +1,6 @@
 #pragma once
 
+#include <cstddef>
 #include <cstdint>
 
 namespace simnexus {
@@ -8,7 +9,9 @@ inline constexpr wchar_t kSharedMemoryName[] = L"Local\\SimNexusTelemetryV3";
 inline constexpr wchar_t kLegacySharedMemoryName[] = L"Local\\SimNexusTelemetry";
 inline constexpr char kBridgeMagic[8] = {'S', 'N', 'X', 'T', 'L', 'M', '0', '3'};
 inline constexpr std::uint32_t kBridgeProtocolVersion = 3;
-inline constexpr std::uint32_t kPayloadRevision = 3;
+inline constexpr std::uint32_t kPayloadRevision = 4;
+// Oldest payload revision the app still accepts; revision 4 added world placement.
+inline constexpr std::uint32_t kMinPayloadRevision = 3;
 inline constexpr std::uint32_t kHeartbeatIntervalMs = 250;
 inline constexpr std::uint32_t kHeartbeatStaleAfterMs = 2'000;
 
@@ -76,12 +79,23 @@ struct TelemetryData {
   char destination_company_id[64];
   char job_market[32];
 
-  std::uint8_t reserved[584];
+  // Revision 4: truck world placement, carved out of the reserved block.
+  double world_x;
+  double world_y;
+  double world_z;
+  float heading;
+  float pitch;
+  float roll;
+  std::uint8_t placement_valid;
+  std::uint8_t reserved_placement[3];
+
+  std::uint8_t reserved[544];
 };
 
 static_assert(sizeof(TelemetryBridgeHeader) == 24, "Unexpected bridge header size");
 static_assert(alignof(TelemetryBridgeHeader) == 8, "Unexpected bridge header alignment");
 static_assert(sizeof(TelemetryData) == 2048, "Unexpected telemetry payload size");
 static_assert(alignof(TelemetryData) == 8, "Unexpected telemetry payload alignment");
+static_assert(offsetof(TelemetryData, world_x) == 1464, "Unexpected world placement offset");
 
 } // namespace simnexus
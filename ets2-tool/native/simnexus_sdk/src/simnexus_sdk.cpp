@@ -31,7 +31,7 @@
 #endif
 
 #ifndef SIMNEXUS_DLL_VERSION
-#define SIMNEXUS_DLL_VERSION "3.0.0"
+#define SIMNEXUS_DLL_VERSION "3.1.0"
 #endif
 
 namespace {
@@ -299,11 +299,12 @@ bool initialize_shared_memory(
   log_message(message);
   std::snprintf(
       message, sizeof(message),
-      "[SimNexus DLL] Layout: align=%zu heartbeat=%zu telemetry_timestamp=%zu frame_id=%zu job_active=%zu job_event=%zu job_id=%zu source_city=%zu",
+      "[SimNexus DLL] Layout: align=%zu heartbeat=%zu telemetry_timestamp=%zu frame_id=%zu job_active=%zu job_event=%zu job_id=%zu source_city=%zu world_x=%zu",
       alignof(TelemetryData), offsetof(TelemetryData, heartbeat_timestamp_ms),
       offsetof(TelemetryData, telemetry_timestamp_ms), offsetof(TelemetryData, frame_id),
       offsetof(TelemetryData, job_active), offsetof(TelemetryData, job_event),
-      offsetof(TelemetryData, job_id), offsetof(TelemetryData, source_city));
+      offsetof(TelemetryData, job_id), offsetof(TelemetryData, source_city),
+      offsetof(TelemetryData, world_x));
   log_message(message);
   return true;
 }
@@ -370,6 +371,22 @@ SCSAPI_VOID telemetry_store_speed(
   g_state.telemetry_callback_seen = 1;
 }
 
+SCSAPI_VOID telemetry_store_placement(
+    const scs_string_t, const scs_u32_t, const scs_value_t* const value,
+    const scs_context_t) {
+  if (value == nullptr) return;
+  StateLock lock;
+  const scs_value_dplacement_t& placement = value->value_dplacement;
+  g_state.world_x = placement.position.x;
+  g_state.world_y = placement.position.y;
+  g_state.world_z = placement.position.z;
+  g_state.heading = placement.orientation.heading;
+  g_state.pitch = placement.orientation.pitch;
+  g_state.roll = placement.orientation.roll;
+  g_state.placement_valid = 1;
+  g_state.telemetry_callback_seen = 1;
+}
+
 SCSAPI_VOID telemetry_configuration(
     const scs_event_t, const void* const event_info, const scs_context_t) {
   const auto* config = static_cast<const scs_telemetry_configuration_t*>(event_info);
@@ -582,6 +599,8 @@ void register_telemetry_channels(const scs_telemetry_init_params_v100_t* params)
                    telemetry_store_float, &g_state.fuel_liters);
   register_channel(params, SCS_TELEMETRY_TRUCK_CHANNEL_odometer, SCS_VALUE_TYPE_float,
                    telemetry_store_float_to_double, &g_state.odometer_km);
+  register_channel(params, SCS_TELEMETRY_TRUCK_CHANNEL_world_placement, SCS_VALUE_TYPE_dplacement,
+                   telemetry_store_placement, nullptr);
   register_channel(params, SCS_TELEMETRY_CHANNEL_local_scale, SCS_VALUE_TYPE_float,
                    telemetry_store_float, &g_state.map_scale);
   register_channel(params, SCS_TELEMETRY_CHANNEL_game_time, SCS_VALUE_TYPE_u32,
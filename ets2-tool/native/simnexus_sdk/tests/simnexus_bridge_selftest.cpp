@@ -48,7 +48,8 @@ bool verify(const TelemetryBridgeHeader* header, const TelemetryData* payload) {
   MemoryBarrier();
   const auto after = InterlockedCompareExchange64(
       reinterpret_cast<volatile LONG64*>(const_cast<std::int64_t*>(&header->sequence)), 0, 0);
-  return before == after && (after & 1) == 0 && copy.payload_revision == 3 &&
+  return before == after && (after & 1) == 0 && copy.payload_revision == simnexus::kPayloadRevision &&
+         copy.placement_valid == 1 && copy.world_x == -31'250.5 &&
          copy.plugin_initialized == 1 && copy.sdk_connected == 1 && copy.job_active == 1 &&
          std::strcmp(copy.source_city, "L\xC3\xBC" "beck") == 0 &&
          std::strcmp(copy.destination_city, "Hamburg") == 0 &&
@@ -99,8 +100,13 @@ int wmain(int argc, wchar_t** argv) {
   state.job_delivery_time_min = 600;
   state.game_time_min = 120;
   state.job_planned_distance_km = 815.0;
+  state.world_x = -31'250.5;
+  state.world_y = 42.0;
+  state.world_z = -8'120.25;
+  state.heading = 0.25f;
+  state.placement_valid = 1;
   store_text(state.build_id, "selftest-v3");
-  store_text(state.dll_version, "3.0.0");
+  store_text(state.dll_version, "3.1.0");
   store_text(state.game_id, "eut2");
   store_text(state.dll_path, "<synthetic-selftest>");
   store_text(state.job_id, "job-selftest-v3");
@@ -3,7 +3,9 @@ use tauri::{AppHandle, State, command};
 
 use crate::features::ets2save::errors::AppError;
 use crate::features::ets2save::link_service;
-use crate::features::ets2save::models::{EtsJobLink, EtsJobWriteResult, EtsSaveSlot};
+use crate::features::ets2save::models::{
+    EtsJobLink, EtsJobWriteResult, EtsSaveSlot, EtsSaveTarget,
+};
 use crate::features::ets2save::snapshot::{self, SaveSnapshotInput};
 use crate::shared::current_profile::snapshot_save_context;
 use crate::shared::ets2data;
@@ -26,6 +28,7 @@ pub async fn ets_get_last_quicksave(
 pub async fn ets_prepare_job_link(
     vtc_job_id: String,
     profile_id: String,
+    save_target: Option<EtsSaveTarget>,
     app: AppHandle,
     profile_state: State<'_, AppProfileState>,
     db: State<'_, EtsDbState>,
@@ -35,6 +38,7 @@ pub async fn ets_prepare_job_link(
         &db.pool,
         &vtc_job_id,
         &profile_id,
+        &save_target.unwrap_or_default(),
         profile_state.inner(),
     )
     .await
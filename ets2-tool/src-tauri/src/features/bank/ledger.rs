@@ -0,0 +1,129 @@
+use chrono::Utc;
+use rusqlite::{Connection, params};
+use serde::Serialize;
+
+use crate::shared::sqlite_schema::create_indexes;
+
+pub const KIND_TRIP_RESULT: &str = "trip_result";
+pub const KIND_LOAN_DISBURSEMENT: &str = "loan_disbursement";
+pub const KIND_LOAN_INSTALLMENT: &str = "loan_installment";
+pub const KIND_OVERDUE_PAYMENT: &str = "overdue_payment";
+pub const KIND_LOAN_REPAYMENT: &str = "loan_repayment";
+pub const KIND_WORKSHOP: &str = "workshop";
+pub const KIND_EMPLOYEE_JOB: &str = "employee_job";
+pub const KIND_PAYROLL: &str = "payroll";
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerEntry {
+    pub id: i64,
+    pub bank_day: i64,
+    pub kind: String,
+    pub amount: i64,
+    pub balance_after: i64,
+    pub loan_id: Option<i64>,
+    pub description: String,
+    pub created_at_utc: String,
+}
+
+pub fn ensure_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS bank_ledger (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            bank_day INTEGER NOT NULL,
+            kind TEXT NOT NULL,
+            amount INTEGER NOT NULL,
+            balance_after INTEGER NOT NULL,
+            loan_id INTEGER,
+            description TEXT NOT NULL,
+            created_at_utc TEXT NOT NULL
+        );
+        "#,
+    )
+    .map_err(|e| e.to_string())?;
+    create_indexes(
+        conn,
+        &[
+            "CREATE INDEX IF NOT EXISTS idx_bank_ledger_loan ON bank_ledger(loan_id)",
+            "CREATE INDEX IF NOT EXISTS idx_bank_ledger_kind ON bank_ledger(kind, bank_day)",
+        ],
+    )
+}
+
+/// Moves `amount` in or out of the company account and books it.
+///
+/// This is the only place that changes `bank_state.cash_balance`. Returns
+/// the balance after the movement.
+pub fn post(
+    conn: &Connection,
+    kind: &str,
+    amount: i64,
+    loan_id: Option<i64>,
+    description: &str,
+) -> Result<i64, String> {
+    conn.execute(
+        "UPDATE bank_state SET cash_balance = cash_balance + ?1 WHERE id = 1",
+        params![amount],
+    )
+    .map_err(|e| e.to_string())?;
+    let (balance_after, bank_day) = conn
+        .query_row(
+            "SELECT cash_balance, bank_day FROM bank_state WHERE id = 1",
+            [],
+            |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)),
+        )
+        .map_err(|e| e.to_string())?;
+    conn.execute(
+        r#"
+        INSERT INTO bank_ledger (
+            bank_day, kind, amount, balance_after, loan_id, description, created_at_utc
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+        "#,
+        params![
+            bank_day,
+            kind,
+            amount,
+            balance_after,
+            loan_id,
+            description,
+            Utc::now().to_rfc3339()
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(balance_after)
+}
+
+pub fn list_entries(
+    conn: &Connection,
+    loan_id: Option<i64>,
+    limit: usize,
+) -> Result<Vec<LedgerEntry>, String> {
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT id, bank_day, kind, amount, balance_after, loan_id, description, created_at_utc
+            FROM bank_ledger
+            WHERE ?1 IS NULL OR loan_id = ?1
+            ORDER BY id DESC
+            LIMIT ?2
+            "#,
+        )
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params![loan_id, limit as i64], |row| {
+            Ok(LedgerEntry {
+                id: row.get(0)?,
+                bank_day: row.get(1)?,
+                kind: row.get(2)?,
+                amount: row.get(3)?,
+                balance_after: row.get(4)?,
+                loan_id: row.get(5)?,
+                description: row.get(6)?,
+                created_at_utc: row.get(7)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,871 @@
+//! Loan products, amortization and installment processing.
+//!
+//! Loans are annuities: every installment has the same size and splits into
+//! interest and principal. Interest accrues daily on the outstanding
+//! principal; an installment pays whatever interest has accrued since the
+//! last one and the rest goes to principal. An installment the account cannot
+//! cover moves into `overdue_amount` with a late fee and costs reputation
+//! until it is paid off from later income.
+
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension, params};
+use serde::Serialize;
+
+use crate::features::bank::ledger;
+use crate::features::{events, reputation};
+use crate::shared::sqlite_schema::create_indexes;
+
+pub const STATUS_ACTIVE: &str = "active";
+pub const STATUS_REPAID: &str = "repaid";
+
+const INSTALLMENT_PENDING: &str = "pending";
+const INSTALLMENT_PAID: &str = "paid";
+const INSTALLMENT_OVERDUE: &str = "overdue";
+const INSTALLMENT_PAID_LATE: &str = "paid_late";
+const INSTALLMENT_CANCELLED: &str = "cancelled";
+
+const DAYS_PER_YEAR: f64 = 365.0;
+const MAX_ACTIVE_LOANS: i64 = 3;
+const LATE_FEE_RATE: f64 = 0.05;
+const MIN_LATE_FEE: i64 = 100;
+const OVERDUE_REPUTATION_PENALTY: i64 = 3;
+/// Rate discount per company level above the product minimum.
+const RATE_DISCOUNT_PER_LEVEL: f64 = 0.0015;
+/// Extra borrowing limit per company level above the product minimum.
+const LIMIT_BONUS_PER_LEVEL: f64 = 0.10;
+/// Installment plan of the starting debt from before loans existed.
+const LEGACY_PRODUCT_ID: &str = "legacy";
+const LEGACY_INTERVAL_DAYS: i64 = 7;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LoanProduct {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub min_level: i64,
+    pub min_principal: i64,
+    pub max_principal: i64,
+    pub installment_count: i64,
+    pub interval_days: i64,
+    pub annual_rate: f64,
+}
+
+pub const LOAN_PRODUCTS: [LoanProduct; 3] = [
+    LoanProduct {
+        id: "starter",
+        name: "Starter credit",
+        min_level: 1,
+        min_principal: 10_000,
+        max_principal: 60_000,
+        installment_count: 8,
+        interval_days: 7,
+        annual_rate: 0.089,
+    },
+    LoanProduct {
+        id: "business",
+        name: "Business loan",
+        min_level: 3,
+        min_principal: 50_000,
+        max_principal: 200_000,
+        installment_count: 12,
+        interval_days: 7,
+        annual_rate: 0.069,
+    },
+    LoanProduct {
+        id: "fleet",
+        name: "Fleet expansion loan",
+        min_level: 6,
+        min_principal: 150_000,
+        max_principal: 600_000,
+        installment_count: 24,
+        interval_days: 7,
+        annual_rate: 0.054,
+    },
+];
+
+/// A product with rate and limit adjusted to the company level.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LoanOffer {
+    pub product: LoanProduct,
+    pub available: bool,
+    pub annual_rate: f64,
+    pub max_principal: i64,
+    /// Installment for the maximum principal, as a guide.
+    pub example_installment: i64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Loan {
+    pub id: i64,
+    pub product_id: String,
+    pub principal: i64,
+    pub annual_rate: f64,
+    pub installment_count: i64,
+    pub interval_days: i64,
+    pub installment: i64,
+    pub outstanding_principal: i64,
+    pub accrued_interest: f64,
+    pub overdue_amount: i64,
+    pub paid_installments: i64,
+    pub missed_installments: i64,
+    pub next_due_bank_day: Option<i64>,
+    pub status: String,
+    pub opened_bank_day: i64,
+    pub opened_at_utc: String,
+    pub closed_at_utc: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LoanInstallment {
+    pub seq: i64,
+    pub due_bank_day: i64,
+    pub installment: i64,
+    pub principal_part: i64,
+    pub interest_part: i64,
+    pub balance_after: i64,
+    pub status: String,
+    pub settled_bank_day: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlannedInstallment {
+    pub seq: i64,
+    pub installment: i64,
+    pub principal_part: i64,
+    pub interest_part: i64,
+    pub balance_after: i64,
+}
+
+pub fn ensure_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS bank_loans (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            product_id TEXT NOT NULL,
+            principal INTEGER NOT NULL,
+            annual_rate REAL NOT NULL,
+            installment_count INTEGER NOT NULL,
+            interval_days INTEGER NOT NULL,
+            installment INTEGER NOT NULL,
+            outstanding_principal INTEGER NOT NULL,
+            accrued_interest REAL NOT NULL DEFAULT 0,
+            overdue_amount INTEGER NOT NULL DEFAULT 0,
+            paid_installments INTEGER NOT NULL DEFAULT 0,
+            missed_installments INTEGER NOT NULL DEFAULT 0,
+            status TEXT NOT NULL DEFAULT 'active',
+            opened_bank_day INTEGER NOT NULL,
+            opened_at_utc TEXT NOT NULL,
+            closed_at_utc TEXT
+        );
+
+        CREATE TABLE IF NOT EXISTS bank_loan_installments (
+            loan_id INTEGER NOT NULL,
+            seq INTEGER NOT NULL,
+            due_bank_day INTEGER NOT NULL,
+            installment INTEGER NOT NULL,
+            principal_part INTEGER NOT NULL,
+            interest_part INTEGER NOT NULL,
+            balance_after INTEGER NOT NULL,
+            status TEXT NOT NULL DEFAULT 'pending',
+            settled_bank_day INTEGER,
+            PRIMARY KEY (loan_id, seq)
+        );
+        "#,
+    )
+    .map_err(|e| e.to_string())?;
+    create_indexes(
+        conn,
+        &[
+            "CREATE INDEX IF NOT EXISTS idx_bank_loans_status ON bank_loans(status)",
+            "CREATE INDEX IF NOT EXISTS idx_bank_loan_installments_due ON bank_loan_installments(status, due_bank_day)",
+        ],
+    )
+}
+
+pub fn offers(company_level: i64) -> Vec<LoanOffer> {
+    LOAN_PRODUCTS
+        .iter()
+        .map(|product| {
+            let levels_above = (company_level - product.min_level).max(0) as f64;
+            let annual_rate = (product.annual_rate - levels_above * RATE_DISCOUNT_PER_LEVEL)
+                .max(product.annual_rate * 0.6);
+            let annual_rate = (annual_rate * 10_000.0).round() / 10_000.0;
+            let max_principal = ((product.max_principal as f64)
+                * (1.0 + levels_above * LIMIT_BONUS_PER_LEVEL).min(2.0))
+            .round() as i64;
+            LoanOffer {
+                product: *product,
+                available: company_level >= product.min_level,
+                annual_rate,
+                max_principal,
+                example_installment: annuity_payment(
+                    max_principal,
+                    period_rate(annual_rate, product.interval_days),
+                    product.installment_count,
+                ),
+            }
+        })
+        .collect()
+}
+
+/// Builds the planned installments of an annuity loan.
+pub fn amortization_schedule(
+    principal: i64,
+    annual_rate: f64,
+    installment_count: i64,
+    interval_days: i64,
+) -> Vec<PlannedInstallment> {
+    let count = installment_count.max(1);
+    let rate = period_rate(annual_rate, interval_days);
+    let payment = annuity_payment(principal, rate, count);
+    let mut balance = principal.max(0);
+    let mut schedule = Vec::with_capacity(count as usize);
+    for seq in 1..=count {
+        let interest_part = ((balance as f64) * rate).round() as i64;
+        let principal_part = if seq == count {
+            balance
+        } else {
+            (payment - interest_part).clamp(0, balance)
+        };
+        balance -= principal_part;
+        schedule.push(PlannedInstallment {
+            seq,
+            installment: principal_part + interest_part,
+            principal_part,
+            interest_part,
+            balance_after: balance,
+        });
+    }
+    schedule
+}
+
+fn period_rate(annual_rate: f64, interval_days: i64) -> f64 {
+    annual_rate.max(0.0) * interval_days.max(1) as f64 / DAYS_PER_YEAR
+}
+
+fn annuity_payment(principal: i64, period_rate: f64, count: i64) -> i64 {
+    let principal = principal.max(0) as f64;
+    let count = count.max(1);
+    if period_rate <= f64::EPSILON {
+        return (principal / count as f64).ceil() as i64;
+    }
+    (principal * period_rate / (1.0 - (1.0 + period_rate).powi(-(count as i32)))).round() as i64
+}
+
+pub fn take_loan(conn: &Connection, product_id: &str, principal: i64) -> Result<Loan, String> {
+    super::atomically(conn, |conn| {
+        let level = reputation::load_state(conn)?.level;
+        let offer = offers(level)
+            .into_iter()
+            .find(|offer| offer.product.id == product_id)
+            .ok_or_else(|| format!("Unknown loan product '{}'", product_id))?;
+        if !offer.available {
+            return Err(format!(
+                "{} requires company level {}",
+                offer.product.name, offer.product.min_level
+            ));
+        }
+        if principal < offer.product.min_principal || principal > offer.max_principal {
+            return Err(format!(
+                "{} is available between EUR {} and EUR {}",
+                offer.product.name, offer.product.min_principal, offer.max_principal
+            ));
+        }
+        let (active_loans, overdue): (i64, i64) = conn
+        .query_row(
+            "SELECT COUNT(*), COALESCE(SUM(overdue_amount), 0) FROM bank_loans WHERE status = ?1",
+            params![STATUS_ACTIVE],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|e| e.to_string())?;
+        if overdue > 0 {
+            return Err("Settle overdue installments before taking a new loan".to_string());
+        }
+        if active_loans >= MAX_ACTIVE_LOANS {
+            return Err(format!(
+                "At most {} loans can run at the same time",
+                MAX_ACTIVE_LOANS
+            ));
+        }
+
+        let loan_id = open_loan(
+            conn,
+            offer.product.id,
+            principal,
+            offer.annual_rate,
+            offer.product.installment_count,
+            offer.product.interval_days,
+        )?;
+        ledger::post(
+            conn,
+            ledger::KIND_LOAN_DISBURSEMENT,
+            principal,
+            Some(loan_id),
+            &format!("{} #{} paid out", offer.product.name, loan_id),
+        )?;
+        sync_summary(conn)?;
+        let loan = load_loan(conn, loan_id)?;
+        events::record_event(
+            conn,
+            "bank",
+            "Loan approved",
+            &format!(
+                "{} of EUR {} at {:.2}% over {} installments of EUR {}.",
+                offer.product.name,
+                principal,
+                offer.annual_rate * 100.0,
+                loan.installment_count,
+                loan.installment
+            ),
+            "low",
+        )?;
+        Ok(loan)
+    })
+}
+
+/// Pays a loan off early. Without `amount` the whole debt is settled;
+/// money goes to overdue installments first, then interest, then principal.
+pub fn repay_loan(conn: &Connection, loan_id: i64, amount: Option<i64>) -> Result<Loan, String> {
+    super::atomically(conn, |conn| {
+        let mut loan = load_loan(conn, loan_id)?;
+        if loan.status != STATUS_ACTIVE {
+            return Err(format!("Loan #{} is already closed", loan_id));
+        }
+        let interest_due = loan.accrued_interest.round().max(0.0) as i64;
+        let total_due = loan.overdue_amount + interest_due + loan.outstanding_principal;
+        let payment = amount.unwrap_or(total_due).min(total_due);
+        if payment <= 0 {
+            return Err("Repayment amount must be positive".to_string());
+        }
+        let cash = current_cash(conn)?;
+        if cash < payment {
+            return Err(format!(
+                "Not enough cash: EUR {} available, EUR {} needed",
+                cash, payment
+            ));
+        }
+
+        let mut remaining = payment;
+        let to_overdue = remaining.min(loan.overdue_amount);
+        loan.overdue_amount -= to_overdue;
+        remaining -= to_overdue;
+        let to_interest = remaining.min(interest_due);
+        loan.accrued_interest = (loan.accrued_interest - to_interest as f64).max(0.0);
+        remaining -= to_interest;
+        loan.outstanding_principal -= remaining.min(loan.outstanding_principal);
+
+        ledger::post(
+            conn,
+            ledger::KIND_LOAN_REPAYMENT,
+            -payment,
+            Some(loan_id),
+            &format!("Early repayment of loan #{}", loan_id),
+        )?;
+        if loan.overdue_amount == 0 {
+            mark_overdue_installments_paid(conn, loan_id, current_bank_day(conn)?)?;
+        }
+        store_balances(conn, &loan)?;
+        close_if_settled(conn, loan_id)?;
+        reschedule_pending(conn, loan_id)?;
+        sync_summary(conn)?;
+        load_loan(conn, loan_id)
+    })
+}
+
+/// Runs one bank day: accrues interest and collects every installment due.
+pub fn process_day(conn: &Connection, bank_day: i64) -> Result<(), String> {
+    super::atomically(conn, |conn| {
+        for mut loan in list_loans(conn, false)? {
+            loan.accrued_interest +=
+                loan.outstanding_principal as f64 * loan.annual_rate / DAYS_PER_YEAR;
+            store_balances(conn, &loan)?;
+            while let Some(installment) = next_pending_installment(conn, loan.id)? {
+                if installment.due_bank_day > bank_day {
+                    break;
+                }
+                collect_installment(conn, &mut loan, &installment, bank_day)?;
+            }
+            close_if_settled(conn, loan.id)?;
+        }
+        settle_overdue(conn)?;
+        sync_summary(conn)
+    })
+}
+
+fn collect_installment(
+    conn: &Connection,
+    loan: &mut Loan,
+    installment: &LoanInstallment,
+    bank_day: i64,
+) -> Result<(), String> {
+    let interest = (loan.accrued_interest.round().max(0.0) as i64).min(installment.installment);
+    let principal = if installment.seq >= loan.installment_count {
+        loan.outstanding_principal
+    } else {
+        (installment.installment - interest).clamp(0, loan.outstanding_principal)
+    };
+    let due = principal + interest;
+    loan.outstanding_principal -= principal;
+    loan.accrued_interest = (loan.accrued_interest - interest as f64).max(0.0);
+
+    let status = if current_cash(conn)? >= due {
+        ledger::post(
+            conn,
+            ledger::KIND_LOAN_INSTALLMENT,
+            -due,
+            Some(loan.id),
+            &format!(
+                "Installment {}/{} of loan #{}",
+                installment.seq, loan.installment_count, loan.id
+            ),
+        )?;
+        loan.paid_installments += 1;
+        INSTALLMENT_PAID
+    } else {
+        let late_fee = ((due as f64) * LATE_FEE_RATE)
+            .round()
+            .max(MIN_LATE_FEE as f64) as i64;
+        loan.overdue_amount += due + late_fee;
+        loan.missed_installments += 1;
+        reputation::apply_penalty(conn, OVERDUE_REPUTATION_PENALTY)?;
+        events::record_event(
+            conn,
+            "bank",
+            "Loan installment overdue",
+            &format!(
+                "Installment {}/{} of loan #{} (EUR {}) could not be paid. Late fee: EUR {}.",
+                installment.seq, loan.installment_count, loan.id, due, late_fee
+            ),
+            "high",
+        )?;
+        INSTALLMENT_OVERDUE
+    };
+
+    conn.execute(
+        r#"
+        UPDATE bank_loan_installments
+        SET status = ?1, settled_bank_day = ?2, principal_part = ?3, interest_part = ?4
+        WHERE loan_id = ?5 AND seq = ?6
+        "#,
+        params![
+            status,
+            (status == INSTALLMENT_PAID).then_some(bank_day),
+            principal,
+            interest,
+            loan.id,
+            installment.seq
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    if loan.outstanding_principal == 0 {
+        conn.execute(
+            "UPDATE bank_loan_installments SET status = ?1 WHERE loan_id = ?2 AND status = ?3",
+            params![INSTALLMENT_CANCELLED, loan.id, INSTALLMENT_PENDING],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    store_balances(conn, loan)
+}
+
+/// Pays overdue installments from whatever cash the account holds.
+pub fn settle_overdue(conn: &Connection) -> Result<(), String> {
+    super::atomically(conn, |conn| {
+        let bank_day = current_bank_day(conn)?;
+        for mut loan in list_loans(conn, false)? {
+            if loan.overdue_amount == 0 {
+                continue;
+            }
+            let cash = current_cash(conn)?;
+            if cash <= 0 {
+                break;
+            }
+            let payment = cash.min(loan.overdue_amount);
+            ledger::post(
+                conn,
+                ledger::KIND_OVERDUE_PAYMENT,
+                -payment,
+                Some(loan.id),
+                &format!("Overdue payment on loan #{}", loan.id),
+            )?;
+            loan.overdue_amount -= payment;
+            if loan.overdue_amount == 0 {
+                mark_overdue_installments_paid(conn, loan.id, bank_day)?;
+            }
+            store_balances(conn, &loan)?;
+            close_if_settled(conn, loan.id)?;
+        }
+        sync_summary(conn)
+    })
+}
+
+pub fn list_loans(conn: &Connection, include_closed: bool) -> Result<Vec<Loan>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "{} WHERE ?1 OR loans.status = ?2 ORDER BY loans.id",
+            LOAN_SELECT
+        ))
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params![include_closed, STATUS_ACTIVE], map_loan)
+        .map_err(|e| e.to_string())?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())
+}
+
+pub fn load_loan(conn: &Connection, loan_id: i64) -> Result<Loan, String> {
+    conn.query_row(
+        &format!("{} WHERE loans.id = ?1", LOAN_SELECT),
+        params![loan_id],
+        map_loan,
+    )
+    .optional()
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| format!("Loan #{} not found", loan_id))
+}
+
+pub fn load_schedule(conn: &Connection, loan_id: i64) -> Result<Vec<LoanInstallment>, String> {
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT seq, due_bank_day, installment, principal_part, interest_part,
+                   balance_after, status, settled_bank_day
+            FROM bank_loan_installments
+            WHERE loan_id = ?1
+            ORDER BY seq
+            "#,
+        )
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params![loan_id], map_installment)
+        .map_err(|e| e.to_string())?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())
+}
+
+/// Turns the hardcoded starting debt of older career databases into a
+/// regular loan so it follows the same installment rules.
+pub fn migrate_legacy_debt(conn: &Connection) -> Result<(), String> {
+    let loans: i64 = conn
+        .query_row("SELECT COUNT(*) FROM bank_loans", [], |row| row.get(0))
+        .map_err(|e| e.to_string())?;
+    if loans > 0 {
+        return Ok(());
+    }
+    let (debt, rate, installment): (i64, f64, i64) = conn
+        .query_row(
+            "SELECT debt_balance, interest_rate, installment FROM bank_state WHERE id = 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .map_err(|e| e.to_string())?;
+    if debt <= 0 {
+        return Ok(());
+    }
+    let count = ((debt + installment.max(1) - 1) / installment.max(1)).clamp(1, 52);
+    open_loan(
+        conn,
+        LEGACY_PRODUCT_ID,
+        debt,
+        rate,
+        count,
+        LEGACY_INTERVAL_DAYS,
+    )?;
+    sync_summary(conn)
+}
+
+/// Writes the loan totals into `bank_state` so the overview stays cheap.
+pub fn sync_summary(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        r#"
+        UPDATE bank_state
+        SET
+            debt_balance = COALESCE((
+                SELECT SUM(outstanding_principal + overdue_amount)
+                FROM bank_loans WHERE status = ?1
+            ), 0),
+            interest_rate = COALESCE((
+                SELECT SUM(outstanding_principal * annual_rate) / SUM(outstanding_principal)
+                FROM bank_loans WHERE status = ?1 AND outstanding_principal > 0
+            ), 0),
+            installment = COALESCE((
+                SELECT SUM(next.installment)
+                FROM bank_loan_installments next
+                JOIN bank_loans loans ON loans.id = next.loan_id
+                WHERE loans.status = ?1
+                  AND next.seq = (
+                      SELECT MIN(seq) FROM bank_loan_installments
+                      WHERE loan_id = next.loan_id AND status = ?2
+                  )
+            ), 0)
+        WHERE id = 1
+        "#,
+        params![STATUS_ACTIVE, INSTALLMENT_PENDING],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn open_loan(
+    conn: &Connection,
+    product_id: &str,
+    principal: i64,
+    annual_rate: f64,
+    installment_count: i64,
+    interval_days: i64,
+) -> Result<i64, String> {
+    let bank_day = current_bank_day(conn)?;
+    let schedule = amortization_schedule(principal, annual_rate, installment_count, interval_days);
+    let installment = schedule.first().map(|row| row.installment).unwrap_or(0);
+    conn.execute(
+        r#"
+        INSERT INTO bank_loans (
+            product_id, principal, annual_rate, installment_count, interval_days,
+            installment, outstanding_principal, status, opened_bank_day, opened_at_utc
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?2, ?7, ?8, ?9)
+        "#,
+        params![
+            product_id,
+            principal,
+            annual_rate,
+            installment_count,
+            interval_days,
+            installment,
+            STATUS_ACTIVE,
+            bank_day,
+            Utc::now().to_rfc3339()
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    let loan_id = conn.last_insert_rowid();
+    for row in schedule {
+        conn.execute(
+            r#"
+            INSERT INTO bank_loan_installments (
+                loan_id, seq, due_bank_day, installment, principal_part,
+                interest_part, balance_after, status
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            "#,
+            params![
+                loan_id,
+                row.seq,
+                bank_day + row.seq * interval_days,
+                row.installment,
+                row.principal_part,
+                row.interest_part,
+                row.balance_after,
+                INSTALLMENT_PENDING
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(loan_id)
+}
+
+/// Re-plans the pending installments of an active loan over the principal
+/// still outstanding, keeping their due days. Used after a partial prepayment.
+fn reschedule_pending(conn: &Connection, loan_id: i64) -> Result<(), String> {
+    let loan = load_loan(conn, loan_id)?;
+    if loan.status != STATUS_ACTIVE {
+        return Ok(());
+    }
+    let pending = load_schedule(conn, loan_id)?
+        .into_iter()
+        .filter(|row| row.status == INSTALLMENT_PENDING)
+        .collect::<Vec<_>>();
+    if pending.is_empty() {
+        return Ok(());
+    }
+    let plan = amortization_schedule(
+        loan.outstanding_principal,
+        loan.annual_rate,
+        pending.len() as i64,
+        loan.interval_days,
+    );
+    for (row, planned) in pending.iter().zip(&plan) {
+        conn.execute(
+            r#"
+            UPDATE bank_loan_installments
+            SET installment = ?1, principal_part = ?2, interest_part = ?3, balance_after = ?4
+            WHERE loan_id = ?5 AND seq = ?6
+            "#,
+            params![
+                planned.installment,
+                planned.principal_part,
+                planned.interest_part,
+                planned.balance_after,
+                loan_id,
+                row.seq
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    conn.execute(
+        "UPDATE bank_loans SET installment = ?1 WHERE id = ?2",
+        params![
+            plan.first().map(|row| row.installment).unwrap_or(0),
+            loan_id
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn next_pending_installment(
+    conn: &Connection,
+    loan_id: i64,
+) -> Result<Option<LoanInstallment>, String> {
+    conn.query_row(
+        r#"
+        SELECT seq, due_bank_day, installment, principal_part, interest_part,
+               balance_after, status, settled_bank_day
+        FROM bank_loan_installments
+        WHERE loan_id = ?1 AND status = ?2
+        ORDER BY seq
+        LIMIT 1
+        "#,
+        params![loan_id, INSTALLMENT_PENDING],
+        map_installment,
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+fn mark_overdue_installments_paid(
+    conn: &Connection,
+    loan_id: i64,
+    bank_day: i64,
+) -> Result<(), String> {
+    conn.execute(
+        r#"
+        UPDATE bank_loan_installments
+        SET status = ?1, settled_bank_day = ?2
+        WHERE loan_id = ?3 AND status = ?4
+        "#,
+        params![
+            INSTALLMENT_PAID_LATE,
+            bank_day,
+            loan_id,
+            INSTALLMENT_OVERDUE
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn store_balances(conn: &Connection, loan: &Loan) -> Result<(), String> {
+    conn.execute(
+        r#"
+        UPDATE bank_loans
+        SET outstanding_principal = ?1, accrued_interest = ?2, overdue_amount = ?3,
+            paid_installments = ?4, missed_installments = ?5
+        WHERE id = ?6
+        "#,
+        params![
+            loan.outstanding_principal,
+            loan.accrued_interest,
+            loan.overdue_amount,
+            loan.paid_installments,
+            loan.missed_installments,
+            loan.id
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn close_if_settled(conn: &Connection, loan_id: i64) -> Result<(), String> {
+    let loan = load_loan(conn, loan_id)?;
+    if loan.status != STATUS_ACTIVE || loan.outstanding_principal > 0 || loan.overdue_amount > 0 {
+        return Ok(());
+    }
+    conn.execute(
+        r#"
+        UPDATE bank_loan_installments SET status = ?1 WHERE loan_id = ?2 AND status = ?3
+        "#,
+        params![INSTALLMENT_CANCELLED, loan_id, INSTALLMENT_PENDING],
+    )
+    .map_err(|e| e.to_string())?;
+    conn.execute(
+        "UPDATE bank_loans SET status = ?1, accrued_interest = 0, closed_at_utc = ?2 WHERE id = ?3",
+        params![STATUS_REPAID, Utc::now().to_rfc3339(), loan_id],
+    )
+    .map_err(|e| e.to_string())?;
+    events::record_event(
+        conn,
+        "bank",
+        "Loan repaid",
+        &format!(
+            "Loan #{} over EUR {} is fully repaid.",
+            loan_id, loan.principal
+        ),
+        "low",
+    )
+}
+
+fn current_cash(conn: &Connection) -> Result<i64, String> {
+    conn.query_row(
+        "SELECT cash_balance FROM bank_state WHERE id = 1",
+        [],
+        |row| row.get(0),
+    )
+    .map_err(|e| e.to_string())
+}
+
+fn current_bank_day(conn: &Connection) -> Result<i64, String> {
+    conn.query_row("SELECT bank_day FROM bank_state WHERE id = 1", [], |row| {
+        row.get(0)
+    })
+    .map_err(|e| e.to_string())
+}
+
+const LOAN_SELECT: &str = r#"
+    SELECT
+        loans.id, loans.product_id, loans.principal, loans.annual_rate,
+        loans.installment_count, loans.interval_days, loans.installment,
+        loans.outstanding_principal, loans.accrued_interest, loans.overdue_amount,
+        loans.paid_installments, loans.missed_installments,
+        (
+            SELECT MIN(due_bank_day) FROM bank_loan_installments
+            WHERE loan_id = loans.id AND status = 'pending'
+        ),
+        loans.status, loans.opened_bank_day, loans.opened_at_utc, loans.closed_at_utc
+    FROM bank_loans loans
+"#;
+
+fn map_loan(row: &rusqlite::Row<'_>) -> rusqlite::Result<Loan> {
+    Ok(Loan {
+        id: row.get(0)?,
+        product_id: row.get(1)?,
+        principal: row.get(2)?,
+        annual_rate: row.get(3)?,
+        installment_count: row.get(4)?,
+        interval_days: row.get(5)?,
+        installment: row.get(6)?,
+        outstanding_principal: row.get(7)?,
+        accrued_interest: row.get(8)?,
+        overdue_amount: row.get(9)?,
+        paid_installments: row.get(10)?,
+        missed_installments: row.get(11)?,
+        next_due_bank_day: row.get(12)?,
+        status: row.get(13)?,
+        opened_bank_day: row.get(14)?,
+        opened_at_utc: row.get(15)?,
+        closed_at_utc: row.get(16)?,
+    })
+}
+
+fn map_installment(row: &rusqlite::Row<'_>) -> rusqlite::Result<LoanInstallment> {
+    Ok(LoanInstallment {
+        seq: row.get(0)?,
+        due_bank_day: row.get(1)?,
+        installment: row.get(2)?,
+        principal_part: row.get(3)?,
+        interest_part: row.get(4)?,
+        balance_after: row.get(5)?,
+        status: row.get(6)?,
+        settled_bank_day: row.get(7)?,
+    })
+}
@@ -1,6 +1,24 @@
+//! Company account, loans and the cash ledger of career mode.
+//!
+//! Cash only moves through [`ledger::post`], so every change of
+//! `bank_state.cash_balance` has a matching `bank_ledger` row. Loans run on a
+//! bank calendar that advances with the in-game days seen in telemetry
+//! `game_time_min`; see [`advance_calendar`].
+
+pub mod ledger;
+pub mod loans;
+
 use rusqlite::{Connection, params};
 use serde::Serialize;
 
+use crate::features::bank::ledger::LedgerEntry;
+use crate::features::bank::loans::{Loan, LoanOffer};
+use crate::features::reputation;
+use crate::shared::sqlite_schema::ensure_columns;
+use crate::state::CareerRuntime;
+
+const MINUTES_PER_GAME_DAY: u32 = 1_440;
+
 #[derive(Debug, Clone, Serialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct BankState {
@@ -8,6 +26,18 @@ pub struct BankState {
     pub debt_balance: i64,
     pub interest_rate: f64,
     pub installment: i64,
+    pub overdue_balance: i64,
+    pub bank_day: i64,
+    pub next_due_bank_day: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BankOverview {
+    pub state: BankState,
+    pub loans: Vec<Loan>,
+    pub offers: Vec<LoanOffer>,
+    pub recent_ledger: Vec<LedgerEntry>,
 }
 
 pub fn ensure_tables(conn: &Connection) -> Result<(), String> {
@@ -23,6 +53,14 @@ pub fn ensure_tables(conn: &Connection) -> Result<(), String> {
         "#,
     )
     .map_err(|e| e.to_string())?;
+    ensure_columns(
+        conn,
+        "bank_state",
+        &[
+            ("bank_day", "INTEGER NOT NULL DEFAULT 0"),
+            ("last_game_day", "INTEGER"),
+        ],
+    )?;
 
     conn.execute(
         r#"
@@ -33,54 +71,271 @@ pub fn ensure_tables(conn: &Connection) -> Result<(), String> {
     )
     .map_err(|e| e.to_string())?;
 
-    Ok(())
+    ledger::ensure_tables(conn)?;
+    loans::ensure_tables(conn)?;
+    loans::migrate_legacy_debt(conn)
 }
 
 pub fn load_state(conn: &Connection) -> Result<BankState, String> {
     conn.query_row(
         r#"
-        SELECT cash_balance, debt_balance, interest_rate, installment
+        SELECT
+            cash_balance,
+            debt_balance,
+            interest_rate,
+            installment,
+            (SELECT COALESCE(SUM(overdue_amount), 0) FROM bank_loans WHERE status = ?1),
+            bank_day,
+            (
+                SELECT MIN(due_bank_day) FROM bank_loan_installments
+                WHERE status = 'pending'
+            )
         FROM bank_state
         WHERE id = 1
         "#,
-        [],
+        params![loans::STATUS_ACTIVE],
         |row| {
             Ok(BankState {
                 cash_balance: row.get(0)?,
                 debt_balance: row.get(1)?,
                 interest_rate: row.get(2)?,
                 installment: row.get(3)?,
+                overdue_balance: row.get(4)?,
+                bank_day: row.get(5)?,
+                next_due_bank_day: row.get(6)?,
             })
         },
     )
     .map_err(|e| e.to_string())
 }
 
-pub fn apply_trip_result(conn: &Connection, net_income: i64) -> Result<BankState, String> {
-    let current = load_state(conn)?;
-    let interest_charge = if current.debt_balance > 0 {
-        ((current.debt_balance as f64) * current.interest_rate / 240.0).round() as i64
-    } else {
-        0
-    };
-    let principal_payment = if current.debt_balance > 0 {
-        current.installment.min(current.debt_balance)
-    } else {
-        0
+pub fn load_overview(conn: &Connection, ledger_limit: usize) -> Result<BankOverview, String> {
+    let level = reputation::load_state(conn)?.level;
+    Ok(BankOverview {
+        state: load_state(conn)?,
+        loans: loans::list_loans(conn, true)?,
+        offers: loans::offers(level),
+        recent_ledger: ledger::list_entries(conn, None, ledger_limit)?,
+    })
+}
+
+/// Books the net result of a finished trip and uses it to pay off overdue
+/// installments.
+pub fn apply_trip_result(
+    conn: &Connection,
+    net_income: i64,
+    description: &str,
+) -> Result<BankState, String> {
+    atomically(conn, |conn| {
+        ledger::post(
+            conn,
+            ledger::KIND_TRIP_RESULT,
+            net_income,
+            None,
+            description,
+        )?;
+        loans::settle_overdue(conn)?;
+        load_state(conn)
+    })
+}
+
+/// Returns the in-game day of `game_time_min` when it differs from the last
+/// one this session saw, so callers only touch the database once per game
+/// day. The calendar itself keeps the last processed day in `bank_state`.
+pub fn game_day_changed(runtime: &CareerRuntime, game_time_min: u32) -> Option<i64> {
+    if game_time_min == 0 {
+        return None;
+    }
+    let game_day = i64::from(game_time_min / MINUTES_PER_GAME_DAY);
+    let mut last = runtime.last_game_day.lock().ok()?;
+    if *last == Some(game_day) {
+        return None;
+    }
+    *last = Some(game_day);
+    Some(game_day)
+}
+
+/// Advances the bank calendar to `game_day` and runs every bank day passed.
+/// Each day is committed on its own, so an error keeps the days before it.
+///
+/// The calendar only moves forward: an earlier game day (another profile or
+/// an older save) just becomes the new reference point.
+pub fn advance_calendar(conn: &Connection, game_day: i64) -> Result<i64, String> {
+    let (bank_day, last_game_day): (i64, Option<i64>) = conn
+        .query_row(
+            "SELECT bank_day, last_game_day FROM bank_state WHERE id = 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|e| e.to_string())?;
+
+    let Some(last) = last_game_day.filter(|last| game_day > *last) else {
+        set_calendar(conn, bank_day, game_day)?;
+        return Ok(0);
     };
+    let elapsed = game_day - last;
+    for offset in 1..=elapsed {
+        atomically(conn, |conn| {
+            set_calendar(conn, bank_day + offset, last + offset)?;
+            loans::process_day(conn, bank_day + offset)
+        })?;
+    }
+    Ok(elapsed)
+}
 
-    let next_cash = current.cash_balance + net_income - interest_charge - principal_payment;
-    let next_debt = (current.debt_balance - principal_payment).max(0);
+/// Runs `f` inside a savepoint and rolls everything back when it fails.
+/// Savepoints nest, so bank operations can call each other freely.
+pub(crate) fn atomically<T>(
+    conn: &Connection,
+    f: impl FnOnce(&Connection) -> Result<T, String>,
+) -> Result<T, String> {
+    conn.execute_batch("SAVEPOINT bank_op")
+        .map_err(|e| e.to_string())?;
+    match f(conn) {
+        Ok(value) => {
+            conn.execute_batch("RELEASE bank_op")
+                .map_err(|e| e.to_string())?;
+            Ok(value)
+        }
+        Err(error) => {
+            let _ = conn.execute_batch("ROLLBACK TO bank_op; RELEASE bank_op");
+            Err(error)
+        }
+    }
+}
 
+fn set_calendar(conn: &Connection, bank_day: i64, game_day: i64) -> Result<(), String> {
     conn.execute(
-        r#"
-        UPDATE bank_state
-        SET cash_balance = ?1, debt_balance = ?2
-        WHERE id = 1
-        "#,
-        params![next_cash, next_debt],
+        "UPDATE bank_state SET bank_day = ?1, last_game_day = ?2 WHERE id = 1",
+        params![bank_day, game_day],
     )
     .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::events;
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        reputation::ensure_tables(&conn).unwrap();
+        events::ensure_tables(&conn).unwrap();
+        ensure_tables(&conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn amortization_repays_the_principal_exactly() {
+        let schedule = loans::amortization_schedule(60_000, 0.089, 8, 7);
+        assert_eq!(schedule.len(), 8);
+        assert_eq!(
+            schedule.iter().map(|row| row.principal_part).sum::<i64>(),
+            60_000
+        );
+        assert_eq!(schedule.last().unwrap().balance_after, 0);
+        // Annuity: every installment is the same; the last one absorbs rounding.
+        let payment = schedule[0].installment;
+        assert!(schedule[..7].iter().all(|row| row.installment == payment));
+        assert!((schedule[7].installment - payment).abs() <= 8);
+        assert!(schedule[0].interest_part > schedule[7].interest_part);
+    }
+
+    #[test]
+    fn migrates_legacy_debt_and_collects_installments_on_game_days() {
+        let conn = setup();
+        let loans = loans::list_loans(&conn, false).unwrap();
+        assert_eq!(loans.len(), 1);
+        assert_eq!(loans[0].principal, 38_000);
+        assert_eq!(load_state(&conn).unwrap().debt_balance, 38_000);
+
+        // The first observation only sets the reference day.
+        assert_eq!(advance_calendar(&conn, 100).unwrap(), 0);
+        assert_eq!(advance_calendar(&conn, 107).unwrap(), 7);
+
+        let state = load_state(&conn).unwrap();
+        assert_eq!(state.bank_day, 7);
+        assert!(state.cash_balance < 145_000);
+        assert!(state.debt_balance < 38_000);
+        let entries = ledger::list_entries(&conn, None, 10).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].kind, ledger::KIND_LOAN_INSTALLMENT);
+        assert_eq!(entries[0].balance_after, state.cash_balance);
+    }
+
+    #[test]
+    fn missed_installments_go_overdue_and_are_settled_from_income() {
+        let conn = setup();
+        let reputation_before = reputation::load_state(&conn).unwrap().score;
+        let cash = load_state(&conn).unwrap().cash_balance;
+        ledger::post(&conn, "test", -cash, None, "drain").unwrap();
 
-    load_state(conn)
+        advance_calendar(&conn, 0).unwrap();
+        advance_calendar(&conn, 7).unwrap();
+        let state = load_state(&conn).unwrap();
+        assert!(state.overdue_balance > 0);
+        assert_eq!(
+            reputation::load_state(&conn).unwrap().score,
+            reputation_before - 3
+        );
+        assert!(loans::take_loan(&conn, "starter", 20_000).is_err());
+
+        let state = apply_trip_result(&conn, 10_000, "Trip").unwrap();
+        assert_eq!(state.overdue_balance, 0);
+        let schedule = loans::load_schedule(&conn, 1).unwrap();
+        assert_eq!(schedule[0].status, "paid_late");
+
+        let loan = loans::take_loan(&conn, "starter", 20_000).unwrap();
+        assert_eq!(loan.outstanding_principal, 20_000);
+        let repaid = loans::repay_loan(&conn, loan.id, None).unwrap();
+        assert_eq!(repaid.status, loans::STATUS_REPAID);
+    }
+
+    #[test]
+    fn long_gaps_process_every_day() {
+        let conn = setup();
+        advance_calendar(&conn, 10).unwrap();
+        assert_eq!(advance_calendar(&conn, 40).unwrap(), 30);
+
+        let state = load_state(&conn).unwrap();
+        assert_eq!(state.bank_day, 30);
+        let schedule = loans::load_schedule(&conn, 1).unwrap();
+        assert_eq!(
+            schedule
+                .iter()
+                .filter(|row| row.due_bank_day <= 30 && row.status == "paid")
+                .count(),
+            4
+        );
+    }
+
+    #[test]
+    fn failed_operations_roll_back() {
+        let conn = setup();
+        let cash = load_state(&conn).unwrap().cash_balance;
+        let result: Result<(), String> = atomically(&conn, |conn| {
+            ledger::post(conn, "test", -1_000, None, "rolled back")?;
+            Err("boom".to_string())
+        });
+        assert!(result.is_err());
+        assert_eq!(load_state(&conn).unwrap().cash_balance, cash);
+        assert!(ledger::list_entries(&conn, None, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn partial_prepayment_replans_the_remaining_installments() {
+        let conn = setup();
+        let loan = loans::take_loan(&conn, "starter", 40_000).unwrap();
+        let repaid = loans::repay_loan(&conn, loan.id, Some(10_000)).unwrap();
+        assert_eq!(repaid.outstanding_principal, 30_000);
+        assert!(repaid.installment < loan.installment);
+
+        let schedule = loans::load_schedule(&conn, loan.id).unwrap();
+        assert_eq!(
+            schedule.iter().map(|row| row.principal_part).sum::<i64>(),
+            30_000
+        );
+        assert_eq!(schedule.last().unwrap().balance_after, 0);
+    }
 }
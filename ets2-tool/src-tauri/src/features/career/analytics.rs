@@ -1,4 +1,4 @@
-use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -10,6 +10,7 @@ use sha2::{Digest, Sha256};
 use tauri::AppHandle;
 use tauri_plugin_dialog::DialogExt;
 
+use crate::features::career::delivery_log::{self, DeliveryLogEntry};
 use crate::features::ets2save::dispatcher as save_dispatcher;
 use crate::shared::decrypt::decrypt_if_needed;
 use crate::shared::extract::extract_profile_name;
@@ -149,6 +150,17 @@ pub struct AnalyticsScanResult {
     pub note: String,
 }
 
+/// In-game seconds that pass per real second. SCS scales time by the same factor as the map
+/// (the SDK's `local.scale` channel: "scale applied to distance and time to compensate for
+/// the scale of the map"), which is 19 in ETS2 and 20 in ATS.
+fn game_time_scale(game: &str) -> f64 {
+    if game.eq_ignore_ascii_case("ats") {
+        20.0
+    } else {
+        19.0
+    }
+}
+
 #[derive(Debug, Clone)]
 struct ScannedJobRecord {
     game: Option<String>,
@@ -431,6 +443,9 @@ pub fn scan_profile_job_history(
     let mut inserted_jobs = 0_i64;
     let mut updated_jobs = 0_i64;
     let mut sources_used = BTreeSet::new();
+    let mut local_fingerprints = load_local_tracking_fingerprints(conn)?;
+    let mut seen_delivery_uids = HashSet::new();
+    let mut skipped_local_duplicates = 0_i64;
 
     let entries = fs::read_dir(&save_root).map_err(|error| error.to_string())?;
     for entry in entries.flatten() {
@@ -528,6 +543,50 @@ pub fn scan_profile_job_history(
             }
             sources_used.insert(scanned.source.clone());
         }
+
+        let delivery_log = delivery_log::parse_delivery_log(&content);
+        for entry in &delivery_log.entries {
+            // Free-roam entries carry no job.
+            if entry.cargo.is_none() {
+                continue;
+            }
+            let scanned = build_delivery_log_record(
+                &game,
+                &profile_id,
+                &profile_name,
+                &save_dir,
+                &save_name,
+                &detected_at,
+                delivery_log.game_time_min,
+                entry,
+            );
+            // Older saves repeat the same history, so only the first copy counts.
+            if !seen_delivery_uids.insert(scanned.job_uid.clone()) {
+                continue;
+            }
+            if consume_local_fingerprint(&mut local_fingerprints, entry) {
+                skipped_local_duplicates += 1;
+                continue;
+            }
+
+            let was_existing = get_item_by_uid(conn, &scanned.job_uid)?.is_some();
+            upsert_scanned_job(conn, &scanned)?;
+            detected_jobs += 1;
+            if was_existing {
+                updated_jobs += 1;
+            } else {
+                inserted_jobs += 1;
+            }
+            sources_used.insert(scanned.source.clone());
+        }
+    }
+
+    let mut note = "Imported the save delivery log and currently readable save-linked job data. Delivery dates are estimated from in-game time.".to_string();
+    if skipped_local_duplicates > 0 {
+        note.push_str(&format!(
+            " {} deliveries were already covered by local tracking and were skipped.",
+            skipped_local_duplicates
+        ));
     }
 
     Ok(AnalyticsScanResult {
@@ -539,7 +598,7 @@ pub fn scan_profile_job_history(
         inserted_jobs,
         updated_jobs,
         sources_used: sources_used.into_iter().collect(),
-        note: "Imported currently readable save-linked job data. Historical completeness is limited by ETS2/ATS save contents.".to_string(),
+        note,
     })
 }
 
@@ -1295,6 +1354,272 @@ fn build_active_job_record(
     }
 }
 
+fn build_delivery_log_record(
+    game: &str,
+    profile_id: &str,
+    profile_name: &str,
+    save_dir: &Path,
+    save_name: &str,
+    detected_at: &str,
+    save_game_time_min: Option<i64>,
+    entry: &DeliveryLogEntry,
+) -> ScannedJobRecord {
+    let route = decode_company_pointer(entry.source_company.as_deref());
+    let destination = decode_company_pointer(entry.target_company.as_deref());
+    let completed_at =
+        estimate_delivery_timestamp(game, detected_at, save_game_time_min, entry.game_time_min);
+    let raw = json!({
+        "pointer": entry.pointer,
+        "index": entry.index,
+        "gameTimeMin": entry.game_time_min,
+        "saveGameTimeMin": save_game_time_min,
+        "cargo": entry.cargo,
+        "sourceCompany": entry.source_company,
+        "targetCompany": entry.target_company,
+        "params": entry.params
+    });
+
+    ScannedJobRecord {
+        game: Some(game.to_string()),
+        profile_id: profile_id.to_string(),
+        profile_name: profile_name.to_string(),
+        save_path: save_dir.display().to_string().replace('\\', "/"),
+        source: "save_import".to_string(),
+        source_save_name: save_name.to_string(),
+        detected_at: completed_at
+            .clone()
+            .unwrap_or_else(|| detected_at.to_string()),
+        started_at: None,
+        completed_at,
+        job_uid: stable_scanned_job_uid(
+            profile_id,
+            "delivery_log",
+            entry.cargo.as_deref(),
+            entry.source_company.as_deref(),
+            entry.target_company.as_deref(),
+            entry.distance_km.map(|distance| distance.round() as i64),
+            entry.game_time_min,
+            None,
+        ),
+        status: match entry.delivered {
+            Some(false) => "failed".to_string(),
+            _ => "completed".to_string(),
+        },
+        cargo_name: entry.cargo.as_deref().map(pretty_token_value),
+        source_city: route.1,
+        destination_city: destination.1,
+        source_company: route.0,
+        destination_company: destination.0,
+        distance_km: entry.distance_km,
+        revenue: entry.revenue,
+        costs: None,
+        penalties: None,
+        damage_percent: entry.damage_percent,
+        profit: None,
+        xp: entry.xp,
+        level_after: None,
+        truck_name: None,
+        trailer_name: None,
+        driven_with_truck: Some(true),
+        data_origin_note: Some(
+            "Imported from the save delivery log. The completion date is estimated from in-game time.".to_string(),
+        ),
+        raw_data_json: Some(raw.to_string()),
+    }
+}
+
+/// Maps an in-game delivery time onto wall-clock time using the save's own
+/// `game_time` and file date. Sleeping and ferries skip game time, so the
+/// result is only an approximation for charting.
+fn estimate_delivery_timestamp(
+    game: &str,
+    detected_at: &str,
+    save_game_time_min: Option<i64>,
+    entry_game_time_min: Option<i64>,
+) -> Option<String> {
+    let saved_at = DateTime::parse_from_rfc3339(detected_at).ok()?;
+    let elapsed_game_min = save_game_time_min?.checked_sub(entry_game_time_min?)?;
+    if elapsed_game_min < 0 {
+        return None;
+    }
+    let elapsed_real_sec = (elapsed_game_min as f64 * 60.0 / game_time_scale(game)).round() as i64;
+    let estimated = saved_at.with_timezone(&Utc) - chrono::Duration::seconds(elapsed_real_sec);
+    Some(estimated.to_rfc3339())
+}
+
+fn load_local_tracking_fingerprints(conn: &Connection) -> Result<HashMap<String, usize>, String> {
+    let city_tokens = load_city_tokens(conn)?;
+    let city_token = |name: Option<String>| {
+        name.map(|name| city_tokens.get(&fold_token(&name)).cloned().unwrap_or(name))
+    };
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT cargo_name, source_city, destination_city, revenue
+            FROM career_job_history
+            WHERE source = 'local_tracking'
+            "#,
+        )
+        .map_err(|error| error.to_string())?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, Option<String>>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, Option<i64>>(3)?,
+            ))
+        })
+        .map_err(|error| error.to_string())?;
+
+    let mut fingerprints = HashMap::new();
+    for row in rows {
+        let (cargo, source_city, destination_city, revenue) =
+            row.map_err(|error| error.to_string())?;
+        let fingerprint = delivery_fingerprint(
+            cargo.as_deref(),
+            city_token(source_city).as_deref(),
+            city_token(destination_city).as_deref(),
+            revenue,
+        );
+        *fingerprints.entry(fingerprint).or_insert(0) += 1;
+    }
+    Ok(fingerprints)
+}
+
+/// Game tokens of the cities in `ets2_cities`, keyed by the folded English name, local name
+/// and every alias, so telemetry display names such as `München` map to `munchen`.
+fn load_city_tokens(conn: &Connection) -> Result<HashMap<String, String>, String> {
+    let known: bool = conn
+        .query_row(
+            "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'ets2_cities')",
+            [],
+            |row| row.get(0),
+        )
+        .map_err(|error| error.to_string())?;
+    if !known {
+        return Ok(HashMap::new());
+    }
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT game_token, name_en, name_local, aliases_json FROM ets2_cities ORDER BY id",
+        )
+        .map_err(|error| error.to_string())?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })
+        .map_err(|error| error.to_string())?;
+
+    let mut tokens = HashMap::new();
+    for row in rows {
+        let (game_token, name_en, name_local, aliases_json) =
+            row.map_err(|error| error.to_string())?;
+        let aliases: Vec<String> = serde_json::from_str(&aliases_json).unwrap_or_default();
+        for name in [name_en, name_local].into_iter().chain(aliases) {
+            tokens
+                .entry(fold_token(&name))
+                .or_insert_with(|| game_token.clone());
+        }
+    }
+    Ok(tokens)
+}
+
+/// Matches a save delivery against the local tracking rows. Tracked jobs
+/// without a known income are matched on cargo and route alone. Both sides are
+/// compared as game tokens: the save stores `cargo.apples` and
+/// `company.volatile.tesco.munchen`, the tracking rows the display names.
+fn consume_local_fingerprint(
+    fingerprints: &mut HashMap<String, usize>,
+    entry: &DeliveryLogEntry,
+) -> bool {
+    let cargo = entry
+        .cargo
+        .as_deref()
+        .map(|cargo| cargo.strip_prefix("cargo.").unwrap_or(cargo));
+    let source_city = company_city_token(entry.source_company.as_deref());
+    let destination_city = company_city_token(entry.target_company.as_deref());
+    let candidates = [
+        delivery_fingerprint(cargo, source_city, destination_city, entry.revenue),
+        delivery_fingerprint(cargo, source_city, destination_city, None),
+    ];
+    for candidate in candidates {
+        if let Some(count) = fingerprints.get_mut(&candidate) {
+            if *count > 0 {
+                *count -= 1;
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// City token of a company pointer such as `company.volatile.tesco.munchen`.
+fn company_city_token(value: Option<&str>) -> Option<&str> {
+    let value = value?.trim();
+    let company = value.strip_prefix("company.volatile.").unwrap_or(value);
+    company
+        .rsplit_once('.')
+        .map(|(_, city)| city)
+        .filter(|city| !city.is_empty())
+}
+
+fn delivery_fingerprint(
+    cargo: Option<&str>,
+    source_city: Option<&str>,
+    destination_city: Option<&str>,
+    revenue: Option<i64>,
+) -> String {
+    let token = |value: Option<&str>| fold_token(value.unwrap_or(""));
+    format!(
+        "{}|{}|{}|{}",
+        token(cargo),
+        token(source_city),
+        token(destination_city),
+        revenue.map(|value| value.to_string()).unwrap_or_default()
+    )
+}
+
+/// Lower-case ASCII form of a name or game token: diacritics fold to their base letter the
+/// way SCS builds tokens (`München` -> `munchen`) and separators are dropped, so
+/// `Le Havre` and `le_havre` agree.
+fn fold_token(value: &str) -> String {
+    let mut folded = String::new();
+    for character in value.chars().flat_map(char::to_lowercase) {
+        let base = match character {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ą' => 'a',
+            'ç' | 'ć' | 'č' => 'c',
+            'ď' => 'd',
+            'è' | 'é' | 'ê' | 'ë' | 'ę' | 'ě' => 'e',
+            'ì' | 'í' | 'î' | 'ï' => 'i',
+            'ł' => 'l',
+            'ñ' | 'ń' | 'ň' => 'n',
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ő' => 'o',
+            'ř' => 'r',
+            'ś' | 'š' => 's',
+            'ť' => 't',
+            'ù' | 'ú' | 'û' | 'ü' | 'ů' | 'ű' => 'u',
+            'ý' => 'y',
+            'ź' | 'ż' | 'ž' => 'z',
+            'ß' => {
+                folded.push_str("ss");
+                continue;
+            }
+            other => other,
+        };
+        if base.is_ascii_alphanumeric() {
+            folded.push(base);
+        }
+    }
+    folded
+}
+
 fn item_primary_date(item: &AnalyticsJobHistoryItem) -> NaiveDate {
     parse_rfc3339_date(item.started_at.as_deref())
         .or_else(|| parse_rfc3339_date(Some(&item.detected_at)))
@@ -1537,3 +1862,77 @@ fn csv_cell_opt_f64(value: Option<f64>, digits: usize) -> String {
             .unwrap_or_default(),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracking_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            r#"
+            CREATE TABLE career_job_history (
+                source TEXT NOT NULL,
+                cargo_name TEXT,
+                source_city TEXT,
+                destination_city TEXT,
+                revenue INTEGER
+            );
+            CREATE TABLE ets2_cities (
+                id TEXT PRIMARY KEY,
+                game_token TEXT NOT NULL,
+                name_en TEXT NOT NULL,
+                name_local TEXT NOT NULL,
+                aliases_json TEXT NOT NULL
+            );
+            INSERT INTO ets2_cities VALUES
+                ('de_munchen', 'munchen', 'Munich', 'München', '[]'),
+                ('fr_le_havre', 'le_havre', 'Le Havre', 'Le Havre', '[]');
+            "#,
+        )
+        .unwrap();
+        conn
+    }
+
+    fn save_delivery(source: &str, target: &str, revenue: Option<i64>) -> DeliveryLogEntry {
+        DeliveryLogEntry {
+            cargo: Some("cargo.apples".to_string()),
+            source_company: Some(format!("company.volatile.{}", source)),
+            target_company: Some(format!("company.volatile.{}", target)),
+            revenue,
+            ..DeliveryLogEntry::default()
+        }
+    }
+
+    #[test]
+    fn tracked_display_names_match_save_tokens() {
+        let conn = tracking_db();
+        conn.execute_batch(
+            r#"
+            INSERT INTO career_job_history VALUES
+                ('local_tracking', 'Apples', 'München', 'Le Havre', 14200),
+                ('local_tracking', 'Apples', 'Munich', 'Le Havre', NULL);
+            "#,
+        )
+        .unwrap();
+        let mut fingerprints = load_local_tracking_fingerprints(&conn).unwrap();
+
+        let delivery = save_delivery("tesco.munchen", "posped.le_havre", Some(14200));
+        assert!(consume_local_fingerprint(&mut fingerprints, &delivery));
+        // The second tracked run has no income and matches on cargo and route.
+        let repeat = save_delivery("tesco.munchen", "posped.le_havre", Some(9000));
+        assert!(consume_local_fingerprint(&mut fingerprints, &repeat));
+        assert!(!consume_local_fingerprint(&mut fingerprints, &repeat));
+    }
+
+    #[test]
+    fn fold_token_matches_scs_tokens() {
+        assert_eq!(fold_token("München"), "munchen");
+        assert_eq!(fold_token("Kraków"), "krakow");
+        assert_eq!(fold_token("le_havre"), fold_token("Le Havre"));
+        assert_eq!(
+            company_city_token(Some("company.volatile.tesco.munchen")),
+            Some("munchen")
+        );
+    }
+}
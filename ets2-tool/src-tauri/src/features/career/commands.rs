@@ -12,27 +12,51 @@ use crate::events::{
     EVT_DISPATCHER_ASSIGN_PREPARE_ERROR, EVT_DISPATCHER_ASSIGN_PREPARE_PROGRESS,
     EVT_DISPATCHER_JOB_UPDATED,
 };
+use crate::features::bank::ledger::{self, LedgerEntry};
+use crate::features::bank::loans::{self, Loan, LoanInstallment};
+use crate::features::bank::{self, BankOverview};
 use crate::features::career::analytics::{
     self, AnalyticsFilters, AnalyticsJobHistoryResponse, AnalyticsScanResult, AnalyticsSummary,
 };
 use crate::features::career::dispatcher::{
-    self, DispatcherCompanyContact, DispatcherCreateOfferInput, DispatcherGenerationConfigInput,
-    DispatcherGenerationStatus, DispatcherHistoryResponse, DispatcherJobDetails,
-    DispatcherJobFilter, DispatcherJobsBySaveContextResponse, DispatcherMarketJob, DispatcherOffer,
-    DispatcherOverview, DispatcherRespondToCounterInput, Job,
+    self, DispatcherChain, DispatcherCompanyContact, DispatcherCreateChainInput,
+    DispatcherCreateOfferInput, DispatcherGenerationConfigInput, DispatcherGenerationStatus,
+    DispatcherHistoryResponse, DispatcherJobDetails, DispatcherJobFilter,
+    DispatcherJobsBySaveContextResponse, DispatcherMarketJob, DispatcherNegotiation,
+    DispatcherOffer, DispatcherOverview, DispatcherRespondToCounterInput, Job,
 };
+use crate::features::career::driving_score::{self, DrivingScoreThresholds, TripDrivingScore};
 use crate::features::career::job_log::{self, JobLogEntry, JobStats};
 use crate::features::career::logbook::TripSummary;
 use crate::features::career::overview::CareerOverview;
 use crate::features::career::plugin_installer::{self, ScsGame};
+use crate::features::career::tachograph::{self, HosOverview, HosViolationEntry};
+use crate::features::career::telemetry;
+use crate::features::career::telemetry_source::{self, ReplaySource, TelemetryRecordingSummary};
+use crate::features::career::trip_track::{self, TrackExportFormat, TripTrack};
+use crate::features::economy::settlement_models::SettlementPenaltyRules;
+use crate::features::economy::settlement_service;
+use crate::features::employees::{
+    self, EmployeeSummary,
+    simulation::{self, EmployeeJob},
+};
 use crate::features::ets2save::errors::{AppError, AppErrorCode};
 use crate::features::ets2save::link_service;
 use crate::features::ets2save::models::{
-    EtsJobLink, EtsJobLinkStatus, EtsJobWriteResult, PostWriteValidationResult,
+    EtsJobLink, EtsJobLinkStatus, EtsJobWriteResult, EtsSaveTarget, PostWriteValidationResult,
+};
+use crate::features::fleet::{
+    self,
+    maintenance::{self, FleetWearReconciliation, MaintenanceStatus, WorkshopVisit},
 };
 use crate::features::hub::events::CareerStatus;
+use crate::features::vehicles::trucks::player_truck_from_content;
+use crate::features::vehicles::{
+    load_save_content_from_save_path, resolve_active_save_from_snapshot,
+};
 use crate::shared::current_profile::snapshot_save_context;
-use crate::state::{AppProfileState, CareerRuntime, CareerState, EtsDbState};
+use crate::shared::sii_parser::parse_trucks_from_sii;
+use crate::state::{AppProfileState, CareerRuntime, CareerState, DecryptCache, EtsDbState};
 
 const EVT_DISPATCHER_ASSIGN_PROGRESS: &str = "vtc://dispatcher/assign_progress";
 const EVT_DISPATCHER_PREPARE_PROGRESS: &str = "vtc://dispatcher/prepare_progress";
@@ -152,6 +176,370 @@ pub fn career_list_trips(career: State<'_, CareerState>) -> Result<Vec<TripSumma
     crate::features::career::logbook::list_trips(&db_path, 200)
 }
 
+#[command]
+pub fn career_list_driving_scores(
+    limit: Option<usize>,
+    career: State<'_, CareerState>,
+) -> Result<Vec<TripDrivingScore>, String> {
+    crate::dev_log!("[career] command: career_list_driving_scores");
+    let conn = open_connection(career.runtime.as_ref())?;
+    driving_score::list_trip_scores(&conn, limit.unwrap_or(50).clamp(1, 500))
+}
+
+#[command]
+pub fn career_get_driving_score_thresholds(
+    career: State<'_, CareerState>,
+) -> Result<DrivingScoreThresholds, String> {
+    crate::dev_log!("[career] command: career_get_driving_score_thresholds");
+    let conn = open_connection(career.runtime.as_ref())?;
+    driving_score::load_thresholds(&conn)
+}
+
+#[command]
+pub fn career_set_driving_score_thresholds(
+    thresholds: DrivingScoreThresholds,
+    career: State<'_, CareerState>,
+) -> Result<DrivingScoreThresholds, String> {
+    crate::dev_log!("[career] command: career_set_driving_score_thresholds");
+    let conn = open_connection(career.runtime.as_ref())?;
+    driving_score::save_thresholds(&conn, thresholds)
+}
+
+#[command]
+pub fn career_get_settlement_rules(
+    career: State<'_, CareerState>,
+) -> Result<SettlementPenaltyRules, String> {
+    crate::dev_log!("[career] command: career_get_settlement_rules");
+    let conn = open_connection(career.runtime.as_ref())?;
+    settlement_service::load_settlement_rules(&conn)
+}
+
+#[command]
+pub fn career_set_settlement_rules(
+    rules: SettlementPenaltyRules,
+    career: State<'_, CareerState>,
+) -> Result<SettlementPenaltyRules, String> {
+    crate::dev_log!("[career] command: career_set_settlement_rules");
+    let conn = open_connection(career.runtime.as_ref())?;
+    settlement_service::save_settlement_rules(&conn, rules)
+}
+
+#[command]
+pub fn career_get_hours_of_service(
+    limit: Option<usize>,
+    career: State<'_, CareerState>,
+) -> Result<HosOverview, String> {
+    crate::dev_log!("[career] command: career_get_hours_of_service");
+    let conn = open_connection(career.runtime.as_ref())?;
+    tachograph::load_overview(
+        career.runtime.as_ref(),
+        &conn,
+        limit.unwrap_or(20).clamp(1, 200),
+    )
+}
+
+#[command]
+pub fn career_list_trip_hos_violations(
+    trip_id: i64,
+    career: State<'_, CareerState>,
+) -> Result<Vec<HosViolationEntry>, String> {
+    crate::dev_log!(
+        "[career] command: career_list_trip_hos_violations trip_id={}",
+        trip_id
+    );
+    let conn = open_connection(career.runtime.as_ref())?;
+    tachograph::list_violations(&conn, Some(trip_id), 200)
+}
+
+#[command]
+pub fn career_get_bank_overview(career: State<'_, CareerState>) -> Result<BankOverview, String> {
+    crate::dev_log!("[career] command: career_get_bank_overview");
+    let conn = open_connection(career.runtime.as_ref())?;
+    bank::load_overview(&conn, 25)
+}
+
+#[command]
+pub fn career_take_loan(
+    product_id: String,
+    principal: i64,
+    career: State<'_, CareerState>,
+) -> Result<Loan, String> {
+    crate::dev_log!(
+        "[career] command: career_take_loan product={} principal={}",
+        product_id,
+        principal
+    );
+    let runtime = career.runtime.as_ref();
+    let conn = open_connection(runtime)?;
+    let loan = loans::take_loan(&conn, &product_id, principal)?;
+    runtime.overview_dirty.store(true, Ordering::Relaxed);
+    Ok(loan)
+}
+
+#[command]
+pub fn career_repay_loan(
+    loan_id: i64,
+    amount: Option<i64>,
+    career: State<'_, CareerState>,
+) -> Result<Loan, String> {
+    crate::dev_log!("[career] command: career_repay_loan loan_id={}", loan_id);
+    let runtime = career.runtime.as_ref();
+    let conn = open_connection(runtime)?;
+    let loan = loans::repay_loan(&conn, loan_id, amount)?;
+    runtime.overview_dirty.store(true, Ordering::Relaxed);
+    Ok(loan)
+}
+
+#[command]
+pub fn career_get_loan_schedule(
+    loan_id: i64,
+    career: State<'_, CareerState>,
+) -> Result<Vec<LoanInstallment>, String> {
+    crate::dev_log!(
+        "[career] command: career_get_loan_schedule loan_id={}",
+        loan_id
+    );
+    let conn = open_connection(career.runtime.as_ref())?;
+    loans::load_schedule(&conn, loan_id)
+}
+
+#[command]
+pub fn career_list_bank_ledger(
+    loan_id: Option<i64>,
+    limit: Option<usize>,
+    career: State<'_, CareerState>,
+) -> Result<Vec<LedgerEntry>, String> {
+    crate::dev_log!("[career] command: career_list_bank_ledger");
+    let conn = open_connection(career.runtime.as_ref())?;
+    ledger::list_entries(&conn, loan_id, limit.unwrap_or(100).clamp(1, 1000))
+}
+
+#[command]
+pub fn career_get_fleet_maintenance(
+    career: State<'_, CareerState>,
+) -> Result<Vec<MaintenanceStatus>, String> {
+    crate::dev_log!("[career] command: career_get_fleet_maintenance");
+    let conn = open_connection(career.runtime.as_ref())?;
+    maintenance::load_statuses(&conn)
+}
+
+#[command]
+pub fn career_list_workshop_visits(
+    include_completed: Option<bool>,
+    limit: Option<usize>,
+    career: State<'_, CareerState>,
+) -> Result<Vec<WorkshopVisit>, String> {
+    crate::dev_log!("[career] command: career_list_workshop_visits");
+    let conn = open_connection(career.runtime.as_ref())?;
+    maintenance::list_visits(
+        &conn,
+        include_completed.unwrap_or(false),
+        limit.unwrap_or(50).clamp(1, 500),
+    )
+}
+
+#[command]
+pub fn career_schedule_workshop_visit(
+    asset_id: String,
+    kind: Option<String>,
+    in_days: Option<i64>,
+    career: State<'_, CareerState>,
+) -> Result<WorkshopVisit, String> {
+    crate::dev_log!(
+        "[career] command: career_schedule_workshop_visit asset_id={}",
+        asset_id
+    );
+    let kind = kind.unwrap_or_else(|| maintenance::VISIT_SERVICE.to_string());
+    if kind != maintenance::VISIT_SERVICE && kind != maintenance::VISIT_REPAIR {
+        return Err(format!("Unsupported workshop visit kind: {}", kind));
+    }
+    let runtime = career.runtime.as_ref();
+    let conn = open_connection(runtime)?;
+    let visit =
+        maintenance::schedule_visit(&conn, &asset_id, &kind, "Booked by dispatcher", in_days)?;
+    runtime.overview_dirty.store(true, Ordering::Relaxed);
+    Ok(visit)
+}
+
+#[command]
+pub fn career_complete_workshop_visit(
+    visit_id: i64,
+    career: State<'_, CareerState>,
+) -> Result<WorkshopVisit, String> {
+    crate::dev_log!(
+        "[career] command: career_complete_workshop_visit visit_id={}",
+        visit_id
+    );
+    let runtime = career.runtime.as_ref();
+    let conn = open_connection(runtime)?;
+    let visit = maintenance::complete_visit(&conn, visit_id)?;
+    runtime.overview_dirty.store(true, Ordering::Relaxed);
+    Ok(visit)
+}
+
+#[command]
+pub fn career_hire_driver(
+    name: String,
+    experience_km: Option<f64>,
+    career: State<'_, CareerState>,
+) -> Result<EmployeeSummary, String> {
+    crate::dev_log!("[career] command: career_hire_driver name={}", name);
+    let runtime = career.runtime.as_ref();
+    let conn = open_connection(runtime)?;
+    let employee = employees::hire_driver(&conn, &name, experience_km.unwrap_or(0.0))?;
+    runtime.overview_dirty.store(true, Ordering::Relaxed);
+    Ok(employee)
+}
+
+#[command]
+pub fn career_dismiss_employee(
+    employee_id: String,
+    career: State<'_, CareerState>,
+) -> Result<(), String> {
+    crate::dev_log!(
+        "[career] command: career_dismiss_employee employee_id={}",
+        employee_id
+    );
+    let runtime = career.runtime.as_ref();
+    let conn = open_connection(runtime)?;
+    employees::dismiss_employee(&conn, &employee_id)?;
+    runtime.overview_dirty.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
+#[command]
+pub fn career_list_employee_jobs(
+    employee_id: Option<String>,
+    limit: Option<usize>,
+    career: State<'_, CareerState>,
+) -> Result<Vec<EmployeeJob>, String> {
+    crate::dev_log!("[career] command: career_list_employee_jobs");
+    let conn = open_connection(career.runtime.as_ref())?;
+    simulation::list_jobs(
+        &conn,
+        employee_id.as_deref(),
+        limit.unwrap_or(50).clamp(1, 500),
+    )
+}
+
+#[command]
+pub async fn career_reconcile_fleet_wear(
+    profile_state: State<'_, AppProfileState>,
+    decrypt_cache: State<'_, DecryptCache>,
+    career: State<'_, CareerState>,
+) -> Result<FleetWearReconciliation, String> {
+    crate::dev_log!("[career] command: career_reconcile_fleet_wear");
+    let save_path = resolve_active_save_from_snapshot(
+        profile_state.current_save.lock().unwrap().clone(),
+        profile_state.current_profile.lock().unwrap().clone(),
+    )?;
+    let decrypt_cache = decrypt_cache.inner().clone();
+    let truck = tauri::async_runtime::spawn_blocking(move || {
+        let (content, _) = load_save_content_from_save_path(&save_path, &decrypt_cache)?;
+        let trucks = parse_trucks_from_sii(&content);
+        player_truck_from_content(&content, &trucks)
+            .ok_or_else(|| "Player truck not found in save".to_string())
+    })
+    .await
+    .map_err(|error| format!("career_reconcile_fleet_wear join failed: {}", error))??;
+
+    let runtime = career.runtime.as_ref();
+    let conn = open_connection(runtime)?;
+    let reconciliation =
+        maintenance::reconcile_save_wear(&conn, fleet::PLAYER_TRUCK_ASSET_ID, &truck)?;
+    runtime.overview_dirty.store(true, Ordering::Relaxed);
+    Ok(reconciliation)
+}
+
+#[command]
+pub fn career_get_trip_track(
+    trip_id: i64,
+    career: State<'_, CareerState>,
+) -> Result<TripTrack, String> {
+    crate::dev_log!(
+        "[career] command: career_get_trip_track trip_id={}",
+        trip_id
+    );
+    let conn = open_connection(career.runtime.as_ref())?;
+    trip_track::load_track(&conn, trip_id)
+}
+
+#[command]
+pub fn career_export_trip_track(
+    app: AppHandle,
+    trip_id: i64,
+    format: String,
+    career: State<'_, CareerState>,
+) -> Result<Option<String>, String> {
+    crate::dev_log!(
+        "[career] command: career_export_trip_track trip_id={} format={}",
+        trip_id,
+        format
+    );
+    let format = TrackExportFormat::try_from(format.as_str())?;
+    let conn = open_connection(career.runtime.as_ref())?;
+    trip_track::export_track(&app, &conn, trip_id, format)
+}
+
+#[command]
+pub fn career_start_telemetry_recording(
+    path: Option<String>,
+    career: State<'_, CareerState>,
+) -> Result<String, String> {
+    let runtime = career.runtime.as_ref();
+    crate::dev_log!("[career] command: career_start_telemetry_recording");
+    let game = runtime
+        .active_game
+        .lock()
+        .map_err(|_| "Career active_game lock poisoned".to_string())?
+        .clone();
+    let db_path = runtime
+        .db_path
+        .lock()
+        .map_err(|_| "Career db_path lock poisoned".to_string())?
+        .clone();
+    let path = path
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            telemetry_source::default_recording_path(db_path.as_deref(), game.as_deref())
+        });
+
+    telemetry_source::start_recording(&path, game)?;
+    Ok(path.display().to_string())
+}
+
+#[command]
+pub fn career_stop_telemetry_recording() -> Result<Option<TelemetryRecordingSummary>, String> {
+    crate::dev_log!("[career] command: career_stop_telemetry_recording");
+    telemetry_source::stop_recording()
+}
+
+#[command]
+pub fn career_start_telemetry_replay(
+    app: AppHandle,
+    path: String,
+    speed: Option<f64>,
+    career: State<'_, CareerState>,
+) -> Result<usize, String> {
+    crate::dev_log!(
+        "[career] command: career_start_telemetry_replay path={}",
+        path
+    );
+    let source = ReplaySource::open(&PathBuf::from(path.trim()), speed.unwrap_or(1.0))?;
+    let frames = source.frame_count();
+    telemetry::start_replay(app, career.runtime.clone(), source)?;
+    Ok(frames)
+}
+
+#[command]
+pub fn career_stop_telemetry_replay(career: State<'_, CareerState>) -> Result<(), String> {
+    crate::dev_log!("[career] command: career_stop_telemetry_replay");
+    career.runtime.replay_stop.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
 #[command]
 pub fn career_get_active_job(
     career: State<'_, CareerState>,
@@ -463,6 +851,59 @@ pub fn dispatcher_respond_to_counter(
     Ok(result)
 }
 
+#[command]
+pub fn dispatcher_get_offer_negotiation(
+    offer_id: String,
+    career: State<'_, CareerState>,
+) -> Result<DispatcherNegotiation, String> {
+    let conn = open_connection(career.runtime.as_ref())?;
+    dispatcher::dispatcher_get_offer_negotiation(&conn, &offer_id)
+}
+
+#[command]
+pub fn dispatcher_create_chain(
+    input: DispatcherCreateChainInput,
+    career: State<'_, CareerState>,
+    profile: State<'_, AppProfileState>,
+) -> Result<DispatcherChain, String> {
+    let runtime = career.runtime.as_ref();
+    let save_context = resolve_dispatcher_save_context(profile.inner())?;
+    let conn = open_connection(runtime)?;
+    let result = dispatcher::dispatcher_create_chain(&conn, input, &save_context)?;
+    runtime.overview_dirty.store(true, Ordering::Relaxed);
+    Ok(result)
+}
+
+#[command]
+pub fn dispatcher_get_chain(
+    chain_id: String,
+    career: State<'_, CareerState>,
+) -> Result<DispatcherChain, String> {
+    let conn = open_connection(career.runtime.as_ref())?;
+    dispatcher::dispatcher_get_chain(&conn, &chain_id)
+}
+
+#[command]
+pub fn dispatcher_get_chains(
+    limit: Option<usize>,
+    career: State<'_, CareerState>,
+) -> Result<Vec<DispatcherChain>, String> {
+    let conn = open_connection(career.runtime.as_ref())?;
+    dispatcher::dispatcher_get_chains(&conn, limit.unwrap_or(50))
+}
+
+#[command]
+pub fn dispatcher_cancel_chain(
+    chain_id: String,
+    career: State<'_, CareerState>,
+) -> Result<DispatcherChain, String> {
+    let runtime = career.runtime.as_ref();
+    let conn = open_connection(runtime)?;
+    let result = dispatcher::dispatcher_cancel_chain(&conn, &chain_id)?;
+    runtime.overview_dirty.store(true, Ordering::Relaxed);
+    Ok(result)
+}
+
 #[command]
 pub fn dispatcher_get_dispatcher_overview(
     career: State<'_, CareerState>,
@@ -573,6 +1014,7 @@ pub fn dispatcher_assign_job_to_active_save(
 #[command]
 pub async fn dispatcher_assign_and_prepare_ets_link(
     job_id: String,
+    save_target: Option<EtsSaveTarget>,
     app: AppHandle,
     career: State<'_, CareerState>,
     profile: State<'_, AppProfileState>,
@@ -584,6 +1026,7 @@ pub async fn dispatcher_assign_and_prepare_ets_link(
         profile.inner(),
         &db.pool,
         &job_id,
+        &save_target.unwrap_or_default(),
     )
     .await
 }
@@ -592,6 +1035,7 @@ pub async fn dispatcher_assign_and_prepare_ets_link(
 pub async fn dispatcher_assign_and_prepare_and_write(
     job_id: String,
     auto_write: bool,
+    save_target: Option<EtsSaveTarget>,
     app: AppHandle,
     career: State<'_, CareerState>,
     profile: State<'_, AppProfileState>,
@@ -604,6 +1048,7 @@ pub async fn dispatcher_assign_and_prepare_and_write(
         &db.pool,
         &job_id,
         auto_write,
+        &save_target.unwrap_or_default(),
     )
     .await
 }
@@ -698,27 +1143,37 @@ async fn dispatcher_assign_and_prepare_ets_link_inner(
     profile: &AppProfileState,
     db_pool: &SqlitePool,
     job_id: &str,
+    save_target: &EtsSaveTarget,
 ) -> Result<DispatcherJobDetails, String> {
     let save_context = resolve_required_dispatcher_save_context(profile)?;
     emit_assign_prepare_progress(app, job_id, "assigning");
 
     let assigned_or_current = {
         let conn = open_connection(runtime)?;
-        match dispatcher::dispatcher_assign_job_to_active_save(&conn, job_id, &save_context) {
-            Ok(details) => details,
-            Err(error) if error == "job_already_assigned" => {
-                let current = dispatcher::dispatcher_get_job_by_id(&conn, job_id, &save_context)?;
-                if matches!(
-                    current.job.status.as_str(),
-                    "assigned_to_save" | "prepared" | "injected" | "completed"
-                ) {
-                    current
-                } else {
-                    return Err(error);
+        let assigned =
+            match dispatcher::dispatcher_assign_job_to_active_save(&conn, job_id, &save_context) {
+                Ok(details) => details,
+                Err(error) if error == "job_already_assigned" => {
+                    let current =
+                        dispatcher::dispatcher_get_job_by_id(&conn, job_id, &save_context)?;
+                    if matches!(
+                        current.job.status.as_str(),
+                        "assigned_to_save" | "prepared" | "injected" | "completed"
+                    ) {
+                        current
+                    } else {
+                        return Err(error);
+                    }
                 }
-            }
-            Err(error) => return Err(error),
-        }
+                Err(error) => return Err(error),
+            };
+        dispatcher::dispatcher_record_chain_save_target(
+            &conn,
+            job_id,
+            save_target.slot.as_deref(),
+            save_target.clone_to_new_slot,
+        )?;
+        assigned
     };
 
     runtime.overview_dirty.store(true, Ordering::Relaxed);
@@ -758,6 +1213,7 @@ async fn dispatcher_assign_and_prepare_ets_link_inner(
             .profile_reference
             .as_deref()
             .unwrap_or_default(),
+        save_target,
         profile,
     )
     .await
@@ -784,13 +1240,14 @@ async fn dispatcher_assign_and_prepare_ets_link_inner(
     }
 }
 
-async fn dispatcher_assign_prepare_write_inner(
+pub(super) async fn dispatcher_assign_prepare_write_inner(
     app: Option<&AppHandle>,
     runtime: &CareerRuntime,
     profile: &AppProfileState,
     db_pool: &SqlitePool,
     job_id: &str,
     auto_write: bool,
+    save_target: &EtsSaveTarget,
 ) -> Result<DispatcherAssignPrepareWriteDto, String> {
     let save_context = resolve_required_dispatcher_save_context(profile)?;
     let mut assign_result = "pending".to_string();
@@ -809,35 +1266,51 @@ async fn dispatcher_assign_prepare_write_inner(
             formatted
         })?;
 
-    let game_sii_path = save_context
-        .save_reference
-        .as_ref()
-        .map(|save_reference| PathBuf::from(save_reference).join("game.sii"));
-    let sha_before = game_sii_path.as_deref().and_then(read_file_sha256_hex_opt);
+    let game_sii_path = match save_target.slot.as_deref() {
+        Some(slot) => save_context.profile_reference.as_ref().map(|profile| {
+            PathBuf::from(profile)
+                .join("save")
+                .join(slot)
+                .join("game.sii")
+        }),
+        None => save_context
+            .save_reference
+            .as_ref()
+            .map(|save_reference| PathBuf::from(save_reference).join("game.sii")),
+    };
 
     emit_dispatcher_progress(app, EVT_DISPATCHER_ASSIGN_PROGRESS, job_id, "assigning");
 
     let assigned_or_current = {
         let conn = open_connection(runtime)?;
-        match dispatcher::dispatcher_assign_job_to_active_save(&conn, job_id, &save_context) {
-            Ok(details) => {
-                assign_result = "assigned".to_string();
-                details
-            }
-            Err(error) if error == "job_already_assigned" => {
-                let current = dispatcher::dispatcher_get_job_by_id(&conn, job_id, &save_context)?;
-                if matches!(
-                    current.job.status.as_str(),
-                    "assigned_to_save" | "prepared" | "injected" | "completed"
-                ) {
-                    assign_result = "already_assigned".to_string();
-                    current
-                } else {
-                    return Err(error);
+        let assigned =
+            match dispatcher::dispatcher_assign_job_to_active_save(&conn, job_id, &save_context) {
+                Ok(details) => {
+                    assign_result = "assigned".to_string();
+                    details
                 }
-            }
-            Err(error) => return Err(error),
-        }
+                Err(error) if error == "job_already_assigned" => {
+                    let current =
+                        dispatcher::dispatcher_get_job_by_id(&conn, job_id, &save_context)?;
+                    if matches!(
+                        current.job.status.as_str(),
+                        "assigned_to_save" | "prepared" | "injected" | "completed"
+                    ) {
+                        assign_result = "already_assigned".to_string();
+                        current
+                    } else {
+                        return Err(error);
+                    }
+                }
+                Err(error) => return Err(error),
+            };
+        dispatcher::dispatcher_record_chain_save_target(
+            &conn,
+            job_id,
+            save_target.slot.as_deref(),
+            save_target.clone_to_new_slot,
+        )?;
+        assigned
     };
     runtime.overview_dirty.store(true, Ordering::Relaxed);
 
@@ -873,6 +1346,7 @@ async fn dispatcher_assign_prepare_write_inner(
                 .profile_reference
                 .as_deref()
                 .unwrap_or_default(),
+            save_target,
             profile,
         )
         .await
@@ -898,6 +1372,17 @@ async fn dispatcher_assign_prepare_write_inner(
         prepare_result = "already_prepared_or_written".to_string();
     }
 
+    // A cloned slot only exists after the prepare step; hash the save the
+    // link actually points at.
+    let game_sii_path = match link.as_ref() {
+        Some(link) => link_service::job_link_game_sii_path(db_pool, link)
+            .await
+            .ok()
+            .or(game_sii_path),
+        None => game_sii_path,
+    };
+    let sha_before = game_sii_path.as_deref().and_then(read_file_sha256_hex_opt);
+
     let mut write_attempted = false;
     let mut write_applied = false;
     let mut written_result: Option<EtsJobWriteResult> = None;
@@ -960,7 +1445,12 @@ async fn dispatcher_assign_prepare_write_inner(
     emit_dispatcher_job_updated_new(app, &details);
 
     let sha_after = if auto_write {
-        game_sii_path.as_deref().and_then(read_file_sha256_hex_opt)
+        written_result
+            .as_ref()
+            .map(|result| PathBuf::from(&result.save_path))
+            .or(game_sii_path)
+            .as_deref()
+            .and_then(read_file_sha256_hex_opt)
     } else {
         None
     };
@@ -1194,6 +1684,7 @@ mod tests {
     };
     use crate::features::career::dispatcher;
     use crate::features::ets2save::link_service;
+    use crate::features::ets2save::models::EtsSaveTarget;
     use crate::state::{AppProfileState, CareerRuntime};
 
     const FIXTURE_GAME_SII: &str = "SiiNunit\n{\ncompany : company.volatile.test_company.berlin {\n job_offer: 1\n job_offer[0]: _nameless.offer.001\n}\njob_offer_data : _nameless.offer.001 {\n target: test_company.munich\n expiration_time: 100\n urgency: 1\n shortest_distance_km: 120\n cargo: cargo.old\n company_truck: false\n trailer_variant: original.variant\n trailer_definition: original.trailer\n units_count: 1\n fill_ratio: 1\n trailer_place: 0\n}\n selected_job: old.job.info\n}\n";
@@ -1293,6 +1784,7 @@ mod tests {
                 &context.profile_state,
                 &context.pool,
                 &context.job_id,
+                &EtsSaveTarget::default(),
             )
             .await
             .unwrap();
@@ -1324,6 +1816,7 @@ mod tests {
                 &context.profile_state,
                 &context.pool,
                 &context.job_id,
+                &EtsSaveTarget::default(),
             )
             .await
             .unwrap_err();
@@ -1361,6 +1854,7 @@ mod tests {
                 &context.profile_state,
                 &context.pool,
                 &context.job_id,
+                &EtsSaveTarget::default(),
             )
             .await
             .unwrap();
@@ -1370,6 +1864,7 @@ mod tests {
                 &context.profile_state,
                 &context.pool,
                 &context.job_id,
+                &EtsSaveTarget::default(),
             )
             .await
             .unwrap();
@@ -1401,6 +1896,7 @@ mod tests {
                 &context.profile_state,
                 &context.pool,
                 &context.job_id,
+                &EtsSaveTarget::default(),
             )
             .await
             .unwrap();
@@ -1442,6 +1938,7 @@ mod tests {
                 &context.pool,
                 &context.job_id,
                 true,
+                &EtsSaveTarget::default(),
             )
             .await
             .unwrap();
@@ -1490,6 +1987,7 @@ mod tests {
                 &context.pool,
                 &context.job_id,
                 true,
+                &EtsSaveTarget::default(),
             )
             .await
             .unwrap();
@@ -1500,6 +1998,7 @@ mod tests {
                 &context.pool,
                 &context.job_id,
                 true,
+                &EtsSaveTarget::default(),
             )
             .await
             .unwrap();
@@ -1518,4 +2017,64 @@ mod tests {
             assert_eq!(link_count, 1);
         });
     }
+
+    #[test]
+    fn assign_prepare_write_into_new_slot_keeps_quicksave_untouched() {
+        tauri::async_runtime::block_on(async {
+            let context = setup_test_context("test_company", "Test Company", "Berlin").await;
+            let profile_path = PathBuf::from(
+                context
+                    .profile_state
+                    .current_profile
+                    .lock()
+                    .unwrap()
+                    .clone()
+                    .unwrap(),
+            );
+            let quicksave_game = profile_path.join("save/quicksave/game.sii");
+            let quicksave_info = profile_path.join("save/quicksave/info.sii");
+            fs::write(
+                &quicksave_info,
+                "SiiNunit\n{\nsave_container : _nameless.1 {\n name: \"\"\n version: 80\n}\n}\n",
+            )
+            .unwrap();
+
+            let result = dispatcher_assign_prepare_write_inner(
+                None,
+                &context.runtime,
+                &context.profile_state,
+                &context.pool,
+                &context.job_id,
+                true,
+                &EtsSaveTarget {
+                    slot: Some("quicksave".to_string()),
+                    clone_to_new_slot: true,
+                },
+            )
+            .await
+            .unwrap();
+
+            assert!(result.write_applied);
+            assert_eq!(
+                fs::read_to_string(&quicksave_game).unwrap(),
+                FIXTURE_GAME_SII
+            );
+            let cloned_game = profile_path.join("save/1/game.sii");
+            let written = result.write_output.unwrap();
+            assert_eq!(
+                PathBuf::from(&written.save_path),
+                PathBuf::from(cloned_game.display().to_string().replace('\\', "/"))
+            );
+            assert_ne!(fs::read_to_string(&cloned_game).unwrap(), FIXTURE_GAME_SII);
+            assert_eq!(written.load_path_warning, None);
+            assert_eq!(result.sha_changed, Some(true));
+            let cloned_info = fs::read_to_string(profile_path.join("save/1/info.sii")).unwrap();
+            assert!(cloned_info.contains(" name: \"quicksave (Dispatcher)\""));
+            assert!(
+                fs::read_to_string(&quicksave_info)
+                    .unwrap()
+                    .contains(" name: \"\"")
+            );
+        });
+    }
 }
@@ -3,7 +3,10 @@ use std::path::{Path, PathBuf};
 
 use crate::features::career::analytics;
 use crate::features::career::dispatcher;
+use crate::features::career::driving_score;
 use crate::features::career::job_log;
+use crate::features::career::tachograph;
+use crate::features::career::trip_track;
 use crate::features::{auth, companies, vtc};
 use crate::features::{bank, contracts, economy, employees, events, fleet, reputation};
 use crate::shared::sqlite_schema::ensure_columns;
@@ -67,6 +70,9 @@ pub fn init_logbook(db_path: &Path) -> Result<(), String> {
     dispatcher::ensure_tables(&conn)?;
     job_log::ensure_tables(&conn)?;
     analytics::ensure_tables(&conn)?;
+    trip_track::ensure_tables(&conn)?;
+    driving_score::ensure_tables(&conn)?;
+    tachograph::ensure_tables(&conn)?;
 
     auth::db::ensure_tables(&conn)?;
     companies::db::ensure_tables(&conn)?;
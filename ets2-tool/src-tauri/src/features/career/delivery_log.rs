@@ -0,0 +1,297 @@
+//! Parser for the `delivery_log` / `delivery_log_entry` units stored in
+//! `game.sii`. The game keeps one entry per finished delivery; every entry is
+//! a positional `params[]` array, so the parser maps the known slots and keeps
+//! the raw values around for anything it does not understand yet.
+
+// SCS does not document the `params[]` layout. The slots below were read off the
+// 3013 entries of a real save (`version: 1`, 24 params per entry; an excerpt is
+// in `test-fixtures/delivery_log`). Other slots seen there: 8 minutes left to the
+// deadline (negative when late or cancelled), 13 offered revenue, 15 start time,
+// 16 truck, 17 a second distance, 18 job market, 22 cargo mass, 23 units. Every
+// value is still range-checked and the raw params are kept with each imported
+// job, since other game versions may differ.
+const PARAM_GAME_TIME: usize = 0;
+const PARAM_SOURCE_COMPANY: usize = 1;
+const PARAM_TARGET_COMPANY: usize = 2;
+const PARAM_CARGO: usize = 3;
+const PARAM_XP: usize = 4;
+const PARAM_REVENUE: usize = 5;
+const PARAM_DISTANCE_KM: usize = 6;
+const PARAM_CARGO_DAMAGE: usize = 7;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeliveryLogEntry {
+    pub pointer: String,
+    pub index: usize,
+    pub game_time_min: Option<i64>,
+    pub source_company: Option<String>,
+    pub target_company: Option<String>,
+    pub cargo: Option<String>,
+    pub revenue: Option<i64>,
+    pub delivered: Option<bool>,
+    pub distance_km: Option<f64>,
+    pub xp: Option<i64>,
+    pub damage_percent: Option<f64>,
+    pub params: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DeliveryLog {
+    pub game_time_min: Option<i64>,
+    pub entries: Vec<DeliveryLogEntry>,
+}
+
+/// Reads every delivery log entry from a decrypted `game.sii`.
+///
+/// Entries are returned in the order the `delivery_log` unit lists them. Saves
+/// without a `delivery_log` unit fall back to the order of the entry units.
+/// Free-roam entries (no cargo, no companies) are returned as well.
+pub fn parse_delivery_log(content: &str) -> DeliveryLog {
+    let mut order = Vec::new();
+    let mut units = Vec::new();
+    let mut game_time_min = None;
+
+    let mut current: Option<(String, String, Vec<String>)> = None;
+    for line in content.lines() {
+        let trimmed = line.trim();
+
+        if current.is_some() {
+            if trimmed == "}" {
+                if let Some((unit_class, pointer, fields)) = current.take() {
+                    match unit_class.as_str() {
+                        "delivery_log" => order = collect_array(&fields, "entries"),
+                        _ => units.push((pointer, collect_array(&fields, "params"))),
+                    }
+                }
+            } else if let Some((_, _, fields)) = current.as_mut() {
+                fields.push(trimmed.to_string());
+            }
+            continue;
+        }
+
+        if game_time_min.is_none() {
+            if let Some(value) = trimmed.strip_prefix("game_time:") {
+                game_time_min = value.trim().parse::<i64>().ok();
+            }
+        }
+
+        let Some(header) = trimmed.strip_suffix('{') else {
+            continue;
+        };
+        let Some((unit_class, pointer)) = header.split_once(':') else {
+            continue;
+        };
+        let unit_class = unit_class.trim();
+        if unit_class == "delivery_log" || unit_class == "delivery_log_entry" {
+            current = Some((
+                unit_class.to_string(),
+                pointer.trim().to_string(),
+                Vec::new(),
+            ));
+        }
+    }
+
+    let mut entries = Vec::new();
+    if order.is_empty() {
+        for (pointer, params) in units {
+            let index = entries.len();
+            entries.push(build_entry(pointer, index, params));
+        }
+    } else {
+        for pointer in order {
+            if let Some(position) = units.iter().position(|(unit, _)| *unit == pointer) {
+                let (pointer, params) = units.swap_remove(position);
+                let index = entries.len();
+                entries.push(build_entry(pointer, index, params));
+            }
+        }
+    }
+
+    DeliveryLog {
+        game_time_min,
+        entries,
+    }
+}
+
+fn build_entry(pointer: String, index: usize, params: Vec<String>) -> DeliveryLogEntry {
+    let text = |slot: usize| {
+        params
+            .get(slot)
+            .map(String::as_str)
+            .filter(|value| !value.is_empty() && *value != "null")
+            .map(ToString::to_string)
+    };
+    let integer = |slot: usize| params.get(slot).and_then(|value| parse_number(value));
+    let float = |slot: usize| {
+        params
+            .get(slot)
+            .and_then(|value| value.parse::<f64>().ok())
+            .filter(|value| value.is_finite())
+    };
+
+    DeliveryLogEntry {
+        game_time_min: integer(PARAM_GAME_TIME),
+        source_company: text(PARAM_SOURCE_COMPANY),
+        target_company: text(PARAM_TARGET_COMPANY),
+        cargo: text(PARAM_CARGO),
+        revenue: integer(PARAM_REVENUE).filter(|value| *value >= 0),
+        // There is no status slot: cancelled jobs are logged with no revenue.
+        delivered: text(PARAM_CARGO)
+            .and(integer(PARAM_REVENUE))
+            .filter(|value| *value >= 0)
+            .map(|value| value > 0),
+        distance_km: float(PARAM_DISTANCE_KM).filter(|value| *value > 0.0),
+        xp: integer(PARAM_XP).filter(|value| *value >= 0),
+        // Damage is expected as a 0..1 fraction; anything else means the slot
+        // holds something else.
+        damage_percent: float(PARAM_CARGO_DAMAGE)
+            .filter(|value| (0.0..=1.0).contains(value))
+            .map(|value| value * 100.0),
+        pointer,
+        index,
+        params,
+    }
+}
+
+fn collect_array(fields: &[String], name: &str) -> Vec<String> {
+    let prefix = format!("{name}[");
+    let mut values = Vec::new();
+    for field in fields {
+        let Some(rest) = field.strip_prefix(&prefix) else {
+            continue;
+        };
+        let Some((slot, value)) = rest.split_once("]:") else {
+            continue;
+        };
+        let Ok(slot) = slot.trim().parse::<usize>() else {
+            continue;
+        };
+        if values.len() <= slot {
+            values.resize(slot + 1, String::new());
+        }
+        values[slot] = value.trim().trim_matches('"').to_string();
+    }
+    values
+}
+
+fn parse_number(value: &str) -> Option<i64> {
+    let value = value.trim();
+    value.parse::<i64>().ok().or_else(|| {
+        value
+            .parse::<f64>()
+            .ok()
+            .map(|parsed| parsed.round() as i64)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Four entries copied from a real `game.sii`: an on-time delivery, a late
+    /// one, a cancelled one and a free-roam entry.
+    const REAL_SAVE: &str = include_str!("../../../test-fixtures/delivery_log/game_excerpt.sii");
+
+    const SAMPLE: &str = r#"SiiNunit
+{
+delivery_log_entry : _nameless.3 {
+ params: 8
+ params[0]: 88000
+ params[1]: "company.volatile.tesco.berlin"
+ params[2]: "company.volatile.ikea.praha"
+ params[3]: "cargo.apples"
+ params[4]: 620
+ params[5]: 12500
+ params[6]: 351
+ params[7]: "0.042"
+}
+delivery_log_entry : _nameless.4 {
+ params: 4
+ params[0]: 70000
+ params[1]: "company.volatile.aldi.wien"
+ params[2]: "company.volatile.lidl.graz"
+ params[3]: "cargo.milk"
+}
+}
+"#;
+
+    #[test]
+    fn parses_a_real_delivery_log() {
+        let log = parse_delivery_log(REAL_SAVE);
+
+        assert_eq!(log.game_time_min, Some(19338837));
+        assert_eq!(log.entries.len(), 4);
+
+        let on_time = &log.entries[0];
+        assert_eq!(on_time.pointer, "_nameless.279.56cb.8c70");
+        assert_eq!(on_time.game_time_min, Some(19338149));
+        assert_eq!(
+            on_time.source_company.as_deref(),
+            Some("company.volatile.nbfc.calais")
+        );
+        assert_eq!(
+            on_time.target_company.as_deref(),
+            Some("company.volatile.cnp.duisburg")
+        );
+        assert_eq!(on_time.cargo.as_deref(), Some("cargo.comp_process"));
+        assert_eq!(on_time.xp, Some(626));
+        assert_eq!(on_time.revenue, Some(46263));
+        assert_eq!(on_time.distance_km, Some(380.0));
+        assert_eq!(on_time.damage_percent, Some(0.0));
+        assert_eq!(on_time.delivered, Some(true));
+        assert_eq!(on_time.params.len(), 24);
+
+        let late = &log.entries[1];
+        assert_eq!(late.revenue, Some(3993));
+        assert_eq!(late.delivered, Some(true));
+
+        let cancelled = &log.entries[2];
+        assert_eq!(cancelled.cargo.as_deref(), Some("cargo.flour"));
+        assert_eq!(cancelled.revenue, Some(0));
+        assert_eq!(cancelled.xp, Some(0));
+        assert_eq!(cancelled.delivered, Some(false));
+
+        let free_roam = &log.entries[3];
+        assert_eq!(free_roam.cargo, None);
+        assert_eq!(free_roam.delivered, None);
+    }
+
+    #[test]
+    fn out_of_range_slots_are_ignored() {
+        let content = SAMPLE
+            .replace("params[5]: 12500", "params[5]: -3")
+            .replace("params[7]: \"0.042\"", "params[7]: \"42\"");
+        let log = parse_delivery_log(&content);
+
+        let apples = &log.entries[0];
+        assert_eq!(apples.revenue, None);
+        assert_eq!(apples.delivered, None);
+        assert_eq!(apples.damage_percent, None);
+        assert_eq!(apples.xp, Some(620));
+    }
+
+    #[test]
+    fn falls_back_to_unit_order_without_delivery_log() {
+        let log = parse_delivery_log(SAMPLE);
+
+        assert_eq!(log.game_time_min, None);
+        assert_eq!(log.entries.len(), 2);
+        assert_eq!(log.entries[0].pointer, "_nameless.3");
+        assert!((log.entries[0].damage_percent.unwrap_or_default() - 4.2).abs() < 1e-9);
+        assert_eq!(log.entries[1].cargo.as_deref(), Some("cargo.milk"));
+        assert_eq!(log.entries[1].revenue, None);
+    }
+
+    #[test]
+    fn follows_the_delivery_log_order() {
+        let content = REAL_SAVE.replace(
+            " entries[0]: _nameless.279.56cb.8c70\n entries[1]: _nameless.279.5688.e730",
+            " entries[0]: _nameless.279.5688.e730\n entries[1]: _nameless.279.56cb.8c70",
+        );
+        let log = parse_delivery_log(&content);
+
+        assert_eq!(log.entries[0].pointer, "_nameless.279.5688.e730");
+        assert_eq!(log.entries[0].index, 0);
+        assert_eq!(log.entries[1].pointer, "_nameless.279.56cb.8c70");
+    }
+}
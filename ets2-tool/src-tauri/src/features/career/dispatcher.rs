@@ -13,10 +13,14 @@ use crate::features::economy::compensation_models::{
 use crate::features::economy::compensation_service;
 use crate::shared::sqlite_schema::ensure_columns;
 
+mod chains;
 mod generation;
 mod models;
+mod negotiation;
 mod repo;
+mod save_market;
 mod schema;
+mod settlement;
 
 use models::{
     DISPATCHER_ACTIVE_JOB_STATUSES, DISPATCHER_MAX_GENERATION_BATCH, DISPATCHER_OPEN_JOB_STATUSES,
@@ -24,10 +28,13 @@ use models::{
 };
 #[allow(unused_imports)]
 pub use models::{
-    DispatcherCompanyContact, DispatcherCreateOfferInput, DispatcherGenerationConfigInput,
+    DispatcherChain, DispatcherChainLeg, DispatcherChainLegInput, DispatcherChainProgress,
+    DispatcherCompanyContact, DispatcherCompanyPersona, DispatcherCreateChainInput,
+    DispatcherCreateOfferInput, DispatcherDeliveryReport, DispatcherGenerationConfigInput,
     DispatcherGenerationRunResult, DispatcherGenerationStatus, DispatcherHistoryResponse,
-    DispatcherHistorySummary, DispatcherJobDetails, DispatcherJobFilter,
-    DispatcherJobsBySaveContextResponse, DispatcherMarketJob, DispatcherOffer, DispatcherOverview,
+    DispatcherHistorySummary, DispatcherJobDetails, DispatcherJobFilter, DispatcherJobSettlement,
+    DispatcherJobsBySaveContextResponse, DispatcherMarketJob, DispatcherNegotiation,
+    DispatcherNegotiationRound, DispatcherOffer, DispatcherOverview,
     DispatcherRespondToCounterInput, DispatcherSaveContext,
 };
 use schema::{ensure_dispatcher_generation_config, ensure_dispatcher_tables};
@@ -503,6 +510,32 @@ pub fn current_assignment(conn: &Connection) -> Result<Option<JobAssignment>, St
     }))
 }
 
+/// Id of the linked dispatcher job a trip started at `trip_started_at_utc` drove: one still
+/// running in the game, or one the game reported as finished since the trip began. Those jobs
+/// are paid and rated by the settlement, not by the logbook.
+pub fn linked_job_for_trip(
+    conn: &Connection,
+    trip_started_at_utc: &str,
+) -> Result<Option<String>, String> {
+    if !chains::table_exists(conn, "dispatcher_jobs")? {
+        return Ok(None);
+    }
+    conn.query_row(
+        r#"
+        SELECT id
+        FROM dispatcher_jobs
+        WHERE ets2_job_link_status IN ('synced', 'completed')
+          AND (status = 'injected' OR completed_at_utc >= ?1)
+        ORDER BY updated_at_utc DESC
+        LIMIT 1
+        "#,
+        [trip_started_at_utc],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
 pub fn load_job_pricing_context(
     conn: &Connection,
     job_id: &str,
@@ -1268,6 +1301,24 @@ fn dispatcher_average_speed_kmh(urgency: Urgency) -> f64 {
     }
 }
 
+fn dispatcher_estimated_duration_minutes(distance_km: f64, urgency: Urgency) -> i64 {
+    ((distance_km / dispatcher_average_speed_kmh(urgency)) * 60.0).ceil() as i64 + 35
+}
+
+/// Distance of a dispatcher template lane between two cities, in either direction.
+fn template_lane_distance_km(from_city: &str, to_city: &str) -> Option<f64> {
+    DISPATCHER_TEMPLATES
+        .iter()
+        .find(|template| {
+            let forward = template.origin_city.eq_ignore_ascii_case(from_city)
+                && template.destination_city.eq_ignore_ascii_case(to_city);
+            let backward = template.origin_city.eq_ignore_ascii_case(to_city)
+                && template.destination_city.eq_ignore_ascii_case(from_city);
+            forward || backward
+        })
+        .map(|template| template.distance_km)
+}
+
 fn load_dispatcher_open_signatures(
     conn: &Connection,
     save_context: &DispatcherSaveContext,
@@ -1369,6 +1420,27 @@ fn generate_dispatcher_jobs_for_context(
         hasher.finish()
     };
 
+    // A readable save decides the market: only depot/cargo/trailer combinations the game
+    // itself offers are used, even when none of them is left. The built-in templates are
+    // only for saves we cannot read.
+    if let Some(offers) = save_market::load_save_market_offers(save_context) {
+        let created = save_market::generate_save_market_jobs(
+            conn,
+            save_context,
+            &offers,
+            count,
+            &mut signatures,
+            base_seed,
+        )?;
+        if created == 0 {
+            crate::dev_log!(
+                "[dispatcher] save market yielded no jobs from {} offers",
+                offers.len()
+            );
+        }
+        return Ok(created);
+    }
+
     let mut created = 0usize;
     let max_attempts = (count * 12).max(DISPATCHER_TEMPLATES.len() * 4);
 
@@ -1417,9 +1489,7 @@ fn generate_dispatcher_jobs_for_context(
         let insurance_cost = (economy_state.insurance_daily_cost / 6).max(45);
         let profit_estimate = total_reward - fuel_cost - toll_cost - insurance_cost;
         let estimated_duration_minutes =
-            ((template.distance_km / dispatcher_average_speed_kmh(template.urgency)) * 60.0).ceil()
-                as i64
-                + 35;
+            dispatcher_estimated_duration_minutes(template.distance_km, template.urgency);
         let now = Utc::now();
         let expires_at = now
             + Duration::hours(match template.urgency {
@@ -1705,6 +1775,7 @@ fn map_dispatcher_job_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Dispatche
         ets2_job_link_status: row.get("ets2_job_link_status")?,
         last_error_code: row.get("last_error_code")?,
         last_error_message: row.get("last_error_message")?,
+        save_cargo: row.get("save_cargo")?,
         accepted_at_utc: row.get("accepted_at_utc")?,
         completed_at_utc: row.get("completed_at_utc")?,
         created_at_utc: row.get("created_at_utc")?,
@@ -1894,6 +1965,7 @@ fn to_dispatcher_market_job(row: DispatcherJobRow) -> DispatcherMarketJob {
         ets2_job_link_status: row.ets2_job_link_status,
         last_error_code: row.last_error_code,
         last_error_message: row.last_error_message,
+        save_cargo: row.save_cargo,
         accepted_at_utc: row.accepted_at_utc,
         completed_at_utc: row.completed_at_utc,
     }
@@ -2123,7 +2195,96 @@ pub fn dispatcher_cancel_job(
     job_id: &str,
     save_context: &DispatcherSaveContext,
 ) -> Result<DispatcherJobDetails, String> {
-    repo::dispatcher_cancel_job(conn, job_id, save_context)
+    let details = repo::dispatcher_cancel_job(conn, job_id, save_context)?;
+    chains::advance_chain(conn, job_id, false)?;
+    Ok(details)
+}
+
+pub fn dispatcher_claim_job_for_employee(
+    conn: &Connection,
+    employee_id: &str,
+    difficulties: &[&str],
+    max_distance_km: f64,
+    save_context: &DispatcherSaveContext,
+) -> Result<Option<DispatcherMarketJob>, String> {
+    repo::dispatcher_claim_job_for_employee(
+        conn,
+        employee_id,
+        difficulties,
+        max_distance_km,
+        save_context,
+    )
+}
+
+pub fn dispatcher_finish_employee_job(
+    conn: &Connection,
+    job_id: &str,
+    employee_id: &str,
+    delivered_with_incident: bool,
+) -> Result<bool, String> {
+    repo::dispatcher_finish_employee_job(conn, job_id, employee_id, delivered_with_incident)
+}
+
+pub fn dispatcher_create_chain(
+    conn: &Connection,
+    input: DispatcherCreateChainInput,
+    save_context: &DispatcherSaveContext,
+) -> Result<DispatcherChain, String> {
+    chains::create_chain(conn, input, save_context)
+}
+
+pub fn dispatcher_get_chain(conn: &Connection, chain_id: &str) -> Result<DispatcherChain, String> {
+    prepare_dispatcher_system(conn)?;
+    chains::load_chain(conn, chain_id)
+}
+
+pub fn dispatcher_get_chains(
+    conn: &Connection,
+    limit: usize,
+) -> Result<Vec<DispatcherChain>, String> {
+    chains::list_chains(conn, limit)
+}
+
+pub fn dispatcher_cancel_chain(
+    conn: &Connection,
+    chain_id: &str,
+) -> Result<DispatcherChain, String> {
+    chains::cancel_chain(conn, chain_id)
+}
+
+pub fn dispatcher_record_chain_save_target(
+    conn: &Connection,
+    job_id: &str,
+    slot: Option<&str>,
+    clone_to_new_slot: bool,
+) -> Result<(), String> {
+    chains::record_chain_save_target(conn, job_id, slot, clone_to_new_slot)
+}
+
+pub fn dispatcher_chain_save_target(
+    conn: &Connection,
+    job_id: &str,
+) -> Result<Option<(Option<String>, bool)>, String> {
+    chains::chain_save_target(conn, job_id)
+}
+
+/// Called once a leg reached a terminal state in the game; releases the next leg of its
+/// chain or breaks the chain. Returns `None` for jobs that are not chain legs.
+pub fn dispatcher_advance_chain(
+    conn: &Connection,
+    job_id: &str,
+    delivered: bool,
+) -> Result<Option<DispatcherChainProgress>, String> {
+    chains::advance_chain(conn, job_id, delivered)
+}
+
+pub fn dispatcher_settle_linked_job(
+    conn: &Connection,
+    job_id: &str,
+    link_id: &str,
+    report: &DispatcherDeliveryReport,
+) -> Result<Option<DispatcherJobSettlement>, String> {
+    settlement::settle_linked_job(conn, job_id, link_id, report)
 }
 
 pub fn dispatcher_get_job_history(
@@ -2266,7 +2427,14 @@ pub fn dispatcher_get_offers(conn: &Connection) -> Result<Vec<DispatcherOffer>,
                 linked_job_id,
                 created_at_utc,
                 updated_at_utc,
-                expires_at_utc
+                expires_at_utc,
+                proposed_deadline_hours,
+                proposed_bonus,
+                counter_deadline_hours,
+                counter_bonus,
+                final_deadline_hours,
+                final_bonus,
+                negotiation_round
             FROM dispatcher_offers
             ORDER BY created_at_utc DESC
             LIMIT 200
@@ -2326,51 +2494,42 @@ pub fn dispatcher_create_offer(
             market_seed: Utc::now().timestamp_millis().unsigned_abs(),
         },
     )?;
-    let target_rate = target_pricing.final_rate_per_km.max(0.55);
-
-    let success_rate = dispatcher_success_rate_for_company(conn, company_id)?;
-    let score = dispatcher_offer_acceptance_score(
+    let target = negotiation::NegotiationTerms::normalized(
+        target_pricing.final_rate_per_km.max(0.55),
+        negotiation::NEGOTIATION_DEFAULT_DEADLINE_HOURS,
+        0,
+    );
+    let proposal = negotiation::NegotiationTerms::normalized(
         requested_rate,
-        target_rate,
-        profile.payment_tier,
-        reputation.reputation,
-        success_rate,
+        input
+            .proposed_deadline_hours
+            .unwrap_or(negotiation::NEGOTIATION_DEFAULT_DEADLINE_HOURS),
+        input.proposed_bonus.unwrap_or(0),
     );
 
+    let success_rate = dispatcher_success_rate_for_company(conn, company_id)?;
+    let persona = negotiation::company_persona(&profile, &reputation, success_rate);
+    let decision = negotiation::evaluate_proposal(&persona, &target, &proposal, 1);
+
     let now = Utc::now();
-    let default_expiry = Some((now + Duration::hours(36)).to_rfc3339());
-    let (status, counter_rate, final_rate, response_reason, expires) = if score >= 72.0 {
-        (
-            "accepted".to_string(),
-            None,
-            Some(requested_rate),
-            Some("offer_aligned_with_profile".to_string()),
+    let (status, counter, accepted, response_reason, expires) = match decision {
+        negotiation::NegotiationDecision::Accept => (
+            "accepted",
             None,
-        )
-    } else if score >= 48.0 {
-        (
-            "countered".to_string(),
-            Some(((requested_rate + target_rate) / 2.0 * 100.0).round() / 100.0),
-            None,
-            Some("counter_offer_requested".to_string()),
-            default_expiry,
-        )
-    } else if score >= 38.0 {
-        (
-            "under_review".to_string(),
-            None,
-            None,
-            Some("manual_review_pending".to_string()),
-            Some((now + Duration::hours(18)).to_rfc3339()),
-        )
-    } else {
-        (
-            "rejected".to_string(),
+            Some(proposal),
+            "offer_aligned_with_profile".to_string(),
             None,
+        ),
+        negotiation::NegotiationDecision::Counter(terms) => (
+            "countered",
+            Some(terms),
             None,
-            Some("rate_above_company_band".to_string()),
-            None,
-        )
+            "counter_offer_requested".to_string(),
+            Some((now + Duration::hours(36)).to_rfc3339()),
+        ),
+        negotiation::NegotiationDecision::WalkAway(reason) => {
+            ("rejected", None, None, reason.to_string(), None)
+        }
     };
 
     let offer_id = format!(
@@ -2418,10 +2577,20 @@ pub fn dispatcher_create_offer(
             linked_job_id,
             created_at_utc,
             updated_at_utc,
-            expires_at_utc
+            expires_at_utc,
+            proposed_deadline_hours,
+            proposed_bonus,
+            counter_deadline_hours,
+            counter_bonus,
+            final_deadline_hours,
+            final_bonus,
+            target_rate_per_km,
+            target_deadline_hours,
+            negotiation_round
         )
         VALUES (
-            ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, NULL, ?17, ?17, ?18
+            ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, NULL, ?17, ?17,
+            ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, 1
         )
         "#,
         params![
@@ -2438,14 +2607,26 @@ pub fn dispatcher_create_offer(
             Some(equipment_type),
             input.contract_scope,
             status,
-            counter_rate,
-            final_rate,
+            counter.map(|terms| terms.rate_per_km),
+            accepted.map(|terms| terms.rate_per_km),
             response_reason,
             created,
             expires,
+            proposal.deadline_hours,
+            proposal.bonus,
+            counter.map(|terms| terms.deadline_hours),
+            counter.map(|terms| terms.bonus),
+            accepted.map(|terms| terms.deadline_hours),
+            accepted.map(|terms| terms.bonus),
+            target.rate_per_km,
+            target.deadline_hours,
         ],
     )
     .map_err(|e| e.to_string())?;
+    negotiation::record_negotiation_round(
+        conn, &offer_id, 1, "player", &proposal, "proposed", None,
+    )?;
+    record_company_negotiation_decision(conn, &offer_id, 1, &proposal, &decision)?;
 
     let mut created_offer = load_dispatcher_offer_by_id(conn, &offer_id)?
         .ok_or_else(|| "dispatcher_offer_not_found_after_insert".to_string())?;
@@ -2493,19 +2674,117 @@ pub fn dispatcher_respond_to_counter(
         return Err("dispatcher_offer_not_countered".to_string());
     }
 
+    let counter = negotiation::NegotiationTerms::normalized(
+        offer.counter_rate_per_km.unwrap_or(0.0),
+        offer
+            .counter_deadline_hours
+            .unwrap_or(negotiation::NEGOTIATION_DEFAULT_DEADLINE_HOURS),
+        offer.counter_bonus.unwrap_or(0),
+    );
     let now = Utc::now().to_rfc3339();
+    let proposes_terms = input.counter_rate_per_km.is_some()
+        || input.counter_deadline_hours.is_some()
+        || input.counter_bonus.is_some();
+
     if input.accept_counter {
         conn.execute(
-            "UPDATE dispatcher_offers SET status = 'accepted', final_rate_per_km = counter_rate_per_km, response_reason = 'counter_accepted', updated_at_utc = ?2, expires_at_utc = NULL WHERE id = ?1",
+            "UPDATE dispatcher_offers SET status = 'accepted', final_rate_per_km = counter_rate_per_km, final_deadline_hours = counter_deadline_hours, final_bonus = counter_bonus, response_reason = 'counter_accepted', updated_at_utc = ?2, expires_at_utc = NULL WHERE id = ?1",
             params![input.offer_id, now],
         )
         .map_err(|e| e.to_string())?;
+        negotiation::record_negotiation_round(
+            conn,
+            &offer.id,
+            offer.negotiation_round,
+            "player",
+            &counter,
+            "accepted",
+            None,
+        )?;
+    } else if proposes_terms {
+        let proposal = negotiation::NegotiationTerms::normalized(
+            input.counter_rate_per_km.unwrap_or(counter.rate_per_km),
+            input
+                .counter_deadline_hours
+                .unwrap_or(counter.deadline_hours),
+            input.counter_bonus.unwrap_or(counter.bonus),
+        );
+        let round = offer.negotiation_round + 1;
+        let (target_rate, target_deadline): (Option<f64>, Option<i64>) = conn
+            .query_row(
+                "SELECT target_rate_per_km, target_deadline_hours FROM dispatcher_offers WHERE id = ?1",
+                [offer.id.as_str()],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .map_err(|e| e.to_string())?;
+        // Offers from before negotiation rounds existed only know the company's counter.
+        let target = negotiation::NegotiationTerms::normalized(
+            target_rate.unwrap_or(counter.rate_per_km),
+            target_deadline.unwrap_or(negotiation::NEGOTIATION_DEFAULT_DEADLINE_HOURS),
+            0,
+        );
+        let persona = negotiation::load_company_persona(conn, &offer.company_id)?;
+        let decision = negotiation::evaluate_proposal(&persona, &target, &proposal, round);
+        negotiation::record_negotiation_round(
+            conn, &offer.id, round, "player", &proposal, "proposed", None,
+        )?;
+
+        match decision {
+            negotiation::NegotiationDecision::Accept => {
+                conn.execute(
+                    "UPDATE dispatcher_offers SET status = 'accepted', final_rate_per_km = ?2, final_deadline_hours = ?3, final_bonus = ?4, response_reason = 'proposal_accepted', negotiation_round = ?5, updated_at_utc = ?6, expires_at_utc = NULL WHERE id = ?1",
+                    params![
+                        offer.id,
+                        proposal.rate_per_km,
+                        proposal.deadline_hours,
+                        proposal.bonus,
+                        round,
+                        now
+                    ],
+                )
+                .map_err(|e| e.to_string())?;
+            }
+            negotiation::NegotiationDecision::Counter(terms) => {
+                conn.execute(
+                    "UPDATE dispatcher_offers SET counter_rate_per_km = ?2, counter_deadline_hours = ?3, counter_bonus = ?4, response_reason = 'counter_offer_requested', negotiation_round = ?5, updated_at_utc = ?6, expires_at_utc = ?7 WHERE id = ?1",
+                    params![
+                        offer.id,
+                        terms.rate_per_km,
+                        terms.deadline_hours,
+                        terms.bonus,
+                        round,
+                        now,
+                        (Utc::now() + Duration::hours(36)).to_rfc3339()
+                    ],
+                )
+                .map_err(|e| e.to_string())?;
+            }
+            negotiation::NegotiationDecision::WalkAway(reason) => {
+                conn.execute(
+                    "UPDATE dispatcher_offers SET status = 'rejected', response_reason = ?2, negotiation_round = ?3, updated_at_utc = ?4, expires_at_utc = NULL WHERE id = ?1",
+                    params![offer.id, reason, round, now],
+                )
+                .map_err(|e| e.to_string())?;
+                negotiation::apply_negotiation_breakdown(conn, &offer.company_id, round)?;
+            }
+        }
+        record_company_negotiation_decision(conn, &offer.id, round, &proposal, &decision)?;
     } else {
         conn.execute(
             "UPDATE dispatcher_offers SET status = 'rejected', response_reason = 'counter_declined', updated_at_utc = ?2, expires_at_utc = NULL WHERE id = ?1",
             params![input.offer_id, now],
         )
         .map_err(|e| e.to_string())?;
+        negotiation::record_negotiation_round(
+            conn,
+            &offer.id,
+            offer.negotiation_round,
+            "player",
+            &counter,
+            "declined",
+            Some("counter_declined"),
+        )?;
+        negotiation::apply_negotiation_breakdown(conn, &offer.company_id, offer.negotiation_round)?;
     }
 
     let updated = load_dispatcher_offer_by_id(conn, &input.offer_id)?
@@ -2518,6 +2797,56 @@ pub fn dispatcher_respond_to_counter(
         .ok_or_else(|| format!("dispatcher_offer_not_found:{}", input.offer_id))
 }
 
+pub fn dispatcher_get_offer_negotiation(
+    conn: &Connection,
+    offer_id: &str,
+) -> Result<DispatcherNegotiation, String> {
+    prepare_dispatcher_system(conn)?;
+    let offer = load_dispatcher_offer_by_id(conn, offer_id)?
+        .ok_or_else(|| format!("dispatcher_offer_not_found:{offer_id}"))?;
+    let persona = negotiation::load_company_persona(conn, &offer.company_id)?;
+    let rounds = negotiation::load_negotiation_rounds(conn, offer_id)?;
+    Ok(DispatcherNegotiation {
+        offer,
+        persona,
+        rounds,
+    })
+}
+
+fn record_company_negotiation_decision(
+    conn: &Connection,
+    offer_id: &str,
+    round: i64,
+    proposal: &negotiation::NegotiationTerms,
+    decision: &negotiation::NegotiationDecision,
+) -> Result<(), String> {
+    match decision {
+        negotiation::NegotiationDecision::Accept => negotiation::record_negotiation_round(
+            conn, offer_id, round, "company", proposal, "accepted", None,
+        ),
+        negotiation::NegotiationDecision::Counter(terms) => negotiation::record_negotiation_round(
+            conn,
+            offer_id,
+            round,
+            "company",
+            terms,
+            "countered",
+            None,
+        ),
+        negotiation::NegotiationDecision::WalkAway(reason) => {
+            negotiation::record_negotiation_round(
+                conn,
+                offer_id,
+                round,
+                "company",
+                proposal,
+                "walked_away",
+                Some(reason),
+            )
+        }
+    }
+}
+
 fn map_dispatcher_offer_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<DispatcherOffer> {
     Ok(DispatcherOffer {
         id: row.get(0)?,
@@ -2540,6 +2869,13 @@ fn map_dispatcher_offer_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Dispatc
         created_at_utc: row.get(17)?,
         updated_at_utc: row.get(18)?,
         expires_at_utc: row.get(19)?,
+        proposed_deadline_hours: row.get(20)?,
+        proposed_bonus: row.get(21)?,
+        counter_deadline_hours: row.get(22)?,
+        counter_bonus: row.get(23)?,
+        final_deadline_hours: row.get(24)?,
+        final_bonus: row.get(25)?,
+        negotiation_round: row.get(26)?,
     })
 }
 
@@ -2554,7 +2890,9 @@ fn load_dispatcher_offer_by_id(
                 id, company_id, company_name, user_id, offer_type, requested_job_type,
                 requested_cargo_type, requested_region, proposed_rate_per_km, note,
                 equipment_type, contract_scope, status, counter_rate_per_km, final_rate_per_km,
-                response_reason, linked_job_id, created_at_utc, updated_at_utc, expires_at_utc
+                response_reason, linked_job_id, created_at_utc, updated_at_utc, expires_at_utc,
+                proposed_deadline_hours, proposed_bonus, counter_deadline_hours, counter_bonus,
+                final_deadline_hours, final_bonus, negotiation_round
             FROM dispatcher_offers
             WHERE id = ?1
             "#,
@@ -2610,11 +2948,13 @@ fn maybe_create_dispatcher_contract(
             agreed_rate_modifier,
             preferred_cargo_type,
             region_scope,
+            deadline_hours,
+            bonus,
             active_from_utc,
             active_until_utc,
             status
         )
-        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, NULL, 'active')
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, NULL, 'active')
         "#,
         params![
             format!("contract-{}", offer.id),
@@ -2624,6 +2964,8 @@ fn maybe_create_dispatcher_contract(
             rate_modifier,
             offer.requested_cargo_type,
             offer.requested_region,
+            offer.final_deadline_hours,
+            offer.final_bonus.unwrap_or(0).max(0),
             Utc::now().to_rfc3339(),
         ],
     )
@@ -2631,6 +2973,47 @@ fn maybe_create_dispatcher_contract(
     Ok(())
 }
 
+/// Copies the deadline and bonus of the company's active contract onto an accepted job so
+/// the settlement pays against the negotiated terms.
+fn attach_dispatcher_contract_terms(
+    conn: &Connection,
+    job_id: &str,
+    company_id: &str,
+    cargo_type: &str,
+) -> Result<(), String> {
+    let contract = conn
+        .query_row(
+            r#"
+            SELECT id, deadline_hours, bonus
+            FROM dispatcher_contracts
+            WHERE company_id = ?1
+              AND status = 'active'
+              AND (preferred_cargo_type IS NULL OR lower(preferred_cargo_type) = lower(?2))
+            ORDER BY active_from_utc DESC
+            LIMIT 1
+            "#,
+            params![company_id, cargo_type],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, Option<i64>>(1)?,
+                    row.get::<_, i64>(2)?,
+                ))
+            },
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    let Some((contract_id, deadline_hours, bonus)) = contract else {
+        return Ok(());
+    };
+    conn.execute(
+        "UPDATE dispatcher_jobs SET contract_id = ?2, contract_deadline_hours = ?3, contract_bonus = ?4 WHERE id = ?1",
+        params![job_id, contract_id, deadline_hours, bonus.max(0)],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
 fn dispatcher_equipment_ok(conn: &Connection, required: &str) -> Result<bool, String> {
     let trucks: i64 = conn
         .query_row(
@@ -2683,41 +3066,6 @@ fn dispatcher_success_rate_for_company(conn: &Connection, company_id: &str) -> R
     Ok((completed as f64 / base).clamp(0.0, 1.0))
 }
 
-fn dispatcher_offer_acceptance_score(
-    requested_rate: f64,
-    target_rate: f64,
-    payment_tier: CompanyPaymentTier,
-    reputation: u16,
-    success_rate: f64,
-) -> f64 {
-    let ratio = if target_rate <= 0.0 {
-        1.0
-    } else {
-        requested_rate / target_rate
-    };
-    let rate_score = if ratio <= 0.92 {
-        93.0
-    } else if ratio <= 1.0 {
-        82.0
-    } else if ratio <= 1.08 {
-        68.0
-    } else if ratio <= 1.16 {
-        46.0
-    } else {
-        20.0
-    };
-    let tier_bonus = match payment_tier {
-        CompanyPaymentTier::Budget => -8.0,
-        CompanyPaymentTier::Standard => 0.0,
-        CompanyPaymentTier::Good => 4.0,
-        CompanyPaymentTier::Premium => 8.0,
-        CompanyPaymentTier::Elite => 12.0,
-    };
-    let reputation_score = (reputation as f64 / 10.0).clamp(0.0, 100.0);
-    (rate_score * 0.5 + reputation_score * 0.25 + success_rate * 100.0 * 0.25 + tier_bonus)
-        .clamp(0.0, 100.0)
-}
-
 fn base_rate_type_for_dispatcher_job(job_type: &str) -> BaseRateType {
     match normalize_dispatcher_job_type(job_type).as_str() {
         "quick_job" => BaseRateType::QuickJob,
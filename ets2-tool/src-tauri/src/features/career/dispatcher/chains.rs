@@ -0,0 +1,934 @@
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension, params};
+use uuid::Uuid;
+
+use crate::features::economy;
+use crate::features::economy::compensation_models::{JobCompensationInput, Urgency};
+use crate::features::economy::compensation_service;
+
+use super::models::{
+    DISPATCHER_HISTORY_JOB_STATUSES, DispatcherChain, DispatcherChainLeg, DispatcherChainProgress,
+    DispatcherCreateChainInput, DispatcherSaveContext,
+};
+use super::{
+    base_rate_type_for_dispatcher_job, build_dispatcher_route_reference,
+    cargo_type_from_dispatcher_string, dispatcher_estimated_duration_minutes,
+    dispatcher_job_type_modifier, dispatcher_risk_note, equipment_type_for_dispatcher_job,
+    normalize_dispatcher_cargo_type, normalize_dispatcher_job_type, payment_tier_to_db,
+    prepare_dispatcher_system, template_lane_distance_km,
+};
+
+const CHAIN_MIN_LEGS: usize = 2;
+const CHAIN_MAX_LEGS: usize = 6;
+const CHAIN_BONUS_PER_EXTRA_LEG: f64 = 0.04;
+const CHAIN_BONUS_CAP: f64 = 0.12;
+/// Empty repositioning between two legs is paid at this share of the average chain rate.
+const CHAIN_DEADHEAD_RATE_SHARE: f64 = 0.5;
+
+#[derive(Debug, Clone, PartialEq)]
+struct ChainPricing {
+    leg_rewards: Vec<i64>,
+    legs_total_reward: i64,
+    deadhead_compensation: i64,
+    chain_bonus: i64,
+    total_reward: i64,
+}
+
+/// Prices the chain as one contract: the stand-alone leg prices, compensation for empty
+/// repositioning between legs and a commitment bonus per extra leg. The total is split
+/// across the legs in proportion to their stand-alone price so the legs add up exactly.
+fn price_chain(legs: &[(f64, i64)], deadhead_km: f64) -> ChainPricing {
+    let legs_total_reward: i64 = legs.iter().map(|(_, reward)| *reward).sum();
+    let total_distance: f64 = legs.iter().map(|(distance, _)| *distance).sum();
+    let average_rate = if total_distance > 0.0 {
+        legs_total_reward as f64 / total_distance
+    } else {
+        0.0
+    };
+    let deadhead_compensation =
+        (deadhead_km.max(0.0) * average_rate * CHAIN_DEADHEAD_RATE_SHARE).round() as i64;
+    let bonus_share =
+        (CHAIN_BONUS_PER_EXTRA_LEG * legs.len().saturating_sub(1) as f64).min(CHAIN_BONUS_CAP);
+    let chain_bonus = (legs_total_reward as f64 * bonus_share).round() as i64;
+    let total_reward = legs_total_reward + deadhead_compensation + chain_bonus;
+
+    let mut leg_rewards = Vec::with_capacity(legs.len());
+    let mut assigned = 0i64;
+    for (index, (_, reward)) in legs.iter().enumerate() {
+        let share = if index + 1 == legs.len() {
+            total_reward - assigned
+        } else if legs_total_reward > 0 {
+            (total_reward as f64 * *reward as f64 / legs_total_reward as f64).round() as i64
+        } else {
+            total_reward / legs.len() as i64
+        };
+        assigned += share;
+        leg_rewards.push(share);
+    }
+
+    ChainPricing {
+        leg_rewards,
+        legs_total_reward,
+        deadhead_compensation,
+        chain_bonus,
+        total_reward,
+    }
+}
+
+pub(super) fn urgency_from_level(level: &str) -> Urgency {
+    match level {
+        "critical" => Urgency::Express,
+        "high" => Urgency::Priority,
+        _ => Urgency::Normal,
+    }
+}
+
+fn normalize_urgency_level(value: Option<&str>) -> &'static str {
+    match value
+        .map(|value| value.trim().to_ascii_lowercase())
+        .as_deref()
+    {
+        Some("critical" | "express") => "critical",
+        Some("high" | "priority") => "high",
+        _ => "normal",
+    }
+}
+
+struct PlannedLeg {
+    company_id: String,
+    company_name: String,
+    job_type: String,
+    cargo_type: String,
+    cargo_label: String,
+    origin_city: String,
+    origin_country: String,
+    destination_city: String,
+    destination_country: String,
+    cargo_mass_kg: f64,
+    distance_km: f64,
+    estimated_duration_minutes: i64,
+    fuel_cost: i64,
+    toll_cost: i64,
+    standalone_reward: i64,
+    pricing: economy::compensation_models::JobCompensationResult,
+    final_rate: f64,
+}
+
+pub(super) fn create_chain(
+    conn: &Connection,
+    input: DispatcherCreateChainInput,
+    save_context: &DispatcherSaveContext,
+) -> Result<DispatcherChain, String> {
+    prepare_dispatcher_system(conn)?;
+    if input.legs.len() < CHAIN_MIN_LEGS {
+        return Err("dispatcher_chain_needs_two_legs".to_string());
+    }
+    if input.legs.len() > CHAIN_MAX_LEGS {
+        return Err("dispatcher_chain_too_many_legs".to_string());
+    }
+
+    let economy_state = economy::load_state(conn)?;
+    let urgency_level = normalize_urgency_level(input.urgency_level.as_deref());
+    let urgency = urgency_from_level(urgency_level);
+    let seed = Utc::now().timestamp_millis() as u64;
+
+    let mut planned = Vec::with_capacity(input.legs.len());
+    for (index, leg) in input.legs.iter().enumerate() {
+        let company_id = leg.company_id.trim();
+        if company_id.is_empty()
+            || leg.origin_city.trim().is_empty()
+            || leg.destination_city.trim().is_empty()
+        {
+            return Err(format!("dispatcher_chain_leg_incomplete:{index}"));
+        }
+
+        let Some(distance_km) = leg
+            .distance_km
+            .filter(|distance| *distance > 0.0)
+            .or_else(|| {
+                template_lane_distance_km(leg.origin_city.trim(), leg.destination_city.trim())
+            })
+        else {
+            return Err(format!("dispatcher_chain_leg_distance_unknown:{index}"));
+        };
+
+        let job_type =
+            normalize_dispatcher_job_type(leg.job_type.as_deref().unwrap_or("freight_market"));
+        let cargo_type = normalize_dispatcher_cargo_type(leg.cargo_type.as_deref().unwrap_or(""));
+        let profile = compensation_service::load_company_payment_profile(conn, company_id, None)?;
+        let pricing = compensation_service::calculate_job_compensation(
+            conn,
+            &JobCompensationInput {
+                company_id: company_id.to_string(),
+                company_name: profile.company_name.clone(),
+                distance_km,
+                base_rate_type: base_rate_type_for_dispatcher_job(&job_type),
+                equipment_type: equipment_type_for_dispatcher_job("own_truck"),
+                cargo_type: cargo_type_from_dispatcher_string(&cargo_type),
+                urgency,
+                origin_country_code: leg.origin_country.trim().to_string(),
+                destination_country_code: leg.destination_country.trim().to_string(),
+                market_seed: seed + index as u64,
+            },
+        )?;
+        let final_rate = pricing.final_rate_per_km * dispatcher_job_type_modifier(&job_type);
+
+        planned.push(PlannedLeg {
+            company_name: profile
+                .company_name
+                .clone()
+                .unwrap_or_else(|| company_id.to_string()),
+            company_id: company_id.to_string(),
+            cargo_label: leg
+                .cargo_label
+                .clone()
+                .filter(|label| !label.trim().is_empty())
+                .unwrap_or_else(|| cargo_type.clone()),
+            job_type,
+            cargo_type,
+            origin_city: leg.origin_city.trim().to_string(),
+            origin_country: leg.origin_country.trim().to_string(),
+            destination_city: leg.destination_city.trim().to_string(),
+            destination_country: leg.destination_country.trim().to_string(),
+            cargo_mass_kg: leg.cargo_mass_kg.unwrap_or(12000.0),
+            distance_km,
+            estimated_duration_minutes: dispatcher_estimated_duration_minutes(distance_km, urgency),
+            fuel_cost: (distance_km * 0.31 * economy_state.diesel_price_per_liter).round() as i64,
+            toll_cost: (distance_km * economy_state.toll_per_km).round() as i64,
+            standalone_reward: (distance_km * final_rate).round() as i64,
+            pricing,
+            final_rate,
+        });
+    }
+
+    let mut deadhead_km = 0.0;
+    for pair in planned.windows(2) {
+        let (previous, next) = (&pair[0], &pair[1]);
+        if previous
+            .destination_city
+            .eq_ignore_ascii_case(&next.origin_city)
+        {
+            continue;
+        }
+        // Empty runs between lanes no template covers are not compensated.
+        deadhead_km +=
+            template_lane_distance_km(&previous.destination_city, &next.origin_city).unwrap_or(0.0);
+    }
+
+    let chain_pricing = price_chain(
+        &planned
+            .iter()
+            .map(|leg| (leg.distance_km, leg.standalone_reward))
+            .collect::<Vec<_>>(),
+        deadhead_km,
+    );
+    let chain_id = format!("chain-{}", Uuid::new_v4().simple());
+    let title = input
+        .title
+        .as_deref()
+        .map(str::trim)
+        .filter(|title| !title.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| {
+            std::iter::once(planned[0].origin_city.as_str())
+                .chain(planned.iter().map(|leg| leg.destination_city.as_str()))
+                .collect::<Vec<_>>()
+                .join(" → ")
+        });
+    let now = Utc::now().to_rfc3339();
+
+    // The chain and all of its legs are created together or not at all.
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    tx.execute(
+        r#"
+        INSERT INTO dispatcher_job_chains (
+            id, title, status, leg_count, current_leg, legs_total_reward, deadhead_km,
+            deadhead_compensation, chain_bonus, total_reward, created_at_utc, updated_at_utc
+        )
+        VALUES (?1, ?2, 'active', ?3, 0, ?4, ?5, ?6, ?7, ?8, ?9, ?9)
+        "#,
+        params![
+            chain_id,
+            title,
+            planned.len() as i64,
+            chain_pricing.legs_total_reward,
+            deadhead_km,
+            chain_pricing.deadhead_compensation,
+            chain_pricing.chain_bonus,
+            chain_pricing.total_reward,
+            now,
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    let insurance_cost = (economy_state.insurance_daily_cost / 6).max(45);
+    for (index, (leg, reward)) in planned
+        .iter()
+        .zip(chain_pricing.leg_rewards.iter())
+        .enumerate()
+    {
+        let (status, accepted_at) = if index == 0 {
+            ("accepted", Some(now.as_str()))
+        } else {
+            ("chain_waiting", None)
+        };
+        let bonus_note = format!("Chain leg {}/{}: {}", index + 1, planned.len(), title);
+
+        tx.execute(
+            r#"
+            INSERT INTO dispatcher_jobs (
+                id, source_type, company_id, company_name, job_type, cargo_type,
+                origin_city, origin_country, destination_city, destination_country,
+                distance_km, cargo_mass_kg, urgency_level, difficulty_level,
+                equipment_type_required, trailer_type_required, base_rate_per_km,
+                calculated_rate_per_km, total_reward, estimated_duration_minutes,
+                payment_tier_snapshot, payment_multiplier_snapshot, country_multiplier_snapshot,
+                reputation_multiplier_snapshot, cargo_multiplier_snapshot,
+                urgency_multiplier_snapshot, equipment_multiplier_snapshot,
+                market_variation_snapshot, customer_multiplier_snapshot, company_reputation,
+                fuel_cost_estimate, profit_estimate, risk_note, bonus_note,
+                expires_at_utc, status, progress_km, profile_reference, save_reference,
+                quicksave_reference, save_session_id, route_reference, ets2_job_link_status,
+                accepted_at_utc, completed_at_utc, created_at_utc, updated_at_utc,
+                chain_id, chain_leg_index
+            )
+            VALUES (
+                ?1, 'chain', ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, 'normal',
+                'own_truck', NULL, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23,
+                ?24, ?25, ?26, ?27, ?28, ?29, ?30, NULL, ?31, 0, ?32, ?33, ?34, ?35, ?36,
+                'pending_route', ?37, NULL, ?38, ?38, ?39, ?40
+            )
+            "#,
+            params![
+                format!("{chain_id}-leg{}", index + 1),
+                leg.company_id,
+                leg.company_name,
+                leg.job_type,
+                leg.cargo_type,
+                leg.origin_city,
+                leg.origin_country,
+                leg.destination_city,
+                leg.destination_country,
+                leg.distance_km,
+                leg.cargo_mass_kg,
+                urgency_level,
+                leg.pricing.base_rate_per_km,
+                leg.final_rate,
+                reward,
+                leg.estimated_duration_minutes,
+                payment_tier_to_db(leg.pricing.company_payment_tier),
+                leg.pricing.company_payment_multiplier,
+                leg.pricing.country_multiplier,
+                leg.pricing.company_reputation_multiplier,
+                leg.pricing.cargo_multiplier,
+                leg.pricing.urgency_multiplier,
+                leg.pricing.equipment_multiplier,
+                leg.pricing.market_variation,
+                leg.pricing.customer_multiplier,
+                leg.pricing.company_reputation as i64,
+                leg.fuel_cost,
+                reward - leg.fuel_cost - leg.toll_cost - insurance_cost,
+                dispatcher_risk_note("normal", urgency_level),
+                bonus_note,
+                status,
+                save_context.profile_reference.as_deref(),
+                save_context.save_reference.as_deref(),
+                save_context.quicksave_reference.as_deref(),
+                save_context.save_session_id.as_deref(),
+                build_dispatcher_route_reference(
+                    &leg.company_id,
+                    &leg.origin_country,
+                    &leg.origin_city,
+                    &leg.destination_country,
+                    &leg.destination_city,
+                    &leg.job_type,
+                ),
+                accepted_at,
+                now,
+                chain_id,
+                index as i64,
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+
+    crate::dev_log!(
+        "[dispatcher] chain {} created: {} legs, total {} (legs {}, deadhead {} km, bonus {}), cargo {}",
+        chain_id,
+        planned.len(),
+        chain_pricing.total_reward,
+        chain_pricing.legs_total_reward,
+        deadhead_km,
+        chain_pricing.chain_bonus,
+        planned
+            .iter()
+            .map(|leg| leg.cargo_label.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    load_chain(conn, &chain_id)
+}
+
+/// Remembers the save slot the first leg was prepared into, so the following legs are
+/// written the same way. Other jobs are ignored.
+pub(super) fn record_chain_save_target(
+    conn: &Connection,
+    job_id: &str,
+    slot: Option<&str>,
+    clone_to_new_slot: bool,
+) -> Result<(), String> {
+    prepare_dispatcher_system(conn)?;
+    conn.execute(
+        r#"
+        UPDATE dispatcher_job_chains
+        SET save_target_slot = ?2, save_target_clone = ?3
+        WHERE id = (
+            SELECT chain_id FROM dispatcher_jobs WHERE id = ?1 AND chain_leg_index = 0
+        )
+        "#,
+        params![job_id, slot, clone_to_new_slot],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Save slot and clone flag recorded for the chain of `job_id`; `None` for jobs that
+/// are not chain legs.
+pub(super) fn chain_save_target(
+    conn: &Connection,
+    job_id: &str,
+) -> Result<Option<(Option<String>, bool)>, String> {
+    prepare_dispatcher_system(conn)?;
+    conn.query_row(
+        r#"
+        SELECT c.save_target_slot, c.save_target_clone
+        FROM dispatcher_jobs j
+        JOIN dispatcher_job_chains c ON c.id = j.chain_id
+        WHERE j.id = ?1
+        "#,
+        [job_id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+pub(super) fn table_exists(conn: &Connection, table: &str) -> Result<bool, String> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?1",
+        [table],
+        |row| row.get::<_, i64>(0),
+    )
+    .map(|count| count > 0)
+    .map_err(|e| e.to_string())
+}
+
+pub(super) fn load_chain(conn: &Connection, chain_id: &str) -> Result<DispatcherChain, String> {
+    let mut chain = conn
+        .query_row(
+            r#"
+            SELECT id, title, status, leg_count, legs_total_reward, deadhead_km,
+                   deadhead_compensation, chain_bonus, total_reward, created_at_utc,
+                   completed_at_utc
+            FROM dispatcher_job_chains
+            WHERE id = ?1
+            "#,
+            [chain_id],
+            |row| {
+                Ok(DispatcherChain {
+                    id: row.get(0)?,
+                    title: row.get(1)?,
+                    status: row.get(2)?,
+                    leg_count: row.get(3)?,
+                    completed_legs: 0,
+                    current_leg_job_id: None,
+                    legs_total_reward: row.get(4)?,
+                    deadhead_km: row.get(5)?,
+                    deadhead_compensation: row.get(6)?,
+                    chain_bonus: row.get(7)?,
+                    total_reward: row.get(8)?,
+                    earned_reward: 0,
+                    legs: Vec::new(),
+                    created_at_utc: row.get(9)?,
+                    completed_at_utc: row.get(10)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("dispatcher_chain_not_found:{chain_id}"))?;
+
+    let ledger_query = if table_exists(conn, "vtc_job_ledger")? {
+        "(SELECT MAX(revenue) FROM vtc_job_ledger l WHERE l.vtc_job_id = j.id AND l.event_type = 'job_delivered')"
+    } else {
+        "NULL"
+    };
+    let mut stmt = conn
+        .prepare(&format!(
+            r#"
+            SELECT j.chain_leg_index, j.id, j.company_name, j.origin_city, j.destination_city,
+                   j.cargo_type, j.distance_km, j.total_reward, j.status, j.ets2_job_link_status,
+                   {ledger_query}, j.completed_at_utc
+            FROM dispatcher_jobs j
+            WHERE j.chain_id = ?1
+            ORDER BY j.chain_leg_index
+            "#
+        ))
+        .map_err(|e| e.to_string())?;
+    chain.legs = stmt
+        .query_map([chain_id], |row| {
+            Ok(DispatcherChainLeg {
+                leg_index: row.get(0)?,
+                job_id: row.get(1)?,
+                company_name: row.get(2)?,
+                origin_city: row.get(3)?,
+                destination_city: row.get(4)?,
+                cargo_type: row.get(5)?,
+                distance_km: row.get(6)?,
+                total_reward: row.get(7)?,
+                status: row.get(8)?,
+                ets2_job_link_status: row.get(9)?,
+                ledger_revenue: row.get(10)?,
+                completed_at_utc: row.get(11)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let completed = chain.legs.iter().filter(|leg| leg.status == "completed");
+    chain.completed_legs = completed.clone().count() as i64;
+    chain.earned_reward = completed.map(|leg| leg.total_reward).sum();
+    chain.current_leg_job_id = (chain.status == "active")
+        .then(|| {
+            chain
+                .legs
+                .iter()
+                .find(|leg| leg.status != "completed" && leg.status != "chain_waiting")
+                .map(|leg| leg.job_id.clone())
+        })
+        .flatten();
+
+    Ok(chain)
+}
+
+pub(super) fn list_chains(conn: &Connection, limit: usize) -> Result<Vec<DispatcherChain>, String> {
+    prepare_dispatcher_system(conn)?;
+    let ids = conn
+        .prepare(
+            "SELECT id FROM dispatcher_job_chains ORDER BY status = 'active' DESC, updated_at_utc DESC LIMIT ?1",
+        )
+        .map_err(|e| e.to_string())?
+        .query_map([limit as i64], |row| row.get::<_, String>(0))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    ids.iter().map(|id| load_chain(conn, id)).collect()
+}
+
+/// Records a chain event in the job ledger so partial completion is visible next to the
+/// per-leg delivery entries. Revenue stays empty; the leg delivery already carries it.
+fn insert_chain_ledger_event(
+    conn: &Connection,
+    job_id: &str,
+    event_type: &str,
+    chain: &DispatcherChain,
+) -> Result<(), String> {
+    if !table_exists(conn, "vtc_job_ledger")? || !table_exists(conn, "ets_job_links")? {
+        return Ok(());
+    }
+    let link_id = conn
+        .query_row(
+            "SELECT link_id FROM ets_job_links WHERE vtc_job_id = ?1 ORDER BY updated_at_utc DESC LIMIT 1",
+            [job_id],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    let Some(link_id) = link_id else {
+        return Ok(());
+    };
+
+    let payload = serde_json::json!({
+        "chainId": chain.id,
+        "completedLegs": chain.completed_legs,
+        "legCount": chain.leg_count,
+        "earnedReward": chain.earned_reward,
+        "totalReward": chain.total_reward,
+    });
+    conn.execute(
+        r#"
+        INSERT INTO vtc_job_ledger (
+            link_id, vtc_job_id, event_type, revenue, payload_json, created_at_utc
+        )
+        VALUES (?1, ?2, ?3, NULL, ?4, ?5)
+        "#,
+        params![
+            link_id,
+            job_id,
+            event_type,
+            payload.to_string(),
+            Utc::now().to_rfc3339()
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Leg statuses a cancelled chain takes back: waiting legs and the leg currently driven.
+const CHAIN_CANCELLABLE_LEG_STATUSES: &str = "'chain_waiting', 'assigned_to_save', 'prepared', 'injected', 'planned', 'accepted', 'in_transit', 'delayed'";
+
+/// Moves a chain forward after one of its legs reached a terminal state. A delivered leg
+/// releases the next leg (inheriting the save context of the delivered one) and the chain
+/// completes with its last leg; a failed or cancelled leg breaks the chain and cancels the
+/// legs that were still waiting. Legs that are not terminal yet, or were already handled,
+/// leave the chain unchanged. Returns `None` for jobs that are not part of a chain.
+pub(super) fn advance_chain(
+    conn: &Connection,
+    job_id: &str,
+    delivered: bool,
+) -> Result<Option<DispatcherChainProgress>, String> {
+    prepare_dispatcher_system(conn)?;
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    let progress = advance_chain_in(&tx, job_id, delivered)?;
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(progress)
+}
+
+fn advance_chain_in(
+    conn: &Connection,
+    job_id: &str,
+    delivered: bool,
+) -> Result<Option<DispatcherChainProgress>, String> {
+    let leg = conn
+        .query_row(
+            "SELECT chain_id, chain_leg_index, status FROM dispatcher_jobs WHERE id = ?1",
+            [job_id],
+            |row| {
+                Ok((
+                    row.get::<_, Option<String>>(0)?,
+                    row.get::<_, Option<i64>>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            },
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    let Some((Some(chain_id), Some(leg_index), leg_status)) = leg else {
+        return Ok(None);
+    };
+
+    let (chain_status, leg_count): (String, i64) = conn
+        .query_row(
+            "SELECT status, leg_count FROM dispatcher_job_chains WHERE id = ?1",
+            [&chain_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|e| e.to_string())?;
+    let leg_terminal = if delivered {
+        leg_status == "completed"
+    } else {
+        DISPATCHER_HISTORY_JOB_STATUSES.contains(&leg_status.as_str()) && leg_status != "completed"
+    };
+    if chain_status != "active" || !leg_terminal {
+        return Ok(Some(DispatcherChainProgress {
+            chain: load_chain(conn, &chain_id)?,
+            next_job_id: None,
+        }));
+    }
+
+    let now = Utc::now().to_rfc3339();
+    let mut next_job_id = None;
+    let event_type = if delivered {
+        let next = conn
+            .query_row(
+                "SELECT id FROM dispatcher_jobs WHERE chain_id = ?1 AND chain_leg_index = ?2 AND status = 'chain_waiting'",
+                params![chain_id, leg_index + 1],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()
+            .map_err(|e| e.to_string())?;
+        match next {
+            Some(next_id) => {
+                conn.execute(
+                    r#"
+                    UPDATE dispatcher_jobs
+                    SET status = 'accepted',
+                        accepted_at_utc = ?2,
+                        updated_at_utc = ?2,
+                        profile_reference = (SELECT profile_reference FROM dispatcher_jobs WHERE id = ?3),
+                        save_reference = (SELECT save_reference FROM dispatcher_jobs WHERE id = ?3),
+                        quicksave_reference = (SELECT quicksave_reference FROM dispatcher_jobs WHERE id = ?3),
+                        save_session_id = (SELECT save_session_id FROM dispatcher_jobs WHERE id = ?3)
+                    WHERE id = ?1
+                    "#,
+                    params![next_id, now, job_id],
+                )
+                .map_err(|e| e.to_string())?;
+                conn.execute(
+                    "UPDATE dispatcher_job_chains SET current_leg = ?2, updated_at_utc = ?3 WHERE id = ?1",
+                    params![chain_id, leg_index + 1, now],
+                )
+                .map_err(|e| e.to_string())?;
+                next_job_id = Some(next_id);
+                Some("chain_leg_completed")
+            }
+            None if leg_index + 1 >= leg_count => {
+                conn.execute(
+                    "UPDATE dispatcher_job_chains SET status = 'completed', completed_at_utc = ?2, updated_at_utc = ?2 WHERE id = ?1",
+                    params![chain_id, now],
+                )
+                .map_err(|e| e.to_string())?;
+                Some("chain_completed")
+            }
+            // The next leg was already released by an earlier event for this leg.
+            None => None,
+        }
+    } else {
+        conn.execute(
+            "UPDATE dispatcher_job_chains SET status = 'broken', updated_at_utc = ?2 WHERE id = ?1",
+            params![chain_id, now],
+        )
+        .map_err(|e| e.to_string())?;
+        conn.execute(
+            "UPDATE dispatcher_jobs SET status = 'cancelled', updated_at_utc = ?2 WHERE chain_id = ?1 AND status = 'chain_waiting'",
+            params![chain_id, now],
+        )
+        .map_err(|e| e.to_string())?;
+        Some("chain_broken")
+    };
+
+    let chain = load_chain(conn, &chain_id)?;
+    if let Some(event_type) = event_type {
+        insert_chain_ledger_event(conn, job_id, event_type, &chain)?;
+    }
+    Ok(Some(DispatcherChainProgress { chain, next_job_id }))
+}
+
+pub(super) fn cancel_chain(conn: &Connection, chain_id: &str) -> Result<DispatcherChain, String> {
+    prepare_dispatcher_system(conn)?;
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    let now = Utc::now().to_rfc3339();
+    let changed = tx
+        .execute(
+            "UPDATE dispatcher_job_chains SET status = 'cancelled', updated_at_utc = ?2 WHERE id = ?1 AND status = 'active'",
+            params![chain_id, now],
+        )
+        .map_err(|e| e.to_string())?;
+    if changed == 0 {
+        return Err("dispatcher_chain_not_active".to_string());
+    }
+    tx.execute(
+        &format!(
+            "UPDATE dispatcher_jobs SET status = 'cancelled', updated_at_utc = ?2 WHERE chain_id = ?1 AND status IN ({CHAIN_CANCELLABLE_LEG_STATUSES})"
+        ),
+        params![chain_id, now],
+    )
+    .map_err(|e| e.to_string())?;
+    let chain = load_chain(&tx, chain_id)?;
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(chain)
+}
+
+#[cfg(test)]
+mod tests {
+    use rusqlite::Connection;
+
+    use super::{
+        advance_chain, cancel_chain, chain_save_target, create_chain, price_chain,
+        record_chain_save_target,
+    };
+    use crate::features::career::dispatcher::models::{
+        DispatcherChain, DispatcherChainLegInput, DispatcherCreateChainInput, DispatcherSaveContext,
+    };
+
+    fn leg(origin: &str, destination: &str, distance_km: f64) -> DispatcherChainLegInput {
+        DispatcherChainLegInput {
+            company_id: "north-axis-logistics".to_string(),
+            origin_city: origin.to_string(),
+            origin_country: "DE".to_string(),
+            destination_city: destination.to_string(),
+            destination_country: "DE".to_string(),
+            cargo_type: None,
+            cargo_label: None,
+            cargo_mass_kg: None,
+            job_type: None,
+            distance_km: Some(distance_km),
+        }
+    }
+
+    #[test]
+    fn chain_price_covers_legs_deadhead_and_bonus() {
+        let pricing = price_chain(&[(500.0, 1000), (250.0, 500), (250.0, 500)], 100.0);
+        assert_eq!(pricing.legs_total_reward, 2000);
+        // average rate 2.0/km, deadhead paid at half rate
+        assert_eq!(pricing.deadhead_compensation, 100);
+        assert_eq!(pricing.chain_bonus, 160);
+        assert_eq!(pricing.total_reward, 2260);
+        assert_eq!(
+            pricing.leg_rewards.iter().sum::<i64>(),
+            pricing.total_reward
+        );
+        assert_eq!(pricing.leg_rewards[0], 1130);
+    }
+
+    fn save_context() -> DispatcherSaveContext {
+        DispatcherSaveContext {
+            profile_reference: Some("profiles/main".to_string()),
+            save_reference: Some("profiles/main/save/quicksave".to_string()),
+            quicksave_reference: Some("profiles/main/save/quicksave".to_string()),
+            save_session_id: Some("savectx-test".to_string()),
+        }
+    }
+
+    fn two_leg_chain(conn: &Connection) -> DispatcherChain {
+        create_chain(
+            conn,
+            DispatcherCreateChainInput {
+                title: None,
+                urgency_level: None,
+                legs: vec![
+                    leg("Hamburg", "Berlin", 290.0),
+                    leg("Berlin", "Dresden", 190.0),
+                ],
+            },
+            &save_context(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn delivered_legs_release_the_next_leg_until_the_chain_completes() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::features::economy::ensure_tables(&conn).unwrap();
+
+        let chain = two_leg_chain(&conn);
+        assert_eq!(chain.title, "Hamburg → Berlin → Dresden");
+        assert_eq!(chain.legs.len(), 2);
+        assert_eq!(chain.legs[0].status, "accepted");
+        assert_eq!(chain.legs[1].status, "chain_waiting");
+        assert_eq!(
+            chain.legs.iter().map(|leg| leg.total_reward).sum::<i64>(),
+            chain.total_reward
+        );
+        assert_eq!(
+            chain.current_leg_job_id.as_deref(),
+            Some(chain.legs[0].job_id.as_str())
+        );
+
+        conn.execute(
+            "UPDATE dispatcher_jobs SET status = 'completed' WHERE id = ?1",
+            [&chain.legs[0].job_id],
+        )
+        .unwrap();
+        let progress = advance_chain(&conn, &chain.legs[0].job_id, true)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            progress.next_job_id.as_deref(),
+            Some(chain.legs[1].job_id.as_str())
+        );
+        assert_eq!(progress.chain.completed_legs, 1);
+        assert_eq!(progress.chain.earned_reward, chain.legs[0].total_reward);
+        assert_eq!(progress.chain.legs[1].status, "accepted");
+
+        // A repeated event for the same leg must not complete the chain early.
+        let repeated = advance_chain(&conn, &chain.legs[0].job_id, true)
+            .unwrap()
+            .unwrap();
+        assert_eq!(repeated.next_job_id, None);
+        assert_eq!(repeated.chain.status, "active");
+
+        conn.execute(
+            "UPDATE dispatcher_jobs SET status = 'completed' WHERE id = ?1",
+            [&chain.legs[1].job_id],
+        )
+        .unwrap();
+        let done = advance_chain(&conn, &chain.legs[1].job_id, true)
+            .unwrap()
+            .unwrap();
+        assert_eq!(done.next_job_id, None);
+        assert_eq!(done.chain.status, "completed");
+        assert_eq!(done.chain.earned_reward, chain.total_reward);
+    }
+
+    #[test]
+    fn cancelling_a_chain_also_cancels_the_leg_in_progress() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::features::economy::ensure_tables(&conn).unwrap();
+
+        let chain = two_leg_chain(&conn);
+        conn.execute(
+            "UPDATE dispatcher_jobs SET status = 'in_transit' WHERE id = ?1",
+            [&chain.legs[0].job_id],
+        )
+        .unwrap();
+
+        let cancelled = cancel_chain(&conn, &chain.id).unwrap();
+        assert_eq!(cancelled.status, "cancelled");
+        assert!(cancelled.legs.iter().all(|leg| leg.status == "cancelled"));
+        assert_eq!(cancelled.current_leg_job_id, None);
+    }
+
+    #[test]
+    fn later_legs_reuse_the_save_target_of_the_first_leg() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::features::economy::ensure_tables(&conn).unwrap();
+
+        let chain = two_leg_chain(&conn);
+        let (first, second) = (&chain.legs[0].job_id, &chain.legs[1].job_id);
+        assert_eq!(
+            chain_save_target(&conn, second).unwrap(),
+            Some((None, false))
+        );
+
+        record_chain_save_target(&conn, first, Some("quicksave"), true).unwrap();
+        // Only the first leg decides where the chain is written.
+        record_chain_save_target(&conn, second, Some("autosave"), false).unwrap();
+        assert_eq!(
+            chain_save_target(&conn, second).unwrap(),
+            Some((Some("quicksave".to_string()), true))
+        );
+        assert_eq!(chain_save_target(&conn, "not-a-chain-leg").unwrap(), None);
+    }
+
+    #[test]
+    fn a_failing_leg_insert_leaves_no_partial_chain() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::features::economy::ensure_tables(&conn).unwrap();
+        two_leg_chain(&conn);
+        conn.execute_batch(
+            "CREATE TRIGGER fail_second_leg BEFORE INSERT ON dispatcher_jobs
+             WHEN NEW.chain_leg_index = 1
+             BEGIN SELECT RAISE(ABORT, 'leg rejected'); END;",
+        )
+        .unwrap();
+
+        assert!(
+            create_chain(
+                &conn,
+                DispatcherCreateChainInput {
+                    title: None,
+                    urgency_level: None,
+                    legs: vec![
+                        leg("Kiel", "Hamburg", 95.0),
+                        leg("Hamburg", "Bremen", 120.0)
+                    ],
+                },
+                &save_context(),
+            )
+            .is_err()
+        );
+        let count = |table: &str| -> i64 {
+            conn.query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| {
+                row.get(0)
+            })
+            .unwrap()
+        };
+        assert_eq!(count("dispatcher_job_chains"), 1);
+        assert_eq!(count("dispatcher_jobs"), 2);
+    }
+}
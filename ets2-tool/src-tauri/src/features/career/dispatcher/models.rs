@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::features::economy::settlement_models::DeliverySettlement;
+
 pub type DispatcherSaveContext = crate::shared::models::save_context::SaveContext;
 
 pub(super) const DISPATCHER_DEFAULT_INTERVAL_MINUTES: i64 = 10;
@@ -17,6 +19,8 @@ pub(super) const DISPATCHER_ACTIVE_JOB_STATUSES: &[&str] = &[
     "delayed",
     "problematic",
 ];
+/// Market job handed to a simulated driver; never shown as the player's job.
+pub(super) const DISPATCHER_EMPLOYEE_JOB_STATUS: &str = "employee_assigned";
 pub(super) const DISPATCHER_BUSY_JOB_STATUSES: &[&str] = &["accepted", "in_transit", "delayed"];
 pub(super) const DISPATCHER_HISTORY_JOB_STATUSES: &[&str] = &[
     "completed",
@@ -33,6 +37,7 @@ pub(super) const DISPATCHER_ALL_JOB_STATUSES: &[&str] = &[
     "injected",
     "failed",
     "planned",
+    "employee_assigned",
     "accepted",
     "in_transit",
     "delayed",
@@ -116,6 +121,8 @@ pub struct DispatcherMarketJob {
     pub ets2_job_link_status: Option<String>,
     pub last_error_code: Option<String>,
     pub last_error_message: Option<String>,
+    /// Save cargo token (e.g. `apples`) for jobs built from the offers of the active save.
+    pub save_cargo: Option<String>,
     pub accepted_at_utc: Option<String>,
     pub completed_at_utc: Option<String>,
 }
@@ -190,6 +197,50 @@ pub struct DispatcherOffer {
     pub expires_at_utc: Option<String>,
     pub created_at_utc: String,
     pub updated_at_utc: String,
+    pub proposed_deadline_hours: Option<i64>,
+    pub proposed_bonus: Option<i64>,
+    pub counter_deadline_hours: Option<i64>,
+    pub counter_bonus: Option<i64>,
+    pub final_deadline_hours: Option<i64>,
+    pub final_bonus: Option<i64>,
+    /// Number of proposals the player has made on this offer so far.
+    pub negotiation_round: i64,
+}
+
+/// How a company behaves at the negotiating table, derived from its payment profile and the
+/// reputation the player has with it.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DispatcherCompanyPersona {
+    /// Share above its own price the company is willing to pay without haggling.
+    pub budget_tolerance: f64,
+    /// Player proposals the company entertains before it walks away.
+    pub patience: i64,
+    /// 0..1, grows with reputation, clean deliveries and completed jobs.
+    pub loyalty: f64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DispatcherNegotiationRound {
+    pub round_index: i64,
+    /// `player` or `company`.
+    pub actor: String,
+    pub rate_per_km: f64,
+    pub deadline_hours: i64,
+    pub bonus: i64,
+    /// `proposed`, `countered`, `accepted`, `declined` or `walked_away`.
+    pub decision: String,
+    pub reason: Option<String>,
+    pub created_at_utc: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DispatcherNegotiation {
+    pub offer: DispatcherOffer,
+    pub persona: DispatcherCompanyPersona,
+    pub rounds: Vec<DispatcherNegotiationRound>,
 }
 
 #[derive(Debug, Clone, Serialize, PartialEq)]
@@ -235,6 +286,79 @@ pub struct DispatcherCreateOfferInput {
     pub note: Option<String>,
     pub equipment_type: Option<String>,
     pub contract_scope: Option<String>,
+    pub proposed_deadline_hours: Option<i64>,
+    pub proposed_bonus: Option<i64>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DispatcherChainLegInput {
+    pub company_id: String,
+    pub origin_city: String,
+    pub origin_country: String,
+    pub destination_city: String,
+    pub destination_country: String,
+    pub cargo_type: Option<String>,
+    pub cargo_label: Option<String>,
+    pub cargo_mass_kg: Option<f64>,
+    pub job_type: Option<String>,
+    /// Required unless a dispatcher template covers the lane.
+    pub distance_km: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DispatcherCreateChainInput {
+    pub title: Option<String>,
+    pub urgency_level: Option<String>,
+    pub legs: Vec<DispatcherChainLegInput>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DispatcherChainLeg {
+    pub leg_index: i64,
+    pub job_id: String,
+    pub company_name: String,
+    pub origin_city: String,
+    pub destination_city: String,
+    pub cargo_type: String,
+    pub distance_km: f64,
+    /// Share of the chain price paid for this leg.
+    pub total_reward: i64,
+    pub status: String,
+    pub ets2_job_link_status: Option<String>,
+    /// Revenue reported by the game when the leg was delivered.
+    pub ledger_revenue: Option<i64>,
+    pub completed_at_utc: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DispatcherChain {
+    pub id: String,
+    pub title: String,
+    pub status: String,
+    pub leg_count: i64,
+    pub completed_legs: i64,
+    pub current_leg_job_id: Option<String>,
+    pub legs_total_reward: i64,
+    pub deadhead_km: f64,
+    pub deadhead_compensation: i64,
+    pub chain_bonus: i64,
+    pub total_reward: i64,
+    pub earned_reward: i64,
+    pub legs: Vec<DispatcherChainLeg>,
+    pub created_at_utc: String,
+    pub completed_at_utc: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DispatcherChainProgress {
+    pub chain: DispatcherChain,
+    /// Leg that was released by this step and should be injected next.
+    pub next_job_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, PartialEq)]
@@ -242,6 +366,31 @@ pub struct DispatcherCreateOfferInput {
 pub struct DispatcherRespondToCounterInput {
     pub offer_id: String,
     pub accept_counter: bool,
+    /// With `accept_counter = false`, any of these turns the answer into a new proposal
+    /// instead of ending the negotiation. Missing terms keep the company's counter values.
+    pub counter_rate_per_km: Option<f64>,
+    pub counter_deadline_hours: Option<i64>,
+    pub counter_bonus: Option<i64>,
+}
+
+/// What the game reported when a linked job ended.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DispatcherDeliveryReport {
+    pub delivered: bool,
+    pub cancelled: bool,
+    pub delivered_game_min: u32,
+    pub cargo_damage_percent: f64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DispatcherJobSettlement {
+    pub job_id: String,
+    pub company_id: String,
+    pub delivered: bool,
+    /// Itemized payout; only present for delivered jobs.
+    pub settlement: Option<DeliverySettlement>,
+    pub company_reputation: u16,
 }
 
 #[derive(Debug, Clone, Deserialize, PartialEq)]
@@ -298,6 +447,7 @@ pub(super) struct DispatcherJobRow {
     pub ets2_job_link_status: Option<String>,
     pub last_error_code: Option<String>,
     pub last_error_message: Option<String>,
+    pub save_cargo: Option<String>,
     pub accepted_at_utc: Option<String>,
     pub completed_at_utc: Option<String>,
     pub created_at_utc: String,
@@ -0,0 +1,319 @@
+use chrono::Utc;
+use rusqlite::{Connection, params};
+
+use crate::features::economy::compensation_models::{
+    CompanyPaymentProfile, CompanyPaymentTier, CompanyReputationState,
+};
+use crate::features::economy::compensation_service;
+
+use super::dispatcher_success_rate_for_company;
+use super::models::{DispatcherCompanyPersona, DispatcherNegotiationRound};
+
+/// Deadline a company assumes for a quote request when the player does not name one.
+pub(super) const NEGOTIATION_DEFAULT_DEADLINE_HOURS: i64 = 48;
+const NEGOTIATION_MIN_DEADLINE_HOURS: i64 = 6;
+const NEGOTIATION_MAX_DEADLINE_HOURS: i64 = 240;
+/// Lane length used to weigh a flat bonus against the per-km rate.
+const NEGOTIATION_REFERENCE_KM: f64 = 500.0;
+/// How much a longer deadline is worth to the company compared to the same share of rate.
+const NEGOTIATION_DEADLINE_WEIGHT: f64 = 0.2;
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct NegotiationTerms {
+    pub rate_per_km: f64,
+    pub deadline_hours: i64,
+    pub bonus: i64,
+}
+
+impl NegotiationTerms {
+    pub(super) fn normalized(rate_per_km: f64, deadline_hours: i64, bonus: i64) -> Self {
+        Self {
+            rate_per_km: (rate_per_km.max(0.0) * 100.0).round() / 100.0,
+            deadline_hours: deadline_hours.clamp(
+                NEGOTIATION_MIN_DEADLINE_HOURS,
+                NEGOTIATION_MAX_DEADLINE_HOURS,
+            ),
+            bonus: bonus.max(0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum NegotiationDecision {
+    Accept,
+    Counter(NegotiationTerms),
+    WalkAway(&'static str),
+}
+
+pub(super) fn company_persona(
+    profile: &CompanyPaymentProfile,
+    reputation: &CompanyReputationState,
+    success_rate: f64,
+) -> DispatcherCompanyPersona {
+    let streak_share = reputation.reliability_streak.min(20) as f64 / 20.0;
+    let cancel_penalty = reputation.canceled_jobs.min(5) as f64 * 0.04;
+    let loyalty = (reputation.reputation as f64 / 1000.0 * 0.6
+        + streak_share * 0.2
+        + success_rate.clamp(0.0, 1.0) * 0.2
+        - cancel_penalty)
+        .clamp(0.0, 1.0);
+
+    let (tier_tolerance, tier_patience) = match profile.payment_tier {
+        CompanyPaymentTier::Budget => (0.02, 2),
+        CompanyPaymentTier::Standard => (0.05, 3),
+        CompanyPaymentTier::Good => (0.07, 3),
+        CompanyPaymentTier::Premium => (0.10, 4),
+        CompanyPaymentTier::Elite => (0.12, 4),
+    };
+    let budget_tolerance =
+        (tier_tolerance + (profile.payment_multiplier - 1.0) * 0.25 + loyalty * 0.06)
+            .clamp(0.0, 0.25);
+
+    let mut patience = tier_patience;
+    if loyalty >= 0.6 {
+        patience += 1;
+    }
+    if reputation.late_jobs + reputation.canceled_jobs >= 5 {
+        patience -= 1;
+    }
+
+    DispatcherCompanyPersona {
+        budget_tolerance: (budget_tolerance * 1000.0).round() / 1000.0,
+        patience: patience.clamp(1, 5),
+        loyalty: (loyalty * 1000.0).round() / 1000.0,
+    }
+}
+
+pub(super) fn load_company_persona(
+    conn: &Connection,
+    company_id: &str,
+) -> Result<DispatcherCompanyPersona, String> {
+    let profile = compensation_service::load_company_payment_profile(conn, company_id, None)?;
+    let reputation = compensation_service::load_company_reputation(conn, company_id)?;
+    let success_rate = dispatcher_success_rate_for_company(conn, company_id)?;
+    Ok(company_persona(&profile, &reputation, success_rate))
+}
+
+/// How far the proposal lies above the company's own terms, as a share of the job value.
+/// Rate, a flat bonus and extra deadline hours all count against the company's budget.
+fn proposal_gap(target: &NegotiationTerms, proposal: &NegotiationTerms) -> f64 {
+    let rate_gap = if target.rate_per_km > 0.0 {
+        proposal.rate_per_km / target.rate_per_km - 1.0
+    } else {
+        0.0
+    };
+    let reference_value = (target.rate_per_km * NEGOTIATION_REFERENCE_KM).max(1.0);
+    let bonus_gap = proposal.bonus.max(0) as f64 / reference_value;
+    let deadline_gap = ((proposal.deadline_hours - target.deadline_hours) as f64
+        / target.deadline_hours.max(1) as f64
+        * NEGOTIATION_DEADLINE_WEIGHT)
+        .clamp(-0.05, 0.15);
+    rate_gap + bonus_gap + deadline_gap
+}
+
+/// Answers the player's proposal in round `round` (1-based). Within its budget tolerance the
+/// company signs; far above it a company without loyalty walks away at once. Otherwise it
+/// meets the player part of the way, conceding more of its tolerance the longer the talks
+/// run, until its patience is used up.
+pub(super) fn evaluate_proposal(
+    persona: &DispatcherCompanyPersona,
+    target: &NegotiationTerms,
+    proposal: &NegotiationTerms,
+    round: i64,
+) -> NegotiationDecision {
+    let gap = proposal_gap(target, proposal);
+    if gap <= persona.budget_tolerance {
+        return NegotiationDecision::Accept;
+    }
+    if gap > persona.budget_tolerance * 3.0 + 0.15 && persona.loyalty < 0.3 {
+        return NegotiationDecision::WalkAway("rate_above_company_band");
+    }
+    if round >= persona.patience {
+        return NegotiationDecision::WalkAway("company_patience_exhausted");
+    }
+
+    let share = (round as f64 / persona.patience.max(1) as f64).clamp(0.0, 1.0);
+    let concession = persona.budget_tolerance * share;
+    let factor = if gap > 0.0 {
+        (concession / gap).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let deadline_hours = target.deadline_hours
+        + ((proposal.deadline_hours - target.deadline_hours) as f64 * factor).round() as i64;
+    let bonus = ((proposal.bonus as f64 * factor) / 10.0).round() as i64 * 10;
+    NegotiationDecision::Counter(NegotiationTerms::normalized(
+        target.rate_per_km + (proposal.rate_per_km - target.rate_per_km).max(0.0) * factor,
+        deadline_hours,
+        bonus,
+    ))
+}
+
+pub(super) fn record_negotiation_round(
+    conn: &Connection,
+    offer_id: &str,
+    round_index: i64,
+    actor: &str,
+    terms: &NegotiationTerms,
+    decision: &str,
+    reason: Option<&str>,
+) -> Result<(), String> {
+    conn.execute(
+        r#"
+        INSERT INTO dispatcher_offer_rounds (
+            offer_id,
+            round_index,
+            actor,
+            rate_per_km,
+            deadline_hours,
+            bonus,
+            decision,
+            reason,
+            created_at_utc
+        )
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+        "#,
+        params![
+            offer_id,
+            round_index,
+            actor,
+            terms.rate_per_km,
+            terms.deadline_hours,
+            terms.bonus,
+            decision,
+            reason,
+            Utc::now().to_rfc3339(),
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub(super) fn load_negotiation_rounds(
+    conn: &Connection,
+    offer_id: &str,
+) -> Result<Vec<DispatcherNegotiationRound>, String> {
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT round_index, actor, rate_per_km, deadline_hours, bonus, decision, reason, created_at_utc
+            FROM dispatcher_offer_rounds
+            WHERE offer_id = ?1
+            ORDER BY id ASC
+            "#,
+        )
+        .map_err(|e| e.to_string())?;
+    stmt.query_map([offer_id], |row| {
+        Ok(DispatcherNegotiationRound {
+            round_index: row.get(0)?,
+            actor: row.get(1)?,
+            rate_per_km: row.get(2)?,
+            deadline_hours: row.get(3)?,
+            bonus: row.get(4)?,
+            decision: row.get(5)?,
+            reason: row.get(6)?,
+            created_at_utc: row.get(7)?,
+        })
+    })
+    .map_err(|e| e.to_string())?
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(|e| e.to_string())
+}
+
+/// Records the reputation effect of talks that ended without a deal. A flat rejection of the
+/// first proposal costs nothing; once the company has countered, walking away lowers its
+/// reputation and resets the reliability streak, leaving the job counters as they are.
+pub(super) fn apply_negotiation_breakdown(
+    conn: &Connection,
+    company_id: &str,
+    negotiation_round: i64,
+) -> Result<(), String> {
+    if negotiation_round < 2 {
+        return Ok(());
+    }
+    compensation_service::apply_company_negotiation_breakdown(conn, company_id)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        NegotiationDecision, NegotiationTerms, company_persona, evaluate_proposal, proposal_gap,
+    };
+    use crate::features::economy::compensation_models::{
+        CompanyPaymentProfile, CompanyPaymentTier, CompanyReputationState,
+    };
+
+    fn profile(tier: CompanyPaymentTier) -> CompanyPaymentProfile {
+        CompanyPaymentProfile {
+            company_id: "posped".to_string(),
+            company_name: None,
+            payment_tier: tier,
+            payment_multiplier: 1.0,
+            home_country_code: None,
+            cargo_focus: None,
+            updated_at_utc: String::new(),
+        }
+    }
+
+    fn reputation(value: u16, streak: u16) -> CompanyReputationState {
+        CompanyReputationState {
+            company_id: "posped".to_string(),
+            reputation: value,
+            reliability_streak: streak,
+            completed_jobs: 0,
+            late_jobs: 0,
+            damage_incidents: 0,
+            canceled_jobs: 0,
+            updated_at_utc: String::new(),
+        }
+    }
+
+    #[test]
+    fn loyal_premium_companies_are_more_generous_than_budget_ones() {
+        let budget = company_persona(
+            &profile(CompanyPaymentTier::Budget),
+            &reputation(300, 0),
+            0.0,
+        );
+        let premium = company_persona(
+            &profile(CompanyPaymentTier::Premium),
+            &reputation(850, 20),
+            1.0,
+        );
+        assert!(premium.budget_tolerance > budget.budget_tolerance);
+        assert!(premium.patience > budget.patience);
+        assert!(premium.loyalty > budget.loyalty);
+    }
+
+    #[test]
+    fn counters_concede_more_each_round_until_patience_runs_out() {
+        let persona = company_persona(
+            &profile(CompanyPaymentTier::Standard),
+            &reputation(500, 0),
+            0.5,
+        );
+        let target = NegotiationTerms::normalized(1.0, 48, 0);
+        let proposal = NegotiationTerms::normalized(1.2, 72, 100);
+
+        let NegotiationDecision::Counter(first) =
+            evaluate_proposal(&persona, &target, &proposal, 1)
+        else {
+            panic!("expected a counter-offer in round 1");
+        };
+        let NegotiationDecision::Counter(second) =
+            evaluate_proposal(&persona, &target, &proposal, 2)
+        else {
+            panic!("expected a counter-offer in round 2");
+        };
+        assert!(proposal_gap(&target, &second) > proposal_gap(&target, &first));
+        assert!(second.rate_per_km <= proposal.rate_per_km);
+        assert_eq!(
+            evaluate_proposal(&persona, &target, &proposal, persona.patience),
+            NegotiationDecision::WalkAway("company_patience_exhausted")
+        );
+        assert_eq!(
+            evaluate_proposal(&persona, &target, &first, 2),
+            NegotiationDecision::Accept
+        );
+    }
+}
@@ -1,17 +1,20 @@
 use chrono::Utc;
-use rusqlite::{Connection, params};
+use rusqlite::{Connection, OptionalExtension, params, params_from_iter};
 
 use super::models::{
     DISPATCHER_ACTIVE_JOB_STATUSES, DISPATCHER_ALL_JOB_STATUSES, DISPATCHER_BUSY_JOB_STATUSES,
-    DISPATCHER_HISTORY_JOB_STATUSES, DISPATCHER_OPEN_JOB_STATUSES, DispatcherHistoryResponse,
-    DispatcherHistorySummary, DispatcherJobDetails, DispatcherJobFilter,
-    DispatcherJobsBySaveContextResponse, DispatcherMarketJob, DispatcherSaveContext,
+    DISPATCHER_EMPLOYEE_JOB_STATUS, DISPATCHER_HISTORY_JOB_STATUSES, DISPATCHER_OPEN_JOB_STATUSES,
+    DispatcherHistoryResponse, DispatcherHistorySummary, DispatcherJobDetails, DispatcherJobFilter,
+    DispatcherJobRow, DispatcherJobsBySaveContextResponse, DispatcherMarketJob,
+    DispatcherSaveContext,
 };
 use super::{
-    build_dispatcher_route_reference, count_dispatcher_jobs_by_status, dispatcher_equipment_ok,
+    attach_dispatcher_contract_terms, build_dispatcher_route_reference,
+    count_dispatcher_jobs_by_status, dispatcher_equipment_ok,
     dispatcher_reputation_requirement_for, expire_dispatcher_market_jobs,
     list_dispatcher_jobs_by_status, load_dispatcher_job_by_id, load_dispatcher_job_by_id_any,
-    matches_filter_text, prepare_dispatcher_system, to_dispatcher_market_job,
+    map_dispatcher_job_row, matches_filter_text, prepare_dispatcher_system,
+    to_dispatcher_market_job,
 };
 
 pub(super) fn dispatcher_get_jobs_by_save_context(
@@ -304,6 +307,7 @@ pub(super) fn dispatcher_accept_job(
         params![job_id, now],
     )
     .map_err(|e| e.to_string())?;
+    attach_dispatcher_contract_terms(conn, job_id, &row.company_id, &row.cargo_type)?;
     dispatcher_get_job_details(conn, job_id, save_context)
 }
 
@@ -328,7 +332,7 @@ pub(super) fn dispatcher_cancel_job(
 
     if !matches!(
         row.status.as_str(),
-        "accepted" | "planned" | "in_transit" | "delayed" | "problematic"
+        "accepted" | "planned" | "employee_assigned" | "in_transit" | "delayed" | "problematic"
     ) {
         return Err("dispatcher_job_not_cancellable".to_string());
     }
@@ -352,6 +356,120 @@ pub(super) fn dispatcher_cancel_job(
     dispatcher_get_job_details(conn, job_id, save_context)
 }
 
+/// Hands the best paying open market job of `save_context` within
+/// `max_distance_km` to an AI employee. The job becomes `employee_assigned`,
+/// which keeps it out of the player's market and active jobs.
+pub(super) fn dispatcher_claim_job_for_employee(
+    conn: &Connection,
+    employee_id: &str,
+    difficulties: &[&str],
+    max_distance_km: f64,
+    save_context: &DispatcherSaveContext,
+) -> Result<Option<DispatcherMarketJob>, String> {
+    prepare_dispatcher_system(conn)?;
+    expire_dispatcher_market_jobs(conn)?;
+    if difficulties.is_empty() || save_context.profile_reference.is_none() {
+        return Ok(None);
+    }
+
+    let placeholders = (0..difficulties.len())
+        .map(|index| format!("?{}", index + 4))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let sql = format!(
+        "
+        SELECT *
+        FROM dispatcher_jobs
+        WHERE status = 'open'
+          AND distance_km > 0
+          AND distance_km <= ?1
+          AND profile_reference = ?2
+          AND (?3 IS NULL OR save_reference = ?3)
+          AND lower(difficulty_level) IN ({placeholders})
+        ORDER BY total_reward DESC, created_at_utc ASC
+        LIMIT 1
+        "
+    );
+    let mut values: Vec<rusqlite::types::Value> = vec![
+        max_distance_km.into(),
+        save_context.profile_reference.clone().into(),
+        save_context.save_reference.clone().into(),
+    ];
+    values.extend(
+        difficulties
+            .iter()
+            .map(|difficulty| difficulty.to_lowercase().into()),
+    );
+    let row = conn
+        .query_row(&sql, params_from_iter(values), map_dispatcher_job_row)
+        .optional()
+        .map_err(|e| e.to_string())?;
+    let Some(row) = row else {
+        return Ok(None);
+    };
+
+    let now = Utc::now().to_rfc3339();
+    let claimed = conn
+        .execute(
+            r#"
+            UPDATE dispatcher_jobs
+            SET status = ?4,
+                assigned_employee_id = ?2,
+                accepted_at_utc = ?3,
+                updated_at_utc = ?3
+            WHERE id = ?1 AND status = 'open'
+            "#,
+            params![row.id, employee_id, now, DISPATCHER_EMPLOYEE_JOB_STATUS],
+        )
+        .map_err(|e| e.to_string())?;
+    if claimed == 0 {
+        return Ok(None);
+    }
+
+    Ok(Some(to_dispatcher_market_job(DispatcherJobRow {
+        status: DISPATCHER_EMPLOYEE_JOB_STATUS.to_string(),
+        accepted_at_utc: Some(now),
+        ..row
+    })))
+}
+
+/// Closes a job driven by an AI employee. Returns `false` when the job was
+/// taken away in the meantime, e.g. cancelled by the player.
+pub(super) fn dispatcher_finish_employee_job(
+    conn: &Connection,
+    job_id: &str,
+    employee_id: &str,
+    delivered_with_incident: bool,
+) -> Result<bool, String> {
+    prepare_dispatcher_system(conn)?;
+    let status = if delivered_with_incident {
+        "problematic"
+    } else {
+        "completed"
+    };
+    let now = Utc::now().to_rfc3339();
+    let changed = conn
+        .execute(
+            r#"
+            UPDATE dispatcher_jobs
+            SET status = ?3,
+                progress_km = distance_km,
+                completed_at_utc = ?4,
+                updated_at_utc = ?4
+            WHERE id = ?1 AND assigned_employee_id = ?2 AND status = ?5
+            "#,
+            params![
+                job_id,
+                employee_id,
+                status,
+                now,
+                DISPATCHER_EMPLOYEE_JOB_STATUS
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    Ok(changed > 0)
+}
+
 pub(super) fn dispatcher_get_job_history(
     conn: &Connection,
     save_context: &DispatcherSaveContext,
@@ -420,7 +538,10 @@ mod tests {
     use chrono::Utc;
     use rusqlite::{Connection, params};
 
-    use super::dispatcher_assign_job_to_active_save;
+    use super::{
+        dispatcher_assign_job_to_active_save, dispatcher_claim_job_for_employee,
+        dispatcher_get_active_jobs,
+    };
     use crate::features::career::dispatcher::{DispatcherSaveContext, prepare_dispatcher_system};
 
     #[test]
@@ -557,4 +678,87 @@ mod tests {
             Some("profiles/main")
         );
     }
+
+    #[test]
+    fn employees_only_claim_open_jobs_of_the_loaded_save() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::features::economy::ensure_tables(&conn).unwrap();
+        prepare_dispatcher_system(&conn).unwrap();
+
+        let now = Utc::now().to_rfc3339();
+        for (job_id, reward, save_reference) in [
+            ("dispatcher-test-other-save", 2_400, "profiles/main/save/2"),
+            ("dispatcher-test-loaded-save", 900, "profiles/main/save/1"),
+        ] {
+            conn.execute(
+                r#"
+                INSERT INTO dispatcher_jobs (
+                    id, source_type, company_id, company_name, job_type, cargo_type,
+                    origin_city, origin_country, destination_city, destination_country,
+                    distance_km, cargo_mass_kg, urgency_level, difficulty_level,
+                    equipment_type_required, trailer_type_required, base_rate_per_km,
+                    calculated_rate_per_km, total_reward, estimated_duration_minutes,
+                    payment_tier_snapshot, payment_multiplier_snapshot, country_multiplier_snapshot,
+                    reputation_multiplier_snapshot, cargo_multiplier_snapshot,
+                    urgency_multiplier_snapshot, equipment_multiplier_snapshot,
+                    market_variation_snapshot, customer_multiplier_snapshot, company_reputation,
+                    fuel_cost_estimate, profit_estimate, risk_note, bonus_note,
+                    expires_at_utc, status, progress_km, profile_reference, save_reference,
+                    quicksave_reference, save_session_id, route_reference, ets2_job_link_status,
+                    accepted_at_utc, completed_at_utc, created_at_utc, updated_at_utc
+                )
+                VALUES (
+                    ?1, 'generated', 'north-axis-logistics', 'North Axis Logistics', 'quick_job', 'standard',
+                    'Hamburg', 'DE', 'Prague', 'CZ',
+                    642.0, 12000.0, 'normal', 'Normal',
+                    'quick_job', NULL, 1.12,
+                    1.18, ?2, 620,
+                    'standard', 1.0, 1.02,
+                    1.01, 1.0,
+                    1.0, 1.0,
+                    1.0, 1.0, 320,
+                    120, 480, NULL, NULL,
+                    NULL, 'open', 0, 'profiles/main', ?3,
+                    NULL, NULL, NULL, NULL,
+                    NULL, NULL, ?4, ?4
+                )
+                "#,
+                params![job_id, reward, save_reference, now],
+            )
+            .unwrap();
+        }
+
+        let save_context = DispatcherSaveContext {
+            profile_reference: Some("profiles/main".to_string()),
+            save_reference: Some("profiles/main/save/1".to_string()),
+            quicksave_reference: None,
+            save_session_id: None,
+        };
+        let claimed = dispatcher_claim_job_for_employee(
+            &conn,
+            "emp-ai-test",
+            &["easy", "normal"],
+            5_000.0,
+            &save_context,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(claimed.id, "dispatcher-test-loaded-save");
+        assert_eq!(claimed.status, "employee_assigned");
+        assert!(
+            dispatcher_get_active_jobs(&conn, &save_context)
+                .unwrap()
+                .is_empty()
+        );
+
+        let nothing_left = dispatcher_claim_job_for_employee(
+            &conn,
+            "emp-ai-test",
+            &["normal"],
+            5_000.0,
+            &save_context,
+        )
+        .unwrap();
+        assert!(nothing_left.is_none());
+    }
 }
@@ -0,0 +1,732 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use chrono::{Duration, Utc};
+use rusqlite::{Connection, OptionalExtension, params};
+use walkdir::WalkDir;
+use zip::ZipArchive;
+
+use crate::features::economy;
+use crate::features::economy::compensation_models::{CargoType, JobCompensationInput, Urgency};
+use crate::features::economy::compensation_service;
+use crate::features::ets2save::parser::{SaveTemplateScan, scan_save_templates, sii_token};
+use crate::features::ets2save::sii_codec::decode_sii_lines;
+use crate::features::mod_profile_manager::models::GameType;
+use crate::features::mod_profile_manager::sii_mods::{
+    parse_active_mod_values_from_profile_text, workshop_mod_ref_to_id,
+};
+use crate::features::mod_profile_manager::steam_paths::{
+    discover_workshop_sources, find_game_install_dir,
+};
+use crate::shared::decrypt::decrypt_if_needed;
+use crate::shared::hashfs::{HashFsArchive, is_hashfs_archive};
+use crate::shared::paths::{game_sii_from_save, mod_directory_path};
+
+use super::models::DispatcherSaveContext;
+use super::{
+    base_rate_type_for_dispatcher_job, build_dispatcher_job_id, build_dispatcher_route_reference,
+    cargo_type_to_db, dispatcher_bonus_note, dispatcher_difficulty_modifier,
+    dispatcher_estimated_duration_minutes, dispatcher_job_signature, dispatcher_job_type_modifier,
+    dispatcher_risk_note, equipment_type_for_dispatcher_job, payment_tier_to_db,
+};
+
+/// One job offer as it exists in a company depot of the save: source depot, target depot,
+/// cargo and trailer are exactly the tokens the game generated, including mod and DLC content.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct SaveMarketOffer {
+    pub src_company: String,
+    pub src_city: String,
+    pub dst_company: String,
+    pub dst_city: String,
+    pub cargo: String,
+    pub trailer_variant: Option<String>,
+    pub trailer_definition: Option<String>,
+    pub distance_km: f64,
+    pub urgency: i64,
+    pub units_count: i64,
+    pub company_truck: bool,
+}
+
+fn save_value(value: Option<&str>) -> Option<String> {
+    value
+        .map(|value| value.trim().trim_matches('"').trim())
+        .filter(|value| !value.is_empty() && *value != "null")
+        .map(str::to_string)
+}
+
+pub(super) fn collect_save_market_offers(scan: &SaveTemplateScan) -> Vec<SaveMarketOffer> {
+    let mut seen = HashSet::new();
+    let mut offers = Vec::new();
+
+    for depot in &scan.depots {
+        for pointer in &depot.job_offers {
+            let Some(data) = scan.job_offer_data.get(&pointer.pointer) else {
+                continue;
+            };
+            let Some((dst_company, dst_city)) = save_value(data.target.as_deref())
+                .and_then(|target| {
+                    target
+                        .split_once('.')
+                        .map(|(company, city)| (sii_token(company), sii_token(city)))
+                })
+                .filter(|(company, city)| !company.is_empty() && !city.is_empty())
+            else {
+                continue;
+            };
+            let Some(cargo) = save_value(data.cargo.as_deref())
+                .map(|cargo| sii_token(cargo.trim_start_matches("cargo.")))
+                .filter(|cargo| !cargo.is_empty())
+            else {
+                continue;
+            };
+            if dst_city == depot.city_token {
+                continue;
+            }
+            // Offers whose target depot the save does not contain cannot be delivered.
+            if !scan
+                .depots
+                .iter()
+                .any(|target| target.company_token == dst_company && target.city_token == dst_city)
+            {
+                continue;
+            }
+            if !seen.insert((
+                depot.company_token.clone(),
+                depot.city_token.clone(),
+                dst_company.clone(),
+                dst_city.clone(),
+                cargo.clone(),
+            )) {
+                continue;
+            }
+
+            offers.push(SaveMarketOffer {
+                src_company: depot.company_token.clone(),
+                src_city: depot.city_token.clone(),
+                dst_company,
+                dst_city,
+                cargo,
+                trailer_variant: save_value(data.trailer_variant.as_deref()),
+                trailer_definition: save_value(data.trailer_definition.as_deref()),
+                distance_km: data.shortest_distance_km.unwrap_or(0).max(0) as f64,
+                urgency: data.urgency.unwrap_or(0),
+                units_count: data.units_count.unwrap_or(1),
+                company_truck: save_value(data.company_truck.as_deref())
+                    .is_some_and(|value| value != "false"),
+            });
+        }
+    }
+
+    offers
+}
+
+/// Reads the job offers of the save behind `save_context`. `None` means the save could not
+/// be read, in which case the market falls back to the built-in templates.
+pub(super) fn load_save_market_offers(
+    save_context: &DispatcherSaveContext,
+) -> Option<Vec<SaveMarketOffer>> {
+    let save_reference = save_context.save_reference.as_deref()?;
+    let game_sii = game_sii_from_save(Path::new(save_reference));
+    match decode_sii_lines(&game_sii) {
+        Ok(lines) => Some(collect_save_market_offers(&scan_save_templates(&lines))),
+        Err(error) => {
+            crate::dev_log!(
+                "[dispatcher] save market unavailable for {}: {}",
+                game_sii.display(),
+                error.message
+            );
+            None
+        }
+    }
+}
+
+/// Game of the profile behind `save_context`; ATS profiles live under the ATS documents folder.
+fn save_game(save_context: &DispatcherSaveContext) -> GameType {
+    let profile = save_context
+        .profile_reference
+        .as_deref()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    if profile.contains("american truck simulator") {
+        GameType::Ats
+    } else {
+        GameType::Ets2
+    }
+}
+
+/// Archives and folders of the mods active in the profile, highest priority first, as listed
+/// in `active_mods` of profile.sii. Mods that are not installed are skipped.
+fn active_mod_sources(game: GameType, profile_reference: Option<&str>) -> Vec<PathBuf> {
+    let Some(profile_text) = profile_reference
+        .and_then(|profile| decrypt_if_needed(&Path::new(profile).join("profile.sii")).ok())
+    else {
+        return Vec::new();
+    };
+    let mod_dir = mod_directory_path(game.as_str());
+    let workshop_dirs = discover_workshop_sources(game, None)
+        .workshop_sources
+        .into_iter()
+        .filter(|source| source.exists)
+        .map(|source| PathBuf::from(source.path))
+        .collect::<Vec<_>>();
+
+    parse_active_mod_values_from_profile_text(&profile_text)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|value| {
+            let mod_ref = value.split('|').next().unwrap_or_default().trim();
+            if let Some(workshop_id) = workshop_mod_ref_to_id(mod_ref) {
+                return workshop_dirs
+                    .iter()
+                    .map(|dir| dir.join(workshop_id.to_string()))
+                    .find(|path| path.is_dir());
+            }
+            let mod_dir = mod_dir.as_ref()?;
+            ["scs", "zip"]
+                .iter()
+                .map(|extension| mod_dir.join(format!("{mod_ref}.{extension}")))
+                .chain(std::iter::once(mod_dir.join(mod_ref)))
+                .find(|path| path.exists())
+        })
+        .collect()
+}
+
+/// Cargo unit masses by game and active mod list.
+type CargoMassCache = HashMap<(&'static str, Vec<PathBuf>), Arc<HashMap<String, f64>>>;
+
+/// Mass in kg of one cargo unit, by cargo token, as defined in `def/cargo/*.sii` of the
+/// installed game and the mods active in the profile. Later archives (DLCs) override earlier
+/// ones and mods override the game, with the highest-priority mod applied last, like in the
+/// game. Results are cached per game and mod list.
+fn cargo_unit_masses(save_context: &DispatcherSaveContext) -> Arc<HashMap<String, f64>> {
+    static MASSES: OnceLock<Mutex<CargoMassCache>> = OnceLock::new();
+
+    let game = save_game(save_context);
+    let mods = active_mod_sources(game, save_context.profile_reference.as_deref());
+    let cache = MASSES.get_or_init(|| Mutex::new(HashMap::new()));
+    let key = (game.as_str(), mods);
+    if let Some(masses) = cache.lock().ok().and_then(|cache| cache.get(&key).cloned()) {
+        return masses;
+    }
+
+    let mut masses = HashMap::new();
+    if let Some(entries) = find_game_install_dir(game).and_then(|dir| fs::read_dir(dir).ok()) {
+        let mut archives = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| is_hashfs_archive(path))
+            .collect::<Vec<_>>();
+        archives.sort();
+        for path in archives {
+            collect_cargo_definitions(&path, &mut masses);
+        }
+    }
+    for path in key.1.iter().rev() {
+        collect_cargo_definitions(path, &mut masses);
+    }
+
+    let masses = Arc::new(masses);
+    if let Ok(mut cache) = cache.lock() {
+        cache.insert(key, Arc::clone(&masses));
+    }
+    masses
+}
+
+fn is_cargo_definition(path: &str) -> bool {
+    let path = path.replace('\\', "/").to_ascii_lowercase();
+    path.trim_start_matches('/').starts_with("def/cargo/") && path.ends_with(".sii")
+}
+
+/// Reads the cargo definitions of a HashFS or ZIP archive, or of a mod folder including the
+/// archives inside it.
+fn collect_cargo_definitions(path: &Path, masses: &mut HashMap<String, f64>) {
+    if path.is_dir() {
+        let mut files = WalkDir::new(path)
+            .follow_links(false)
+            .into_iter()
+            .flatten()
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| entry.into_path())
+            .collect::<Vec<_>>();
+        files.sort();
+        for file in files {
+            let relative = file.strip_prefix(path).unwrap_or(&file).to_string_lossy();
+            if is_cargo_definition(&relative) {
+                if let Ok(text) = fs::read_to_string(&file) {
+                    collect_cargo_unit_masses(&text, masses);
+                }
+            } else if file.extension().is_some_and(|extension| {
+                extension.eq_ignore_ascii_case("scs") || extension.eq_ignore_ascii_case("zip")
+            }) {
+                collect_cargo_definitions(&file, masses);
+            }
+        }
+        return;
+    }
+
+    if is_hashfs_archive(path) {
+        let Ok(archive) = HashFsArchive::open(path) else {
+            return;
+        };
+        for file in archive.list_files().unwrap_or_default() {
+            if !is_cargo_definition(&file) {
+                continue;
+            }
+            if let Ok(Some(bytes)) = archive.read_file(&file) {
+                collect_cargo_unit_masses(&String::from_utf8_lossy(&bytes), masses);
+            }
+        }
+        return;
+    }
+
+    let Some(mut archive) = File::open(path)
+        .ok()
+        .and_then(|file| ZipArchive::new(file).ok())
+    else {
+        return;
+    };
+    for position in 0..archive.len() {
+        let Ok(mut entry) = archive.by_index(position) else {
+            continue;
+        };
+        if entry.is_dir() || !is_cargo_definition(entry.name()) {
+            continue;
+        }
+        let mut bytes = Vec::new();
+        if entry.read_to_end(&mut bytes).is_ok() {
+            collect_cargo_unit_masses(&String::from_utf8_lossy(&bytes), masses);
+        }
+    }
+}
+
+/// Reads `mass` of every `cargo_data : cargo.<token>` unit in a definition file.
+fn collect_cargo_unit_masses(text: &str, masses: &mut HashMap<String, f64>) {
+    let mut current: Option<String> = None;
+    for line in text.lines() {
+        let line = line.trim();
+        if let Some(rest) = line.strip_prefix("cargo_data") {
+            current = rest
+                .trim_start()
+                .strip_prefix(':')
+                .map(|name| name.trim().trim_end_matches('{').trim())
+                .and_then(|name| name.strip_prefix("cargo."))
+                .map(sii_token);
+            continue;
+        }
+        if line.starts_with('}') {
+            current = None;
+            continue;
+        }
+        let (Some(cargo), Some((key, value))) = (current.as_ref(), line.split_once(':')) else {
+            continue;
+        };
+        if key.trim() != "mass" {
+            continue;
+        }
+        if let Ok(mass) = value.trim().parse::<f64>() {
+            masses.insert(cargo.clone(), mass);
+        }
+    }
+}
+
+/// Total cargo mass of a save offer; 0 when neither the game nor an active mod defines the
+/// cargo.
+fn offer_cargo_mass_kg(offer: &SaveMarketOffer, masses: &HashMap<String, f64>) -> f64 {
+    match masses.get(&offer.cargo) {
+        Some(unit_mass) => unit_mass * offer.units_count.max(1) as f64,
+        None => {
+            crate::dev_log!("[dispatcher] no game data mass for cargo {}", offer.cargo);
+            0.0
+        }
+    }
+}
+
+fn title_from_token(token: &str) -> String {
+    token
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Display name and ISO country of a save city token. Mod cities that the imported map data
+/// does not know keep a name derived from the token and no country.
+fn city_display(conn: &Connection, city_token: &str) -> (String, String) {
+    conn.query_row(
+        "SELECT name_en, country_iso2 FROM ets2_cities WHERE game_token = ?1 ORDER BY id LIMIT 1",
+        [city_token],
+        |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+    )
+    .optional()
+    .ok()
+    .flatten()
+    .unwrap_or_else(|| (title_from_token(city_token), String::new()))
+}
+
+fn company_display(conn: &Connection, company_token: &str) -> String {
+    conn.query_row(
+        "SELECT name_en FROM ets2_companies WHERE game_token = ?1 ORDER BY id LIMIT 1",
+        [company_token],
+        |row| row.get::<_, String>(0),
+    )
+    .optional()
+    .ok()
+    .flatten()
+    .unwrap_or_else(|| title_from_token(company_token))
+}
+
+const HAZARDOUS_CARGO_HINTS: &[&str] = &[
+    "acid",
+    "acetylene",
+    "ammonia",
+    "chlor",
+    "cyanide",
+    "dynamite",
+    "explosive",
+    "fuel",
+    "gas",
+    "hydrogen",
+    "lpg",
+    "nitro",
+    "petrol",
+    "potassium",
+    "propane",
+];
+const FRAGILE_CARGO_HINTS: &[&str] = &["aircond", "electronic", "glass", "med_equip", "radiator"];
+const VALUABLE_CARGO_HINTS: &[&str] = &["lux", "jewel", "cash", "art"];
+
+/// Maps a save cargo/trailer pair onto the pricing categories of the dispatcher. The trailer
+/// body is the more reliable signal for reefers and low loaders; ADR and fragile goods are
+/// recognised by their cargo token.
+fn cargo_category(cargo: &str, trailer_definition: Option<&str>) -> CargoType {
+    let trailer = trailer_definition.unwrap_or_default().to_ascii_lowercase();
+    if ["fridge", "reefer", "insul"]
+        .iter()
+        .any(|hint| trailer.contains(hint))
+    {
+        return CargoType::Refrigerated;
+    }
+    if ["lowloader", "lowbed", "gooseneck"]
+        .iter()
+        .any(|hint| trailer.contains(hint))
+    {
+        return CargoType::Oversize;
+    }
+    if HAZARDOUS_CARGO_HINTS
+        .iter()
+        .any(|hint| cargo.contains(hint))
+    {
+        return CargoType::Hazardous;
+    }
+    if FRAGILE_CARGO_HINTS.iter().any(|hint| cargo.contains(hint)) {
+        return CargoType::Fragile;
+    }
+    if VALUABLE_CARGO_HINTS
+        .iter()
+        .any(|hint| cargo.starts_with(hint))
+    {
+        return CargoType::Valuable;
+    }
+    CargoType::Standard
+}
+
+/// `trailer_def.scs.box.single_3.fridge` -> `box`
+fn trailer_body(trailer_definition: Option<&str>) -> Option<String> {
+    let mut parts = trailer_definition?.split('.');
+    if parts.next()? != "trailer_def" {
+        return None;
+    }
+    parts.nth(1).map(str::to_string)
+}
+
+fn save_urgency(value: i64) -> (Urgency, &'static str) {
+    match value {
+        i64::MIN..=0 => (Urgency::Normal, "normal"),
+        1 => (Urgency::Priority, "high"),
+        _ => (Urgency::Express, "critical"),
+    }
+}
+
+fn save_difficulty(cargo_type: CargoType) -> &'static str {
+    match cargo_type {
+        CargoType::Hazardous | CargoType::Oversize => "hard",
+        CargoType::Refrigerated | CargoType::Fragile | CargoType::Valuable => "normal",
+        CargoType::Standard => "easy",
+    }
+}
+
+pub(super) fn generate_save_market_jobs(
+    conn: &Connection,
+    save_context: &DispatcherSaveContext,
+    offers: &[SaveMarketOffer],
+    count: usize,
+    signatures: &mut HashSet<String>,
+    base_seed: u64,
+) -> Result<usize, String> {
+    if offers.is_empty() {
+        return Ok(0);
+    }
+
+    let economy_state = economy::load_state(conn)?;
+    let cargo_masses = cargo_unit_masses(save_context);
+    let mut created = 0usize;
+
+    for attempt in 0..offers.len() {
+        if created >= count {
+            break;
+        }
+
+        let offer = &offers[((base_seed as usize) + attempt) % offers.len()];
+        let (origin_city, origin_country) = city_display(conn, &offer.src_city);
+        let (destination_city, destination_country) = city_display(conn, &offer.dst_city);
+        let company_name = company_display(conn, &offer.src_company);
+        let (job_type, equipment_type) = if offer.company_truck {
+            ("quick_job", "quick_job")
+        } else {
+            ("freight_market", "own_truck")
+        };
+        let cargo_type = cargo_category(&offer.cargo, offer.trailer_definition.as_deref());
+        let cargo_type_db = cargo_type_to_db(cargo_type);
+        let signature = dispatcher_job_signature(
+            &offer.src_company,
+            &origin_city,
+            &destination_city,
+            cargo_type_db,
+            job_type,
+        );
+        if signatures.contains(&signature) {
+            continue;
+        }
+
+        let distance_km = offer.distance_km;
+        if distance_km <= 0.0 {
+            continue;
+        }
+
+        let (urgency, urgency_level) = save_urgency(offer.urgency);
+        let difficulty_level = save_difficulty(cargo_type);
+        let profile = compensation_service::load_company_payment_profile(
+            conn,
+            &offer.src_company,
+            Some(&company_name),
+        )?;
+        let pricing = compensation_service::calculate_job_compensation(
+            conn,
+            &JobCompensationInput {
+                company_id: offer.src_company.clone(),
+                company_name: Some(company_name.clone()),
+                distance_km,
+                base_rate_type: base_rate_type_for_dispatcher_job(job_type),
+                equipment_type: equipment_type_for_dispatcher_job(equipment_type),
+                cargo_type,
+                urgency,
+                origin_country_code: origin_country.clone(),
+                destination_country_code: destination_country.clone(),
+                market_seed: base_seed + attempt as u64,
+            },
+        )?;
+        let final_rate = pricing.final_rate_per_km
+            * dispatcher_job_type_modifier(job_type)
+            * dispatcher_difficulty_modifier(difficulty_level);
+        let total_reward = (distance_km * final_rate).round() as i64;
+        let fuel_cost = (distance_km * 0.31 * economy_state.diesel_price_per_liter).round() as i64;
+        let toll_cost = (distance_km * economy_state.toll_per_km).round() as i64;
+        let insurance_cost = (economy_state.insurance_daily_cost / 6).max(45);
+        let now = Utc::now();
+        let expires_at = now
+            + Duration::hours(match urgency {
+                Urgency::Express => 8,
+                Urgency::Priority => 14,
+                Urgency::Normal => 20,
+            });
+
+        conn.execute(
+            r#"
+            INSERT INTO dispatcher_jobs (
+                id, source_type, company_id, company_name, job_type, cargo_type,
+                origin_city, origin_country, destination_city, destination_country,
+                distance_km, cargo_mass_kg, urgency_level, difficulty_level,
+                equipment_type_required, trailer_type_required, base_rate_per_km,
+                calculated_rate_per_km, total_reward, estimated_duration_minutes,
+                payment_tier_snapshot, payment_multiplier_snapshot, country_multiplier_snapshot,
+                reputation_multiplier_snapshot, cargo_multiplier_snapshot,
+                urgency_multiplier_snapshot, equipment_multiplier_snapshot,
+                market_variation_snapshot, customer_multiplier_snapshot, company_reputation,
+                fuel_cost_estimate, profit_estimate, risk_note, bonus_note, expires_at_utc,
+                status, progress_km, profile_reference, save_reference, quicksave_reference,
+                save_session_id, route_reference, ets2_job_link_status, accepted_at_utc,
+                completed_at_utc, created_at_utc, updated_at_utc, save_src_company,
+                save_src_city, save_dst_company, save_dst_city, save_cargo,
+                save_trailer_variant, save_trailer_definition
+            )
+            VALUES (
+                ?1, 'generated', ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15,
+                ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30, ?31,
+                ?32, ?33, ?34, 'open', 0, ?35, ?36, ?37, ?38, ?39, 'pending_route', NULL, NULL,
+                ?40, ?40, ?41, ?42, ?43, ?44, ?45, ?46, ?47
+            )
+            "#,
+            params![
+                build_dispatcher_job_id(
+                    &offer.src_company,
+                    (base_seed as usize) + attempt,
+                    created
+                ),
+                offer.src_company,
+                company_name,
+                job_type,
+                cargo_type_db,
+                origin_city,
+                origin_country,
+                destination_city,
+                destination_country,
+                distance_km,
+                offer_cargo_mass_kg(offer, &cargo_masses),
+                urgency_level,
+                difficulty_level,
+                equipment_type,
+                trailer_body(offer.trailer_definition.as_deref()),
+                pricing.base_rate_per_km,
+                final_rate,
+                total_reward,
+                dispatcher_estimated_duration_minutes(distance_km, urgency),
+                payment_tier_to_db(profile.payment_tier),
+                profile.payment_multiplier,
+                pricing.country_multiplier,
+                pricing.company_reputation_multiplier,
+                pricing.cargo_multiplier,
+                pricing.urgency_multiplier,
+                pricing.equipment_multiplier,
+                pricing.market_variation,
+                pricing.customer_multiplier,
+                pricing.company_reputation as i64,
+                fuel_cost,
+                total_reward - fuel_cost - toll_cost - insurance_cost,
+                dispatcher_risk_note(difficulty_level, urgency_level),
+                dispatcher_bonus_note(profile.payment_tier, equipment_type),
+                expires_at.to_rfc3339(),
+                save_context.profile_reference.as_deref(),
+                save_context.save_reference.as_deref(),
+                save_context.quicksave_reference.as_deref(),
+                save_context.save_session_id.as_deref(),
+                build_dispatcher_route_reference(
+                    &offer.src_company,
+                    &origin_country,
+                    &origin_city,
+                    &destination_country,
+                    &destination_city,
+                    job_type,
+                ),
+                now.to_rfc3339(),
+                offer.src_company,
+                offer.src_city,
+                offer.dst_company,
+                offer.dst_city,
+                offer.cargo,
+                offer.trailer_variant.as_deref(),
+                offer.trailer_definition.as_deref(),
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+
+        signatures.insert(signature);
+        created += 1;
+    }
+
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{
+        cargo_category, collect_cargo_definitions, collect_cargo_unit_masses,
+        collect_save_market_offers, trailer_body,
+    };
+    use crate::features::economy::compensation_models::CargoType;
+    use crate::features::ets2save::parser::scan_save_templates;
+    use crate::features::ets2save::sii_codec::split_lines;
+
+    const SAVE: &str = "SiiNunit\n{\ncompany : company.volatile.posped.berlin {\n job_offer: 3\n job_offer[0]: _nameless.offer.001\n job_offer[1]: _nameless.offer.002\n job_offer[2]: _nameless.offer.003\n}\ncompany : company.volatile.tradeaux.hamburg {\n job_offer: 0\n}\njob_offer_data : _nameless.offer.001 {\n target: \"tradeaux.hamburg\"\n urgency: 1\n shortest_distance_km: 290\n cargo: cargo.apples\n company_truck: null\n trailer_variant: trailer.scs.box.single_3.fridge\n trailer_definition: trailer_def.scs.box.single_3.fridge\n units_count: 4\n}\njob_offer_data : _nameless.offer.002 {\n target: \"\"\n cargo: null\n}\njob_offer_data : _nameless.offer.003 {\n target: \"tradeaux.hamburg\"\n shortest_distance_km: 290\n cargo: cargo.apples\n}\n}\n";
+
+    #[test]
+    fn only_filled_save_offers_become_market_candidates() {
+        let offers = collect_save_market_offers(&scan_save_templates(&split_lines(SAVE)));
+        assert_eq!(offers.len(), 1);
+        let offer = &offers[0];
+        assert_eq!(
+            (offer.src_company.as_str(), offer.src_city.as_str()),
+            ("posped", "berlin")
+        );
+        assert_eq!(
+            (offer.dst_company.as_str(), offer.dst_city.as_str()),
+            ("tradeaux", "hamburg")
+        );
+        assert_eq!(offer.cargo, "apples");
+        assert_eq!(offer.distance_km, 290.0);
+        assert!(!offer.company_truck);
+        assert_eq!(
+            trailer_body(offer.trailer_definition.as_deref()).as_deref(),
+            Some("box")
+        );
+        assert_eq!(
+            cargo_category(&offer.cargo, offer.trailer_definition.as_deref()),
+            CargoType::Refrigerated
+        );
+    }
+
+    #[test]
+    fn cargo_unit_mass_comes_from_the_cargo_definition() {
+        let mut masses = HashMap::new();
+        collect_cargo_unit_masses(
+            "SiiNunit\n{\ncargo_data : cargo.apples\n{\n name: \"@@cn_apples@@\"\n volume: 1\n mass: 800\n unit_reward_per_km: 0.02\n}\n}\n",
+            &mut masses,
+        );
+        assert_eq!(masses.get("apples"), Some(&800.0));
+        assert_eq!(masses.len(), 1);
+    }
+
+    #[test]
+    fn mod_folders_and_zip_archives_override_cargo_masses() {
+        use std::io::Write;
+
+        let cargo = |mass: u32| {
+            format!("SiiNunit\n{{\ncargo_data : cargo.apples\n{{\n mass: {mass}\n}}\n}}\n")
+        };
+        let root = std::env::temp_dir().join(format!("cargo_masses_{}", uuid::Uuid::new_v4()));
+        let folder_mod = root.join("folder_mod");
+        std::fs::create_dir_all(folder_mod.join("def").join("cargo")).unwrap();
+        std::fs::write(folder_mod.join("def/cargo/apples.sii"), cargo(900)).unwrap();
+        std::fs::write(folder_mod.join("def/cargo/readme.txt"), cargo(1)).unwrap();
+
+        let zip_mod = root.join("zip_mod.zip");
+        let mut writer = zip::ZipWriter::new(std::fs::File::create(&zip_mod).unwrap());
+        writer
+            .start_file(
+                "def/cargo/apples.sii",
+                zip::write::SimpleFileOptions::default(),
+            )
+            .unwrap();
+        writer.write_all(cargo(1000).as_bytes()).unwrap();
+        writer.finish().unwrap();
+
+        let mut masses = HashMap::from([("apples".to_string(), 800.0)]);
+        collect_cargo_definitions(&folder_mod, &mut masses);
+        assert_eq!(masses.get("apples"), Some(&900.0));
+        collect_cargo_definitions(&zip_mod, &mut masses);
+        assert_eq!(masses.get("apples"), Some(&1000.0));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -83,7 +83,29 @@ pub(super) fn ensure_dispatcher_tables(conn: &Connection) -> Result<(), String>
             linked_job_id TEXT,
             created_at_utc TEXT NOT NULL,
             updated_at_utc TEXT NOT NULL,
-            expires_at_utc TEXT
+            expires_at_utc TEXT,
+            proposed_deadline_hours INTEGER,
+            proposed_bonus INTEGER,
+            counter_deadline_hours INTEGER,
+            counter_bonus INTEGER,
+            final_deadline_hours INTEGER,
+            final_bonus INTEGER,
+            target_rate_per_km REAL,
+            target_deadline_hours INTEGER,
+            negotiation_round INTEGER NOT NULL DEFAULT 0
+        );
+
+        CREATE TABLE IF NOT EXISTS dispatcher_offer_rounds (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            offer_id TEXT NOT NULL,
+            round_index INTEGER NOT NULL,
+            actor TEXT NOT NULL,
+            rate_per_km REAL NOT NULL,
+            deadline_hours INTEGER NOT NULL,
+            bonus INTEGER NOT NULL DEFAULT 0,
+            decision TEXT NOT NULL,
+            reason TEXT,
+            created_at_utc TEXT NOT NULL
         );
 
         CREATE TABLE IF NOT EXISTS dispatcher_contracts (
@@ -94,11 +116,31 @@ pub(super) fn ensure_dispatcher_tables(conn: &Connection) -> Result<(), String>
             agreed_rate_modifier REAL NOT NULL,
             preferred_cargo_type TEXT,
             region_scope TEXT,
+            deadline_hours INTEGER,
+            bonus INTEGER NOT NULL DEFAULT 0,
             active_from_utc TEXT NOT NULL,
             active_until_utc TEXT,
             status TEXT NOT NULL
         );
 
+        CREATE TABLE IF NOT EXISTS dispatcher_job_chains (
+            id TEXT PRIMARY KEY,
+            title TEXT NOT NULL,
+            status TEXT NOT NULL DEFAULT 'active',
+            leg_count INTEGER NOT NULL,
+            current_leg INTEGER NOT NULL DEFAULT 0,
+            legs_total_reward INTEGER NOT NULL DEFAULT 0,
+            deadhead_km REAL NOT NULL DEFAULT 0,
+            deadhead_compensation INTEGER NOT NULL DEFAULT 0,
+            chain_bonus INTEGER NOT NULL DEFAULT 0,
+            total_reward INTEGER NOT NULL DEFAULT 0,
+            created_at_utc TEXT NOT NULL,
+            updated_at_utc TEXT NOT NULL,
+            completed_at_utc TEXT,
+            save_target_slot TEXT,
+            save_target_clone INTEGER NOT NULL DEFAULT 0
+        );
+
         CREATE TABLE IF NOT EXISTS dispatcher_generation_config (
             id INTEGER PRIMARY KEY CHECK (id = 1),
             interval_minutes INTEGER NOT NULL DEFAULT 10,
@@ -113,6 +155,14 @@ pub(super) fn ensure_dispatcher_tables(conn: &Connection) -> Result<(), String>
     ensure_dispatcher_job_columns(conn)?;
     ensure_dispatcher_offer_columns(conn)?;
     ensure_dispatcher_contract_columns(conn)?;
+    ensure_columns(
+        conn,
+        "dispatcher_job_chains",
+        &[
+            ("save_target_slot", "TEXT"),
+            ("save_target_clone", "INTEGER NOT NULL DEFAULT 0"),
+        ],
+    )?;
     ensure_dispatcher_generation_config_columns(conn)?;
     ensure_dispatcher_generation_config(conn)?;
     ensure_dispatcher_indexes(conn)?;
@@ -172,6 +222,26 @@ fn ensure_dispatcher_job_columns(conn: &Connection) -> Result<(), String> {
             ("ets2_job_link_status", "TEXT DEFAULT 'pending_route'"),
             ("last_error_code", "TEXT"),
             ("last_error_message", "TEXT"),
+            ("assigned_employee_id", "TEXT"),
+            ("chain_id", "TEXT"),
+            ("chain_leg_index", "INTEGER"),
+            ("save_src_company", "TEXT"),
+            ("save_src_city", "TEXT"),
+            ("save_dst_company", "TEXT"),
+            ("save_dst_city", "TEXT"),
+            ("save_cargo", "TEXT"),
+            ("save_trailer_variant", "TEXT"),
+            ("save_trailer_definition", "TEXT"),
+            ("settlement_net_reward", "INTEGER"),
+            ("settlement_late_minutes", "INTEGER"),
+            ("settlement_late_fee", "INTEGER"),
+            ("settlement_damage_percent", "REAL"),
+            ("settlement_damage_deduction", "INTEGER"),
+            ("settled_at_utc", "TEXT"),
+            ("contract_id", "TEXT"),
+            ("contract_bonus", "INTEGER NOT NULL DEFAULT 0"),
+            ("contract_deadline_hours", "INTEGER"),
+            ("deadline_game_min", "INTEGER"),
             ("accepted_at_utc", "TEXT"),
             ("completed_at_utc", "TEXT"),
             ("created_at_utc", "TEXT NOT NULL DEFAULT ''"),
@@ -246,6 +316,15 @@ fn ensure_dispatcher_offer_columns(conn: &Connection) -> Result<(), String> {
             ("created_at_utc", "TEXT NOT NULL DEFAULT ''"),
             ("updated_at_utc", "TEXT NOT NULL DEFAULT ''"),
             ("expires_at_utc", "TEXT"),
+            ("proposed_deadline_hours", "INTEGER"),
+            ("proposed_bonus", "INTEGER"),
+            ("counter_deadline_hours", "INTEGER"),
+            ("counter_bonus", "INTEGER"),
+            ("final_deadline_hours", "INTEGER"),
+            ("final_bonus", "INTEGER"),
+            ("target_rate_per_km", "REAL"),
+            ("target_deadline_hours", "INTEGER"),
+            ("negotiation_round", "INTEGER NOT NULL DEFAULT 0"),
         ],
     )?;
 
@@ -288,6 +367,8 @@ fn ensure_dispatcher_contract_columns(conn: &Connection) -> Result<(), String> {
             ("agreed_rate_modifier", "REAL NOT NULL DEFAULT 1"),
             ("preferred_cargo_type", "TEXT"),
             ("region_scope", "TEXT"),
+            ("deadline_hours", "INTEGER"),
+            ("bonus", "INTEGER NOT NULL DEFAULT 0"),
             ("active_from_utc", "TEXT NOT NULL DEFAULT ''"),
             ("active_until_utc", "TEXT"),
             ("status", "TEXT NOT NULL DEFAULT 'active'"),
@@ -339,6 +420,8 @@ fn ensure_dispatcher_indexes(conn: &Connection) -> Result<(), String> {
             "CREATE INDEX IF NOT EXISTS idx_dispatcher_jobs_context ON dispatcher_jobs(profile_reference, save_reference, status)",
             "CREATE INDEX IF NOT EXISTS idx_dispatcher_jobs_source ON dispatcher_jobs(source_type, status, created_at_utc DESC)",
             "CREATE INDEX IF NOT EXISTS idx_dispatcher_offers_status ON dispatcher_offers(status, created_at_utc DESC)",
+            "CREATE INDEX IF NOT EXISTS idx_dispatcher_jobs_chain ON dispatcher_jobs(chain_id, chain_leg_index)",
+            "CREATE INDEX IF NOT EXISTS idx_dispatcher_offer_rounds_offer ON dispatcher_offer_rounds(offer_id, round_index)",
         ],
     )?;
     Ok(())
@@ -479,6 +562,8 @@ mod tests {
         assert!(offer_columns.contains("created_at_utc"));
         assert!(offer_columns.contains("updated_at_utc"));
         assert!(offer_columns.contains("status"));
+        assert!(offer_columns.contains("negotiation_round"));
+        assert!(offer_columns.contains("target_rate_per_km"));
 
         let contract_columns = existing_columns(&conn, "dispatcher_contracts").unwrap();
         assert!(contract_columns.contains("active_from_utc"));
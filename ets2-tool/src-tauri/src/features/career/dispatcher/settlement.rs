@@ -0,0 +1,336 @@
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension, params};
+
+use crate::features::bank;
+use crate::features::economy::compensation_models::CompanyReputationOutcome;
+use crate::features::economy::settlement_models::{DeliverySettlement, DeliverySettlementInput};
+use crate::features::economy::{compensation_service, settlement_service};
+
+use super::chains::{table_exists, urgency_from_level};
+use super::models::{DispatcherDeliveryReport, DispatcherJobSettlement};
+use super::{cargo_type_from_dispatcher_string, prepare_dispatcher_system};
+
+struct SettlementJob {
+    company_id: String,
+    origin_city: String,
+    destination_city: String,
+    total_reward: i64,
+    contract_bonus: i64,
+    deadline_game_min: Option<i64>,
+    cargo_type: String,
+    urgency_level: String,
+    settled_at_utc: Option<String>,
+}
+
+/// Settles a linked job once the game reports its end. Delivered jobs are paid the contract
+/// reward plus any negotiated bonus minus late fees and damage deductions, measured against
+/// the deadline stamped on the job when it started. The net reward is credited to the bank;
+/// the VTC ledger already holds the game's revenue and only gets the bonus, fees and
+/// deductions as line items. Every outcome feeds the company reputation. A job is settled
+/// only once.
+pub(super) fn settle_linked_job(
+    conn: &Connection,
+    job_id: &str,
+    link_id: &str,
+    report: &DispatcherDeliveryReport,
+) -> Result<Option<DispatcherJobSettlement>, String> {
+    prepare_dispatcher_system(conn)?;
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    let settled = settle_linked_job_in(&tx, job_id, link_id, report)?;
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(settled)
+}
+
+fn settle_linked_job_in(
+    conn: &Connection,
+    job_id: &str,
+    link_id: &str,
+    report: &DispatcherDeliveryReport,
+) -> Result<Option<DispatcherJobSettlement>, String> {
+    let job = conn
+        .query_row(
+            r#"
+            SELECT company_id, origin_city, destination_city, total_reward, contract_bonus,
+                   deadline_game_min, cargo_type, urgency_level, settled_at_utc
+            FROM dispatcher_jobs
+            WHERE id = ?1
+            "#,
+            [job_id],
+            |row| {
+                Ok(SettlementJob {
+                    company_id: row.get(0)?,
+                    origin_city: row.get(1)?,
+                    destination_city: row.get(2)?,
+                    total_reward: row.get(3)?,
+                    contract_bonus: row.get(4)?,
+                    deadline_game_min: row.get(5)?,
+                    cargo_type: row.get(6)?,
+                    urgency_level: row.get(7)?,
+                    settled_at_utc: row.get(8)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    let Some(job) = job else {
+        return Ok(None);
+    };
+    if job.settled_at_utc.is_some() {
+        return Ok(None);
+    }
+
+    let now = Utc::now().to_rfc3339();
+    let settlement = if report.delivered {
+        let rules = settlement_service::load_settlement_rules(conn)?;
+        let settlement = settlement_service::settle_delivery(
+            &DeliverySettlementInput {
+                contract_reward: job.total_reward,
+                contract_bonus: job.contract_bonus,
+                cargo_type: cargo_type_from_dispatcher_string(&job.cargo_type),
+                urgency: urgency_from_level(&job.urgency_level),
+                deadline_game_min: job
+                    .deadline_game_min
+                    .and_then(|value| u32::try_from(value).ok()),
+                delivered_game_min: (report.delivered_game_min > 0)
+                    .then_some(report.delivered_game_min),
+                cargo_damage_percent: report.cargo_damage_percent,
+            },
+            &rules,
+        );
+        record_settlement_ledger(conn, job_id, link_id, &settlement, &now)?;
+        bank::apply_trip_result(
+            conn,
+            settlement.net_reward,
+            &format!(
+                "Dispatcher job {} -> {}",
+                job.origin_city, job.destination_city
+            ),
+        )?;
+        Some(settlement)
+    } else {
+        None
+    };
+
+    conn.execute(
+        r#"
+        UPDATE dispatcher_jobs
+        SET settlement_net_reward = ?2,
+            settlement_late_minutes = ?3,
+            settlement_late_fee = ?4,
+            settlement_damage_percent = ?5,
+            settlement_damage_deduction = ?6,
+            settled_at_utc = ?7,
+            updated_at_utc = ?7
+        WHERE id = ?1
+        "#,
+        params![
+            job_id,
+            settlement.as_ref().map_or(0, |value| value.net_reward),
+            settlement.as_ref().map(|value| value.late_minutes),
+            settlement.as_ref().map(|value| value.late_fee),
+            settlement
+                .as_ref()
+                .map(|value| value.cargo_damage_percent)
+                .unwrap_or(report.cargo_damage_percent),
+            settlement.as_ref().map(|value| value.damage_deduction),
+            now,
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    let reputation = compensation_service::apply_company_reputation_outcome(
+        conn,
+        &job.company_id,
+        CompanyReputationOutcome {
+            completed: report.delivered,
+            on_time: settlement.as_ref().is_some_and(|value| value.on_time),
+            damage_percent: report.cargo_damage_percent,
+            canceled: report.cancelled,
+        },
+    )?;
+
+    Ok(Some(DispatcherJobSettlement {
+        job_id: job_id.to_string(),
+        company_id: job.company_id,
+        delivered: report.delivered,
+        settlement,
+        company_reputation: reputation.reputation,
+    }))
+}
+
+fn record_settlement_ledger(
+    conn: &Connection,
+    job_id: &str,
+    link_id: &str,
+    settlement: &DeliverySettlement,
+    now: &str,
+) -> Result<(), String> {
+    if !table_exists(conn, "vtc_job_ledger")? {
+        return Ok(());
+    }
+    // The link service books the game's revenue when the delivery is reported, so only the
+    // adjustments on top of it are added here.
+    for item in settlement
+        .line_items
+        .iter()
+        .filter(|item| item.kind != "contract_reward")
+    {
+        conn.execute(
+            r#"
+            INSERT INTO vtc_job_ledger (
+                link_id, vtc_job_id, event_type, revenue, payload_json, created_at_utc
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            "#,
+            params![
+                link_id,
+                job_id,
+                format!("settlement_{}", item.kind),
+                item.amount,
+                serde_json::to_string(item).unwrap_or_default(),
+                now,
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rusqlite::Connection;
+
+    use super::settle_linked_job;
+    use crate::features::career::dispatcher::models::DispatcherDeliveryReport;
+    use crate::features::{bank, economy};
+
+    fn prepare(conn: &Connection) {
+        economy::ensure_tables(conn).unwrap();
+        bank::ensure_tables(conn).unwrap();
+        super::prepare_dispatcher_system(conn).unwrap();
+        conn.execute_batch(
+            r#"
+            CREATE TABLE vtc_job_ledger (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                link_id TEXT NOT NULL,
+                vtc_job_id TEXT NOT NULL,
+                event_type TEXT NOT NULL,
+                revenue INTEGER,
+                payload_json TEXT,
+                created_at_utc TEXT NOT NULL
+            );
+            "#,
+        )
+        .unwrap();
+    }
+
+    fn insert_job(conn: &Connection) {
+        conn.execute(
+            r#"
+            INSERT INTO dispatcher_jobs (
+                id, source_type, company_id, company_name, job_type, cargo_type, origin_city,
+                origin_country, destination_city, destination_country, distance_km, cargo_mass_kg,
+                urgency_level, difficulty_level, equipment_type_required, base_rate_per_km,
+                calculated_rate_per_km, total_reward, payment_tier_snapshot,
+                payment_multiplier_snapshot, country_multiplier_snapshot,
+                reputation_multiplier_snapshot, cargo_multiplier_snapshot,
+                urgency_multiplier_snapshot, equipment_multiplier_snapshot,
+                market_variation_snapshot, customer_multiplier_snapshot, company_reputation,
+                deadline_game_min, status, created_at_utc, updated_at_utc
+            )
+            VALUES (
+                'job-1', 'generated', 'posped', 'Posped', 'freight_market', 'valuable', 'Berlin',
+                'DE', 'Hamburg', 'DE', 290, 12000, 'critical', 'normal', 'own_truck', 1.1, 1.2,
+                20000, 'standard', 1, 1, 1, 1, 1, 1, 1, 1, 500, 2000, 'completed', 'now', 'now'
+            )
+            "#,
+            [],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn late_damaged_delivery_is_settled_once_and_costs_reputation() {
+        let conn = Connection::open_in_memory().unwrap();
+        prepare(&conn);
+        insert_job(&conn);
+        let cash_before = bank::load_state(&conn).unwrap().cash_balance;
+        let report = DispatcherDeliveryReport {
+            delivered: true,
+            cancelled: false,
+            delivered_game_min: 2_200,
+            cargo_damage_percent: 3.0,
+        };
+
+        let settled = settle_linked_job(&conn, "job-1", "link-1", &report)
+            .unwrap()
+            .unwrap();
+        let breakdown = settled.settlement.unwrap();
+        // Express: 185 billable minutes -> 4 started hours at 8 %.
+        assert_eq!(breakdown.late_fee, 6_400);
+        // Valuable: 2.5 % above tolerance at factor 2.
+        assert_eq!(breakdown.damage_deduction, 1_000);
+        assert_eq!(breakdown.net_reward, 12_600);
+        assert!(settled.company_reputation < 500);
+
+        let net: i64 = conn
+            .query_row(
+                "SELECT settlement_net_reward FROM dispatcher_jobs WHERE id = 'job-1'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(net, 12_600);
+        assert_eq!(
+            bank::load_state(&conn).unwrap().cash_balance - cash_before,
+            12_600
+        );
+        let ledger: Vec<(String, i64)> = conn
+            .prepare("SELECT event_type, revenue FROM vtc_job_ledger ORDER BY id")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            ledger,
+            vec![
+                ("settlement_late_fee".to_string(), -6_400),
+                ("settlement_damage_deduction".to_string(), -1_000),
+            ]
+        );
+        assert!(
+            settle_linked_job(&conn, "job-1", "link-1", &report)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn contract_deadline_and_bonus_are_settled_from_the_job() {
+        let conn = Connection::open_in_memory().unwrap();
+        prepare(&conn);
+        insert_job(&conn);
+        conn.execute(
+            "UPDATE dispatcher_jobs SET contract_id = 'contract-1', contract_bonus = 750, deadline_game_min = 2300 WHERE id = 'job-1'",
+            [],
+        )
+        .unwrap();
+        let report = DispatcherDeliveryReport {
+            delivered: true,
+            cancelled: false,
+            delivered_game_min: 2_200,
+            cargo_damage_percent: 0.0,
+        };
+
+        let breakdown = settle_linked_job(&conn, "job-1", "link-1", &report)
+            .unwrap()
+            .unwrap()
+            .settlement
+            .unwrap();
+        assert!(breakdown.on_time);
+        assert_eq!(breakdown.late_fee, 0);
+        assert_eq!(breakdown.contract_bonus, 750);
+        assert_eq!(breakdown.net_reward, 20_750);
+    }
+}
@@ -0,0 +1,575 @@
+//! Driving behaviour scoring for logbook trips.
+//!
+//! `logbook` feeds every live sample into [`update`], which keeps cheap
+//! counters on the active trip. When the trip is finalized the counters are
+//! turned into a 0–100 score with one sub-score per category and stored in
+//! `trip_driving_scores`, so the VTC can rank drivers across trips.
+//!
+//! Rest compliance comes from the tachograph's violation count and weighs into
+//! the score like any other category.
+
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension, params};
+use serde::{Deserialize, Serialize};
+
+use crate::features::career::logbook::TelemetrySample;
+use crate::shared::sqlite_schema::create_indexes;
+use crate::state::DrivingBehaviourState;
+pub use crate::state::DrivingScoreThresholds;
+
+/// Category weights of the overall score; they add up to 1.
+const WEIGHT_SMOOTHNESS: f64 = 0.20;
+const WEIGHT_SPEEDING: f64 = 0.20;
+const WEIGHT_OVER_REVVING: f64 = 0.10;
+const WEIGHT_IDLING: f64 = 0.10;
+const WEIGHT_GEAR_USAGE: f64 = 0.10;
+const WEIGHT_FUEL_EFFICIENCY: f64 = 0.20;
+const WEIGHT_REST_COMPLIANCE: f64 = 0.10;
+
+/// Rates per 100 km use at least this distance so short hops are not punished
+/// twice for a single event.
+const MIN_RATE_DISTANCE_KM: f64 = 10.0;
+/// Speed above which low engine speed counts as lugging.
+const LUGGING_MIN_SPEED_KPH: f64 = 15.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DrivingMetrics {
+    pub distance_km: f64,
+    pub driving_seconds: f64,
+    pub idle_seconds: f64,
+    pub over_rev_seconds: f64,
+    pub lugging_seconds: f64,
+    pub harsh_acceleration_events: i64,
+    pub harsh_braking_events: i64,
+    pub speeding_events: i64,
+    pub fuel_used_liters: f64,
+    pub fuel_liters_per_100km: Option<f64>,
+    pub planned_distance_km: Option<f64>,
+    /// Tachograph violations recorded during the trip.
+    pub rest_violations: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DrivingScoreBreakdown {
+    pub score: f64,
+    pub smoothness: f64,
+    pub speeding: f64,
+    pub over_revving: f64,
+    pub idling: f64,
+    pub gear_usage: f64,
+    pub fuel_efficiency: f64,
+    pub rest_compliance: f64,
+    pub metrics: DrivingMetrics,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TripDrivingScore {
+    pub trip_id: i64,
+    pub scored_at_utc: String,
+    pub origin: Option<String>,
+    pub destination: Option<String>,
+    pub cargo: Option<String>,
+    pub breakdown: DrivingScoreBreakdown,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DrivingScoreOverview {
+    pub scored_trips: i64,
+    pub average_score: Option<f64>,
+    pub best_score: Option<f64>,
+    pub recent: Vec<TripDrivingScore>,
+}
+
+pub fn ensure_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS driving_score_config (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            thresholds_json TEXT NOT NULL,
+            updated_at_utc TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS trip_driving_scores (
+            trip_id INTEGER PRIMARY KEY,
+            score REAL NOT NULL,
+            breakdown_json TEXT NOT NULL,
+            thresholds_json TEXT NOT NULL,
+            scored_at_utc TEXT NOT NULL
+        );
+        "#,
+    )
+    .map_err(|e| e.to_string())?;
+    create_indexes(
+        conn,
+        &[
+            "CREATE INDEX IF NOT EXISTS idx_trip_driving_scores_score ON trip_driving_scores(score DESC)",
+        ],
+    )
+}
+
+pub fn load_thresholds(conn: &Connection) -> Result<DrivingScoreThresholds, String> {
+    ensure_tables(conn)?;
+    let stored = conn
+        .query_row(
+            "SELECT thresholds_json FROM driving_score_config WHERE id = 1",
+            [],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    Ok(stored
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default())
+}
+
+pub fn save_thresholds(
+    conn: &Connection,
+    thresholds: DrivingScoreThresholds,
+) -> Result<DrivingScoreThresholds, String> {
+    ensure_tables(conn)?;
+    let thresholds = sanitize_thresholds(thresholds);
+    let json = serde_json::to_string(&thresholds).map_err(|e| e.to_string())?;
+    conn.execute(
+        r#"
+        INSERT INTO driving_score_config (id, thresholds_json, updated_at_utc)
+        VALUES (1, ?1, ?2)
+        ON CONFLICT(id) DO UPDATE SET
+            thresholds_json = excluded.thresholds_json,
+            updated_at_utc = excluded.updated_at_utc
+        "#,
+        params![json, Utc::now().to_rfc3339()],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(thresholds)
+}
+
+fn sanitize_thresholds(input: DrivingScoreThresholds) -> DrivingScoreThresholds {
+    let defaults = DrivingScoreThresholds::default();
+    let positive = |value: f64, fallback: f64| {
+        if value.is_finite() && value > 0.0 {
+            value
+        } else {
+            fallback
+        }
+    };
+    DrivingScoreThresholds {
+        harsh_acceleration_kph_per_s: positive(
+            input.harsh_acceleration_kph_per_s,
+            defaults.harsh_acceleration_kph_per_s,
+        ),
+        harsh_braking_kph_per_s: positive(
+            input.harsh_braking_kph_per_s,
+            defaults.harsh_braking_kph_per_s,
+        ),
+        over_rev_rpm: positive(input.over_rev_rpm, defaults.over_rev_rpm),
+        lugging_rpm: positive(input.lugging_rpm, defaults.lugging_rpm),
+        idle_speed_kph: positive(input.idle_speed_kph, defaults.idle_speed_kph),
+        idle_grace_seconds: if input.idle_grace_seconds.is_finite() {
+            input.idle_grace_seconds.max(0.0)
+        } else {
+            defaults.idle_grace_seconds
+        },
+        target_fuel_liters_per_100km: positive(
+            input.target_fuel_liters_per_100km,
+            defaults.target_fuel_liters_per_100km,
+        ),
+    }
+}
+
+/// Folds one unpaused live sample into the trip counters.
+pub fn update(
+    state: &mut DrivingBehaviourState,
+    sample: &TelemetrySample,
+    last_speed_kph: f32,
+    delta_ms: i64,
+    thresholds: &DrivingScoreThresholds,
+) {
+    let delta_seconds = delta_ms.max(0) as f64 / 1000.0;
+    let speed = f64::from(sample.speed_kph.abs());
+    let rpm = f64::from(sample.rpm);
+    let moving = speed > thresholds.idle_speed_kph;
+
+    if sample.planned_distance_km > 0.0 {
+        state.planned_distance_km = Some(sample.planned_distance_km);
+    }
+
+    if moving {
+        state.driving_seconds += delta_seconds;
+        state.idle_stop_seconds = 0.0;
+    } else if sample.engine_enabled {
+        state.idle_stop_seconds += delta_seconds;
+        if state.idle_stop_seconds > thresholds.idle_grace_seconds {
+            state.idle_seconds += delta_seconds;
+        }
+    }
+
+    if sample.engine_enabled && rpm > thresholds.over_rev_rpm {
+        state.over_rev_seconds += delta_seconds;
+    }
+    if sample.gear > 0 && speed > LUGGING_MIN_SPEED_KPH && rpm > 0.0 && rpm < thresholds.lugging_rpm
+    {
+        state.lugging_seconds += delta_seconds;
+    }
+
+    // Very short deltas turn sensor jitter into huge accelerations.
+    if delta_ms >= 50 {
+        let acceleration = f64::from(sample.speed_kph.abs() - last_speed_kph.abs()) / delta_seconds;
+        track_harsh_event(
+            acceleration,
+            thresholds.harsh_acceleration_kph_per_s,
+            &mut state.in_harsh_acceleration,
+            &mut state.harsh_acceleration_events,
+        );
+        track_harsh_event(
+            -acceleration,
+            thresholds.harsh_braking_kph_per_s,
+            &mut state.in_harsh_braking,
+            &mut state.harsh_braking_events,
+        );
+    }
+}
+
+fn track_harsh_event(value: f64, threshold: f64, active: &mut bool, events: &mut i64) {
+    if value >= threshold {
+        if !*active {
+            *events += 1;
+        }
+        *active = true;
+    } else if value < threshold * 0.5 {
+        *active = false;
+    }
+}
+
+pub fn score_trip(
+    state: &DrivingBehaviourState,
+    distance_km: f64,
+    fuel_used_liters: f64,
+    speeding_events: i64,
+    hos_violations: i64,
+    thresholds: &DrivingScoreThresholds,
+) -> DrivingScoreBreakdown {
+    let per_100km = |events: i64| events as f64 * 100.0 / distance_km.max(MIN_RATE_DISTANCE_KM);
+    let share = |seconds: f64, total: f64| if total > 0.0 { seconds / total } else { 0.0 };
+    // Fuel is rated against the planned route, so detours count as extra
+    // consumption; trips without a plan fall back to the driven distance.
+    let fuel_distance_km = state
+        .planned_distance_km
+        .filter(|planned| *planned >= 1.0)
+        .unwrap_or(distance_km);
+    let fuel_liters_per_100km =
+        (fuel_distance_km >= 1.0).then(|| fuel_used_liters * 100.0 / fuel_distance_km);
+
+    let smoothness = clamp_score(
+        100.0 - 12.0 * per_100km(state.harsh_acceleration_events + state.harsh_braking_events),
+    );
+    let speeding = clamp_score(100.0 - 15.0 * per_100km(speeding_events));
+    let over_revving =
+        clamp_score(100.0 - 400.0 * share(state.over_rev_seconds, state.driving_seconds));
+    let gear_usage =
+        clamp_score(100.0 - 400.0 * share(state.lugging_seconds, state.driving_seconds));
+    let idling = clamp_score(
+        100.0
+            - 200.0
+                * share(
+                    state.idle_seconds,
+                    state.driving_seconds + state.idle_seconds,
+                ),
+    );
+    let fuel_efficiency = match fuel_liters_per_100km {
+        Some(actual) => {
+            let ratio = actual / thresholds.target_fuel_liters_per_100km;
+            clamp_score(100.0 - 200.0 * (ratio - 1.0).max(0.0))
+        }
+        None => 100.0,
+    };
+    let rest_compliance = clamp_score(100.0 - 50.0 * hos_violations as f64);
+
+    let score = smoothness * WEIGHT_SMOOTHNESS
+        + speeding * WEIGHT_SPEEDING
+        + over_revving * WEIGHT_OVER_REVVING
+        + idling * WEIGHT_IDLING
+        + gear_usage * WEIGHT_GEAR_USAGE
+        + fuel_efficiency * WEIGHT_FUEL_EFFICIENCY
+        + rest_compliance * WEIGHT_REST_COMPLIANCE;
+
+    DrivingScoreBreakdown {
+        score: round_score(score),
+        smoothness: round_score(smoothness),
+        speeding: round_score(speeding),
+        over_revving: round_score(over_revving),
+        idling: round_score(idling),
+        gear_usage: round_score(gear_usage),
+        fuel_efficiency: round_score(fuel_efficiency),
+        rest_compliance: round_score(rest_compliance),
+        metrics: DrivingMetrics {
+            distance_km,
+            driving_seconds: state.driving_seconds,
+            idle_seconds: state.idle_seconds,
+            over_rev_seconds: state.over_rev_seconds,
+            lugging_seconds: state.lugging_seconds,
+            harsh_acceleration_events: state.harsh_acceleration_events,
+            harsh_braking_events: state.harsh_braking_events,
+            speeding_events,
+            fuel_used_liters,
+            fuel_liters_per_100km,
+            planned_distance_km: state.planned_distance_km,
+            rest_violations: hos_violations,
+        },
+    }
+}
+
+pub fn store_trip_score(
+    conn: &Connection,
+    trip_id: i64,
+    breakdown: &DrivingScoreBreakdown,
+    thresholds: &DrivingScoreThresholds,
+) -> Result<(), String> {
+    ensure_tables(conn)?;
+    conn.execute(
+        r#"
+        INSERT OR REPLACE INTO trip_driving_scores (
+            trip_id, score, breakdown_json, thresholds_json, scored_at_utc
+        )
+        VALUES (?1, ?2, ?3, ?4, ?5)
+        "#,
+        params![
+            trip_id,
+            breakdown.score,
+            serde_json::to_string(breakdown).map_err(|e| e.to_string())?,
+            serde_json::to_string(thresholds).map_err(|e| e.to_string())?,
+            Utc::now().to_rfc3339()
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub fn list_trip_scores(conn: &Connection, limit: usize) -> Result<Vec<TripDrivingScore>, String> {
+    ensure_tables(conn)?;
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT s.trip_id, s.scored_at_utc, t.origin, t.destination, t.cargo, s.breakdown_json
+            FROM trip_driving_scores s
+            LEFT JOIN trips t ON t.id = s.trip_id
+            ORDER BY s.scored_at_utc DESC, s.trip_id DESC
+            LIMIT ?1
+            "#,
+        )
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params![limit as i64], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, Option<String>>(3)?,
+                row.get::<_, Option<String>>(4)?,
+                row.get::<_, String>(5)?,
+            ))
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(rows
+        .into_iter()
+        .map(
+            |(trip_id, scored_at_utc, origin, destination, cargo, breakdown_json)| {
+                TripDrivingScore {
+                    trip_id,
+                    scored_at_utc,
+                    origin,
+                    destination,
+                    cargo,
+                    breakdown: serde_json::from_str(&breakdown_json).unwrap_or_default(),
+                }
+            },
+        )
+        .collect())
+}
+
+pub fn load_overview(conn: &Connection, limit: usize) -> Result<DrivingScoreOverview, String> {
+    ensure_tables(conn)?;
+    let (scored_trips, average_score, best_score) = conn
+        .query_row(
+            "SELECT COUNT(*), AVG(score), MAX(score) FROM trip_driving_scores",
+            [],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, Option<f64>>(1)?,
+                    row.get::<_, Option<f64>>(2)?,
+                ))
+            },
+        )
+        .map_err(|e| e.to_string())?;
+
+    Ok(DrivingScoreOverview {
+        scored_trips,
+        average_score: average_score.map(round_score),
+        best_score,
+        recent: list_trip_scores(conn, limit)?,
+    })
+}
+
+fn clamp_score(value: f64) -> f64 {
+    if value.is_finite() {
+        value.clamp(0.0, 100.0)
+    } else {
+        0.0
+    }
+}
+
+fn round_score(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(speed_kph: f32, rpm: f32, gear: i32) -> TelemetrySample {
+        TelemetrySample {
+            timestamp: 1,
+            speed_kph,
+            rpm,
+            gear,
+            fuel_liters: 400.0,
+            fuel_capacity_liters: 600.0,
+            engine_enabled: true,
+            paused: false,
+            game_time_min: 600,
+            planned_distance_km: 300.0,
+        }
+    }
+
+    #[test]
+    fn counts_harsh_events_and_revving() {
+        let thresholds = DrivingScoreThresholds::default();
+        let mut state = DrivingBehaviourState::default();
+
+        // 0 -> 20 km/h in one second is a single harsh acceleration.
+        let mut last_speed = 0.0;
+        for (speed, rpm) in [(10.0, 1_500.0), (20.0, 2_300.0), (22.0, 1_400.0)] {
+            update(
+                &mut state,
+                &sample(speed, rpm, 6),
+                last_speed,
+                500,
+                &thresholds,
+            );
+            last_speed = speed;
+        }
+        // Emergency stop.
+        update(
+            &mut state,
+            &sample(5.0, 800.0, 6),
+            last_speed,
+            1_000,
+            &thresholds,
+        );
+
+        assert_eq!(state.harsh_acceleration_events, 1);
+        assert_eq!(state.harsh_braking_events, 1);
+        assert!((state.over_rev_seconds - 0.5).abs() < 1e-9);
+        assert_eq!(state.planned_distance_km, Some(300.0));
+    }
+
+    #[test]
+    fn scores_clean_trips_higher_than_rough_ones() {
+        let thresholds = DrivingScoreThresholds::default();
+        let clean = DrivingBehaviourState {
+            driving_seconds: 3_600.0,
+            ..Default::default()
+        };
+        let rough = DrivingBehaviourState {
+            driving_seconds: 3_600.0,
+            idle_seconds: 900.0,
+            over_rev_seconds: 600.0,
+            harsh_acceleration_events: 6,
+            harsh_braking_events: 4,
+            ..Default::default()
+        };
+
+        let clean_score = score_trip(&clean, 100.0, 30.0, 0, 0, &thresholds);
+        let rough_score = score_trip(&rough, 100.0, 55.0, 3, 1, &thresholds);
+
+        assert_eq!(clean_score.score, 100.0);
+        assert!(rough_score.score < 60.0, "{}", rough_score.score);
+        assert_eq!(rough_score.rest_compliance, 50.0);
+        assert_eq!(rough_score.metrics.fuel_liters_per_100km, Some(55.0));
+    }
+
+    #[test]
+    fn rest_violations_weigh_into_the_score() {
+        let thresholds = DrivingScoreThresholds::default();
+        let state = DrivingBehaviourState {
+            driving_seconds: 3_600.0,
+            ..Default::default()
+        };
+
+        let breakdown = score_trip(&state, 100.0, 30.0, 0, 2, &thresholds);
+
+        assert_eq!(breakdown.rest_compliance, 0.0);
+        assert_eq!(breakdown.metrics.rest_violations, 2);
+        assert_eq!(breakdown.score, 90.0);
+    }
+
+    #[test]
+    fn rates_fuel_on_planned_distance() {
+        let thresholds = DrivingScoreThresholds::default();
+        let planned = DrivingBehaviourState {
+            driving_seconds: 3_600.0,
+            planned_distance_km: Some(100.0),
+            ..Default::default()
+        };
+        let unplanned = DrivingBehaviourState {
+            planned_distance_km: None,
+            ..planned.clone()
+        };
+
+        // A 25 km detour burns fuel the planned route did not need.
+        let detour = score_trip(&planned, 125.0, 45.0, 0, 0, &thresholds);
+        let without_plan = score_trip(&unplanned, 125.0, 45.0, 0, 0, &thresholds);
+
+        assert_eq!(detour.metrics.fuel_liters_per_100km, Some(45.0));
+        assert_eq!(without_plan.metrics.fuel_liters_per_100km, Some(36.0));
+        assert!(detour.fuel_efficiency < without_plan.fuel_efficiency);
+    }
+
+    #[test]
+    fn stores_thresholds_and_scores() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE trips (id INTEGER PRIMARY KEY, origin TEXT, destination TEXT, cargo TEXT);
+             INSERT INTO trips (id, origin, destination, cargo) VALUES (1, 'Berlin', 'Praha', 'Apples');",
+        )
+        .unwrap();
+
+        let saved = save_thresholds(
+            &conn,
+            DrivingScoreThresholds {
+                over_rev_rpm: 1_800.0,
+                harsh_braking_kph_per_s: -3.0,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(saved.over_rev_rpm, 1_800.0);
+        assert_eq!(saved.harsh_braking_kph_per_s, 12.0);
+        assert_eq!(load_thresholds(&conn).unwrap(), saved);
+
+        let breakdown = score_trip(&DrivingBehaviourState::default(), 50.0, 15.0, 1, 0, &saved);
+        store_trip_score(&conn, 1, &breakdown, &saved).unwrap();
+
+        let overview = load_overview(&conn, 5).unwrap();
+        assert_eq!(overview.scored_trips, 1);
+        assert_eq!(overview.recent[0].origin.as_deref(), Some("Berlin"));
+        assert_eq!(overview.recent[0].breakdown, breakdown);
+    }
+}
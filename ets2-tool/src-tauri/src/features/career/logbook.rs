@@ -6,12 +6,14 @@ use rusqlite::{Connection, OptionalExtension, params};
 use serde::Serialize;
 
 use crate::features::career::dispatcher;
+use crate::features::career::driving_score;
+use crate::features::career::tachograph;
 use crate::features::economy::compensation_models::{
     BaseRateType, CargoType, CompanyPaymentTier, CompanyReputationOutcome, EquipmentType,
     JobCompensationInput, UpsertCompanyPaymentProfileInput, Urgency,
 };
 use crate::features::{bank, contracts, economy, employees, events, fleet, reputation};
-use crate::state::{ActiveTripState, CareerRuntime, LiveTelemetryState};
+use crate::state::{ActiveTripState, CareerRuntime, DrivingBehaviourState, LiveTelemetryState};
 
 #[derive(Debug, Clone, Copy)]
 pub struct TelemetrySample {
@@ -23,6 +25,8 @@ pub struct TelemetrySample {
     pub fuel_capacity_liters: f32,
     pub engine_enabled: bool,
     pub paused: bool,
+    pub game_time_min: u32,
+    pub planned_distance_km: f64,
 }
 
 #[derive(Debug, Clone, Serialize, PartialEq)]
@@ -44,6 +48,7 @@ pub struct TripSummary {
     pub speeding_events: i64,
     pub fuel_used_liters: f64,
     pub status: String,
+    pub driving_score: Option<f64>,
 }
 
 #[derive(Debug, Clone, Serialize, PartialEq)]
@@ -61,6 +66,10 @@ pub struct ActiveTripView {
     pub max_speed_kph: f32,
     pub speeding_events: i64,
     pub fuel_used_liters: f64,
+    pub driving_score: f64,
+    /// Driving time left before the tachograph requires a break or rest.
+    pub remaining_drive_minutes: u32,
+    pub hos_violations: i64,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -86,7 +95,23 @@ pub fn process_snapshot(runtime: &CareerRuntime, sample: TelemetrySample) -> Res
 
     let now = Utc::now();
     let now_ms = now.timestamp_millis();
+    track_hours_of_service(runtime, sample, now_ms)?;
+    if let Some(game_day) = bank::game_day_changed(runtime, sample.game_time_min) {
+        let conn = open_connection(runtime)?;
+        if bank::advance_calendar(&conn, game_day)? > 0 {
+            let bank_day = bank::load_state(&conn)?.bank_day;
+            fleet::maintenance::run_due_visits(&conn, bank_day)?;
+            runtime.overview_dirty.store(true, Ordering::Relaxed);
+        }
+    }
+
     let mut pending_finalize: Option<(ActiveTripState, FinalizeReason)> = None;
+    let cargo_damage_percent = runtime
+        .active_job
+        .lock()
+        .map_err(|_| "Career active_job lock poisoned".to_string())?
+        .as_ref()
+        .map(|job| (f64::from(job.cargo_damage) * 100.0).clamp(0.0, 100.0));
 
     {
         let mut active_guard = runtime
@@ -96,6 +121,9 @@ pub fn process_snapshot(runtime: &CareerRuntime, sample: TelemetrySample) -> Res
 
         if let Some(active) = active_guard.as_mut() {
             update_active_trip(active, sample, now_ms);
+            if let Some(damage) = cargo_damage_percent {
+                active.cargo_damage_percent = active.cargo_damage_percent.max(damage);
+            }
             runtime.overview_dirty.store(true, Ordering::Relaxed);
 
             if let Some(target_distance_km) = active.job_target_distance_km {
@@ -153,6 +181,13 @@ pub fn process_snapshot(runtime: &CareerRuntime, sample: TelemetrySample) -> Res
 }
 
 pub fn current_active_trip(runtime: &CareerRuntime) -> Result<Option<ActiveTripView>, String> {
+    let remaining_drive_minutes = runtime
+        .tachograph
+        .lock()
+        .map_err(|_| "Career tachograph lock poisoned".to_string())?
+        .as_ref()
+        .map(|state| state.status().remaining_drive_minutes)
+        .unwrap_or(tachograph::CONTINUOUS_DRIVE_LIMIT_MIN);
     let active_guard = runtime
         .active_trip
         .lock()
@@ -175,6 +210,17 @@ pub fn current_active_trip(runtime: &CareerRuntime) -> Result<Option<ActiveTripV
         max_speed_kph: trip.max_speed_kph,
         speeding_events: trip.speeding_events,
         fuel_used_liters: trip.fuel_used_liters,
+        driving_score: driving_score::score_trip(
+            &trip.driving,
+            trip.distance_km,
+            trip.fuel_used_liters,
+            trip.speeding_events,
+            trip.hos_violations,
+            &trip.score_thresholds,
+        )
+        .score,
+        remaining_drive_minutes,
+        hos_violations: trip.hos_violations,
     }))
 }
 
@@ -206,8 +252,10 @@ pub fn list_trips_from_connection(
                 COALESCE(max_speed_kph, 0),
                 COALESCE(speeding_events, 0),
                 COALESCE(fuel_used_liters, 0),
-                COALESCE(status, 'completed')
+                COALESCE(status, 'completed'),
+                scores.score
             FROM trips
+            LEFT JOIN trip_driving_scores scores ON scores.trip_id = trips.id
             ORDER BY id DESC
             LIMIT ?1
             "#,
@@ -233,6 +281,7 @@ pub fn list_trips_from_connection(
                 speeding_events: row.get(13)?,
                 fuel_used_liters: row.get(14)?,
                 status: row.get(15)?,
+                driving_score: row.get(16)?,
             })
         })
         .map_err(|e| e.to_string())?;
@@ -262,6 +311,7 @@ fn start_trip(
         contracts::select_dispatch_assignment(&conn)?
     };
     employees::mark_driver_status(&conn, "on_duty")?;
+    let score_thresholds = driving_score::load_thresholds(&conn)?;
 
     conn.execute(
         r#"
@@ -325,6 +375,14 @@ fn start_trip(
         fuel_used_liters: 0.0,
         last_fuel_liters: sample.fuel_liters,
         last_speed_kph: sample.speed_kph,
+        driving: DrivingBehaviourState {
+            planned_distance_km: (sample.planned_distance_km > 0.0)
+                .then_some(sample.planned_distance_km),
+            ..Default::default()
+        },
+        score_thresholds,
+        hos_violations: 0,
+        cargo_damage_percent: 0.0,
     };
 
     let mut active_guard = runtime
@@ -350,6 +408,15 @@ fn finalize_trip(
         0.0
     };
 
+    let driving = driving_score::score_trip(
+        &active.driving,
+        active.distance_km,
+        active.fuel_used_liters,
+        active.speeding_events,
+        active.hos_violations,
+        &active.score_thresholds,
+    );
+
     let job_target_distance = active.job_target_distance_km.unwrap_or(0.0);
     let job_total_progress = active.job_progress_base_km + active.distance_km;
     let is_dispatch_job =
@@ -375,14 +442,31 @@ fn finalize_trip(
         };
         let company_context = resolve_company_context(&conn, active, is_dispatch_job)?;
 
-        let wear = fleet::apply_trip_wear(&conn, distance_for_result, active.speeding_events)?;
+        let wear = fleet::apply_trip_wear(
+            &conn,
+            distance_for_result,
+            active.speeding_events,
+            active.cargo_damage_percent,
+            ended_at.timestamp_millis().unsigned_abs() ^ active.trip_id as u64,
+        )?;
         let costs = economy::estimate_trip_costs(
             &conn,
             active.fuel_used_liters,
             distance_for_result,
             wear.repair_reserve,
         )?;
-        let gross_income = if dispatcher_completed {
+        let linked_dispatcher_job = if is_dispatch_job {
+            None
+        } else {
+            let started_at_utc = chrono::DateTime::from_timestamp_millis(active.started_at_utc_ms)
+                .unwrap_or(ended_at)
+                .to_rfc3339();
+            dispatcher::linked_job_for_trip(&conn, &started_at_utc)?
+        };
+        let gross_income = if linked_dispatcher_job.is_some() {
+            // The settlement credits the net reward of linked dispatcher jobs.
+            0
+        } else if dispatcher_completed {
             (job_target_distance * active.job_price_per_km.unwrap_or_default()).round() as i64
         } else {
             let pricing = economy::compensation_service::calculate_job_compensation(
@@ -403,23 +487,43 @@ fn finalize_trip(
 
             pricing.final_price + active.bonus_payout
         };
-        let net_income = gross_income - costs.total_cost;
+        let hours_of_service = tachograph::trip_settlement(&conn, active.trip_id)?;
+        let net_income = gross_income - costs.total_cost - hours_of_service.fines;
 
         income = Some(net_income);
-        let bank_state = bank::apply_trip_result(&conn, net_income)?;
-        let reputation_state =
-            reputation::apply_trip_outcome(&conn, distance_for_result, active.speeding_events)?;
-        let company_outcome = CompanyReputationOutcome {
-            completed: true,
-            on_time: true,
-            damage_percent: ((active.speeding_events.max(0) as f64) * 1.8).min(100.0),
-            canceled: false,
-        };
-        let company_reputation = economy::compensation_service::apply_company_reputation_outcome(
+        bank::apply_trip_result(
             &conn,
-            &company_context.company_id,
-            company_outcome,
+            net_income,
+            &format!("Trip {} -> {}", active.origin, active.destination),
+        )?;
+        let reputation_state = reputation::apply_trip_outcome(
+            &conn,
+            distance_for_result,
+            active.speeding_events,
+            Some(driving.score),
+            hours_of_service.reputation_penalty,
         )?;
+        let company_reputation = if linked_dispatcher_job.is_some() {
+            economy::compensation_service::load_company_reputation(
+                &conn,
+                &company_context.company_id,
+            )?
+        } else {
+            economy::compensation_service::apply_company_reputation_outcome(
+                &conn,
+                &company_context.company_id,
+                CompanyReputationOutcome {
+                    completed: true,
+                    on_time: true,
+                    damage_percent: if active.cargo_damage_percent > 0.0 {
+                        active.cargo_damage_percent
+                    } else {
+                        ((active.speeding_events.max(0) as f64) * 1.8).min(100.0)
+                    },
+                    canceled: false,
+                },
+            )?
+        };
 
         if dispatcher_completed {
             let _ = dispatcher::complete_job(&conn, &active.job_id)?;
@@ -452,7 +556,34 @@ fn finalize_trip(
             )?;
         }
 
-        if wear.player_condition < 82.0 {
+        if hours_of_service.violations > 0 {
+            events::record_event(
+                &conn,
+                "compliance",
+                "Driving time fines settled",
+                &format!(
+                    "{} tachograph violation(s) on route {} -> {} cost EUR {}.",
+                    hours_of_service.violations,
+                    active.origin,
+                    active.destination,
+                    hours_of_service.fines
+                ),
+                "high",
+            )?;
+        }
+
+        if wear.breakdown {
+            events::record_event(
+                &conn,
+                "fleet",
+                "Truck breakdown",
+                &format!(
+                    "The player truck broke down on {} -> {} and was towed for EUR {}.",
+                    active.origin, active.destination, wear.towing_cost
+                ),
+                "high",
+            )?;
+        } else if wear.player_condition < 82.0 {
             events::record_event(
                 &conn,
                 "fleet",
@@ -464,17 +595,16 @@ fn finalize_trip(
                 "medium",
             )?;
         }
-
-        if bank_state.debt_balance > 0 {
+        if let Some(visit) = wear.scheduled_visit.as_ref() {
             events::record_event(
                 &conn,
-                "bank",
-                "Loan installment processed",
+                "fleet",
+                "Workshop visit scheduled",
                 &format!(
-                    "Debt is now EUR {} after the latest repayment cycle.",
-                    bank_state.debt_balance
+                    "{} of {} booked for bank day {} (estimate EUR {}).",
+                    visit.kind, visit.asset_id, visit.scheduled_bank_day, visit.estimated_cost
                 ),
-                "medium",
+                "low",
             )?;
         }
 
@@ -529,7 +659,7 @@ fn finalize_trip(
             cargo = ?4,
             distance_km = ?5,
             income = ?6,
-            damage = 0,
+            damage = ?15,
             duration_seconds = ?7,
             avg_speed_kph = ?8,
             max_speed_kph = ?9,
@@ -553,14 +683,78 @@ fn finalize_trip(
             active.fuel_used_liters,
             trip_status,
             telemetry_json(sample),
-            active.trip_id
+            active.trip_id,
+            active.cargo_damage_percent
         ],
     )
     .map_err(|e| e.to_string())?;
+    driving_score::store_trip_score(&conn, active.trip_id, &driving, &active.score_thresholds)?;
+    tachograph::persist_state(runtime, &conn)?;
 
     Ok(())
 }
 
+/// Advances the tachograph and books whatever it reports: violations go to
+/// the active trip, a detected sleep puts the driver to rest.
+fn track_hours_of_service(
+    runtime: &CareerRuntime,
+    sample: TelemetrySample,
+    now_ms: i64,
+) -> Result<(), String> {
+    if !tachograph::is_restored(runtime) {
+        tachograph::restore_state(runtime, &open_connection(runtime)?)?;
+    }
+    let observation = tachograph::observe(
+        runtime,
+        sample.game_time_min,
+        sample.speed_kph,
+        sample.paused,
+        now_ms,
+    )?;
+    if observation.is_quiet() {
+        return Ok(());
+    }
+
+    let trip_id = {
+        let mut active_guard = runtime
+            .active_trip
+            .lock()
+            .map_err(|_| "Career active_trip lock poisoned".to_string())?;
+        active_guard.as_mut().map(|active| {
+            active.hos_violations += observation.violations.len() as i64;
+            active.trip_id
+        })
+    };
+
+    let conn = open_connection(runtime)?;
+    tachograph::record_violations(&conn, trip_id, &observation.violations)?;
+    for violation in &observation.violations {
+        events::record_event(
+            &conn,
+            "compliance",
+            "Driving time violation",
+            &violation.describe(),
+            "high",
+        )?;
+    }
+    if let Some(minutes) = observation.slept_minutes {
+        employees::mark_driver_status(&conn, "resting")?;
+        events::record_event(
+            &conn,
+            "compliance",
+            "Rest logged",
+            &format!(
+                "{} of sleep recorded on the tachograph.",
+                tachograph::format_minutes(minutes)
+            ),
+            "low",
+        )?;
+    }
+    tachograph::persist_state(runtime, &conn)?;
+    runtime.overview_dirty.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
 fn update_active_trip(active: &mut ActiveTripState, sample: TelemetrySample, now_ms: i64) {
     if !sample.paused {
         let delta_ms = (now_ms - active.last_update_utc_ms).clamp(0, 3000);
@@ -585,6 +779,14 @@ fn update_active_trip(active: &mut ActiveTripState, sample: TelemetrySample, now
         if sample.fuel_liters < active.last_fuel_liters {
             active.fuel_used_liters += (active.last_fuel_liters - sample.fuel_liters) as f64;
         }
+
+        driving_score::update(
+            &mut active.driving,
+            &sample,
+            active.last_speed_kph,
+            delta_ms,
+            &active.score_thresholds,
+        );
     }
 
     active.last_fuel_liters = sample.fuel_liters;
@@ -620,7 +822,7 @@ fn update_runtime_telemetry(
     Ok(())
 }
 
-fn open_connection(runtime: &CareerRuntime) -> Result<Connection, String> {
+pub(crate) fn open_connection(runtime: &CareerRuntime) -> Result<Connection, String> {
     let db_path = runtime
         .db_path
         .lock()
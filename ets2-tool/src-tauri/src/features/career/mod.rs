@@ -1,7 +1,9 @@
 pub mod analytics;
 pub mod commands;
 pub mod db;
+pub mod delivery_log;
 pub mod dispatcher;
+pub mod driving_score;
 pub mod job_log;
 pub mod job_tracking;
 pub mod logbook;
@@ -10,5 +12,8 @@ pub mod overview;
 pub mod plugin_installer;
 pub mod scs_sdk_telemetry;
 pub mod service;
+pub mod tachograph;
 pub mod telemetry;
 pub mod telemetry_debug;
+pub mod telemetry_source;
+pub mod trip_track;
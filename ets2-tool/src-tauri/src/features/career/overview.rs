@@ -3,6 +3,7 @@ use serde::Serialize;
 use std::collections::HashSet;
 
 use crate::features::career::dispatcher::{self, Job};
+use crate::features::career::driving_score::{self, DrivingScoreOverview};
 use crate::features::career::job_log::{self, JobLogEntry, JobStats};
 use crate::features::career::logbook::{self, ActiveTripView, TripSummary};
 use crate::features::economy::compensation_models::{
@@ -62,6 +63,7 @@ pub struct CareerOverview {
     pub recent_jobs: Vec<JobLogEntry>,
     pub job_stats: JobStats,
     pub last_telemetry: Option<LiveTelemetryState>,
+    pub driving_scores: DrivingScoreOverview,
     pub dashboard: CareerDashboardMetrics,
     pub statistics: CareerStatistics,
 }
@@ -93,6 +95,7 @@ pub fn load_overview(runtime: &CareerRuntime) -> Result<CareerOverview, String>
     let mut recent_jobs = job_log::list_recent_jobs(&conn, 8)?;
     job_log::enrich_job_entries(&conn, &mut recent_jobs)?;
     let job_stats = job_log::load_job_stats(&conn)?;
+    let driving_scores = driving_score::load_overview(&conn, 8)?;
     let mut jobs = dispatcher::list_jobs(&conn, 8)?;
     let mut current_job = dispatcher::current_job(&conn)?;
     let last_telemetry = runtime
@@ -165,6 +168,7 @@ pub fn load_overview(runtime: &CareerRuntime) -> Result<CareerOverview, String>
         recent_jobs,
         job_stats,
         last_telemetry,
+        driving_scores,
         dashboard,
         statistics,
     })
@@ -193,6 +193,8 @@ mod platform {
                 fuel_capacity_liters: zone4._prefix[1],
                 engine_enabled: zone5.engine_enabled != 0,
                 paused: zone1_after.paused != 0,
+                game_time_min: 0,
+                planned_distance_km: 0.0,
             }))
         }
 
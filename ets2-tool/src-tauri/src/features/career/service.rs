@@ -1,16 +1,21 @@
 use rusqlite::Connection;
+use sqlx::SqlitePool;
 use std::sync::Arc;
 use std::sync::atomic::Ordering;
 use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter, Manager};
 
-use crate::features::career::dispatcher;
+use crate::features::bank;
+use crate::features::career::dispatcher::{self, DispatcherDeliveryReport};
 use crate::features::career::plugin_installer::{self, ScsGame};
 use crate::features::career::telemetry::GameId;
-use crate::features::career::{db, overlay, overview, telemetry};
+use crate::features::career::{commands, db, logbook, overlay, overview, telemetry};
+use crate::features::employees::simulation;
+use crate::features::ets2save::models::{EtsJobLink, EtsJobLinkStatus, EtsSaveTarget};
 use crate::features::hub::events::CareerStatus;
+use crate::features::telemetry::events::TelemetryJobEventPayload;
 use crate::shared::current_profile::snapshot_save_context;
-use crate::state::{AppProfileState, CareerRuntime};
+use crate::state::{AppProfileState, CareerRuntime, CareerState};
 
 fn choose_game(ets2_running: bool, ats_running: bool, previous: Option<&str>) -> Option<GameId> {
     match (ets2_running, ats_running, previous) {
@@ -34,6 +39,96 @@ fn to_scs_game(game: GameId) -> ScsGame {
     }
 }
 
+/// Lets hired drivers catch up with the bank calendar. Runs with the
+/// dispatcher poll because drivers only take market jobs of the loaded save.
+fn simulate_employees(
+    conn: &Connection,
+    save_context: &dispatcher::DispatcherSaveContext,
+) -> Result<bool, String> {
+    if !save_context.is_ready() {
+        return Ok(false);
+    }
+    let bank_day = bank::load_state(conn)?.bank_day;
+    Ok(!simulation::simulate_until(conn, bank_day, save_context)?.is_empty())
+}
+
+/// Follow-up of a telemetry delivery/failure event: settles the linked dispatcher job with
+/// late fees and damage deductions and updates the company reputation.
+pub fn settle_dispatcher_job(
+    app: &AppHandle,
+    link: &EtsJobLink,
+    event: &TelemetryJobEventPayload,
+) -> Result<(), String> {
+    let delivered = match link.status {
+        EtsJobLinkStatus::Completed => true,
+        EtsJobLinkStatus::Error => false,
+        _ => return Ok(()),
+    };
+    let career = app.state::<CareerState>();
+    let runtime = career.runtime.as_ref();
+    let conn = logbook::open_connection(runtime)?;
+    dispatcher::dispatcher_settle_linked_job(
+        &conn,
+        &link.vtc_job_id,
+        &link.link_id,
+        &DispatcherDeliveryReport {
+            delivered,
+            cancelled: event.job_cancelled,
+            delivered_game_min: event.game_time_min,
+            cargo_damage_percent: event.cargo_damage * 100.0,
+        },
+    )?;
+    runtime.overview_dirty.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Follow-up of a telemetry delivery/failure event: advances the chain of the finished leg
+/// and injects the released next leg into the active quicksave.
+pub async fn continue_dispatcher_chain(
+    app: &AppHandle,
+    db_pool: &SqlitePool,
+    link: &EtsJobLink,
+) -> Result<(), String> {
+    let delivered = match link.status {
+        EtsJobLinkStatus::Completed => true,
+        EtsJobLinkStatus::Error => false,
+        _ => return Ok(()),
+    };
+    let career = app.state::<CareerState>();
+    let profile = app.state::<AppProfileState>();
+    let runtime = career.runtime.as_ref();
+
+    let (next_job_id, save_target) = {
+        let conn = logbook::open_connection(runtime)?;
+        let next_job_id = dispatcher::dispatcher_advance_chain(&conn, &link.vtc_job_id, delivered)?
+            .and_then(|progress| progress.next_job_id);
+        // Later legs go into the same save slot as the first one.
+        let save_target = dispatcher::dispatcher_chain_save_target(&conn, &link.vtc_job_id)?
+            .map(|(slot, clone_to_new_slot)| EtsSaveTarget {
+                slot,
+                clone_to_new_slot,
+            })
+            .unwrap_or_default();
+        (next_job_id, save_target)
+    };
+    runtime.overview_dirty.store(true, Ordering::Relaxed);
+    let Some(next_job_id) = next_job_id else {
+        return Ok(());
+    };
+
+    commands::dispatcher_assign_prepare_write_inner(
+        Some(app),
+        runtime,
+        profile.inner(),
+        db_pool,
+        &next_job_id,
+        true,
+        &save_target,
+    )
+    .await
+    .map(|_| ())
+}
+
 pub fn start_background(app: AppHandle, runtime: Arc<CareerRuntime>) {
     crate::dev_log!("[trace] START career_background_startup");
     std::thread::spawn(move || {
@@ -234,6 +329,17 @@ pub fn start_background(app: AppHandle, runtime: Arc<CareerRuntime>) {
                                     );
                                 }
                             }
+
+                            match simulate_employees(&conn, &save_context) {
+                                Ok(true) => runtime.overview_dirty.store(true, Ordering::Relaxed),
+                                Ok(false) => {}
+                                Err(error) => {
+                                    crate::dev_log!(
+                                        "[career] employee simulation failed: {}",
+                                        error
+                                    );
+                                }
+                            }
                         }
                         Err(error) => {
                             crate::dev_log!("[career] dispatcher db open failed: {}", error);
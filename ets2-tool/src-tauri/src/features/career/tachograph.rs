@@ -0,0 +1,771 @@
+//! Hours-of-service tracking for career drivers.
+//!
+//! A simplified EU tachograph (Regulation (EC) No 561/2006) runs on game
+//! minutes from telemetry. Moving counts as driving; standstill, pauses and
+//! the time skip of an in-game sleep count as rest. The counters span trips
+//! and app restarts through `tachograph_state`, and every violation is stored
+//! in `trip_hos_violations` against the trip it happened on so `logbook` can
+//! settle fines and reputation when the trip is finalized.
+//!
+//! Split breaks (15 + 30 minutes) and split daily rests are not modelled: a
+//! break only counts once the truck stood still for the full duration.
+//!
+//! Sources without a game clock (the SCS SDK reader) drive the tachograph on
+//! unpaused wall time scaled to game minutes instead. In-game sleeps cannot be
+//! told apart from a pause on that clock, so only standstill counts as rest.
+
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension, params};
+use serde::{Deserialize, Serialize};
+
+use crate::shared::sqlite_schema::create_indexes;
+use crate::state::{CareerRuntime, TachographState};
+
+/// Driving without a break that triggers a continuous-driving violation.
+pub const CONTINUOUS_DRIVE_LIMIT_MIN: u32 = 270;
+/// Standstill that resets continuous driving.
+pub const BREAK_MIN: u32 = 45;
+pub const DAILY_DRIVE_LIMIT_MIN: u32 = 540;
+/// Allowed twice per week instead of the regular daily limit.
+pub const EXTENDED_DAILY_DRIVE_LIMIT_MIN: u32 = 600;
+const EXTENDED_DAYS_PER_WEEK: u8 = 2;
+pub const WEEKLY_DRIVE_LIMIT_MIN: u32 = 3_360;
+pub const REGULAR_DAILY_REST_MIN: u32 = 660;
+/// Allowed three times between two weekly rests.
+pub const REDUCED_DAILY_REST_MIN: u32 = 540;
+const REDUCED_DAILY_RESTS_PER_WEEK: u8 = 3;
+/// Reduced weekly rest; anything this long restarts the driving week.
+pub const WEEKLY_REST_MIN: u32 = 1_440;
+/// A daily rest must start at the latest 24 hours after the duty period
+/// began, minus the time it takes.
+const DUTY_PERIOD_LIMIT_MIN: u32 = 24 * 60 - REDUCED_DAILY_REST_MIN;
+/// Game time jumps between two samples that only a sleep or a ferry explain.
+const SLEEP_JUMP_MIN: u32 = 60;
+const MOVING_SPEED_KPH: f32 = 1.0;
+/// Longest wall-clock gap between two samples that still advances the sample clock.
+const MAX_SAMPLE_GAP_MS: i64 = 3_000;
+/// Game minutes per real minute at the default time compression.
+const TIME_SCALE: f64 = 19.0;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HosViolationKind {
+    ContinuousDriving,
+    DailyDriving,
+    WeeklyDriving,
+    DailyRest,
+    ReducedRest,
+}
+
+impl HosViolationKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HosViolationKind::ContinuousDriving => "continuous_driving",
+            HosViolationKind::DailyDriving => "daily_driving",
+            HosViolationKind::WeeklyDriving => "weekly_driving",
+            HosViolationKind::DailyRest => "daily_rest",
+            HosViolationKind::ReducedRest => "reduced_rest",
+        }
+    }
+
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "continuous_driving" => Some(HosViolationKind::ContinuousDriving),
+            "daily_driving" => Some(HosViolationKind::DailyDriving),
+            "weekly_driving" => Some(HosViolationKind::WeeklyDriving),
+            "daily_rest" => Some(HosViolationKind::DailyRest),
+            "reduced_rest" => Some(HosViolationKind::ReducedRest),
+            _ => None,
+        }
+    }
+
+    /// Roadside inspection fine in EUR, deducted from the trip result.
+    pub fn fine(self) -> i64 {
+        match self {
+            HosViolationKind::ContinuousDriving => 150,
+            HosViolationKind::DailyDriving => 300,
+            HosViolationKind::WeeklyDriving => 500,
+            HosViolationKind::DailyRest => 300,
+            HosViolationKind::ReducedRest => 200,
+        }
+    }
+
+    /// Reputation points lost when the trip is settled.
+    pub fn reputation_penalty(self) -> i64 {
+        match self {
+            HosViolationKind::ContinuousDriving | HosViolationKind::ReducedRest => 1,
+            HosViolationKind::DailyDriving | HosViolationKind::DailyRest => 2,
+            HosViolationKind::WeeklyDriving => 3,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            HosViolationKind::ContinuousDriving => "Break missed after 4.5 h of driving",
+            HosViolationKind::DailyDriving => "Daily driving time exceeded",
+            HosViolationKind::WeeklyDriving => "Weekly driving time exceeded",
+            HosViolationKind::DailyRest => "Daily rest started too late",
+            HosViolationKind::ReducedRest => "Too many reduced daily rests",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HosViolation {
+    pub kind: HosViolationKind,
+    pub game_time_min: u32,
+    /// Measured value at the moment of the violation, in minutes or rests.
+    pub measured: u32,
+    pub limit: u32,
+    pub fine: i64,
+}
+
+impl HosViolation {
+    fn new(kind: HosViolationKind, game_time_min: u32, measured: u32, limit: u32) -> Self {
+        Self {
+            kind,
+            game_time_min,
+            measured,
+            limit,
+            fine: kind.fine(),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self.kind {
+            HosViolationKind::ReducedRest => format!(
+                "{}: {} of {} allowed this week. Fine: EUR {}.",
+                self.kind.label(),
+                self.measured,
+                self.limit,
+                self.fine
+            ),
+            _ => format!(
+                "{}: {} / {}. Fine: EUR {}.",
+                self.kind.label(),
+                format_minutes(self.measured),
+                format_minutes(self.limit),
+                self.fine
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HosViolationEntry {
+    pub id: i64,
+    pub trip_id: Option<i64>,
+    #[serde(flatten)]
+    pub violation: HosViolation,
+    pub recorded_at_utc: String,
+}
+
+/// What one sample changed on the tachograph.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HosObservation {
+    pub violations: Vec<HosViolation>,
+    /// Game minutes skipped while paused, i.e. the driver slept.
+    pub slept_minutes: Option<u32>,
+    /// A daily or weekly rest was completed and the day counters reset.
+    pub rest_completed: bool,
+}
+
+impl HosObservation {
+    pub fn is_quiet(&self) -> bool {
+        self.violations.is_empty() && self.slept_minutes.is_none() && !self.rest_completed
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HosStatus {
+    pub remaining_drive_minutes: u32,
+    pub continuous_drive_minutes: u32,
+    pub daily_drive_minutes: u32,
+    pub daily_limit_minutes: u32,
+    pub weekly_drive_minutes: u32,
+    pub current_rest_minutes: u32,
+    pub extended_days_left: u8,
+    pub reduced_rests_left: u8,
+    /// Game minutes until the next daily rest has to begin.
+    pub minutes_until_daily_rest: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HosOverview {
+    pub status: HosStatus,
+    pub recent_violations: Vec<HosViolationEntry>,
+}
+
+/// Fines and reputation owed for one trip.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HosSettlement {
+    pub violations: i64,
+    pub fines: i64,
+    pub reputation_penalty: i64,
+}
+
+impl TachographState {
+    /// Feeds one live sample, falling back to the sample clock when the source
+    /// reports no game time.
+    pub fn observe(
+        &mut self,
+        game_time_min: u32,
+        speed_kph: f32,
+        paused: bool,
+        now_ms: i64,
+    ) -> HosObservation {
+        let minute = self.clock_minute(game_time_min, paused, now_ms);
+        self.advance(minute, speed_kph, paused)
+    }
+
+    fn clock_minute(&mut self, game_time_min: u32, paused: bool, now_ms: i64) -> u32 {
+        let from_samples = game_time_min == 0;
+        if from_samples != self.clock_from_samples {
+            // The two clocks are unrelated; start over from the next sample.
+            self.clock_from_samples = from_samples;
+            self.last_game_time_min = None;
+        }
+        let last_ms = self.last_sample_ms.replace(now_ms);
+        if !from_samples {
+            return game_time_min;
+        }
+        if !paused {
+            let delta_ms = last_ms
+                .map(|last| (now_ms - last).clamp(0, MAX_SAMPLE_GAP_MS))
+                .unwrap_or(0);
+            self.sample_clock_min += delta_ms as f64 / 60_000.0 * TIME_SCALE;
+        }
+        // Zero means "no game time" to `advance`.
+        1 + self.sample_clock_min as u32
+    }
+
+    /// Advances the tachograph to `game_time_min`.
+    pub fn advance(&mut self, game_time_min: u32, speed_kph: f32, paused: bool) -> HosObservation {
+        let mut observation = HosObservation::default();
+        if game_time_min == 0 {
+            return observation;
+        }
+        let Some(last) = self.last_game_time_min.replace(game_time_min) else {
+            return observation;
+        };
+        // Time running backwards means another profile or save was loaded.
+        if game_time_min <= last {
+            return observation;
+        }
+
+        let delta = game_time_min - last;
+        let moving = !paused && speed_kph.abs() > MOVING_SPEED_KPH && delta < SLEEP_JUMP_MIN;
+        if moving {
+            if self.stationary_min > 0 {
+                self.end_rest(game_time_min, &mut observation);
+            }
+            self.drive(game_time_min, delta, &mut observation);
+        } else {
+            if paused && delta >= SLEEP_JUMP_MIN {
+                observation.slept_minutes = Some(delta);
+            }
+            self.rest(delta, &mut observation);
+        }
+        observation
+    }
+
+    pub fn status(&self) -> HosStatus {
+        let daily_limit = self.daily_limit();
+        let remaining = (CONTINUOUS_DRIVE_LIMIT_MIN.saturating_sub(self.continuous_drive_min))
+            .min(daily_limit.saturating_sub(self.daily_drive_min))
+            .min(WEEKLY_DRIVE_LIMIT_MIN.saturating_sub(self.weekly_drive_min));
+        HosStatus {
+            remaining_drive_minutes: remaining,
+            continuous_drive_minutes: self.continuous_drive_min,
+            daily_drive_minutes: self.daily_drive_min,
+            daily_limit_minutes: daily_limit,
+            weekly_drive_minutes: self.weekly_drive_min,
+            current_rest_minutes: self.stationary_min,
+            extended_days_left: EXTENDED_DAYS_PER_WEEK.saturating_sub(self.extended_days_used),
+            reduced_rests_left: REDUCED_DAILY_RESTS_PER_WEEK
+                .saturating_sub(self.reduced_rests_used),
+            minutes_until_daily_rest: self.duty_started_game_min.map(|started| {
+                let elapsed = self
+                    .last_game_time_min
+                    .unwrap_or(started)
+                    .saturating_sub(started);
+                DUTY_PERIOD_LIMIT_MIN.saturating_sub(elapsed)
+            }),
+        }
+    }
+
+    fn daily_limit(&self) -> u32 {
+        if self.day_extended || self.extended_days_used < EXTENDED_DAYS_PER_WEEK {
+            EXTENDED_DAILY_DRIVE_LIMIT_MIN
+        } else {
+            DAILY_DRIVE_LIMIT_MIN
+        }
+    }
+
+    fn drive(&mut self, now: u32, delta: u32, observation: &mut HosObservation) {
+        self.continuous_drive_min += delta;
+        self.daily_drive_min += delta;
+        self.weekly_drive_min += delta;
+        let duty_started = *self
+            .duty_started_game_min
+            .get_or_insert(now.saturating_sub(delta));
+
+        if self.continuous_drive_min > CONTINUOUS_DRIVE_LIMIT_MIN && !self.continuous_flagged {
+            self.continuous_flagged = true;
+            observation.violations.push(HosViolation::new(
+                HosViolationKind::ContinuousDriving,
+                now,
+                self.continuous_drive_min,
+                CONTINUOUS_DRIVE_LIMIT_MIN,
+            ));
+        }
+
+        if self.daily_drive_min > DAILY_DRIVE_LIMIT_MIN
+            && !self.day_extended
+            && self.extended_days_used < EXTENDED_DAYS_PER_WEEK
+        {
+            self.day_extended = true;
+            self.extended_days_used += 1;
+        }
+        let daily_limit = self.daily_limit();
+        if self.daily_drive_min > daily_limit && !self.daily_flagged {
+            self.daily_flagged = true;
+            observation.violations.push(HosViolation::new(
+                HosViolationKind::DailyDriving,
+                now,
+                self.daily_drive_min,
+                daily_limit,
+            ));
+        }
+
+        if self.weekly_drive_min > WEEKLY_DRIVE_LIMIT_MIN && !self.weekly_flagged {
+            self.weekly_flagged = true;
+            observation.violations.push(HosViolation::new(
+                HosViolationKind::WeeklyDriving,
+                now,
+                self.weekly_drive_min,
+                WEEKLY_DRIVE_LIMIT_MIN,
+            ));
+        }
+
+        let on_duty = now.saturating_sub(duty_started);
+        if on_duty > DUTY_PERIOD_LIMIT_MIN && !self.duty_flagged {
+            self.duty_flagged = true;
+            observation.violations.push(HosViolation::new(
+                HosViolationKind::DailyRest,
+                now,
+                on_duty,
+                DUTY_PERIOD_LIMIT_MIN,
+            ));
+        }
+    }
+
+    fn rest(&mut self, delta: u32, observation: &mut HosObservation) {
+        let before = self.stationary_min;
+        self.stationary_min += delta;
+        let crossed = |limit: u32| before < limit && self.stationary_min >= limit;
+
+        if crossed(BREAK_MIN) {
+            self.continuous_drive_min = 0;
+            self.continuous_flagged = false;
+        }
+        if crossed(REDUCED_DAILY_REST_MIN) {
+            self.daily_drive_min = 0;
+            self.day_extended = false;
+            self.duty_started_game_min = None;
+            self.daily_flagged = false;
+            self.duty_flagged = false;
+            observation.rest_completed = true;
+        }
+        if crossed(WEEKLY_REST_MIN) {
+            self.weekly_drive_min = 0;
+            self.extended_days_used = 0;
+            self.reduced_rests_used = 0;
+            self.weekly_flagged = false;
+        }
+    }
+
+    fn end_rest(&mut self, now: u32, observation: &mut HosObservation) {
+        let rest = std::mem::take(&mut self.stationary_min);
+        if (REDUCED_DAILY_REST_MIN..REGULAR_DAILY_REST_MIN).contains(&rest) {
+            self.reduced_rests_used = self.reduced_rests_used.saturating_add(1);
+            if self.reduced_rests_used > REDUCED_DAILY_RESTS_PER_WEEK {
+                observation.violations.push(HosViolation::new(
+                    HosViolationKind::ReducedRest,
+                    now,
+                    u32::from(self.reduced_rests_used),
+                    u32::from(REDUCED_DAILY_RESTS_PER_WEEK),
+                ));
+            }
+        }
+    }
+}
+
+pub fn ensure_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS tachograph_state (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            state_json TEXT NOT NULL,
+            updated_at_utc TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS trip_hos_violations (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            trip_id INTEGER,
+            kind TEXT NOT NULL,
+            game_time_min INTEGER NOT NULL,
+            measured INTEGER NOT NULL,
+            limit_value INTEGER NOT NULL,
+            fine INTEGER NOT NULL DEFAULT 0,
+            recorded_at_utc TEXT NOT NULL
+        );
+        "#,
+    )
+    .map_err(|e| e.to_string())?;
+    create_indexes(
+        conn,
+        &[
+            "CREATE INDEX IF NOT EXISTS idx_trip_hos_violations_trip ON trip_hos_violations(trip_id)",
+        ],
+    )
+}
+
+/// Loads the persisted counters into the session unless they are loaded already.
+pub fn restore_state(runtime: &CareerRuntime, conn: &Connection) -> Result<(), String> {
+    let mut guard = runtime
+        .tachograph
+        .lock()
+        .map_err(|_| "Career tachograph lock poisoned".to_string())?;
+    if guard.is_none() {
+        *guard = Some(load_state(conn)?);
+    }
+    Ok(())
+}
+
+pub fn is_restored(runtime: &CareerRuntime) -> bool {
+    runtime
+        .tachograph
+        .lock()
+        .map(|guard| guard.is_some())
+        .unwrap_or(false)
+}
+
+/// Feeds one live sample into the session's tachograph.
+pub fn observe(
+    runtime: &CareerRuntime,
+    game_time_min: u32,
+    speed_kph: f32,
+    paused: bool,
+    now_ms: i64,
+) -> Result<HosObservation, String> {
+    Ok(runtime
+        .tachograph
+        .lock()
+        .map_err(|_| "Career tachograph lock poisoned".to_string())?
+        .get_or_insert_with(TachographState::default)
+        .observe(game_time_min, speed_kph, paused, now_ms))
+}
+
+fn load_state(conn: &Connection) -> Result<TachographState, String> {
+    let stored = conn
+        .query_row(
+            "SELECT state_json FROM tachograph_state WHERE id = 1",
+            [],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    Ok(stored
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default())
+}
+
+pub fn persist_state(runtime: &CareerRuntime, conn: &Connection) -> Result<(), String> {
+    let Some(state) = runtime
+        .tachograph
+        .lock()
+        .map_err(|_| "Career tachograph lock poisoned".to_string())?
+        .clone()
+    else {
+        return Ok(());
+    };
+    let json = serde_json::to_string(&state).map_err(|e| e.to_string())?;
+    conn.execute(
+        r#"
+        INSERT INTO tachograph_state (id, state_json, updated_at_utc)
+        VALUES (1, ?1, ?2)
+        ON CONFLICT(id) DO UPDATE SET
+            state_json = excluded.state_json,
+            updated_at_utc = excluded.updated_at_utc
+        "#,
+        params![json, Utc::now().to_rfc3339()],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Status of the session's tachograph, read from the database when no sample
+/// arrived yet.
+pub fn current_status(runtime: &CareerRuntime, conn: &Connection) -> Result<HosStatus, String> {
+    let loaded = runtime
+        .tachograph
+        .lock()
+        .map_err(|_| "Career tachograph lock poisoned".to_string())?
+        .as_ref()
+        .map(TachographState::status);
+    match loaded {
+        Some(status) => Ok(status),
+        None => Ok(load_state(conn)?.status()),
+    }
+}
+
+pub fn record_violations(
+    conn: &Connection,
+    trip_id: Option<i64>,
+    violations: &[HosViolation],
+) -> Result<(), String> {
+    let recorded_at = Utc::now().to_rfc3339();
+    for violation in violations {
+        conn.execute(
+            r#"
+            INSERT INTO trip_hos_violations (
+                trip_id, kind, game_time_min, measured, limit_value, fine, recorded_at_utc
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            "#,
+            params![
+                trip_id,
+                violation.kind.as_str(),
+                violation.game_time_min,
+                violation.measured,
+                violation.limit,
+                violation.fine,
+                recorded_at
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+pub fn list_violations(
+    conn: &Connection,
+    trip_id: Option<i64>,
+    limit: usize,
+) -> Result<Vec<HosViolationEntry>, String> {
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT id, trip_id, kind, game_time_min, measured, limit_value, fine, recorded_at_utc
+            FROM trip_hos_violations
+            WHERE ?1 IS NULL OR trip_id = ?1
+            ORDER BY id DESC
+            LIMIT ?2
+            "#,
+        )
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params![trip_id, limit as i64], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, Option<i64>>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, u32>(3)?,
+                row.get::<_, u32>(4)?,
+                row.get::<_, u32>(5)?,
+                row.get::<_, i64>(6)?,
+                row.get::<_, String>(7)?,
+            ))
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut entries = Vec::new();
+    for row in rows {
+        let (id, trip_id, kind, game_time_min, measured, limit, fine, recorded_at_utc) =
+            row.map_err(|e| e.to_string())?;
+        let Some(kind) = HosViolationKind::from_str(&kind) else {
+            continue;
+        };
+        entries.push(HosViolationEntry {
+            id,
+            trip_id,
+            violation: HosViolation {
+                kind,
+                game_time_min,
+                measured,
+                limit,
+                fine,
+            },
+            recorded_at_utc,
+        });
+    }
+    Ok(entries)
+}
+
+/// Sums what the violations recorded during `trip_id` cost the company.
+pub fn trip_settlement(conn: &Connection, trip_id: i64) -> Result<HosSettlement, String> {
+    let mut settlement = HosSettlement::default();
+    for entry in list_violations(conn, Some(trip_id), usize::MAX >> 1)? {
+        settlement.violations += 1;
+        settlement.fines += entry.violation.fine;
+        settlement.reputation_penalty += entry.violation.kind.reputation_penalty();
+    }
+    Ok(settlement)
+}
+
+pub fn load_overview(
+    runtime: &CareerRuntime,
+    conn: &Connection,
+    limit: usize,
+) -> Result<HosOverview, String> {
+    Ok(HosOverview {
+        status: current_status(runtime, conn)?,
+        recent_violations: list_violations(conn, None, limit)?,
+    })
+}
+
+pub fn format_minutes(minutes: u32) -> String {
+    format!("{}h {:02}m", minutes / 60, minutes % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Drives for `minutes` in one-minute steps starting after `start`.
+    fn drive(state: &mut TachographState, start: u32, minutes: u32) -> Vec<HosViolation> {
+        (1..=minutes)
+            .flat_map(|step| state.advance(start + step, 80.0, false).violations)
+            .collect()
+    }
+
+    #[test]
+    fn flags_missed_break_once_and_resets_after_45_minutes() {
+        let mut state = TachographState::default();
+        state.advance(1_000, 0.0, false);
+
+        let violations = drive(&mut state, 1_000, 280);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].kind, HosViolationKind::ContinuousDriving);
+        assert_eq!(violations[0].measured, 271);
+        assert_eq!(state.status().remaining_drive_minutes, 0);
+
+        for minute in 1..=BREAK_MIN {
+            state.advance(1_280 + minute, 0.0, false);
+        }
+        assert_eq!(state.continuous_drive_min, 0);
+        assert_eq!(state.status().remaining_drive_minutes, 270);
+        assert!(drive(&mut state, 1_325, 30).is_empty());
+    }
+
+    #[test]
+    fn runs_on_sample_time_without_game_clock() {
+        let mut state = TachographState::default();
+        let mut now_ms = 0;
+        let mut violations = Vec::new();
+        // 15 real minutes at 19x are close to five game hours of driving.
+        for _ in 0..300 {
+            violations.extend(state.observe(0, 80.0, false, now_ms).violations);
+            now_ms += 3_000;
+        }
+        assert!(state.clock_from_samples);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].kind, HosViolationKind::ContinuousDriving);
+
+        // Paused samples do not advance the sample clock.
+        let before = state.sample_clock_min;
+        state.observe(0, 0.0, true, now_ms + 60_000);
+        assert_eq!(state.sample_clock_min, before);
+
+        // Once the game clock shows up, the sample clock is not mixed into it.
+        assert!(
+            state
+                .observe(42_000, 80.0, false, now_ms + 63_000)
+                .is_quiet()
+        );
+        assert!(!state.clock_from_samples);
+        assert_eq!(state.last_game_time_min, Some(42_000));
+    }
+
+    #[test]
+    fn sleep_resets_the_day_and_counts_reduced_rests() {
+        let mut state = TachographState::default();
+        state.advance(10, 0.0, false);
+        let mut now = 10;
+        for _ in 0..4 {
+            // Four hours of driving, then a paused 9.5 h sleep.
+            assert!(drive(&mut state, now, 240).is_empty());
+            now += 240;
+            let observation = state.advance(now + 570, 0.0, true);
+            assert_eq!(observation.slept_minutes, Some(570));
+            assert!(observation.rest_completed);
+            now += 570;
+            assert_eq!(state.daily_drive_min, 0);
+        }
+
+        // The fourth reduced rest is only counted when driving resumes.
+        let violations = drive(&mut state, now, 1);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].kind, HosViolationKind::ReducedRest);
+
+        // A full day off restarts the week.
+        state.advance(now + 1 + WEEKLY_REST_MIN, 0.0, true);
+        assert_eq!(state.weekly_drive_min, 0);
+        assert_eq!(state.status().reduced_rests_left, 3);
+    }
+
+    #[test]
+    fn allows_two_extended_days_per_week() {
+        let mut state = TachographState::default();
+        state.advance(1, 0.0, false);
+        let mut now = 1;
+        let mut daily_violations = Vec::new();
+        for _ in 0..3 {
+            // 10 h of driving in four blocks with proper breaks.
+            for block in [240, 240, 120] {
+                daily_violations.extend(
+                    drive(&mut state, now, block)
+                        .into_iter()
+                        .filter(|violation| violation.kind == HosViolationKind::DailyDriving),
+                );
+                now += block;
+                state.advance(now + BREAK_MIN, 0.0, false);
+                now += BREAK_MIN;
+            }
+            state.advance(now + REGULAR_DAILY_REST_MIN, 0.0, true);
+            now += REGULAR_DAILY_REST_MIN;
+        }
+
+        assert_eq!(daily_violations.len(), 1);
+        assert_eq!(daily_violations[0].limit, DAILY_DRIVE_LIMIT_MIN);
+        assert_eq!(state.status().extended_days_left, 0);
+    }
+
+    #[test]
+    fn stores_violations_and_settles_trips() {
+        let conn = Connection::open_in_memory().unwrap();
+        ensure_tables(&conn).unwrap();
+
+        let violations = [
+            HosViolation::new(HosViolationKind::ContinuousDriving, 300, 271, 270),
+            HosViolation::new(HosViolationKind::WeeklyDriving, 900, 3_361, 3_360),
+        ];
+        record_violations(&conn, Some(7), &violations).unwrap();
+        record_violations(&conn, None, &violations[..1]).unwrap();
+
+        let settlement = trip_settlement(&conn, 7).unwrap();
+        assert_eq!(
+            settlement,
+            HosSettlement {
+                violations: 2,
+                fines: 650,
+                reputation_penalty: 4,
+            }
+        );
+        assert_eq!(list_violations(&conn, None, 10).unwrap().len(), 3);
+    }
+}
@@ -1,4 +1,6 @@
 use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::Ordering;
 use std::time::Duration;
@@ -10,7 +12,11 @@ use windows_sys::Win32::System::Memory::{
     FILE_MAP_READ, MEMORY_MAPPED_VIEW_ADDRESS, MapViewOfFile, OpenFileMappingW, UnmapViewOfFile,
 };
 
+use crate::features::career::db;
 use crate::features::career::logbook::{self, TelemetrySample};
+use crate::features::career::telemetry_source::{self, ReplaySource, TelemetrySource};
+use crate::features::career::trip_track;
+use crate::features::telemetry::broadcast;
 use crate::features::telemetry::simnexus_protocol::{
     self, BRIDGE_PROTOCOL_VERSION, LEGACY_SHARED_MEMORY_NAME, SHARED_MEMORY_NAME, TelemetryDataV3,
 };
@@ -72,6 +78,18 @@ pub struct TelemetryJob {
     pub event: Option<JobEvent>,
 }
 
+/// Truck position in game-map world coordinates (metres, `y` up), angles in
+/// degrees. Heading is counterclockwise from north like the SDK reports it.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub struct WorldPlacement {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub heading_deg: f64,
+    pub pitch_deg: f64,
+    pub roll_deg: f64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct TelemetrySnapshot {
     pub protocol_version: u32,
@@ -87,7 +105,11 @@ pub struct TelemetrySnapshot {
     pub fuel_liters: f32,
     pub fuel_capacity_liters: f32,
     pub map_scale: f32,
+    #[serde(default)]
+    pub placement: Option<WorldPlacement>,
     pub gear: i32,
+    #[serde(default)]
+    pub game_time_min: u32,
     pub paused: u8,
     pub engine_enabled: bool,
     pub plugin_initialized: bool,
@@ -118,6 +140,21 @@ struct FrontendTelemetryPayload {
     sdk_connected: bool,
 }
 
+fn world_placement(payload: &TelemetryDataV3) -> Option<WorldPlacement> {
+    if !simnexus_protocol::has_world_placement(payload) {
+        return None;
+    }
+    let placement = WorldPlacement {
+        x: payload.world_x,
+        y: payload.world_y,
+        z: payload.world_z,
+        heading_deg: f64::from(payload.heading) * 360.0,
+        pitch_deg: f64::from(payload.pitch) * 360.0,
+        roll_deg: f64::from(payload.roll) * 360.0,
+    };
+    (placement.x.is_finite() && placement.z.is_finite()).then_some(placement)
+}
+
 fn payload_has_active_job(payload: &TelemetryDataV3) -> bool {
     payload.job_active != 0
 }
@@ -216,7 +253,9 @@ impl SharedBridge {
             fuel_liters: payload.fuel_liters,
             fuel_capacity_liters: payload.fuel_capacity_liters,
             map_scale: payload.map_scale,
+            placement: world_placement(&payload),
             gear: payload.gear,
+            game_time_min: payload.game_time_min,
             paused: payload.game_paused,
             engine_enabled: payload.engine_enabled != 0,
             plugin_initialized: payload.plugin_initialized != 0,
@@ -280,135 +319,292 @@ impl SharedBridge {
     }
 }
 
+/// Reads the SimNexus shared memory mapping written by the game plugin.
+#[derive(Default)]
+pub struct SharedMemorySource {
+    bridge: Option<SharedBridge>,
+}
+
+impl TelemetrySource for SharedMemorySource {
+    fn label(&self) -> String {
+        format!("SimNexus shared memory {}", SHARED_MEMORY_NAME)
+    }
+
+    fn is_connected(&self) -> bool {
+        self.bridge.is_some()
+    }
+
+    fn connect(&mut self) -> Result<(), String> {
+        self.bridge = Some(SharedBridge::connect()?);
+        crate::dev_log!(
+            "[career] connected shared memory: {} ({})",
+            SHARED_MEMORY_NAME,
+            simnexus_protocol::layout_diagnostic()
+        );
+        Ok(())
+    }
+
+    fn disconnect(&mut self) {
+        self.bridge = None;
+    }
+
+    fn read_snapshot(&mut self) -> Result<Option<TelemetrySnapshot>, String> {
+        match self.bridge.as_ref() {
+            Some(bridge) => bridge.read_snapshot(),
+            None => Ok(None),
+        }
+    }
+}
+
 pub fn ensure_running(app: AppHandle, runtime: Arc<CareerRuntime>, game: GameId) {
     *runtime.active_game.lock().unwrap() = Some(game.as_str().to_string());
     runtime.telemetry_stop.store(false, Ordering::Relaxed);
+    if runtime.replay_running.load(Ordering::SeqCst)
+        || runtime
+            .telemetry_running
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+    {
+        return;
+    }
+
+    std::thread::spawn(move || {
+        run_source(&app, &runtime, SharedMemorySource::default(), || {
+            runtime.stop_all.load(Ordering::Relaxed)
+                || runtime.telemetry_stop.load(Ordering::Relaxed)
+                || runtime.replay_running.load(Ordering::SeqCst)
+        });
+        runtime.telemetry_running.store(false, Ordering::SeqCst);
+    });
+}
+
+/// Feeds a recorded session through the same pipeline as live telemetry. The live reader is
+/// parked for the duration and restarted afterwards; the replay writes to its own scratch
+/// database (see [`replay_db_path`]) so recorded jobs and trips never reach the career logbook.
+pub fn start_replay(
+    app: AppHandle,
+    runtime: Arc<CareerRuntime>,
+    source: ReplaySource,
+) -> Result<(), String> {
     if runtime
-        .telemetry_running
+        .replay_running
         .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
         .is_err()
     {
-        return;
+        return Err(
+            "A replay is already running. Stop it before starting another one.".to_string(),
+        );
     }
+    let replay = match replay_runtime(&runtime) {
+        Ok(replay) => replay,
+        Err(error) => {
+            runtime.replay_running.store(false, Ordering::SeqCst);
+            return Err(error);
+        }
+    };
+    runtime.replay_stop.store(false, Ordering::Relaxed);
+    if let Some(game) = source.game() {
+        *replay.active_game.lock().unwrap() = Some(game.to_string());
+    }
+    let live_game = runtime
+        .telemetry_running
+        .load(Ordering::SeqCst)
+        .then(|| runtime.active_game.lock().unwrap().clone())
+        .flatten()
+        .and_then(|game| GameId::try_from(game.as_str()).ok());
 
-    crate::dev_log!("[career] SimNexus telemetry reader started");
     std::thread::spawn(move || {
-        let mut bridge: Option<SharedBridge> = None;
-        let mut last_status_fingerprint: Option<String> = None;
-        let mut last_status_log_ms = 0i64;
-        let mut last_error: Option<String> = None;
-        let mut last_error_log_ms = 0i64;
-        let mut last_frontend_tick_emit_ms = 0i64;
-        let mut last_frontend_payload: Option<FrontendTelemetryPayload> = None;
-
-        while !runtime.stop_all.load(Ordering::Relaxed)
-            && !runtime.telemetry_stop.load(Ordering::Relaxed)
+        // The live reader sees `replay_running` on its next poll and exits.
+        while runtime.telemetry_running.load(Ordering::SeqCst) {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        run_source(&app, &replay, source, || {
+            runtime.stop_all.load(Ordering::Relaxed) || runtime.replay_stop.load(Ordering::Relaxed)
+        });
+        runtime.replay_running.store(false, Ordering::SeqCst);
+        if let Some(game) = live_game
+            && !runtime.stop_all.load(Ordering::Relaxed)
         {
-            if bridge.is_none() {
-                match SharedBridge::connect() {
-                    Ok(client) => {
-                        bridge = Some(client);
-                        runtime.plugin_installed.store(true, Ordering::Relaxed);
-                        crate::dev_log!(
-                            "[career] connected shared memory: {} ({})",
-                            SHARED_MEMORY_NAME,
-                            simnexus_protocol::layout_diagnostic()
-                        );
-                    }
-                    Err(error) => {
-                        runtime.bridge_connected.store(false, Ordering::Relaxed);
-                        log_error_rate_limited(&error, &mut last_error, &mut last_error_log_ms);
-                        std::thread::sleep(Duration::from_millis(500));
-                        continue;
-                    }
-                }
-            }
+            ensure_running(app, runtime, game);
+        }
+    });
+    Ok(())
+}
 
-            match bridge.as_ref().unwrap().read_snapshot() {
-                Ok(Some(snapshot)) => {
-                    let now = chrono::Utc::now().timestamp_millis();
-                    runtime.plugin_installed.store(true, Ordering::Relaxed);
-                    runtime.bridge_connected.store(true, Ordering::Relaxed);
-                    last_error = None;
-
-                    let fingerprint = format!(
-                        "{}|{}|{}|{}|{}|{}|{}|{}",
-                        snapshot.dll_build_id,
-                        snapshot.plugin_initialized,
-                        snapshot.sdk_connected,
-                        snapshot.telemetry_active,
-                        snapshot.telemetry_callback_seen,
-                        snapshot.paused,
-                        snapshot.job.is_some(),
-                        snapshot.job_config_seen
-                    );
-                    if last_status_fingerprint.as_ref() != Some(&fingerprint)
-                        || now - last_status_log_ms >= STATUS_LOG_INTERVAL_MS
-                    {
-                        log_snapshot_status(&snapshot);
-                        last_status_fingerprint = Some(fingerprint);
-                        last_status_log_ms = now;
-                    }
+/// Scratch database a replay writes to, next to the career database.
+pub fn replay_db_path(career_db: &Path) -> PathBuf {
+    career_db.with_file_name("career_replay.sqlite")
+}
 
-                    if let Err(error) = crate::features::career::job_tracking::process_snapshot(
-                        runtime.as_ref(),
-                        &snapshot,
-                    ) {
-                        crate::dev_log!("[career] job tracking failed: {}", error);
-                    }
-                    if let Err(error) = logbook::process_snapshot(
-                        runtime.as_ref(),
-                        TelemetrySample {
-                            timestamp: snapshot.simulation_timestamp,
-                            speed_kph: snapshot.speed_kph as f32,
-                            rpm: snapshot.engine_rpm as f32,
-                            gear: snapshot.gear,
-                            fuel_liters: snapshot.fuel_liters,
-                            fuel_capacity_liters: snapshot.fuel_capacity_liters,
-                            engine_enabled: snapshot.engine_enabled,
-                            paused: snapshot.paused != 0,
-                        },
-                    ) {
-                        crate::dev_log!("[career] telemetry logbook sync failed: {}", error);
-                    }
+/// Runtime a replay runs against: its own job, trip and tachograph state and a freshly
+/// initialized scratch database.
+fn replay_runtime(runtime: &CareerRuntime) -> Result<Arc<CareerRuntime>, String> {
+    let career_db = runtime
+        .db_path
+        .lock()
+        .map_err(|_| "Career db_path lock poisoned".to_string())?
+        .clone()
+        .ok_or_else(|| "Career database path not initialized".to_string())?;
+    let scratch = replay_db_path(&career_db);
+    for suffix in ["", "-wal", "-shm"] {
+        let mut path = scratch.clone().into_os_string();
+        path.push(suffix);
+        let path = PathBuf::from(path);
+        if path.exists() {
+            std::fs::remove_file(&path).map_err(|e| e.to_string())?;
+        }
+    }
+    db::init_logbook(&scratch)?;
 
-                    let frontend_payload = FrontendTelemetryPayload {
-                        speed: snapshot.speed_kph as f32,
-                        rpm: snapshot.engine_rpm as f32,
-                        gear: format_gear(snapshot.gear),
-                        fuel: snapshot.fuel_liters,
-                        fuel_capacity: snapshot.fuel_capacity_liters,
-                        engine_on: snapshot.engine_enabled,
-                        timestamp: snapshot.simulation_timestamp,
-                        paused: snapshot.paused != 0,
-                        plugin_installed: true,
-                        sdk_connected: true,
-                    };
-                    if last_frontend_payload.as_ref() != Some(&frontend_payload) {
-                        last_frontend_payload = Some(frontend_payload.clone());
-                        let _ = app.emit("telemetry:update", frontend_payload);
-                    }
-                    if now - last_frontend_tick_emit_ms >= 250 {
-                        last_frontend_tick_emit_ms = now;
-                        let _ = app.emit("career://telemetry_tick", snapshot);
-                    }
-                    std::thread::sleep(Duration::from_millis(100));
+    let replay = CareerRuntime::default();
+    *replay.db_path.lock().unwrap() = Some(scratch);
+    Ok(Arc::new(replay))
+}
+
+/// Sends one snapshot to job tracking and the logbook.
+pub fn dispatch_snapshot(runtime: &CareerRuntime, snapshot: &TelemetrySnapshot) {
+    if let Err(error) = crate::features::career::job_tracking::process_snapshot(runtime, snapshot) {
+        crate::dev_log!("[career] job tracking failed: {}", error);
+    }
+    if let Err(error) = logbook::process_snapshot(
+        runtime,
+        TelemetrySample {
+            timestamp: snapshot.simulation_timestamp,
+            speed_kph: snapshot.speed_kph as f32,
+            rpm: snapshot.engine_rpm as f32,
+            gear: snapshot.gear,
+            fuel_liters: snapshot.fuel_liters,
+            fuel_capacity_liters: snapshot.fuel_capacity_liters,
+            engine_enabled: snapshot.engine_enabled,
+            paused: snapshot.paused != 0,
+            game_time_min: snapshot.game_time_min,
+            planned_distance_km: snapshot
+                .job
+                .as_ref()
+                .map(|job| job.planned_distance_km)
+                .unwrap_or_default(),
+        },
+    ) {
+        crate::dev_log!("[career] telemetry logbook sync failed: {}", error);
+    }
+    if let Err(error) = trip_track::process_snapshot(runtime, snapshot) {
+        crate::dev_log!("[career] trip track sync failed: {}", error);
+    }
+}
+
+/// Drives `source` into `runtime`'s pipeline until `should_stop` returns true. Live reading
+/// and replays pass different conditions, so stopping one never stops the other.
+fn run_source<S: TelemetrySource>(
+    app: &AppHandle,
+    runtime: &CareerRuntime,
+    mut source: S,
+    should_stop: impl Fn() -> bool,
+) {
+    crate::dev_log!("[career] telemetry reader started: {}", source.label());
+    let mut last_status_fingerprint: Option<String> = None;
+    let mut last_status_log_ms = 0i64;
+    let mut last_error: Option<String> = None;
+    let mut last_error_log_ms = 0i64;
+    let mut last_frontend_tick_emit_ms = 0i64;
+    let mut last_frontend_payload: Option<FrontendTelemetryPayload> = None;
+    let mut last_job_event_sequence: Option<u64> = None;
+
+    while !should_stop() {
+        if !source.is_connected() {
+            match source.connect() {
+                Ok(()) => {
+                    runtime.plugin_installed.store(true, Ordering::Relaxed);
                 }
-                Ok(None) => std::thread::sleep(Duration::from_millis(16)),
                 Err(error) => {
                     runtime.bridge_connected.store(false, Ordering::Relaxed);
                     log_error_rate_limited(&error, &mut last_error, &mut last_error_log_ms);
-                    bridge = None;
                     std::thread::sleep(Duration::from_millis(500));
+                    continue;
                 }
             }
         }
 
-        runtime.telemetry_running.store(false, Ordering::Relaxed);
-        runtime.plugin_installed.store(false, Ordering::Relaxed);
-        runtime.bridge_connected.store(false, Ordering::Relaxed);
-        crate::dev_log!("[career] SimNexus telemetry reader stopped");
-    });
+        match source.read_snapshot() {
+            Ok(Some(snapshot)) => {
+                let now = chrono::Utc::now().timestamp_millis();
+                runtime.plugin_installed.store(true, Ordering::Relaxed);
+                runtime.bridge_connected.store(true, Ordering::Relaxed);
+                last_error = None;
+
+                let fingerprint = format!(
+                    "{}|{}|{}|{}|{}|{}|{}|{}",
+                    snapshot.dll_build_id,
+                    snapshot.plugin_initialized,
+                    snapshot.sdk_connected,
+                    snapshot.telemetry_active,
+                    snapshot.telemetry_callback_seen,
+                    snapshot.paused,
+                    snapshot.job.is_some(),
+                    snapshot.job_config_seen
+                );
+                if last_status_fingerprint.as_ref() != Some(&fingerprint)
+                    || now - last_status_log_ms >= STATUS_LOG_INTERVAL_MS
+                {
+                    log_snapshot_status(&snapshot);
+                    last_status_fingerprint = Some(fingerprint);
+                    last_status_log_ms = now;
+                }
+
+                telemetry_source::record_if_active(&snapshot);
+                dispatch_snapshot(runtime, &snapshot);
+                broadcast::publish_snapshot(&snapshot);
+                if last_job_event_sequence != Some(snapshot.job_event_sequence) {
+                    // The first sequence seen belongs to an event from before we connected.
+                    if last_job_event_sequence.is_some() && snapshot.job_event.is_some() {
+                        broadcast::publish_job_event(&json!({
+                            "sequence": snapshot.job_event_sequence,
+                            "event": snapshot.job_event,
+                            "job": snapshot.job,
+                        }));
+                    }
+                    last_job_event_sequence = Some(snapshot.job_event_sequence);
+                }
+
+                let frontend_payload = FrontendTelemetryPayload {
+                    speed: snapshot.speed_kph as f32,
+                    rpm: snapshot.engine_rpm as f32,
+                    gear: format_gear(snapshot.gear),
+                    fuel: snapshot.fuel_liters,
+                    fuel_capacity: snapshot.fuel_capacity_liters,
+                    engine_on: snapshot.engine_enabled,
+                    timestamp: snapshot.simulation_timestamp,
+                    paused: snapshot.paused != 0,
+                    plugin_installed: true,
+                    sdk_connected: true,
+                };
+                if last_frontend_payload.as_ref() != Some(&frontend_payload) {
+                    last_frontend_payload = Some(frontend_payload.clone());
+                    let _ = app.emit("telemetry:update", frontend_payload);
+                }
+                if now - last_frontend_tick_emit_ms >= 250 {
+                    last_frontend_tick_emit_ms = now;
+                    let _ = app.emit("career://telemetry_tick", snapshot);
+                }
+                std::thread::sleep(source.poll_interval());
+            }
+            Ok(None) => {
+                if source.is_exhausted() {
+                    break;
+                }
+                std::thread::sleep(Duration::from_millis(16));
+            }
+            Err(error) => {
+                runtime.bridge_connected.store(false, Ordering::Relaxed);
+                log_error_rate_limited(&error, &mut last_error, &mut last_error_log_ms);
+                source.disconnect();
+                std::thread::sleep(Duration::from_millis(500));
+            }
+        }
+    }
+
+    runtime.plugin_installed.store(false, Ordering::Relaxed);
+    runtime.bridge_connected.store(false, Ordering::Relaxed);
+    crate::dev_log!("[career] telemetry reader stopped: {}", source.label());
 }
 
 fn log_snapshot_status(snapshot: &TelemetrySnapshot) {
@@ -469,4 +665,36 @@ mod tests {
         };
         assert!(payload_has_active_job(&payload));
     }
+
+    #[test]
+    fn replays_write_to_a_fresh_scratch_database() {
+        let root = std::env::temp_dir().join(format!("telemetry_scratch_{}", uuid::Uuid::new_v4()));
+        let career_db = root.join("career.sqlite");
+        db::init_logbook(&career_db).unwrap();
+        let runtime = CareerRuntime::default();
+        *runtime.db_path.lock().unwrap() = Some(career_db.clone());
+
+        let replay = replay_runtime(&runtime).unwrap();
+        let scratch = replay.db_path.lock().unwrap().clone().unwrap();
+        assert_eq!(scratch, replay_db_path(&career_db));
+        assert_ne!(scratch, career_db);
+        rusqlite::Connection::open(&scratch)
+            .unwrap()
+            .execute(
+                "INSERT INTO trips (started_at_utc) VALUES ('2026-01-01T00:00:00Z')",
+                [],
+            )
+            .unwrap();
+
+        let replay = replay_runtime(&runtime).unwrap();
+        let conn =
+            rusqlite::Connection::open(replay.db_path.lock().unwrap().as_ref().unwrap()).unwrap();
+        let trips: i64 = conn
+            .query_row("SELECT COUNT(*) FROM trips", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(trips, 0);
+        assert_eq!(runtime.db_path.lock().unwrap().as_ref(), Some(&career_db));
+
+        let _ = std::fs::remove_dir_all(root);
+    }
 }
@@ -0,0 +1,416 @@
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::features::career::telemetry::TelemetrySnapshot;
+
+pub const RECORDING_FORMAT: &str = "simnexus-telemetry-recording";
+pub const RECORDING_VERSION: u32 = 1;
+
+static ACTIVE_RECORDER: Mutex<Option<TelemetryRecorder>> = Mutex::new(None);
+
+/// Anything that can feed `TelemetrySnapshot`s into the career pipeline.
+pub trait TelemetrySource {
+    fn label(&self) -> String;
+    fn is_connected(&self) -> bool;
+    fn connect(&mut self) -> Result<(), String>;
+    fn disconnect(&mut self);
+    fn read_snapshot(&mut self) -> Result<Option<TelemetrySnapshot>, String>;
+
+    /// Delay after a snapshot was processed before polling again.
+    fn poll_interval(&self) -> Duration {
+        Duration::from_millis(100)
+    }
+
+    /// Finite sources (recordings) return true once every snapshot was served.
+    fn is_exhausted(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TelemetryRecordingHeader {
+    pub format: String,
+    pub version: u32,
+    pub game: Option<String>,
+    pub recorded_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TelemetryRecordingFrame {
+    pub offset_ms: u64,
+    pub snapshot: TelemetrySnapshot,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TelemetryRecordingSummary {
+    pub path: String,
+    pub game: Option<String>,
+    pub frames: u64,
+    pub duration_ms: u64,
+}
+
+/// Writes snapshots as JSON lines: one header line followed by one frame per
+/// snapshot, each stamped with the offset since the recording started.
+pub struct TelemetryRecorder {
+    path: PathBuf,
+    game: Option<String>,
+    writer: BufWriter<File>,
+    started: Instant,
+    frames: u64,
+    last_offset_ms: u64,
+}
+
+impl TelemetryRecorder {
+    pub fn create(path: &Path, game: Option<String>) -> Result<Self, String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|error| {
+                format!(
+                    "Recording folder could not be created at {}: {}",
+                    parent.display(),
+                    error
+                )
+            })?;
+        }
+        let file = File::create(path).map_err(|error| {
+            format!(
+                "Recording file could not be created at {}: {}",
+                path.display(),
+                error
+            )
+        })?;
+        let mut recorder = Self {
+            path: path.to_path_buf(),
+            game: game.clone(),
+            writer: BufWriter::new(file),
+            started: Instant::now(),
+            frames: 0,
+            last_offset_ms: 0,
+        };
+        let header = TelemetryRecordingHeader {
+            format: RECORDING_FORMAT.to_string(),
+            version: RECORDING_VERSION,
+            game,
+            recorded_at: Utc::now().to_rfc3339(),
+        };
+        recorder.write_line(&header)?;
+        Ok(recorder)
+    }
+
+    pub fn record(&mut self, snapshot: &TelemetrySnapshot) -> Result<(), String> {
+        let offset_ms = self.started.elapsed().as_millis() as u64;
+        self.record_at(offset_ms, snapshot)
+    }
+
+    pub fn record_at(
+        &mut self,
+        offset_ms: u64,
+        snapshot: &TelemetrySnapshot,
+    ) -> Result<(), String> {
+        let frame = TelemetryRecordingFrame {
+            offset_ms: offset_ms.max(self.last_offset_ms),
+            snapshot: snapshot.clone(),
+        };
+        self.write_line(&frame)?;
+        self.last_offset_ms = frame.offset_ms;
+        self.frames += 1;
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> Result<TelemetryRecordingSummary, String> {
+        self.writer
+            .flush()
+            .map_err(|error| format!("Recording could not be flushed: {}", error))?;
+        Ok(TelemetryRecordingSummary {
+            path: self.path.display().to_string(),
+            game: self.game,
+            frames: self.frames,
+            duration_ms: self.last_offset_ms,
+        })
+    }
+
+    fn write_line<T: Serialize>(&mut self, value: &T) -> Result<(), String> {
+        let line = serde_json::to_string(value).map_err(|error| error.to_string())?;
+        writeln!(self.writer, "{}", line)
+            .map_err(|error| format!("Recording could not be written: {}", error))
+    }
+}
+
+/// Plays a recording back with its original timing scaled by `speed`.
+/// A speed of zero or less serves every frame immediately.
+pub struct ReplaySource {
+    path: PathBuf,
+    header: TelemetryRecordingHeader,
+    frames: Vec<TelemetryRecordingFrame>,
+    position: usize,
+    speed: f64,
+    started: Option<Instant>,
+}
+
+impl ReplaySource {
+    pub fn open(path: &Path, speed: f64) -> Result<Self, String> {
+        let file = File::open(path).map_err(|error| {
+            format!(
+                "Recording could not be opened at {}: {}",
+                path.display(),
+                error
+            )
+        })?;
+        let mut lines = BufReader::new(file).lines();
+
+        let header_line = lines
+            .next()
+            .transpose()
+            .map_err(|error| error.to_string())?
+            .ok_or_else(|| format!("Recording is empty: {}", path.display()))?;
+        let header: TelemetryRecordingHeader = serde_json::from_str(&header_line)
+            .map_err(|error| format!("Recording header is invalid: {}", error))?;
+        if header.format != RECORDING_FORMAT {
+            return Err(format!("Unsupported recording format: {}", header.format));
+        }
+        if header.version > RECORDING_VERSION {
+            return Err(format!(
+                "Recording version {} is newer than supported version {}",
+                header.version, RECORDING_VERSION
+            ));
+        }
+
+        let mut frames = Vec::new();
+        for (index, line) in lines.enumerate() {
+            let line = line.map_err(|error| error.to_string())?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let frame: TelemetryRecordingFrame = serde_json::from_str(&line)
+                .map_err(|error| format!("Recording frame {} is invalid: {}", index + 1, error))?;
+            frames.push(frame);
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            header,
+            frames,
+            position: 0,
+            speed,
+            started: None,
+        })
+    }
+
+    pub fn game(&self) -> Option<&str> {
+        self.header.game.as_deref()
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    fn frame_due(&self, offset_ms: u64) -> bool {
+        if self.speed <= 0.0 {
+            return true;
+        }
+        let Some(started) = self.started else {
+            return true;
+        };
+        started.elapsed().as_secs_f64() * 1000.0 * self.speed >= offset_ms as f64
+    }
+}
+
+impl TelemetrySource for ReplaySource {
+    fn label(&self) -> String {
+        format!("telemetry replay {}", self.path.display())
+    }
+
+    fn is_connected(&self) -> bool {
+        self.started.is_some()
+    }
+
+    fn connect(&mut self) -> Result<(), String> {
+        self.started = Some(Instant::now());
+        Ok(())
+    }
+
+    fn disconnect(&mut self) {
+        self.position = self.frames.len();
+    }
+
+    fn read_snapshot(&mut self) -> Result<Option<TelemetrySnapshot>, String> {
+        let Some(frame) = self.frames.get(self.position) else {
+            return Ok(None);
+        };
+        if !self.frame_due(frame.offset_ms) {
+            return Ok(None);
+        }
+        self.position += 1;
+        Ok(Some(frame.snapshot.clone()))
+    }
+
+    fn poll_interval(&self) -> Duration {
+        if self.speed <= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_millis(5)
+        }
+    }
+
+    fn is_exhausted(&self) -> bool {
+        self.position >= self.frames.len()
+    }
+}
+
+pub fn start_recording(path: &Path, game: Option<String>) -> Result<(), String> {
+    let mut guard = ACTIVE_RECORDER
+        .lock()
+        .map_err(|_| "Telemetry recorder lock poisoned".to_string())?;
+    if guard.is_some() {
+        return Err("A telemetry recording is already running.".to_string());
+    }
+    *guard = Some(TelemetryRecorder::create(path, game)?);
+    Ok(())
+}
+
+pub fn stop_recording() -> Result<Option<TelemetryRecordingSummary>, String> {
+    let recorder = ACTIVE_RECORDER
+        .lock()
+        .map_err(|_| "Telemetry recorder lock poisoned".to_string())?
+        .take();
+    recorder.map(TelemetryRecorder::finish).transpose()
+}
+
+/// Appends a snapshot to the running recording, if any. A failing recorder is
+/// dropped so it cannot stall the telemetry loop.
+pub fn record_if_active(snapshot: &TelemetrySnapshot) {
+    let Ok(mut guard) = ACTIVE_RECORDER.lock() else {
+        return;
+    };
+    if let Some(recorder) = guard.as_mut() {
+        if let Err(error) = recorder.record(snapshot) {
+            crate::dev_log!("[career] telemetry recording stopped: {}", error);
+            *guard = None;
+        }
+    }
+}
+
+pub fn default_recording_path(db_path: Option<&Path>, game: Option<&str>) -> PathBuf {
+    let root = db_path
+        .and_then(Path::parent)
+        .map(Path::to_path_buf)
+        .unwrap_or_else(std::env::temp_dir)
+        .join("telemetry_recordings");
+    root.join(format!(
+        "{}_{}.jsonl",
+        game.unwrap_or("game"),
+        Utc::now().format("%Y%m%d_%H%M%S")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use rusqlite::Connection;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::features::career::job_log;
+    use crate::features::career::job_tracking;
+    use crate::features::career::telemetry::{JobEvent, TelemetryJob};
+    use crate::state::CareerRuntime;
+
+    fn job_snapshot(sequence: i64, event: Option<JobEvent>) -> TelemetrySnapshot {
+        TelemetrySnapshot {
+            sequence,
+            job_event: event,
+            job: Some(TelemetryJob {
+                job_id: "replay-job".to_string(),
+                source_city: "Berlin".to_string(),
+                destination_city: "Praha".to_string(),
+                source_company: "Tesco".to_string(),
+                destination_company: "Ikea".to_string(),
+                cargo: "Apples".to_string(),
+                income: 12_500,
+                planned_distance_km: 351.0,
+                event,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn replayed_recording_drives_job_tracking() {
+        let root = std::env::temp_dir().join(format!("telemetry_replay_{}", Uuid::new_v4()));
+        let recording_path = root.join("delivery.jsonl");
+
+        let mut recorder =
+            TelemetryRecorder::create(&recording_path, Some("ets2".to_string())).unwrap();
+        recorder.record_at(0, &job_snapshot(1, None)).unwrap();
+        recorder.record_at(500, &job_snapshot(2, None)).unwrap();
+        recorder
+            .record_at(1_000, &job_snapshot(3, Some(JobEvent::Delivered)))
+            .unwrap();
+        recorder
+            .record_at(
+                1_500,
+                &TelemetrySnapshot {
+                    sequence: 4,
+                    job_event: Some(JobEvent::Delivered),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        let summary = recorder.finish().unwrap();
+        assert_eq!(summary.frames, 4);
+        assert_eq!(summary.duration_ms, 1_500);
+
+        let runtime = CareerRuntime::default();
+        *runtime.db_path.lock().unwrap() = Some(root.join("career.sqlite"));
+
+        let mut source = ReplaySource::open(&recording_path, 0.0).unwrap();
+        assert_eq!(source.game(), Some("ets2"));
+        source.connect().unwrap();
+        let mut sequences = Vec::new();
+        while !source.is_exhausted() {
+            let snapshot = source.read_snapshot().unwrap().unwrap();
+            sequences.push(snapshot.sequence);
+            job_tracking::process_snapshot(&runtime, &snapshot).unwrap();
+        }
+        assert_eq!(sequences, vec![1, 2, 3, 4]);
+        assert!(runtime.active_job.lock().unwrap().is_none());
+
+        let conn = Connection::open(root.join("career.sqlite")).unwrap();
+        let job = job_log::get_job(&conn, "replay-job").unwrap().unwrap();
+        assert_eq!(job.status, "completed");
+        assert_eq!(job.cargo, "Apples");
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn replay_waits_for_scaled_frame_offsets() {
+        let root = std::env::temp_dir().join(format!("telemetry_replay_{}", Uuid::new_v4()));
+        let recording_path = root.join("paced.jsonl");
+
+        let mut recorder = TelemetryRecorder::create(&recording_path, None).unwrap();
+        recorder
+            .record_at(0, &TelemetrySnapshot::default())
+            .unwrap();
+        recorder
+            .record_at(60_000, &TelemetrySnapshot::default())
+            .unwrap();
+        recorder.finish().unwrap();
+
+        let mut source = ReplaySource::open(&recording_path, 2.0).unwrap();
+        source.connect().unwrap();
+        assert!(source.read_snapshot().unwrap().is_some());
+        assert!(source.read_snapshot().unwrap().is_none());
+        assert!(!source.is_exhausted());
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
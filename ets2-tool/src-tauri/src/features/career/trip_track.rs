@@ -0,0 +1,574 @@
+//! GPS-style route recording for logbook trips.
+//!
+//! Positions come from the world placement published by revision 4 bridge
+//! DLLs. Every trip keeps a downsampled polyline in `trip_track_points`: a
+//! point is stored once the truck moved far enough or turned noticeably, so
+//! straight motorway stretches stay cheap while junctions keep their shape.
+//! Jumps that no truck can drive (ferries, trains, towing) start a new
+//! segment instead of drawing a straight line across the sea.
+//!
+//! Positions stay in world metres. Lengths shown to the player are scaled by
+//! the map scale, like every other map distance in the app.
+
+use std::fmt::Write as _;
+use std::fs;
+
+use rusqlite::{Connection, OptionalExtension, params};
+use serde::Serialize;
+use serde_json::json;
+use tauri::AppHandle;
+use tauri_plugin_dialog::DialogExt;
+
+use crate::features::career::logbook;
+use crate::features::career::telemetry::TelemetrySnapshot;
+use crate::shared::sqlite_schema::create_indexes;
+use crate::state::{CareerRuntime, TrackDownsampler, TrackPoint};
+
+/// ETS2 world units are metres on a 1:19 scale map.
+pub const MAP_SCALE: f64 = 19.0;
+/// Points closer than this to the last stored point are always dropped.
+const MIN_POINT_SPACING_M: f64 = 25.0;
+/// A point is stored at least this often, even on a perfectly straight road.
+const MAX_POINT_SPACING_M: f64 = 500.0;
+/// Heading change since the last stored point that keeps a curve visible.
+const HEADING_CHANGE_DEG: f64 = 10.0;
+/// Distance between two consecutive samples that can only be a teleport.
+const TELEPORT_JUMP_M: f64 = 1_500.0;
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TripTrackSummary {
+    pub trip_id: i64,
+    pub point_count: usize,
+    pub segment_count: usize,
+    /// Teleport jumps inside the trip; in practice ferry and train crossings.
+    pub ferry_crossings: usize,
+    pub track_length_km: f64,
+    pub straight_line_km: f64,
+    /// Driven length divided by the straight line between start and end.
+    pub detour_ratio: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TripTrack {
+    pub summary: TripTrackSummary,
+    pub origin: Option<String>,
+    pub destination: Option<String>,
+    pub cargo: Option<String>,
+    pub points: Vec<TrackPoint>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrackExportFormat {
+    GeoJson,
+    Csv,
+}
+
+impl TryFrom<&str> for TrackExportFormat {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "geojson" | "json" => Ok(Self::GeoJson),
+            "csv" => Ok(Self::Csv),
+            other => Err(format!("Unknown track export format: {other}")),
+        }
+    }
+}
+
+impl TrackDownsampler {
+    fn new(trip_id: i64, next_seq: i64, segment: i64) -> Self {
+        Self {
+            trip_id,
+            next_seq,
+            segment,
+            last_kept: None,
+            last_seen: None,
+        }
+    }
+
+    /// Returns the points that should be stored for this sample, oldest first.
+    fn push(&mut self, point: TrackPoint) -> Vec<TrackPoint> {
+        let mut keep = Vec::new();
+        let previous = self.last_seen.take();
+        let jumped = previous
+            .is_some_and(|(previous, _)| planar_distance_m(&previous, &point) >= TELEPORT_JUMP_M);
+
+        let stored = if jumped {
+            // Close the old segment where the truck actually left the road.
+            if let Some((previous, false)) = previous {
+                keep.push(self.keep(previous));
+            }
+            self.segment += 1;
+            true
+        } else {
+            self.should_keep(&point)
+        };
+        if stored {
+            keep.push(self.keep(point));
+        }
+        self.last_seen = Some((point, stored));
+        keep
+    }
+
+    /// The final position of the trip, unless it was stored already.
+    fn finish(&mut self) -> Option<TrackPoint> {
+        match self.last_seen.take() {
+            Some((last, false)) => Some(self.keep(last)),
+            _ => None,
+        }
+    }
+
+    fn should_keep(&self, point: &TrackPoint) -> bool {
+        let Some(kept) = self.last_kept else {
+            return true;
+        };
+        let distance = planar_distance_m(&kept, point);
+        distance >= MAX_POINT_SPACING_M
+            || (distance >= MIN_POINT_SPACING_M
+                && heading_delta_deg(kept.heading_deg, point.heading_deg) >= HEADING_CHANGE_DEG)
+    }
+
+    fn keep(&mut self, mut point: TrackPoint) -> TrackPoint {
+        point.seq = self.next_seq;
+        point.segment = self.segment;
+        self.next_seq += 1;
+        self.last_kept = Some(point);
+        point
+    }
+}
+
+pub fn ensure_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS trip_track_points (
+            trip_id INTEGER NOT NULL,
+            seq INTEGER NOT NULL,
+            segment INTEGER NOT NULL DEFAULT 0,
+            recorded_at_ms INTEGER NOT NULL,
+            x REAL NOT NULL,
+            y REAL NOT NULL,
+            z REAL NOT NULL,
+            heading_deg REAL NOT NULL DEFAULT 0,
+            speed_kph REAL NOT NULL DEFAULT 0,
+            PRIMARY KEY (trip_id, seq)
+        );
+        "#,
+    )
+    .map_err(|e| e.to_string())?;
+    create_indexes(
+        conn,
+        &[
+            "CREATE INDEX IF NOT EXISTS idx_trip_track_points_segment ON trip_track_points(trip_id, segment)",
+        ],
+    )
+}
+
+/// Feeds one telemetry snapshot into the track of the active logbook trip.
+///
+/// Must run after `logbook::process_snapshot` so trip starts and ends are
+/// already reflected in the runtime.
+pub fn process_snapshot(
+    runtime: &CareerRuntime,
+    snapshot: &TelemetrySnapshot,
+) -> Result<(), String> {
+    let active_trip_id = runtime
+        .active_trip
+        .lock()
+        .map_err(|_| "Career active_trip lock poisoned".to_string())?
+        .as_ref()
+        .map(|trip| trip.trip_id);
+
+    let mut pending = Vec::new();
+    let mut resume_trip_id = None;
+    {
+        let mut track = runtime
+            .trip_track
+            .lock()
+            .map_err(|_| "Trip track lock poisoned".to_string())?;
+        if track.as_ref().map(|state| state.trip_id) != active_trip_id {
+            if let Some(mut finished) = track.take() {
+                pending.extend(finished.finish().map(|point| (finished.trip_id, point)));
+            }
+            resume_trip_id = active_trip_id;
+        }
+        if let (Some(state), Some(placement)) = (track.as_mut(), snapshot.placement) {
+            if snapshot.paused == 0 {
+                let trip_id = state.trip_id;
+                pending.extend(
+                    state
+                        .push(point_from_snapshot(snapshot, placement))
+                        .into_iter()
+                        .map(|point| (trip_id, point)),
+                );
+            }
+        }
+    }
+
+    if pending.is_empty() && resume_trip_id.is_none() {
+        return Ok(());
+    }
+
+    let conn = logbook::open_connection(runtime)?;
+    store_points(&conn, &pending)?;
+
+    if let Some(trip_id) = resume_trip_id {
+        // Paused dispatcher jobs reuse the trip row, so continue its numbering.
+        let (next_seq, segment) = conn
+            .query_row(
+                "SELECT COALESCE(MAX(seq) + 1, 0), COALESCE(MAX(segment) + 1, 0) FROM trip_track_points WHERE trip_id = ?1",
+                params![trip_id],
+                |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)),
+            )
+            .map_err(|e| e.to_string())?;
+        let mut state = TrackDownsampler::new(trip_id, next_seq, segment);
+        let first = snapshot
+            .placement
+            .filter(|_| snapshot.paused == 0)
+            .map(|placement| state.push(point_from_snapshot(snapshot, placement)))
+            .unwrap_or_default();
+        store_points(
+            &conn,
+            &first
+                .into_iter()
+                .map(|point| (trip_id, point))
+                .collect::<Vec<_>>(),
+        )?;
+        *runtime
+            .trip_track
+            .lock()
+            .map_err(|_| "Trip track lock poisoned".to_string())? = Some(state);
+    }
+
+    Ok(())
+}
+
+pub fn load_track(conn: &Connection, trip_id: i64) -> Result<TripTrack, String> {
+    ensure_tables(conn)?;
+    let (origin, destination, cargo) = conn
+        .query_row(
+            "SELECT origin, destination, cargo FROM trips WHERE id = ?1",
+            params![trip_id],
+            |row| {
+                Ok((
+                    row.get::<_, Option<String>>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                ))
+            },
+        )
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Trip {trip_id} not found"))?;
+
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT seq, segment, recorded_at_ms, x, y, z, heading_deg, speed_kph
+            FROM trip_track_points
+            WHERE trip_id = ?1
+            ORDER BY seq ASC
+            "#,
+        )
+        .map_err(|e| e.to_string())?;
+    let points = stmt
+        .query_map(params![trip_id], |row| {
+            Ok(TrackPoint {
+                seq: row.get(0)?,
+                segment: row.get(1)?,
+                recorded_at_ms: row.get(2)?,
+                x: row.get(3)?,
+                y: row.get(4)?,
+                z: row.get(5)?,
+                heading_deg: row.get(6)?,
+                speed_kph: row.get(7)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(TripTrack {
+        summary: summarize(trip_id, &points),
+        origin,
+        destination,
+        cargo,
+        points,
+    })
+}
+
+pub fn export_track(
+    app: &AppHandle,
+    conn: &Connection,
+    trip_id: i64,
+    format: TrackExportFormat,
+) -> Result<Option<String>, String> {
+    let track = load_track(conn, trip_id)?;
+    if track.points.is_empty() {
+        return Err(format!("Trip {trip_id} has no recorded route"));
+    }
+    let (label, extension, content) = match format {
+        TrackExportFormat::Csv => ("CSV file", "csv", to_csv(&track)),
+        TrackExportFormat::GeoJson => (
+            "GeoJSON file",
+            "geojson",
+            serde_json::to_string_pretty(&to_geojson(&track)).map_err(|e| e.to_string())?,
+        ),
+    };
+    let default_file_name = format!("simnexus_trip_{trip_id}.{extension}");
+    let file_path = app
+        .dialog()
+        .file()
+        .add_filter(label, &[extension])
+        .set_title("Export trip route")
+        .set_file_name(&default_file_name)
+        .blocking_save_file();
+
+    let Some(file_path) = file_path else {
+        return Ok(None);
+    };
+
+    let path = file_path
+        .into_path()
+        .map_err(|_| "The selected export path could not be resolved.".to_string())?;
+    fs::write(&path, content.as_bytes()).map_err(|error| {
+        format!(
+            "The trip route could not be written to {}: {}",
+            path.display(),
+            error
+        )
+    })?;
+
+    Ok(Some(path.display().to_string()))
+}
+
+/// GeoJSON in game-map metres: `[x, -z, y]`, so north points up in GIS tools.
+pub fn to_geojson(track: &TripTrack) -> serde_json::Value {
+    let lines = segments(&track.points)
+        .into_iter()
+        .map(|segment| {
+            segment
+                .iter()
+                .map(|point| json!([point.x, -point.z, point.y]))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "type": "FeatureCollection",
+        "coordinateSystem": "simnexus:game-map-metres",
+        "features": [{
+            "type": "Feature",
+            "geometry": {
+                "type": "MultiLineString",
+                "coordinates": lines,
+            },
+            "properties": {
+                "tripId": track.summary.trip_id,
+                "origin": track.origin,
+                "destination": track.destination,
+                "cargo": track.cargo,
+                "pointCount": track.summary.point_count,
+                "ferryCrossings": track.summary.ferry_crossings,
+                "trackLengthKm": track.summary.track_length_km,
+                "straightLineKm": track.summary.straight_line_km,
+                "detourRatio": track.summary.detour_ratio,
+            },
+        }],
+    })
+}
+
+/// One row per stored point in raw game-map coordinates. The game map has no
+/// published geographic projection, so no latitude or longitude is made up.
+pub fn to_csv(track: &TripTrack) -> String {
+    let mut csv = String::from("segment,seq,recorded_at_ms,x,y,z,heading_deg,speed_kph\n");
+    for point in &track.points {
+        let _ = writeln!(
+            csv,
+            "{},{},{},{:.2},{:.2},{:.2},{:.1},{:.1}",
+            point.segment,
+            point.seq,
+            point.recorded_at_ms,
+            point.x,
+            point.y,
+            point.z,
+            point.heading_deg,
+            point.speed_kph
+        );
+    }
+    csv
+}
+
+fn summarize(trip_id: i64, points: &[TrackPoint]) -> TripTrackSummary {
+    let segments = segments(points);
+    let track_length_m: f64 = segments
+        .iter()
+        .map(|segment| {
+            segment
+                .windows(2)
+                .map(|pair| planar_distance_m(&pair[0], &pair[1]))
+                .sum::<f64>()
+        })
+        .sum();
+    let straight_line_m = match (points.first(), points.last()) {
+        (Some(first), Some(last)) => planar_distance_m(first, last),
+        _ => 0.0,
+    };
+
+    TripTrackSummary {
+        trip_id,
+        point_count: points.len(),
+        segment_count: segments.len(),
+        ferry_crossings: segments.len().saturating_sub(1),
+        track_length_km: map_km(track_length_m),
+        straight_line_km: map_km(straight_line_m),
+        detour_ratio: (straight_line_m >= MAX_POINT_SPACING_M)
+            .then(|| track_length_m / straight_line_m),
+    }
+}
+
+fn segments(points: &[TrackPoint]) -> Vec<&[TrackPoint]> {
+    points
+        .chunk_by(|left, right| left.segment == right.segment)
+        .collect()
+}
+
+fn store_points(conn: &Connection, points: &[(i64, TrackPoint)]) -> Result<(), String> {
+    for (trip_id, point) in points {
+        conn.execute(
+            r#"
+            INSERT OR REPLACE INTO trip_track_points (
+                trip_id, seq, segment, recorded_at_ms, x, y, z, heading_deg, speed_kph
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+            "#,
+            params![
+                trip_id,
+                point.seq,
+                point.segment,
+                point.recorded_at_ms,
+                point.x,
+                point.y,
+                point.z,
+                point.heading_deg,
+                point.speed_kph
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+fn point_from_snapshot(
+    snapshot: &TelemetrySnapshot,
+    placement: crate::features::career::telemetry::WorldPlacement,
+) -> TrackPoint {
+    TrackPoint {
+        seq: -1,
+        segment: 0,
+        recorded_at_ms: i64::try_from(snapshot.simulation_timestamp).unwrap_or(i64::MAX),
+        x: placement.x,
+        y: placement.y,
+        z: placement.z,
+        heading_deg: placement.heading_deg,
+        speed_kph: snapshot.speed_kph,
+    }
+}
+
+/// World metres to the kilometres the game shows for the same stretch of road.
+fn map_km(world_metres: f64) -> f64 {
+    world_metres * MAP_SCALE / 1000.0
+}
+
+fn planar_distance_m(left: &TrackPoint, right: &TrackPoint) -> f64 {
+    (right.x - left.x).hypot(right.z - left.z)
+}
+
+fn heading_delta_deg(left: f64, right: f64) -> f64 {
+    let delta = (right - left).rem_euclid(360.0);
+    delta.min(360.0 - delta)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(x: f64, z: f64, heading_deg: f64, at: i64) -> TrackPoint {
+        TrackPoint {
+            seq: -1,
+            segment: 0,
+            recorded_at_ms: at,
+            x,
+            y: 10.0,
+            z,
+            heading_deg,
+            speed_kph: 80.0,
+        }
+    }
+
+    #[test]
+    fn downsamples_straight_roads_and_splits_ferry_jumps() {
+        let mut sampler = TrackDownsampler::new(7, 0, 0);
+        let mut stored = Vec::new();
+        // 2 km straight north in 20 m steps.
+        for step in 0..=100 {
+            stored.extend(sampler.push(point(0.0, -20.0 * step as f64, 0.0, step)));
+        }
+        // A sharp turn keeps the corner even below the maximum spacing.
+        stored.extend(sampler.push(point(30.0, -2_000.0, 90.0, 101)));
+        // Ferry: the truck reappears 40 km away.
+        stored.extend(sampler.push(point(40_000.0, -2_000.0, 90.0, 102)));
+        stored.extend(sampler.push(point(40_010.0, -2_000.0, 90.0, 103)));
+        stored.extend(sampler.finish());
+
+        assert_eq!(stored.first().unwrap().x, 0.0);
+        assert!(stored.len() < 12, "kept {} points", stored.len());
+        assert!(stored.iter().any(|point| point.x == 30.0));
+        assert!(stored.windows(2).all(|pair| pair[1].seq == pair[0].seq + 1));
+        assert_eq!(stored.last().unwrap().x, 40_010.0);
+        assert_eq!(stored.last().unwrap().segment, 1);
+
+        let summary = summarize(7, &stored);
+        assert_eq!(summary.segment_count, 2);
+        assert_eq!(summary.ferry_crossings, 1);
+        // 2.04 km of world metres on the 1:19 map.
+        assert!((summary.track_length_km - 2.04 * MAP_SCALE).abs() < 0.1);
+    }
+
+    #[test]
+    fn stores_and_exports_tracks() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE trips (id INTEGER PRIMARY KEY, origin TEXT, destination TEXT, cargo TEXT);
+             INSERT INTO trips (id, origin, destination, cargo) VALUES (3, 'Calais', 'Dover', 'Fish & Chips');",
+        )
+        .unwrap();
+        ensure_tables(&conn).unwrap();
+
+        let mut sampler = TrackDownsampler::new(3, 0, 0);
+        let mut stored = Vec::new();
+        for (index, z) in [0.0, -600.0, -1_200.0].into_iter().enumerate() {
+            stored.extend(sampler.push(point(100.0, z, 0.0, 1_700_000_000_000 + index as i64)));
+        }
+        stored.extend(sampler.push(point(100.0, -30_000.0, 0.0, 1_700_000_000_010)));
+        let rows = stored
+            .into_iter()
+            .map(|point| (3, point))
+            .collect::<Vec<_>>();
+        store_points(&conn, &rows).unwrap();
+
+        let track = load_track(&conn, 3).unwrap();
+        assert_eq!(track.points.len(), 4);
+        assert_eq!(track.summary.ferry_crossings, 1);
+
+        let csv = to_csv(&track);
+        assert_eq!(csv.lines().count(), 5);
+        assert!(csv.contains("\n1,3,1700000000010,100.00,10.00,-30000.00,0.0,80.0\n"));
+
+        let geojson = to_geojson(&track);
+        let lines = &geojson["features"][0]["geometry"]["coordinates"];
+        assert_eq!(lines.as_array().unwrap().len(), 2);
+        assert_eq!(lines[0][1], json!([100.0, 600.0, 10.0]));
+    }
+}
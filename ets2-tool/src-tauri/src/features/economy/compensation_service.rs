@@ -11,6 +11,7 @@ use crate::features::economy::compensation_models::{
 const DEFAULT_COMPANY_REPUTATION: u16 = 500;
 const MIN_REPUTATION_MULTIPLIER: f64 = 0.92;
 const MAX_REPUTATION_MULTIPLIER: f64 = 1.28;
+const NEGOTIATION_BREAKDOWN_REPUTATION_PENALTY: u16 = 5;
 
 pub fn calculate_job_compensation(
     conn: &Connection,
@@ -588,6 +589,33 @@ pub fn apply_company_reputation_outcome(
     load_company_reputation(conn, company_id)
 }
 
+/// Reputation effect of failed negotiations: drops the reputation by a fixed amount and
+/// resets the reliability streak. Job counters stay untouched because no job was run.
+pub fn apply_company_negotiation_breakdown(
+    conn: &Connection,
+    company_id: &str,
+) -> Result<CompanyReputationState, String> {
+    let current = load_company_reputation(conn, company_id)?;
+    let next_reputation = current
+        .reputation
+        .saturating_sub(NEGOTIATION_BREAKDOWN_REPUTATION_PENALTY);
+
+    conn.execute(
+        r#"
+        UPDATE company_reputation
+        SET
+            reputation = ?2,
+            reliability_streak = 0,
+            updated_at_utc = ?3
+        WHERE company_id = ?1
+        "#,
+        params![company_id, next_reputation as i64, Utc::now().to_rfc3339()],
+    )
+    .map_err(|e| e.to_string())?;
+
+    load_company_reputation(conn, company_id)
+}
+
 pub fn ensure_company_reputation_row(conn: &Connection, company_id: &str) -> Result<(), String> {
     if company_id.trim().is_empty() {
         return Err("company_id_missing".to_string());
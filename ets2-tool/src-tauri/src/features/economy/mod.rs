@@ -3,6 +3,8 @@ use serde::Serialize;
 
 pub mod compensation_models;
 pub mod compensation_service;
+pub mod settlement_models;
+pub mod settlement_service;
 
 use crate::features::economy::compensation_models::{
     CompanyPaymentTier, UpsertCompanyPaymentProfileInput,
@@ -166,6 +168,7 @@ pub fn ensure_tables(conn: &Connection) -> Result<(), String> {
 
     seed_country_payment_levels(conn)?;
     seed_company_payment_profiles(conn)?;
+    settlement_service::ensure_settlement_tables(conn)?;
 
     Ok(())
 }
@@ -0,0 +1,179 @@
+use serde::{Deserialize, Serialize};
+
+use crate::features::economy::compensation_models::{CargoType, Urgency};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LateFeeRule {
+    /// Lateness in game minutes that is tolerated without a fee.
+    pub grace_minutes: u32,
+    /// Share of the contract reward charged per started hour past the grace period.
+    pub percent_per_hour: f64,
+    pub cap_percent: f64,
+}
+
+impl Default for LateFeeRule {
+    fn default() -> Self {
+        Self {
+            grace_minutes: 60,
+            percent_per_hour: 2.0,
+            cap_percent: 30.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DamageDeductionRule {
+    /// Cargo damage in percent that is tolerated without a deduction.
+    pub tolerance_percent: f64,
+    /// Percent of the contract reward deducted per damage percent above the tolerance.
+    pub deduction_factor: f64,
+    pub cap_percent: f64,
+}
+
+impl Default for DamageDeductionRule {
+    fn default() -> Self {
+        Self {
+            tolerance_percent: 2.0,
+            deduction_factor: 1.0,
+            cap_percent: 50.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LateFeeRules {
+    pub normal: LateFeeRule,
+    pub priority: LateFeeRule,
+    pub express: LateFeeRule,
+}
+
+impl Default for LateFeeRules {
+    fn default() -> Self {
+        Self {
+            normal: LateFeeRule::default(),
+            priority: LateFeeRule {
+                grace_minutes: 30,
+                percent_per_hour: 4.0,
+                cap_percent: 40.0,
+            },
+            express: LateFeeRule {
+                grace_minutes: 15,
+                percent_per_hour: 8.0,
+                cap_percent: 60.0,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DamageDeductionRules {
+    pub standard: DamageDeductionRule,
+    pub fragile: DamageDeductionRule,
+    pub refrigerated: DamageDeductionRule,
+    pub valuable: DamageDeductionRule,
+    pub hazardous: DamageDeductionRule,
+    pub oversize: DamageDeductionRule,
+}
+
+impl Default for DamageDeductionRules {
+    fn default() -> Self {
+        Self {
+            standard: DamageDeductionRule::default(),
+            fragile: DamageDeductionRule {
+                tolerance_percent: 1.0,
+                deduction_factor: 1.5,
+                cap_percent: 75.0,
+            },
+            refrigerated: DamageDeductionRule {
+                tolerance_percent: 2.0,
+                deduction_factor: 1.2,
+                cap_percent: 60.0,
+            },
+            valuable: DamageDeductionRule {
+                tolerance_percent: 0.5,
+                deduction_factor: 2.0,
+                cap_percent: 100.0,
+            },
+            hazardous: DamageDeductionRule {
+                tolerance_percent: 1.0,
+                deduction_factor: 1.5,
+                cap_percent: 80.0,
+            },
+            oversize: DamageDeductionRule {
+                tolerance_percent: 3.0,
+                deduction_factor: 1.0,
+                cap_percent: 50.0,
+            },
+        }
+    }
+}
+
+/// Penalty rules applied when a contracted job is settled.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SettlementPenaltyRules {
+    pub late_fees: LateFeeRules,
+    pub damage_deductions: DamageDeductionRules,
+}
+
+impl SettlementPenaltyRules {
+    pub fn late_fee_rule(&self, urgency: Urgency) -> LateFeeRule {
+        match urgency {
+            Urgency::Normal => self.late_fees.normal,
+            Urgency::Priority => self.late_fees.priority,
+            Urgency::Express => self.late_fees.express,
+        }
+    }
+
+    pub fn damage_rule(&self, cargo_type: CargoType) -> DamageDeductionRule {
+        match cargo_type {
+            CargoType::Standard => self.damage_deductions.standard,
+            CargoType::Fragile => self.damage_deductions.fragile,
+            CargoType::Refrigerated => self.damage_deductions.refrigerated,
+            CargoType::Valuable => self.damage_deductions.valuable,
+            CargoType::Hazardous => self.damage_deductions.hazardous,
+            CargoType::Oversize => self.damage_deductions.oversize,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeliverySettlementInput {
+    pub contract_reward: i64,
+    /// Bonus agreed in a dispatcher contract, paid only for on-time deliveries.
+    pub contract_bonus: i64,
+    pub cargo_type: CargoType,
+    pub urgency: Urgency,
+    /// In-game minute the job was due; `None` when the deadline is unknown.
+    pub deadline_game_min: Option<u32>,
+    pub delivered_game_min: Option<u32>,
+    pub cargo_damage_percent: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SettlementLineItem {
+    /// `contract_reward`, `contract_bonus`, `late_fee` or `damage_deduction`.
+    pub kind: String,
+    /// Signed amount; deductions are negative.
+    pub amount: i64,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DeliverySettlement {
+    pub contract_reward: i64,
+    pub contract_bonus: i64,
+    pub late_minutes: i64,
+    pub on_time: bool,
+    pub late_fee: i64,
+    pub cargo_damage_percent: f64,
+    pub damage_deduction: i64,
+    pub net_reward: i64,
+    pub line_items: Vec<SettlementLineItem>,
+}
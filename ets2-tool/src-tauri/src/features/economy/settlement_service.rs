@@ -0,0 +1,262 @@
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension, params};
+
+use crate::features::economy::settlement_models::{
+    DamageDeductionRule, DeliverySettlement, DeliverySettlementInput, LateFeeRule,
+    SettlementLineItem, SettlementPenaltyRules,
+};
+
+pub fn ensure_settlement_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS settlement_penalty_config (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            rules_json TEXT NOT NULL,
+            updated_at_utc TEXT NOT NULL
+        );
+        "#,
+    )
+    .map_err(|e| e.to_string())
+}
+
+pub fn load_settlement_rules(conn: &Connection) -> Result<SettlementPenaltyRules, String> {
+    ensure_settlement_tables(conn)?;
+    let stored = conn
+        .query_row(
+            "SELECT rules_json FROM settlement_penalty_config WHERE id = 1",
+            [],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    Ok(stored
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default())
+}
+
+pub fn save_settlement_rules(
+    conn: &Connection,
+    rules: SettlementPenaltyRules,
+) -> Result<SettlementPenaltyRules, String> {
+    ensure_settlement_tables(conn)?;
+    let rules = sanitize_rules(rules);
+    let json = serde_json::to_string(&rules).map_err(|e| e.to_string())?;
+    conn.execute(
+        r#"
+        INSERT INTO settlement_penalty_config (id, rules_json, updated_at_utc)
+        VALUES (1, ?1, ?2)
+        ON CONFLICT(id) DO UPDATE SET
+            rules_json = excluded.rules_json,
+            updated_at_utc = excluded.updated_at_utc
+        "#,
+        params![json, Utc::now().to_rfc3339()],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(rules)
+}
+
+/// Settles a delivered job against its contract: lateness beyond the grace period of the
+/// urgency costs a share of the reward per started hour, cargo damage beyond the tolerance
+/// of the cargo type is deducted proportionally. Deductions never exceed the reward.
+pub fn settle_delivery(
+    input: &DeliverySettlementInput,
+    rules: &SettlementPenaltyRules,
+) -> DeliverySettlement {
+    let reward = input.contract_reward.max(0);
+    let late_rule = rules.late_fee_rule(input.urgency);
+    let damage_rule = rules.damage_rule(input.cargo_type);
+
+    let late_minutes = match (input.deadline_game_min, input.delivered_game_min) {
+        (Some(deadline), Some(delivered)) if deadline > 0 && delivered > 0 => {
+            (i64::from(delivered) - i64::from(deadline)).max(0)
+        }
+        _ => 0,
+    };
+    let billable_minutes = late_minutes - i64::from(late_rule.grace_minutes);
+    let late_fee = if billable_minutes > 0 {
+        let started_hours = (billable_minutes + 59) / 60;
+        let percent =
+            (started_hours as f64 * late_rule.percent_per_hour).min(late_rule.cap_percent);
+        (reward as f64 * percent / 100.0).round() as i64
+    } else {
+        0
+    };
+
+    let damage_percent = input.cargo_damage_percent.clamp(0.0, 100.0);
+    let excess_damage = damage_percent - damage_rule.tolerance_percent;
+    let damage_deduction = if excess_damage > 0.0 {
+        let percent = (excess_damage * damage_rule.deduction_factor).min(damage_rule.cap_percent);
+        (reward as f64 * percent / 100.0).round() as i64
+    } else {
+        0
+    };
+
+    let late_fee = late_fee.min(reward);
+    let damage_deduction = damage_deduction.min(reward - late_fee);
+    let on_time = late_minutes <= i64::from(late_rule.grace_minutes);
+    let contract_bonus = if on_time {
+        input.contract_bonus.max(0)
+    } else {
+        0
+    };
+
+    let mut line_items = vec![SettlementLineItem {
+        kind: "contract_reward".to_string(),
+        amount: reward,
+        detail: "Agreed contract reward".to_string(),
+    }];
+    if contract_bonus > 0 {
+        line_items.push(SettlementLineItem {
+            kind: "contract_bonus".to_string(),
+            amount: contract_bonus,
+            detail: "Negotiated bonus for on-time delivery".to_string(),
+        });
+    }
+    if late_fee > 0 {
+        line_items.push(SettlementLineItem {
+            kind: "late_fee".to_string(),
+            amount: -late_fee,
+            detail: format!(
+                "Delivered {late_minutes} min late ({} min grace)",
+                late_rule.grace_minutes
+            ),
+        });
+    }
+    if damage_deduction > 0 {
+        line_items.push(SettlementLineItem {
+            kind: "damage_deduction".to_string(),
+            amount: -damage_deduction,
+            detail: format!(
+                "Cargo damage {damage_percent:.1}% ({:.1}% tolerated)",
+                damage_rule.tolerance_percent
+            ),
+        });
+    }
+
+    DeliverySettlement {
+        contract_reward: reward,
+        contract_bonus,
+        late_minutes,
+        on_time,
+        late_fee,
+        cargo_damage_percent: damage_percent,
+        damage_deduction,
+        net_reward: reward + contract_bonus - late_fee - damage_deduction,
+        line_items,
+    }
+}
+
+fn sanitize_rules(input: SettlementPenaltyRules) -> SettlementPenaltyRules {
+    let mut rules = input;
+    for rule in [
+        &mut rules.late_fees.normal,
+        &mut rules.late_fees.priority,
+        &mut rules.late_fees.express,
+    ] {
+        *rule = sanitize_late_fee_rule(*rule);
+    }
+    for rule in [
+        &mut rules.damage_deductions.standard,
+        &mut rules.damage_deductions.fragile,
+        &mut rules.damage_deductions.refrigerated,
+        &mut rules.damage_deductions.valuable,
+        &mut rules.damage_deductions.hazardous,
+        &mut rules.damage_deductions.oversize,
+    ] {
+        *rule = sanitize_damage_rule(*rule);
+    }
+    rules
+}
+
+fn sanitize_percent(value: f64, fallback: f64) -> f64 {
+    if value.is_finite() {
+        value.clamp(0.0, 100.0)
+    } else {
+        fallback
+    }
+}
+
+fn sanitize_late_fee_rule(rule: LateFeeRule) -> LateFeeRule {
+    let defaults = LateFeeRule::default();
+    LateFeeRule {
+        grace_minutes: rule.grace_minutes.min(24 * 60),
+        percent_per_hour: sanitize_percent(rule.percent_per_hour, defaults.percent_per_hour),
+        cap_percent: sanitize_percent(rule.cap_percent, defaults.cap_percent),
+    }
+}
+
+fn sanitize_damage_rule(rule: DamageDeductionRule) -> DamageDeductionRule {
+    let defaults = DamageDeductionRule::default();
+    DamageDeductionRule {
+        tolerance_percent: sanitize_percent(rule.tolerance_percent, defaults.tolerance_percent),
+        deduction_factor: if rule.deduction_factor.is_finite() {
+            rule.deduction_factor.clamp(0.0, 10.0)
+        } else {
+            defaults.deduction_factor
+        },
+        cap_percent: sanitize_percent(rule.cap_percent, defaults.cap_percent),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::settle_delivery;
+    use crate::features::economy::compensation_models::{CargoType, Urgency};
+    use crate::features::economy::settlement_models::{
+        DeliverySettlementInput, SettlementPenaltyRules,
+    };
+
+    #[test]
+    fn late_and_damaged_delivery_is_itemized() {
+        let rules = SettlementPenaltyRules::default();
+        let settlement = settle_delivery(
+            &DeliverySettlementInput {
+                contract_reward: 10_000,
+                contract_bonus: 500,
+                cargo_type: CargoType::Fragile,
+                urgency: Urgency::Priority,
+                deadline_game_min: Some(1_000),
+                delivered_game_min: Some(1_100),
+                cargo_damage_percent: 5.0,
+            },
+            &rules,
+        );
+
+        // 100 min late, 30 min grace -> 2 started hours at 4 %.
+        assert_eq!(settlement.late_fee, 800);
+        // 4 % above the fragile tolerance at factor 1.5.
+        assert_eq!(settlement.damage_deduction, 600);
+        // Late deliveries forfeit the negotiated bonus.
+        assert_eq!(settlement.contract_bonus, 0);
+        assert_eq!(settlement.net_reward, 8_600);
+        assert!(!settlement.on_time);
+        assert_eq!(
+            settlement
+                .line_items
+                .iter()
+                .map(|item| item.amount)
+                .sum::<i64>(),
+            settlement.net_reward
+        );
+    }
+
+    #[test]
+    fn unknown_deadline_and_tolerated_damage_settle_in_full() {
+        let settlement = settle_delivery(
+            &DeliverySettlementInput {
+                contract_reward: 4_200,
+                contract_bonus: 300,
+                cargo_type: CargoType::Standard,
+                urgency: Urgency::Express,
+                deadline_game_min: None,
+                delivered_game_min: Some(5_000),
+                cargo_damage_percent: 1.5,
+            },
+            &SettlementPenaltyRules::default(),
+        );
+        assert!(settlement.on_time);
+        assert_eq!(settlement.contract_bonus, 300);
+        assert_eq!(settlement.net_reward, 4_500);
+        assert_eq!(settlement.line_items.len(), 2);
+    }
+}
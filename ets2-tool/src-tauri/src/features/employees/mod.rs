@@ -1,5 +1,22 @@
-use rusqlite::{Connection, params};
+//! Company staff of career mode.
+//!
+//! `emp-driver` mirrors the player and only changes status with logbook
+//! trips. Drivers hired through [`hire_driver`] are simulated: they take
+//! dispatcher market jobs on bank days, see [`simulation`].
+
+pub mod simulation;
+
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension, params};
 use serde::Serialize;
+use uuid::Uuid;
+
+use crate::shared::sqlite_schema::ensure_columns;
+
+/// Monthly salary of a freshly hired driver at skill level 1.
+const BASE_DRIVER_SALARY: i64 = 2_600;
+/// Monthly raise per skill level above 1.
+pub const SALARY_RAISE_PER_LEVEL: i64 = 250;
 
 #[derive(Debug, Clone, Serialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -9,6 +26,9 @@ pub struct EmployeeSummary {
     pub role: String,
     pub status: String,
     pub salary: i64,
+    pub simulated: bool,
+    pub skill_level: i64,
+    pub experience_km: f64,
 }
 
 #[derive(Debug, Clone, Serialize, PartialEq)]
@@ -34,6 +54,16 @@ pub fn ensure_tables(conn: &Connection) -> Result<(), String> {
         "#,
     )
     .map_err(|e| e.to_string())?;
+    ensure_columns(
+        conn,
+        "employees",
+        &[
+            ("simulated", "INTEGER NOT NULL DEFAULT 0"),
+            ("skill_level", "INTEGER NOT NULL DEFAULT 1"),
+            ("experience_km", "REAL NOT NULL DEFAULT 0"),
+            ("hired_at_utc", "TEXT"),
+        ],
+    )?;
 
     let employees = [
         ("emp-chief", "Alex Mercer", "Chief", "on_duty", 5200_i64),
@@ -71,14 +101,14 @@ pub fn ensure_tables(conn: &Connection) -> Result<(), String> {
         .map_err(|e| e.to_string())?;
     }
 
-    Ok(())
+    simulation::ensure_tables(conn)
 }
 
 pub fn load_staff(conn: &Connection, limit: usize) -> Result<Vec<EmployeeSummary>, String> {
     let mut stmt = conn
         .prepare(
             r#"
-            SELECT employee_id, name, role, status, salary
+            SELECT employee_id, name, role, status, salary, simulated, skill_level, experience_km
             FROM employees
             WHERE active = 1
             ORDER BY
@@ -102,6 +132,9 @@ pub fn load_staff(conn: &Connection, limit: usize) -> Result<Vec<EmployeeSummary
                 role: row.get(2)?,
                 status: row.get(3)?,
                 salary: row.get(4)?,
+                simulated: row.get::<_, i64>(5)? == 1,
+                skill_level: row.get(6)?,
+                experience_km: row.get(7)?,
             })
         })
         .map_err(|e| e.to_string())?;
@@ -147,3 +180,97 @@ pub fn mark_driver_status(conn: &Connection, status: &str) -> Result<(), String>
 
     Ok(())
 }
+
+pub fn driver_salary_for_level(skill_level: i64) -> i64 {
+    BASE_DRIVER_SALARY + SALARY_RAISE_PER_LEVEL * (skill_level.max(1) - 1)
+}
+
+/// Hires a simulated driver. Prior experience sets the starting skill level
+/// and with it the salary.
+pub fn hire_driver(
+    conn: &Connection,
+    name: &str,
+    experience_km: f64,
+) -> Result<EmployeeSummary, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Driver name must not be empty".to_string());
+    }
+    let experience_km = experience_km.max(0.0);
+    let skill_level = simulation::skill_level_for_experience(experience_km);
+    let employee_id = format!("emp-ai-{}", Uuid::new_v4().simple());
+    conn.execute(
+        r#"
+        INSERT INTO employees (
+            employee_id,
+            name,
+            role,
+            status,
+            salary,
+            simulated,
+            skill_level,
+            experience_km,
+            hired_at_utc
+        )
+        VALUES (?1, ?2, 'Driver', ?3, ?4, 1, ?5, ?6, ?7)
+        "#,
+        params![
+            employee_id,
+            name,
+            simulation::STATUS_AVAILABLE,
+            driver_salary_for_level(skill_level),
+            skill_level,
+            experience_km,
+            Utc::now().to_rfc3339()
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    load_employee(conn, &employee_id)?
+        .ok_or_else(|| format!("Employee {} not found after hiring", employee_id))
+}
+
+/// Lets a simulated driver go. Drivers out on a job have to finish it first.
+pub fn dismiss_employee(conn: &Connection, employee_id: &str) -> Result<(), String> {
+    let employee = load_employee(conn, employee_id)?
+        .ok_or_else(|| format!("Employee {} not found", employee_id))?;
+    if !employee.simulated {
+        return Err(format!("{} cannot be dismissed", employee.name));
+    }
+    if simulation::running_job(conn, employee_id)?.is_some() {
+        return Err(format!("{} is still on a job", employee.name));
+    }
+    conn.execute(
+        "UPDATE employees SET active = 0, status = 'dismissed' WHERE employee_id = ?1",
+        params![employee_id],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub fn load_employee(
+    conn: &Connection,
+    employee_id: &str,
+) -> Result<Option<EmployeeSummary>, String> {
+    conn.query_row(
+        r#"
+        SELECT employee_id, name, role, status, salary, simulated, skill_level, experience_km
+        FROM employees
+        WHERE employee_id = ?1 AND active = 1
+        "#,
+        params![employee_id],
+        |row| {
+            Ok(EmployeeSummary {
+                employee_id: row.get(0)?,
+                name: row.get(1)?,
+                role: row.get(2)?,
+                status: row.get(3)?,
+                salary: row.get(4)?,
+                simulated: row.get::<_, i64>(5)? == 1,
+                skill_level: row.get(6)?,
+                experience_km: row.get(7)?,
+            })
+        },
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
@@ -0,0 +1,880 @@
+//! Bank-day simulation of hired drivers.
+//!
+//! Every bank day idle drivers take the best paying dispatcher market job
+//! they are skilled enough for. A job keeps a driver busy for as many days as
+//! the route needs at [`KM_PER_DRIVING_DAY`]. On delivery the revenue minus
+//! fuel, tolls, insurance, wages and incident costs is booked on the bank
+//! ledger, the driver gains experience and a daily summary is recorded as a
+//! career event.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension, params};
+use serde::Serialize;
+
+use crate::features::bank::{self, ledger};
+use crate::features::career::dispatcher;
+use crate::features::economy;
+use crate::features::events;
+use crate::features::fleet::maintenance;
+use crate::shared::sqlite_schema::create_indexes;
+
+pub const STATUS_AVAILABLE: &str = "available";
+const STATUS_ON_DUTY: &str = "on_duty";
+const JOB_RUNNING: &str = "running";
+const JOB_COMPLETED: &str = "completed";
+const JOB_CANCELLED: &str = "cancelled";
+
+/// Distance a hired driver covers per bank day within driving time limits.
+const KM_PER_DRIVING_DAY: f64 = 650.0;
+/// Longest route a driver accepts, a bit over a week on the road.
+const MAX_JOB_DISTANCE_KM: f64 = 5_000.0;
+const FUEL_LITERS_PER_100KM: f64 = 31.0;
+/// Fuel saved per skill level above 1.
+const FUEL_SAVING_PER_LEVEL: f64 = 0.02;
+/// Revenue bonus per skill level above 1, from better on-time delivery.
+const REVENUE_BONUS_PER_LEVEL: f64 = 0.03;
+/// Incident chance per 1,000 km for a level 1 driver.
+const INCIDENT_CHANCE_PER_1000KM: f64 = 0.08;
+const SALARY_DAYS_PER_MONTH: i64 = 30;
+/// Experience in km needed for skill levels 2 to 6.
+const SKILL_THRESHOLDS_KM: [f64; 5] = [15_000.0, 50_000.0, 120_000.0, 250_000.0, 500_000.0];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Incident {
+    LateDelivery,
+    MinorCollision,
+    Breakdown,
+}
+
+impl Incident {
+    fn from_roll(value: f64) -> Self {
+        if value < 0.5 {
+            Self::LateDelivery
+        } else if value < 0.85 {
+            Self::MinorCollision
+        } else {
+            Self::Breakdown
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::LateDelivery => "late_delivery",
+            Self::MinorCollision => "minor_collision",
+            Self::Breakdown => "breakdown",
+        }
+    }
+
+    /// Share of the revenue the customer withholds.
+    fn revenue_penalty(self) -> f64 {
+        match self {
+            Self::LateDelivery => 0.15,
+            Self::MinorCollision => 0.10,
+            Self::Breakdown => 0.05,
+        }
+    }
+
+    fn cost(self) -> i64 {
+        match self {
+            Self::LateDelivery => 0,
+            Self::MinorCollision => 1_200,
+            Self::Breakdown => 1_500,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EmployeeJob {
+    pub id: i64,
+    pub employee_id: String,
+    pub employee_name: String,
+    pub dispatcher_job_id: String,
+    pub origin_city: String,
+    pub destination_city: String,
+    pub cargo_type: String,
+    pub distance_km: f64,
+    pub started_bank_day: i64,
+    pub due_bank_day: i64,
+    pub revenue: i64,
+    pub operating_cost: i64,
+    pub salary_cost: i64,
+    pub incident: Option<String>,
+    pub incident_cost: i64,
+    pub net_result: i64,
+    pub status: String,
+    pub completed_at_utc: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EmployeeDaySummary {
+    pub bank_day: i64,
+    pub delivered: i64,
+    pub started: i64,
+    pub on_the_road: i64,
+    pub idle: i64,
+    pub incidents: i64,
+    pub net_result: i64,
+}
+
+pub fn ensure_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS employee_jobs (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            employee_id TEXT NOT NULL,
+            dispatcher_job_id TEXT NOT NULL,
+            origin_city TEXT NOT NULL,
+            destination_city TEXT NOT NULL,
+            cargo_type TEXT NOT NULL,
+            distance_km REAL NOT NULL,
+            offered_reward INTEGER NOT NULL,
+            started_bank_day INTEGER NOT NULL,
+            due_bank_day INTEGER NOT NULL,
+            revenue INTEGER NOT NULL DEFAULT 0,
+            operating_cost INTEGER NOT NULL DEFAULT 0,
+            salary_cost INTEGER NOT NULL DEFAULT 0,
+            incident TEXT,
+            incident_cost INTEGER NOT NULL DEFAULT 0,
+            net_result INTEGER NOT NULL DEFAULT 0,
+            status TEXT NOT NULL,
+            created_at_utc TEXT NOT NULL,
+            completed_at_utc TEXT
+        );
+
+        CREATE TABLE IF NOT EXISTS employee_simulation_state (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            last_bank_day INTEGER
+        );
+
+        INSERT OR IGNORE INTO employee_simulation_state (id, last_bank_day) VALUES (1, NULL);
+        "#,
+    )
+    .map_err(|e| e.to_string())?;
+    create_indexes(
+        conn,
+        &[
+            "CREATE INDEX IF NOT EXISTS idx_employee_jobs_employee ON employee_jobs(employee_id, status)",
+            "CREATE INDEX IF NOT EXISTS idx_employee_jobs_due ON employee_jobs(status, due_bank_day)",
+        ],
+    )
+}
+
+pub fn skill_level_for_experience(experience_km: f64) -> i64 {
+    1 + SKILL_THRESHOLDS_KM
+        .iter()
+        .filter(|threshold| experience_km >= **threshold)
+        .count() as i64
+}
+
+/// Market difficulties a driver of `skill_level` is trusted with.
+fn allowed_difficulties(skill_level: i64) -> &'static [&'static str] {
+    match skill_level {
+        ..=2 => &["easy", "normal"],
+        3..=4 => &["easy", "normal", "hard"],
+        _ => &["easy", "normal", "hard", "expert"],
+    }
+}
+
+fn daily_salary(monthly_salary: i64) -> i64 {
+    (monthly_salary + SALARY_DAYS_PER_MONTH / 2) / SALARY_DAYS_PER_MONTH
+}
+
+fn driving_days(distance_km: f64) -> i64 {
+    ((distance_km / KM_PER_DRIVING_DAY).ceil() as i64).max(1)
+}
+
+fn incident_chance(distance_km: f64, skill_level: i64) -> f64 {
+    let skill_factor = (1.3 - 0.1 * skill_level as f64).max(0.6);
+    (INCIDENT_CHANCE_PER_1000KM * distance_km / 1_000.0 * skill_factor).clamp(0.0, 0.6)
+}
+
+fn seed_for(bank_day: i64, employee_id: &str, salt: u64) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    bank_day.hash(&mut hasher);
+    employee_id.hash(&mut hasher);
+    salt.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Runs every bank day after the last simulated one up to `bank_day`, like the
+/// bank catch-up does for loans. Drivers only take market jobs generated for
+/// `save_context`. Each day is committed on its own, so an error keeps the
+/// days before it.
+pub fn simulate_until(
+    conn: &Connection,
+    bank_day: i64,
+    save_context: &dispatcher::DispatcherSaveContext,
+) -> Result<Vec<EmployeeDaySummary>, String> {
+    let last_bank_day: Option<i64> = conn
+        .query_row(
+            "SELECT last_bank_day FROM employee_simulation_state WHERE id = 1",
+            [],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+    let first_day = match last_bank_day {
+        Some(last) if last >= bank_day => return Ok(Vec::new()),
+        Some(last) => last + 1,
+        None => bank_day,
+    };
+
+    let mut summaries = Vec::new();
+    for day in first_day..=bank_day {
+        summaries.push(bank::atomically(conn, |conn| {
+            let summary = simulate_day(conn, day, save_context)?;
+            conn.execute(
+                "UPDATE employee_simulation_state SET last_bank_day = ?1 WHERE id = 1",
+                params![day],
+            )
+            .map_err(|e| e.to_string())?;
+            Ok(summary)
+        })?);
+    }
+    Ok(summaries)
+}
+
+/// Settles due jobs, dispatches idle drivers and pays idle wages for one
+/// bank day. The day is applied completely or not at all.
+pub fn simulate_day(
+    conn: &Connection,
+    bank_day: i64,
+    save_context: &dispatcher::DispatcherSaveContext,
+) -> Result<EmployeeDaySummary, String> {
+    bank::atomically(conn, |conn| run_day(conn, bank_day, save_context))
+}
+
+fn run_day(
+    conn: &Connection,
+    bank_day: i64,
+    save_context: &dispatcher::DispatcherSaveContext,
+) -> Result<EmployeeDaySummary, String> {
+    let mut summary = EmployeeDaySummary {
+        bank_day,
+        ..EmployeeDaySummary::default()
+    };
+
+    for job_id in due_job_ids(conn, bank_day)? {
+        let job = settle_job(conn, job_id, bank_day)?;
+        if job.status == JOB_COMPLETED {
+            summary.delivered += 1;
+        }
+        if job.incident.is_some() {
+            summary.incidents += 1;
+        }
+        summary.net_result += job.net_result;
+    }
+
+    let mut idle_wages = 0;
+    for (employee_id, salary, skill_level) in simulated_drivers(conn)? {
+        if running_job(conn, &employee_id)?.is_some() {
+            summary.on_the_road += 1;
+            continue;
+        }
+        match dispatcher::dispatcher_claim_job_for_employee(
+            conn,
+            &employee_id,
+            allowed_difficulties(skill_level),
+            MAX_JOB_DISTANCE_KM,
+            save_context,
+        )? {
+            Some(market_job) => {
+                start_job(conn, &employee_id, &market_job, bank_day)?;
+                summary.started += 1;
+                summary.on_the_road += 1;
+            }
+            None => {
+                idle_wages += daily_salary(salary);
+                summary.idle += 1;
+                set_status(conn, &employee_id, STATUS_AVAILABLE)?;
+            }
+        }
+    }
+
+    if idle_wages > 0 {
+        ledger::post(
+            conn,
+            ledger::KIND_PAYROLL,
+            -idle_wages,
+            None,
+            &format!("Wages of {} idle driver(s)", summary.idle),
+        )?;
+        summary.net_result -= idle_wages;
+    }
+
+    if summary.delivered + summary.on_the_road + summary.idle > 0 {
+        record_day_summary(conn, &summary)?;
+    }
+    Ok(summary)
+}
+
+fn start_job(
+    conn: &Connection,
+    employee_id: &str,
+    market_job: &dispatcher::DispatcherMarketJob,
+    bank_day: i64,
+) -> Result<(), String> {
+    conn.execute(
+        r#"
+        INSERT INTO employee_jobs (
+            employee_id, dispatcher_job_id, origin_city, destination_city, cargo_type,
+            distance_km, offered_reward, started_bank_day, due_bank_day, status, created_at_utc
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+        "#,
+        params![
+            employee_id,
+            market_job.id,
+            market_job.origin_city,
+            market_job.destination_city,
+            market_job.cargo_type,
+            market_job.distance_km,
+            market_job.total_reward,
+            bank_day,
+            bank_day + driving_days(market_job.distance_km),
+            JOB_RUNNING,
+            Utc::now().to_rfc3339()
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    set_status(conn, employee_id, STATUS_ON_DUTY)
+}
+
+/// Books the result of a finished job and credits the driver's experience.
+fn settle_job(conn: &Connection, job_id: i64, bank_day: i64) -> Result<EmployeeJob, String> {
+    bank::atomically(conn, |conn| book_job(conn, job_id, bank_day))
+}
+
+fn book_job(conn: &Connection, job_id: i64, bank_day: i64) -> Result<EmployeeJob, String> {
+    let (employee_id, dispatcher_job_id, distance_km, offered_reward, started_bank_day): (
+        String,
+        String,
+        f64,
+        i64,
+        i64,
+    ) = conn
+        .query_row(
+            r#"
+            SELECT employee_id, dispatcher_job_id, distance_km, offered_reward, started_bank_day
+            FROM employee_jobs
+            WHERE id = ?1
+            "#,
+            params![job_id],
+            |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                ))
+            },
+        )
+        .map_err(|e| e.to_string())?;
+    let (name, salary, skill_level, experience_km): (String, i64, i64, f64) = conn
+        .query_row(
+            "SELECT name, salary, skill_level, experience_km FROM employees WHERE employee_id = ?1",
+            params![employee_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .map_err(|e| e.to_string())?;
+
+    let salary_cost = daily_salary(salary) * (bank_day - started_bank_day).max(1);
+    let delivered = {
+        let seed = seed_for(bank_day, &employee_id, job_id as u64);
+        let incident = (maintenance::roll(seed) < incident_chance(distance_km, skill_level))
+            .then(|| Incident::from_roll(maintenance::roll(seed.rotate_left(17))));
+        dispatcher::dispatcher_finish_employee_job(
+            conn,
+            &dispatcher_job_id,
+            &employee_id,
+            incident.is_some(),
+        )?
+        .then_some(incident)
+    };
+
+    let (status, revenue, operating_cost, incident, incident_cost) = match delivered {
+        Some(incident) => {
+            let skill_bonus = 1.0 + REVENUE_BONUS_PER_LEVEL * (skill_level - 1) as f64;
+            let penalty = incident.map_or(0.0, Incident::revenue_penalty);
+            let revenue = (offered_reward as f64 * skill_bonus * (1.0 - penalty)).round() as i64;
+            let fuel_liters = distance_km * FUEL_LITERS_PER_100KM / 100.0
+                * (1.0 - FUEL_SAVING_PER_LEVEL * (skill_level - 1) as f64);
+            let costs = economy::estimate_trip_costs(conn, fuel_liters, distance_km, 0)?;
+            (
+                JOB_COMPLETED,
+                revenue,
+                costs.total_cost,
+                incident,
+                incident.map_or(0, Incident::cost),
+            )
+        }
+        // Cancelled under the driver: only the wages are lost.
+        None => (JOB_CANCELLED, 0, 0, None, 0),
+    };
+    let net_result = revenue - operating_cost - salary_cost - incident_cost;
+
+    conn.execute(
+        r#"
+        UPDATE employee_jobs
+        SET
+            revenue = ?2,
+            operating_cost = ?3,
+            salary_cost = ?4,
+            incident = ?5,
+            incident_cost = ?6,
+            net_result = ?7,
+            status = ?8,
+            completed_at_utc = ?9
+        WHERE id = ?1
+        "#,
+        params![
+            job_id,
+            revenue,
+            operating_cost,
+            salary_cost,
+            incident.map(Incident::as_str),
+            incident_cost,
+            net_result,
+            status,
+            Utc::now().to_rfc3339()
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    let job = load_job(conn, job_id)?;
+    ledger::post(
+        conn,
+        ledger::KIND_EMPLOYEE_JOB,
+        net_result,
+        None,
+        &format!(
+            "{}: {} -> {} ({})",
+            name, job.origin_city, job.destination_city, job.status
+        ),
+    )?;
+
+    if status == JOB_COMPLETED {
+        gain_experience(conn, &employee_id, &name, experience_km + distance_km)?;
+    }
+    if let Some(incident) = incident {
+        events::record_event(
+            conn,
+            "employees",
+            "Driver incident",
+            &format!(
+                "{} reported a {} on {} -> {}: EUR {} in costs and {:.0}% of the freight withheld.",
+                name,
+                incident.as_str().replace('_', " "),
+                job.origin_city,
+                job.destination_city,
+                incident_cost,
+                incident.revenue_penalty() * 100.0
+            ),
+            "medium",
+        )?;
+    }
+    set_status(conn, &employee_id, STATUS_AVAILABLE)?;
+    Ok(job)
+}
+
+fn gain_experience(
+    conn: &Connection,
+    employee_id: &str,
+    name: &str,
+    experience_km: f64,
+) -> Result<(), String> {
+    let skill_level = skill_level_for_experience(experience_km);
+    let changed = conn
+        .execute(
+            r#"
+            UPDATE employees
+            SET
+                experience_km = ?2,
+                salary = salary + ?4 * (?3 - skill_level),
+                skill_level = ?3
+            WHERE employee_id = ?1 AND skill_level < ?3
+            "#,
+            params![
+                employee_id,
+                experience_km,
+                skill_level,
+                super::SALARY_RAISE_PER_LEVEL
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    if changed > 0 {
+        return events::record_event(
+            conn,
+            "employees",
+            "Driver promoted",
+            &format!(
+                "{} reached skill level {} after {:.0} km.",
+                name, skill_level, experience_km
+            ),
+            "low",
+        );
+    }
+    conn.execute(
+        "UPDATE employees SET experience_km = ?2 WHERE employee_id = ?1",
+        params![employee_id, experience_km],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn record_day_summary(conn: &Connection, summary: &EmployeeDaySummary) -> Result<(), String> {
+    events::record_event(
+        conn,
+        "employees",
+        &format!("Company day {}", summary.bank_day),
+        &format!(
+            "{} delivered, {} started, {} on the road, {} idle, {} incident(s). Net result: EUR {}.",
+            summary.delivered,
+            summary.started,
+            summary.on_the_road,
+            summary.idle,
+            summary.incidents,
+            summary.net_result
+        ),
+        if summary.net_result < 0 || summary.incidents > 0 {
+            "medium"
+        } else {
+            "low"
+        },
+    )
+}
+
+fn set_status(conn: &Connection, employee_id: &str, status: &str) -> Result<(), String> {
+    conn.execute(
+        "UPDATE employees SET status = ?2 WHERE employee_id = ?1",
+        params![employee_id, status],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn simulated_drivers(conn: &Connection) -> Result<Vec<(String, i64, i64)>, String> {
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT employee_id, salary, skill_level
+            FROM employees
+            WHERE simulated = 1 AND active = 1
+            ORDER BY skill_level DESC, hired_at_utc, employee_id
+            "#,
+        )
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+        .map_err(|e| e.to_string())?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())
+}
+
+fn due_job_ids(conn: &Connection, bank_day: i64) -> Result<Vec<i64>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id FROM employee_jobs WHERE status = ?1 AND due_bank_day <= ?2 ORDER BY id",
+        )
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params![JOB_RUNNING, bank_day], |row| row.get(0))
+        .map_err(|e| e.to_string())?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())
+}
+
+pub fn running_job(conn: &Connection, employee_id: &str) -> Result<Option<EmployeeJob>, String> {
+    let job_id: Option<i64> = conn
+        .query_row(
+            "SELECT id FROM employee_jobs WHERE employee_id = ?1 AND status = ?2",
+            params![employee_id, JOB_RUNNING],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    job_id.map(|job_id| load_job(conn, job_id)).transpose()
+}
+
+pub fn list_jobs(
+    conn: &Connection,
+    employee_id: Option<&str>,
+    limit: usize,
+) -> Result<Vec<EmployeeJob>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "{} WHERE ?1 IS NULL OR j.employee_id = ?1 ORDER BY j.id DESC LIMIT ?2",
+            JOB_SELECT
+        ))
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params![employee_id, limit as i64], map_job)
+        .map_err(|e| e.to_string())?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())
+}
+
+const JOB_SELECT: &str = r#"
+    SELECT
+        j.id, j.employee_id, COALESCE(e.name, j.employee_id), j.dispatcher_job_id,
+        j.origin_city, j.destination_city, j.cargo_type, j.distance_km, j.started_bank_day,
+        j.due_bank_day, j.revenue, j.operating_cost, j.salary_cost, j.incident,
+        j.incident_cost, j.net_result, j.status, j.completed_at_utc
+    FROM employee_jobs j
+    LEFT JOIN employees e ON e.employee_id = j.employee_id
+"#;
+
+fn load_job(conn: &Connection, job_id: i64) -> Result<EmployeeJob, String> {
+    conn.query_row(
+        &format!("{} WHERE j.id = ?1", JOB_SELECT),
+        params![job_id],
+        map_job,
+    )
+    .map_err(|e| e.to_string())
+}
+
+fn map_job(row: &rusqlite::Row<'_>) -> rusqlite::Result<EmployeeJob> {
+    Ok(EmployeeJob {
+        id: row.get(0)?,
+        employee_id: row.get(1)?,
+        employee_name: row.get(2)?,
+        dispatcher_job_id: row.get(3)?,
+        origin_city: row.get(4)?,
+        destination_city: row.get(5)?,
+        cargo_type: row.get(6)?,
+        distance_km: row.get(7)?,
+        started_bank_day: row.get(8)?,
+        due_bank_day: row.get(9)?,
+        revenue: row.get(10)?,
+        operating_cost: row.get(11)?,
+        salary_cost: row.get(12)?,
+        incident: row.get(13)?,
+        incident_cost: row.get(14)?,
+        net_result: row.get(15)?,
+        status: row.get(16)?,
+        completed_at_utc: row.get(17)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skill_grows_with_experience_and_unlocks_harder_jobs() {
+        assert_eq!(skill_level_for_experience(0.0), 1);
+        assert_eq!(skill_level_for_experience(15_000.0), 2);
+        assert_eq!(skill_level_for_experience(130_000.0), 4);
+        assert_eq!(skill_level_for_experience(2_000_000.0), 6);
+        assert!(!allowed_difficulties(1).contains(&"hard"));
+        assert!(allowed_difficulties(3).contains(&"hard"));
+        assert!(allowed_difficulties(6).contains(&"expert"));
+        assert!(incident_chance(1_000.0, 6) < incident_chance(1_000.0, 1));
+    }
+
+    #[test]
+    fn jobs_take_a_driving_day_per_650_km() {
+        assert_eq!(driving_days(120.0), 1);
+        assert_eq!(driving_days(650.0), 1);
+        assert_eq!(driving_days(1_900.0), 3);
+        assert_eq!(daily_salary(2_600), 87);
+    }
+
+    fn setup() -> (Connection, dispatcher::DispatcherSaveContext) {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::features::reputation::ensure_tables(&conn).unwrap();
+        events::ensure_tables(&conn).unwrap();
+        economy::ensure_tables(&conn).unwrap();
+        bank::ensure_tables(&conn).unwrap();
+        dispatcher::ensure_tables(&conn).unwrap();
+        crate::features::employees::ensure_tables(&conn).unwrap();
+        for (employee_id, name, hired_at) in [
+            ("emp-ai-anna", "Anna", "2026-01-01T00:00:00Z"),
+            ("emp-ai-ben", "Ben", "2026-02-01T00:00:00Z"),
+        ] {
+            conn.execute(
+                r#"
+                INSERT INTO employees (
+                    employee_id, name, role, status, salary, simulated, hired_at_utc
+                ) VALUES (?1, ?2, 'Driver', 'available', 2600, 1, ?3)
+                "#,
+                params![employee_id, name, hired_at],
+            )
+            .unwrap();
+        }
+        let save_context = dispatcher::DispatcherSaveContext {
+            profile_reference: Some("profiles/main".to_string()),
+            save_reference: None,
+            quicksave_reference: None,
+            save_session_id: None,
+        };
+        (conn, save_context)
+    }
+
+    fn insert_market_job(conn: &Connection, job_id: &str, reward: i64) {
+        let now = Utc::now().to_rfc3339();
+        conn.execute(
+            r#"
+            INSERT INTO dispatcher_jobs (
+                id, source_type, company_id, company_name, job_type, cargo_type,
+                origin_city, origin_country, destination_city, destination_country,
+                distance_km, cargo_mass_kg, urgency_level, difficulty_level,
+                equipment_type_required, trailer_type_required, base_rate_per_km,
+                calculated_rate_per_km, total_reward, estimated_duration_minutes,
+                payment_tier_snapshot, payment_multiplier_snapshot, country_multiplier_snapshot,
+                reputation_multiplier_snapshot, cargo_multiplier_snapshot,
+                urgency_multiplier_snapshot, equipment_multiplier_snapshot,
+                market_variation_snapshot, customer_multiplier_snapshot, company_reputation,
+                fuel_cost_estimate, profit_estimate, risk_note, bonus_note,
+                expires_at_utc, status, progress_km, profile_reference, save_reference,
+                quicksave_reference, save_session_id, route_reference, ets2_job_link_status,
+                accepted_at_utc, completed_at_utc, created_at_utc, updated_at_utc
+            )
+            VALUES (
+                ?1, 'generated', 'north-axis-logistics', 'North Axis Logistics', 'quick_job', 'standard',
+                'Hamburg', 'DE', 'Prague', 'CZ',
+                642.0, 12000.0, 'normal', 'normal',
+                'quick_job', NULL, 1.12,
+                1.18, ?2, 620,
+                'standard', 1.0, 1.02,
+                1.01, 1.0,
+                1.0, 1.0,
+                1.0, 1.0, 320,
+                120, 480, NULL, NULL,
+                NULL, 'open', 0, 'profiles/main', NULL,
+                NULL, NULL, NULL, NULL,
+                NULL, NULL, ?3, ?3
+            )
+            "#,
+            params![job_id, reward, now],
+        )
+        .unwrap();
+    }
+
+    fn ledger_amounts(conn: &Connection, kind: &str) -> Vec<i64> {
+        let mut stmt = conn
+            .prepare("SELECT amount FROM bank_ledger WHERE kind = ?1 ORDER BY id")
+            .unwrap();
+        stmt.query_map(params![kind], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+    }
+
+    fn cash_balance(conn: &Connection) -> i64 {
+        conn.query_row(
+            "SELECT cash_balance FROM bank_state WHERE id = 1",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn drivers_claim_market_jobs_and_idle_drivers_draw_wages() {
+        let (conn, save_context) = setup();
+        insert_market_job(&conn, "dispatcher-sim-1", 1_500);
+        let opening_balance = cash_balance(&conn);
+
+        let first = simulate_day(&conn, 1, &save_context).unwrap();
+        assert_eq!((first.started, first.on_the_road, first.idle), (1, 1, 1));
+        assert_eq!(first.net_result, -daily_salary(2_600));
+        assert_eq!(ledger_amounts(&conn, ledger::KIND_PAYROLL), vec![-87]);
+
+        let job = running_job(&conn, "emp-ai-anna").unwrap().unwrap();
+        assert_eq!(job.dispatcher_job_id, "dispatcher-sim-1");
+        assert_eq!(job.due_bank_day, 2);
+        let claimed_by: String = conn
+            .query_row(
+                "SELECT assigned_employee_id FROM dispatcher_jobs WHERE id = 'dispatcher-sim-1'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(claimed_by, "emp-ai-anna");
+        assert!(running_job(&conn, "emp-ai-ben").unwrap().is_none());
+
+        let second = simulate_day(&conn, 2, &save_context).unwrap();
+        assert_eq!(second.delivered, 1);
+        assert_eq!(second.idle, 2);
+        let settled = list_jobs(&conn, Some("emp-ai-anna"), 1).unwrap().remove(0);
+        assert_eq!(settled.status, JOB_COMPLETED);
+        assert_eq!(settled.salary_cost, 87);
+        assert!(settled.revenue > 0);
+        assert_eq!(
+            settled.net_result,
+            settled.revenue - settled.operating_cost - settled.salary_cost - settled.incident_cost
+        );
+        assert_eq!(
+            ledger_amounts(&conn, ledger::KIND_EMPLOYEE_JOB),
+            vec![settled.net_result]
+        );
+        assert_eq!(ledger_amounts(&conn, ledger::KIND_PAYROLL), vec![-87, -174]);
+        assert_eq!(
+            second.net_result,
+            settled.net_result - 2 * daily_salary(2_600)
+        );
+        assert_eq!(
+            cash_balance(&conn),
+            opening_balance - 87 + second.net_result
+        );
+        let experience_km: f64 = conn
+            .query_row(
+                "SELECT experience_km FROM employees WHERE employee_id = 'emp-ai-anna'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(experience_km, 642.0);
+    }
+
+    #[test]
+    fn a_failing_day_rolls_back_and_is_retried() {
+        let (conn, save_context) = setup();
+        insert_market_job(&conn, "dispatcher-sim-1", 1_500);
+        insert_market_job(&conn, "dispatcher-sim-2", 1_200);
+        simulate_until(&conn, 1, &save_context).unwrap();
+        let balance_after_day_one = cash_balance(&conn);
+
+        // Ben's row disappears, so settling his job fails after Anna's was booked.
+        conn.execute("DELETE FROM employees WHERE employee_id = 'emp-ai-ben'", [])
+            .unwrap();
+        assert!(simulate_until(&conn, 2, &save_context).is_err());
+
+        assert_eq!(cash_balance(&conn), balance_after_day_one);
+        assert!(ledger_amounts(&conn, ledger::KIND_EMPLOYEE_JOB).is_empty());
+        assert!(running_job(&conn, "emp-ai-anna").unwrap().is_some());
+        let status: String = conn
+            .query_row(
+                "SELECT status FROM dispatcher_jobs WHERE id = 'dispatcher-sim-1'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(status, "employee_assigned");
+        let last_bank_day: Option<i64> = conn
+            .query_row(
+                "SELECT last_bank_day FROM employee_simulation_state WHERE id = 1",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(last_bank_day, Some(1));
+    }
+
+    #[test]
+    fn catches_up_every_missed_bank_day() {
+        let (conn, save_context) = setup();
+        simulate_until(&conn, 1, &save_context).unwrap();
+
+        let summaries = simulate_until(&conn, 31, &save_context).unwrap();
+
+        assert_eq!(summaries.len(), 30);
+        assert_eq!(summaries.first().unwrap().bank_day, 2);
+        assert_eq!(ledger_amounts(&conn, ledger::KIND_PAYROLL).len(), 31);
+    }
+}
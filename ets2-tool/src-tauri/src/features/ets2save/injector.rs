@@ -559,6 +559,13 @@ mod tests {
             dispatcher_status: None,
             last_error_code: None,
             last_error_message: None,
+            save_src_company: None,
+            save_src_city: None,
+            save_dst_company: None,
+            save_dst_city: None,
+            save_cargo: None,
+            save_trailer_variant: None,
+            save_trailer_definition: None,
         };
         let offer_range = crate::features::ets2save::parser::find_job_offer_data_block(
             &lines,
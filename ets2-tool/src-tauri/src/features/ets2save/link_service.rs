@@ -15,10 +15,12 @@ use crate::events::{
 };
 use crate::features::ets2save::errors::{AppError, AppErrorCode};
 use crate::features::ets2save::injector::{build_offer_patch, write_job_offer_patch};
-use crate::features::ets2save::locator::resolve_last_quicksave;
+use crate::features::ets2save::locator::{
+    is_cloned_save, resolve_last_quicksave, resolve_save_slot,
+};
 use crate::features::ets2save::models::{
     DispatcherResolvedSaveLink, DispatcherSaveOfferTemplate, EtsJobLink, EtsJobLinkStatus,
-    EtsJobWriteResult, EtsSaveSlot, VtcDispatcherJob,
+    EtsJobWriteResult, EtsProfile, EtsSaveSlot, EtsSaveTarget, VtcDispatcherJob,
 };
 use crate::features::ets2save::parser::{
     extract_job_offer_pointer, fallback_company_in_city, fallback_company_in_city_with_offers,
@@ -184,6 +186,8 @@ fn ensure_runtime_columns(conn: &RusqliteConnection) -> Result<(), AppError> {
         ],
     )
     .map_err(|error| AppError::new(AppErrorCode::WriteFailed, error))?;
+    ensure_columns(conn, "ets_job_links", &[("clone_source_slot", "TEXT")])
+        .map_err(|error| AppError::new(AppErrorCode::WriteFailed, error))?;
     conn.execute(
         "UPDATE ets_save_depots SET job_offer_count = 0 WHERE job_offer_count IS NULL",
         [],
@@ -211,9 +215,10 @@ pub async fn ets_prepare_job_link(
     pool: &SqlitePool,
     vtc_job_id: &str,
     profile_id: &str,
+    target: &EtsSaveTarget,
     state: &AppProfileState,
 ) -> Result<EtsJobLink, AppError> {
-    prepare_job_link(Some(app), pool, vtc_job_id, profile_id, state).await
+    prepare_job_link(Some(app), pool, vtc_job_id, profile_id, target, state).await
 }
 
 pub async fn prepare_job_link(
@@ -221,18 +226,86 @@ pub async fn prepare_job_link(
     pool: &SqlitePool,
     vtc_job_id: &str,
     profile_id: &str,
+    target: &EtsSaveTarget,
     state: &AppProfileState,
 ) -> Result<EtsJobLink, AppError> {
-    let (profile, save_slot) = resolve_last_quicksave(pool, profile_id, state).await?;
+    let clone_source_slot = target.clone_source_slot().map(str::to_string);
+    let target = &reusable_clone_target(pool, vtc_job_id, target).await?;
+    let (profile, save_slot) = resolve_save_slot(pool, profile_id, state, target).await?;
+    let result = prepare_job_link_for_slot(
+        app,
+        pool,
+        vtc_job_id,
+        target,
+        clone_source_slot.as_deref(),
+        &profile,
+        &save_slot,
+    )
+    .await;
+    if result.is_err() && target.clone_to_new_slot {
+        // The cloned slot only exists for this job.
+        let _ = fs::remove_dir_all(&save_slot.save_path);
+    }
+    result
+}
+
+/// A job that was already prepared into a clone of the same source slot keeps
+/// that clone, so preparing it again does not leave another copy of the save
+/// behind. A different source slot gets a fresh clone.
+async fn reusable_clone_target(
+    pool: &SqlitePool,
+    vtc_job_id: &str,
+    target: &EtsSaveTarget,
+) -> Result<EtsSaveTarget, AppError> {
+    let Some(source_slot) = target.clone_source_slot() else {
+        return Ok(target.clone());
+    };
+    let mut connection = pool.acquire().await?;
+    let Some(link) = load_job_link_by_vtc_job_id_optional(&mut connection, vtc_job_id).await?
+    else {
+        return Ok(target.clone());
+    };
+    let save_slot = match load_save_slot(&mut connection, &link.save_id).await {
+        Ok(save_slot) => save_slot,
+        Err(_) => return Ok(target.clone()),
+    };
+    let reusable = link.clone_source_slot.as_deref() == Some(source_slot)
+        && Path::new(&save_slot.game_sii_path).is_file()
+        && is_cloned_save(Path::new(&save_slot.save_path));
+    Ok(if reusable {
+        EtsSaveTarget {
+            slot: Some(save_slot.slot_name),
+            clone_to_new_slot: false,
+        }
+    } else {
+        target.clone()
+    })
+}
+
+async fn prepare_job_link_for_slot(
+    app: Option<&AppHandle>,
+    pool: &SqlitePool,
+    vtc_job_id: &str,
+    target: &EtsSaveTarget,
+    clone_source_slot: Option<&str>,
+    profile: &EtsProfile,
+    save_slot: &EtsSaveSlot,
+) -> Result<EtsJobLink, AppError> {
+    let explicit_slot = target.slot.is_some() || target.clone_to_new_slot;
     let lines = decode_sii_lines(std::path::Path::new(&save_slot.game_sii_path))?;
     let scan = scan_save_templates(&lines);
     let dispatcher_job = {
         let mut read_connection = pool.acquire().await?;
         load_vtc_dispatcher_job(&mut read_connection, vtc_job_id).await?
     };
-    let save_session_id = dispatcher_job
-        .save_session_id
-        .clone()
+    // An explicitly chosen slot gets its own snapshot session instead of
+    // overwriting the snapshot of the save the job was assigned in.
+    let job_session_id = if explicit_slot {
+        None
+    } else {
+        dispatcher_job.save_session_id.clone()
+    };
+    let save_session_id = job_session_id
         .or_else(|| {
             build_save_session_id(
                 Some(profile.profile_path.as_str()),
@@ -251,10 +324,12 @@ pub async fn prepare_job_link(
         save_reference: dispatcher_job
             .save_reference
             .clone()
+            .filter(|_| !explicit_slot)
             .or_else(|| Some(save_slot.save_path.clone())),
         quicksave_reference: dispatcher_job
             .quicksave_reference
             .clone()
+            .filter(|_| !explicit_slot)
             .or_else(|| Some(save_slot.save_path.clone())),
     };
     let snapshot = snapshot::snapshot_refresh(app, pool, snapshot_input).await?;
@@ -295,6 +370,10 @@ pub async fn prepare_job_link(
             &link_id,
         );
         patch.cargo = format!("cargo.{}", cargo_resolution.resolved_cargo_token.clone());
+        if dispatcher_job.save_trailer_definition.is_some() {
+            patch.trailer_variant = dispatcher_job.save_trailer_variant.clone();
+            patch.trailer_definition = dispatcher_job.save_trailer_definition.clone();
+        }
         patch.target = format!(
             "{}.{}",
             sii_token(&resolved.resolved_dst_company_token),
@@ -304,7 +383,7 @@ pub async fn prepare_job_link(
         let now = Utc::now().to_rfc3339();
         let patch_json = serde_json::to_string(&patch)
             .map_err(|error| AppError::new(AppErrorCode::WriteFailed, error.to_string()))?;
-        let template = build_dispatcher_save_offer_template(
+        let mut template = build_dispatcher_save_offer_template(
             &dispatcher_job,
             &resolved,
             &cargo_resolution,
@@ -313,6 +392,11 @@ pub async fn prepare_job_link(
             &patch,
             &snapshot,
         );
+        if explicit_slot {
+            template.save_reference = Some(save_slot.save_path.clone());
+            template.quicksave_reference = Some(save_slot.save_path.clone());
+            template.save_session_id = Some(save_session_id.clone());
+        }
         let template_json = serde_json::to_string(&template)
             .map_err(|error| AppError::new(AppErrorCode::WriteFailed, error.to_string()))?;
         let previous_status = upsert_prepared_job_link(
@@ -353,6 +437,7 @@ pub async fn prepare_job_link(
                 planned_reward: dispatcher_job.total_reward,
                 patch_json: &patch_json,
                 save_offer_template_json: &template_json,
+                clone_source_slot,
                 now: &now,
             },
         )
@@ -525,13 +610,23 @@ pub async fn ets_get_job_link_status(
     load_job_link_by_vtc_job_id(&mut connection, vtc_job_id).await
 }
 
+/// game.sii of the save slot a link was prepared into.
+pub async fn job_link_game_sii_path(
+    pool: &SqlitePool,
+    link: &EtsJobLink,
+) -> Result<std::path::PathBuf, AppError> {
+    let mut connection = pool.acquire().await?;
+    let save_slot = load_save_slot(&mut connection, &link.save_id).await?;
+    Ok(std::path::PathBuf::from(save_slot.game_sii_path))
+}
+
 pub async fn handle_telemetry_job_event(
     app: &AppHandle,
     pool: &SqlitePool,
     payload: &TelemetryJobEventPayload,
-) -> Result<(), AppError> {
+) -> Result<Option<EtsJobLink>, AppError> {
     if !payload.on_job && !payload.job_finished && !payload.job_delivered {
-        return Ok(());
+        return Ok(None);
     }
 
     let mut connection = begin_immediate(pool).await?;
@@ -624,6 +719,8 @@ pub async fn handle_telemetry_job_event(
             .await?;
             set_dispatcher_link_status(&mut connection, &link.vtc_job_id, EtsJobLinkStatus::Synced)
                 .await?;
+            stamp_dispatcher_job_deadline(&mut connection, &link.vtc_job_id, payload.game_time_min)
+                .await?;
         }
 
         load_job_link_by_id(&mut connection, &link.link_id)
@@ -632,11 +729,12 @@ pub async fn handle_telemetry_job_event(
     }
     .await;
 
-    if let Some(link) = complete_transaction(&mut connection, result).await? {
-        emit_job_link_events(Some(app), &link);
+    let link = complete_transaction(&mut connection, result).await?;
+    if let Some(link) = &link {
+        emit_job_link_events(Some(app), link);
     }
 
-    Ok(())
+    Ok(link)
 }
 
 async fn begin_immediate(
@@ -714,6 +812,7 @@ struct PreparedJobLinkUpsert<'a> {
     planned_reward: i64,
     patch_json: &'a str,
     save_offer_template_json: &'a str,
+    clone_source_slot: Option<&'a str>,
     now: &'a str,
 }
 
@@ -784,6 +883,7 @@ async fn upsert_prepared_job_link(
                 error_code = NULL,
                 error_message = NULL,
                 updated_at_utc = ?32,
+                clone_source_slot = ?33,
                 written_at_utc = NULL,
                 requires_load_at_utc = NULL,
                 synced_at_utc = NULL,
@@ -827,6 +927,7 @@ async fn upsert_prepared_job_link(
         .bind(input.save_offer_template_json)
         .bind(EtsJobLinkStatus::Prepared.as_db())
         .bind(input.now)
+        .bind(input.clone_source_slot)
         .execute(&mut **connection)
         .await?;
         return Ok(Some(link.status));
@@ -868,10 +969,11 @@ async fn upsert_prepared_job_link(
             save_offer_template_json,
             status,
             created_at_utc,
-            updated_at_utc
+            updated_at_utc,
+            clone_source_slot
         )
         VALUES (
-            ?1, ?2, ?3, ?4, ?5, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30, ?31, ?32, ?33
+            ?1, ?2, ?3, ?4, ?5, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30, ?31, ?32, ?33, ?34
         )
         "#,
     )
@@ -908,6 +1010,7 @@ async fn upsert_prepared_job_link(
     .bind(EtsJobLinkStatus::Prepared.as_db())
     .bind(input.now)
     .bind(input.now)
+    .bind(input.clone_source_slot)
     .execute(&mut **connection)
     .await?;
 
@@ -965,6 +1068,34 @@ async fn resolve_prepare_save_mapping(
             .collect(),
     };
     let available_blocks = all_depots;
+
+    // Jobs generated from the save carry the exact depot tokens of the offer they came from.
+    if let (Some(src_company), Some(src_city), Some(dst_company), Some(dst_city)) = (
+        dispatcher_job.save_src_company.as_deref(),
+        dispatcher_job.save_src_city.as_deref(),
+        dispatcher_job.save_dst_company.as_deref(),
+        dispatcher_job.save_dst_city.as_deref(),
+    ) {
+        let source_has_offers = available_blocks.iter().any(|block| {
+            block.company_token == src_company
+                && block.city_token == src_city
+                && block.job_offer_count > 0
+        });
+        let destination_exists = available_blocks
+            .iter()
+            .any(|block| block.company_token == dst_company && block.city_token == dst_city);
+        if source_has_offers && destination_exists {
+            return Ok(PrepareSaveMapping {
+                resolved_src_company_token: src_company.to_string(),
+                resolved_src_city_token: src_city.to_string(),
+                resolved_dst_company_token: dst_company.to_string(),
+                resolved_dst_city_token: dst_city.to_string(),
+                resolution_mode: "save_offer".to_string(),
+                candidate_cities: Vec::new(),
+            });
+        }
+    }
+
     let source_city_resolution = resolve_city_token(&requested_city_token, &depot_index);
     let resolved_requested_city_token = source_city_resolution
         .as_ref()
@@ -1169,6 +1300,39 @@ fn resolve_prepare_cargo_token(
     snapshot_tokens.sort();
     snapshot_tokens.dedup();
 
+    // The cargo of a save offer is only kept while the save still knows it; otherwise
+    // (e.g. after removing the mod that added it) the category resolution below applies.
+    if let Some(save_cargo) = dispatcher_job
+        .save_cargo
+        .as_deref()
+        .map(sii_token)
+        .filter(|token| !token.is_empty())
+    {
+        if snapshot_tokens.is_empty() {
+            return Ok(CargoResolution {
+                requested_cargo_token: save_cargo.clone(),
+                resolved_cargo_token: save_cargo,
+                cargo_resolution_mode: "save_offer".to_string(),
+                cargo_validation_source: "snapshot_unavailable".to_string(),
+                cargo_valid_for_snapshot: false,
+            });
+        }
+        if snapshot_tokens.contains(&save_cargo) {
+            return Ok(CargoResolution {
+                requested_cargo_token: save_cargo.clone(),
+                resolved_cargo_token: save_cargo,
+                cargo_resolution_mode: "save_offer".to_string(),
+                cargo_validation_source: "ets_save_transport_cargo".to_string(),
+                cargo_valid_for_snapshot: true,
+            });
+        }
+        crate::dev_log!(
+            "[ets2save] save cargo {} of job {} is not in the snapshot, resolving by category",
+            save_cargo,
+            dispatcher_job.vtc_job_id
+        );
+    }
+
     if snapshot_tokens.contains(&requested) {
         return Ok(CargoResolution {
             requested_cargo_token: requested.clone(),
@@ -1663,7 +1827,14 @@ async fn load_vtc_dispatcher_job(
             route_reference,
             status AS dispatcher_status,
             last_error_code,
-            last_error_message
+            last_error_message,
+            save_src_company,
+            save_src_city,
+            save_dst_company,
+            save_dst_city,
+            save_cargo,
+            save_trailer_variant,
+            save_trailer_definition
         FROM dispatcher_jobs
         WHERE id = ?1
         "#,
@@ -1798,6 +1969,7 @@ fn map_job_link_row(row: &sqlx::sqlite::SqliteRow) -> Result<EtsJobLink, AppErro
         planned_reward: row.get("planned_reward"),
         patch,
         save_offer_template,
+        clone_source_slot: row.try_get("clone_source_slot").ok().flatten(),
         status: parse_status(&status_raw),
         error_code: row.try_get("error_code").ok(),
         error_message: row.try_get("error_message").ok(),
@@ -1893,6 +2065,34 @@ fn dispatcher_status_for_link_status(status: EtsJobLinkStatus) -> &'static str {
     }
 }
 
+/// Fixes the in-game minute a job is due once the game reports it as started: the contract
+/// deadline when one applies, otherwise the dispatcher's estimated duration.
+async fn stamp_dispatcher_job_deadline(
+    connection: &mut sqlx::pool::PoolConnection<Sqlite>,
+    vtc_job_id: &str,
+    game_time_min: u32,
+) -> Result<(), AppError> {
+    if game_time_min == 0 {
+        return Ok(());
+    }
+    sqlx::query(
+        r#"
+        UPDATE dispatcher_jobs
+        SET deadline_game_min = ?2 + COALESCE(
+            contract_deadline_hours * 60,
+            NULLIF(estimated_duration_minutes, 0)
+        )
+        WHERE id = ?1
+          AND deadline_game_min IS NULL
+        "#,
+    )
+    .bind(vtc_job_id)
+    .bind(i64::from(game_time_min))
+    .execute(&mut **connection)
+    .await?;
+    Ok(())
+}
+
 async fn mark_dispatcher_job_completed(
     connection: &mut sqlx::pool::PoolConnection<Sqlite>,
     vtc_job_id: &str,
@@ -2115,9 +2315,12 @@ fn emit_job_link_events(app: Option<&AppHandle>, link: &EtsJobLink) {
 
 #[cfg(test)]
 mod tests {
-    use super::{create_pool, resolve_prepare_save_mapping};
+    use super::{
+        create_pool, resolve_prepare_cargo_token, resolve_prepare_save_mapping,
+        reusable_clone_target,
+    };
     use crate::features::ets2save::errors::AppErrorCode;
-    use crate::features::ets2save::models::VtcDispatcherJob;
+    use crate::features::ets2save::models::{EtsJobOfferPatch, EtsSaveTarget, VtcDispatcherJob};
     use crate::features::ets2save::snapshot::{SaveSnapshotDepotDto, SaveSnapshotDto};
 
     fn fixture_dispatcher_job() -> VtcDispatcherJob {
@@ -2151,6 +2354,13 @@ mod tests {
             dispatcher_status: Some("assigned_to_save".to_string()),
             last_error_code: None,
             last_error_message: None,
+            save_src_company: None,
+            save_src_city: None,
+            save_dst_company: None,
+            save_dst_city: None,
+            save_cargo: None,
+            save_trailer_variant: None,
+            save_trailer_definition: None,
         }
     }
 
@@ -2212,6 +2422,88 @@ mod tests {
         });
     }
 
+    #[test]
+    fn a_clone_is_reused_only_for_the_same_source_slot() {
+        tauri::async_runtime::block_on(async {
+            let root =
+                std::env::temp_dir().join(format!("ets_clone_reuse_{}", uuid::Uuid::new_v4()));
+            let clone_dir = root.join("save").join("3");
+            std::fs::create_dir_all(&clone_dir).unwrap();
+            std::fs::write(clone_dir.join("game.sii"), "SiiNunit\n{\n}\n").unwrap();
+            std::fs::write(
+                clone_dir.join("info.sii"),
+                "SiiNunit\n{\nsave_container : _nameless.1 {\n name: \"Tour (Dispatcher)\"\n}\n}\n",
+            )
+            .unwrap();
+
+            let patch = EtsJobOfferPatch {
+                target: "tradeaux.hamburg".to_string(),
+                expiration_time: 0,
+                urgency: 0,
+                shortest_distance_km: 520,
+                ferry_time: 0,
+                ferry_price: 0,
+                cargo: "cargo.trucks".to_string(),
+                company_truck: false,
+                trailer_variant: None,
+                trailer_definition: None,
+                units_count: 1,
+                fill_ratio: 1,
+                trailer_place: 0,
+                job_info_unit: None,
+                selected_job_unit: None,
+                company_trailer_pointer: None,
+                company_truck_pointer: None,
+            };
+            let pool = create_pool(&root.join("links.sqlite")).await.unwrap();
+            sqlx::query("INSERT INTO ets_profiles (profile_id, profile_path, game, steam_cloud_enabled, created_at_utc, updated_at_utc) VALUES ('profile-1', ?1, 'ets2', 0, 'now', 'now')")
+                .bind(root.display().to_string())
+                .execute(&pool)
+                .await
+                .unwrap();
+            sqlx::query("INSERT INTO ets_saves (save_id, profile_id, slot_name, save_path, game_sii_path, is_quicksave, modified_at_utc, created_at_utc, updated_at_utc, last_loaded_at_utc) VALUES ('save-3', 'profile-1', '3', ?1, ?2, 0, 'now', 'now', 'now', 'now')")
+                .bind(clone_dir.display().to_string())
+                .bind(clone_dir.join("game.sii").display().to_string())
+                .execute(&pool)
+                .await
+                .unwrap();
+            sqlx::query("INSERT INTO ets_job_links (link_id, profile_id, save_id, vtc_job_id, src_company, src_city, dst_company, dst_city, cargo_id, distance_km, planned_reward, patch_json, status, created_at_utc, updated_at_utc, clone_source_slot) VALUES ('link-1', 'profile-1', 'save-3', 'vtc-1', 'tradeaux', 'berlin', 'tradeaux', 'hamburg', 'cargo_trucks', 520, 12000, ?1, 'error', 'now', 'now', 'quicksave')")
+                .bind(serde_json::to_string(&patch).unwrap())
+                .execute(&pool)
+                .await
+                .unwrap();
+
+            let clone_of = |slot: Option<&str>| EtsSaveTarget {
+                slot: slot.map(str::to_string),
+                clone_to_new_slot: true,
+            };
+            let reused = reusable_clone_target(&pool, "vtc-1", &clone_of(None))
+                .await
+                .unwrap();
+            assert_eq!(reused.slot.as_deref(), Some("3"));
+            assert!(!reused.clone_to_new_slot);
+            assert_eq!(
+                reusable_clone_target(&pool, "vtc-1", &clone_of(Some("quicksave")))
+                    .await
+                    .unwrap()
+                    .slot
+                    .as_deref(),
+                Some("3")
+            );
+
+            let other_source = clone_of(Some("autosave"));
+            assert_eq!(
+                reusable_clone_target(&pool, "vtc-1", &other_source)
+                    .await
+                    .unwrap(),
+                other_source
+            );
+
+            pool.close().await;
+            let _ = std::fs::remove_dir_all(&root);
+        });
+    }
+
     #[test]
     fn host_selection_skips_offerless_depots() {
         tauri::async_runtime::block_on(async {
@@ -2245,6 +2537,62 @@ mod tests {
         });
     }
 
+    #[test]
+    fn save_generated_jobs_keep_their_offer_depots() {
+        tauri::async_runtime::block_on(async {
+            let db_path =
+                std::env::temp_dir().join(format!("ets_job_links_{}.sqlite", uuid::Uuid::new_v4()));
+            let pool = create_pool(&db_path).await.unwrap();
+            let mut connection = pool.acquire().await.unwrap();
+            let source = SaveSnapshotDepotDto {
+                company_token: "posped".to_string(),
+                city_token: "berlin".to_string(),
+                depot_key: "company.volatile.posped.berlin".to_string(),
+                discovered: true,
+                job_offer_count: 3,
+            };
+            let destination = SaveSnapshotDepotDto {
+                company_token: "tradeaux".to_string(),
+                city_token: "hamburg".to_string(),
+                depot_key: "company.volatile.tradeaux.hamburg".to_string(),
+                discovered: true,
+                job_offer_count: 0,
+            };
+            let mut job = fixture_dispatcher_job();
+            job.save_src_company = Some("posped".to_string());
+            job.save_src_city = Some("berlin".to_string());
+            job.save_dst_company = Some("tradeaux".to_string());
+            job.save_dst_city = Some("hamburg".to_string());
+
+            let snapshot = fixture_snapshot(vec![source.clone(), destination]);
+            let resolved = resolve_prepare_save_mapping(&mut connection, &job, &snapshot)
+                .await
+                .unwrap();
+            assert_eq!(resolved.resolution_mode, "save_offer");
+            assert_eq!(resolved.resolved_src_company_token, "posped");
+            assert_eq!(resolved.resolved_dst_city_token, "hamburg");
+
+            // Without the target depot in the save the offer tokens are not trusted.
+            let snapshot = fixture_snapshot(vec![source]);
+            let resolved = resolve_prepare_save_mapping(&mut connection, &job, &snapshot).await;
+            assert!(!matches!(resolved, Ok(mapping) if mapping.resolution_mode == "save_offer"));
+        });
+    }
+
+    #[test]
+    fn save_cargo_must_be_known_to_the_snapshot() {
+        let mut job = fixture_dispatcher_job();
+        job.save_cargo = Some("apples".to_string());
+
+        let known = resolve_prepare_cargo_token(&job, &["apples".to_string()]).unwrap();
+        assert_eq!(known.cargo_resolution_mode, "save_offer");
+        assert!(known.cargo_valid_for_snapshot);
+
+        let unknown = resolve_prepare_cargo_token(&job, &["trucks".to_string()]).unwrap();
+        assert_ne!(unknown.cargo_resolution_mode, "save_offer");
+        assert_eq!(unknown.resolved_cargo_token, "trucks");
+    }
+
     #[test]
     fn host_selection_returns_actionable_error_when_city_has_no_offers() {
         tauri::async_runtime::block_on(async {
@@ -5,8 +5,12 @@ use chrono::{DateTime, Utc};
 use sqlx::SqlitePool;
 
 use crate::features::ets2save::errors::{AppError, AppErrorCode};
-use crate::features::ets2save::models::{EtsProfile, EtsSaveSlot};
-use crate::shared::paths::{ats_base_path, ets2_base_path, quicksave_game_path};
+use crate::features::ets2save::models::{EtsProfile, EtsSaveSlot, EtsSaveTarget};
+use crate::features::ets2save::sii_codec::{decode_sii_lines, write_lines_atomic};
+use crate::features::profile_clone::logic::copy_dir_recursive;
+use crate::features::profile_cloud::logic::{profile_roots, storage_of};
+use crate::shared::extract_save_name::extract_save_name;
+use crate::shared::paths::quicksave_game_path;
 use crate::state::AppProfileState;
 
 fn normalize_profile_id(profile_id: &str) -> String {
@@ -51,9 +55,8 @@ fn resolve_profile_candidate(profile_id: &str, state: &AppProfileState) -> Optio
         }
     }
 
-    let bases = [ets2_base_path(), ats_base_path()];
-    for base in bases.into_iter().flatten() {
-        for root in [base.join("profiles"), base.join("profiles.backup")] {
+    for game in ["ets2", "ats"] {
+        for root in profile_roots(game).into_iter().map(|root| root.path) {
             if !root.exists() {
                 continue;
             }
@@ -106,16 +109,18 @@ async fn upsert_profile(
             created_at_utc,
             updated_at_utc
         )
-        VALUES (?1, ?2, ?3, 0, ?4, ?4)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?5)
         ON CONFLICT(profile_id) DO UPDATE SET
             profile_path = excluded.profile_path,
             game = excluded.game,
+            steam_cloud_enabled = excluded.steam_cloud_enabled,
             updated_at_utc = excluded.updated_at_utc
         "#,
     )
     .bind(profile_id)
     .bind(profile_path)
     .bind(game)
+    .bind(storage_of(Path::new(profile_path)).is_cloud())
     .bind(&now)
     .execute(pool)
     .await?;
@@ -163,9 +168,11 @@ async fn upsert_save_slot(
             updated_at_utc,
             last_loaded_at_utc
         )
-        VALUES (?1, ?2, ?3, ?4, ?5, 1, ?6, ?7, ?7, ?7)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?8, ?8)
         ON CONFLICT(profile_id, save_path) DO UPDATE SET
+            slot_name = excluded.slot_name,
             game_sii_path = excluded.game_sii_path,
+            is_quicksave = excluded.is_quicksave,
             modified_at_utc = excluded.modified_at_utc,
             updated_at_utc = excluded.updated_at_utc,
             last_loaded_at_utc = excluded.last_loaded_at_utc
@@ -176,6 +183,7 @@ async fn upsert_save_slot(
     .bind(slot_name)
     .bind(save_path)
     .bind(game_sii_path)
+    .bind(slot_name.eq_ignore_ascii_case("quicksave"))
     .bind(modified_at_utc)
     .bind(&now)
     .execute(pool)
@@ -205,10 +213,112 @@ async fn upsert_save_slot(
     .map_err(Into::into)
 }
 
+fn validate_slot_name(slot: &str) -> Result<&str, AppError> {
+    let slot = slot.trim();
+    if slot.is_empty() || slot == "." || slot == ".." || slot.contains(['/', '\\']) {
+        return Err(AppError::new(
+            AppErrorCode::SaveNotFound,
+            format!("Invalid save slot: {}", slot),
+        ));
+    }
+    Ok(slot)
+}
+
+const CLONED_SAVE_NAME_SUFFIX: &str = " (Dispatcher)";
+
+fn next_free_slot(save_root: &Path) -> String {
+    (1u32..)
+        .map(|index| index.to_string())
+        .find(|candidate| !save_root.join(candidate).exists())
+        .unwrap_or_default()
+}
+
+/// Copies `source_slot` into the next free numbered slot and returns its name.
+/// The clone gets its own name in info.sii so the load menu tells it apart
+/// from the source.
+pub fn clone_save_to_new_slot(profile_path: &str, source_slot: &str) -> Result<String, AppError> {
+    let save_root = Path::new(profile_path).join("save");
+    let source = save_root.join(validate_slot_name(source_slot)?);
+    if !source.join("game.sii").is_file() {
+        return Err(AppError::new(
+            AppErrorCode::SaveNotFound,
+            format!("Save slot {} has no game.sii", source_slot),
+        ));
+    }
+
+    let new_slot = next_free_slot(&save_root);
+    let target = save_root.join(&new_slot);
+    if let Err(error) = copy_dir_recursive(&source, &target) {
+        let _ = fs::remove_dir_all(&target);
+        return Err(AppError::new(
+            AppErrorCode::WriteFailed,
+            format!("Cloning save {} failed: {}", source_slot, error),
+        ));
+    }
+    if let Err(error) = rename_cloned_save(&target, source_slot) {
+        let _ = fs::remove_dir_all(&target);
+        return Err(error);
+    }
+    Ok(new_slot)
+}
+
+/// Whether `save_dir` was created by `clone_save_to_new_slot`.
+pub fn is_cloned_save(save_dir: &Path) -> bool {
+    decode_sii_lines(&save_dir.join("info.sii"))
+        .ok()
+        .and_then(|lines| {
+            lines
+                .iter()
+                .find(|line| line.trim_start().starts_with("name:"))
+                .and_then(|line| extract_save_name(line))
+        })
+        .is_some_and(|name| name.ends_with(CLONED_SAVE_NAME_SUFFIX))
+}
+
+/// Rewrites the `name` attribute of the clone's info.sii.
+fn rename_cloned_save(target: &Path, source_slot: &str) -> Result<(), AppError> {
+    let info_path = target.join("info.sii");
+    if !info_path.is_file() {
+        return Ok(());
+    }
+    let mut lines = decode_sii_lines(&info_path)?;
+    let name_line = lines
+        .iter_mut()
+        .find(|line| line.trim_start().starts_with("name:"))
+        .ok_or_else(|| {
+            AppError::new(
+                AppErrorCode::DecodeFailed,
+                format!("No save name in {}", info_path.display()),
+            )
+        })?;
+    // Quicksaves carry an empty name (`name: ""`).
+    let base = extract_save_name(name_line)
+        .map(|name| name.trim_matches('"').to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| source_slot.to_string());
+    let indent = &name_line[..name_line.len() - name_line.trim_start().len()];
+    let name = format!("{}{}", base, CLONED_SAVE_NAME_SUFFIX)
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"");
+    *name_line = format!("{}name: \"{}\"", indent, name);
+    write_lines_atomic(&info_path, &lines)
+}
+
 pub async fn resolve_last_quicksave(
     pool: &SqlitePool,
     profile_id: &str,
     state: &AppProfileState,
+) -> Result<(EtsProfile, EtsSaveSlot), AppError> {
+    resolve_save_slot(pool, profile_id, state, &EtsSaveTarget::default()).await
+}
+
+/// Resolves the save a job is written to. Without an explicit slot this is the
+/// quicksave of the active profile (or the active save if it is a quicksave).
+pub async fn resolve_save_slot(
+    pool: &SqlitePool,
+    profile_id: &str,
+    state: &AppProfileState,
+    target: &EtsSaveTarget,
 ) -> Result<(EtsProfile, EtsSaveSlot), AppError> {
     let profile_path = resolve_profile_candidate(profile_id, state).ok_or_else(|| {
         AppError::new(
@@ -232,42 +342,60 @@ pub async fn resolve_last_quicksave(
     )
     .await?;
 
-    let quicksave_dir = state
-        .current_save
-        .lock()
-        .ok()
-        .and_then(|guard| guard.clone())
-        .filter(|save_path| normalize_profile_id(save_path).contains("quicksave"))
-        .map(PathBuf::from)
-        .unwrap_or_else(|| quicksave_game_path(&profile_path));
+    let requested_slot = target
+        .slot
+        .as_deref()
+        .map(validate_slot_name)
+        .transpose()?
+        .map(str::to_string);
+    let slot_name = match target.clone_source_slot() {
+        Some(source) => Some(clone_save_to_new_slot(&profile_path, source)?),
+        None => requested_slot,
+    };
+
+    let save_dir = match slot_name.as_deref() {
+        Some(slot) => Path::new(&profile_path).join("save").join(slot),
+        None => state
+            .current_save
+            .lock()
+            .ok()
+            .and_then(|guard| guard.clone())
+            .filter(|save_path| normalize_profile_id(save_path).contains("quicksave"))
+            .map(PathBuf::from)
+            .unwrap_or_else(|| quicksave_game_path(&profile_path)),
+    };
+    let slot_label = slot_name.as_deref().unwrap_or("quicksave");
 
-    let game_sii_path = if quicksave_dir.is_dir() {
-        quicksave_dir.join("game.sii")
+    let game_sii_path = if save_dir.is_dir() {
+        save_dir.join("game.sii")
     } else {
-        quicksave_dir
+        save_dir
     };
 
     if !game_sii_path.exists() {
         return Err(AppError::new(
             AppErrorCode::SaveNotFound,
-            format!("Quicksave not found for profile {}", profile.profile_id),
+            format!(
+                "Save {} not found for profile {}",
+                slot_label, profile.profile_id
+            ),
         ));
     }
 
     let metadata = fs::metadata(&game_sii_path).map_err(|error| {
         AppError::new(
             AppErrorCode::SaveNotFound,
-            format!("Quicksave metadata unavailable: {}", error),
+            format!("Save metadata unavailable: {}", error),
         )
     })?;
     let modified_at_utc = timestamp_to_rfc3339(&metadata);
     let save_folder = game_sii_path
         .parent()
-        .ok_or_else(|| AppError::new(AppErrorCode::SaveNotFound, "Invalid quicksave path"))?;
+        .ok_or_else(|| AppError::new(AppErrorCode::SaveNotFound, "Invalid save path"))?;
     let save_slot = upsert_save_slot(
         pool,
         &profile.profile_id,
-        "quicksave",
+        slot_label,
         &normalize_profile_id(&save_folder.display().to_string()),
         &normalize_profile_id(&game_sii_path.display().to_string()),
         &modified_at_utc,
@@ -28,6 +28,26 @@ pub struct EtsSaveSlot {
     pub last_loaded_at_utc: Option<String>,
 }
 
+/// Save slot a job is injected into. Without `slot` the quicksave is used;
+/// `clone_to_new_slot` copies the chosen save into the next free numbered slot
+/// first, so the original save stays untouched.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct EtsSaveTarget {
+    #[serde(default)]
+    pub slot: Option<String>,
+    #[serde(default)]
+    pub clone_to_new_slot: bool,
+}
+
+impl EtsSaveTarget {
+    /// The slot a clone is copied from; the quicksave unless a slot is given.
+    pub fn clone_source_slot(&self) -> Option<&str> {
+        self.clone_to_new_slot
+            .then(|| self.slot.as_deref().unwrap_or("quicksave"))
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
 #[sqlx(type_name = "TEXT", rename_all = "snake_case")]
 #[serde(rename_all = "snake_case")]
@@ -82,6 +102,8 @@ pub struct EtsJobLink {
     pub planned_reward: i64,
     pub patch: EtsJobOfferPatch,
     pub save_offer_template: Option<DispatcherSaveOfferTemplate>,
+    /// Slot the job's save was cloned from, when it was prepared into a clone.
+    pub clone_source_slot: Option<String>,
     pub status: EtsJobLinkStatus,
     pub error_code: Option<String>,
     pub error_message: Option<String>,
@@ -291,6 +313,14 @@ pub struct VtcDispatcherJob {
     pub dispatcher_status: Option<String>,
     pub last_error_code: Option<String>,
     pub last_error_message: Option<String>,
+    /// Exact save tokens for jobs generated from the offers of the save.
+    pub save_src_company: Option<String>,
+    pub save_src_city: Option<String>,
+    pub save_dst_company: Option<String>,
+    pub save_dst_city: Option<String>,
+    pub save_cargo: Option<String>,
+    pub save_trailer_variant: Option<String>,
+    pub save_trailer_definition: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -0,0 +1,716 @@
+//! Service intervals, breakdowns and workshop visits of career fleet assets.
+//!
+//! Condition drops with every trip in [`super::apply_trip_wear`], which also
+//! rolls for a breakdown from [`breakdown_probability`]. Real damage flows in
+//! from two places: cargo damage reported by telemetry during the trip, and
+//! the component wear of the player truck in the loaded save
+//! ([`reconcile_save_wear`]). Workshop visits are scheduled on bank days and
+//! paid through the bank ledger once the calendar reaches them. Visits only
+//! repair the career asset; the save itself is never written here.
+
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension, params};
+use serde::Serialize;
+
+use crate::features::bank::{self, ledger};
+use crate::features::economy;
+use crate::features::events;
+use crate::models::trucks::ParsedTruck;
+use crate::shared::sqlite_schema::create_indexes;
+
+pub const TRUCK_SERVICE_INTERVAL_KM: f64 = 30_000.0;
+pub const TRAILER_SERVICE_INTERVAL_KM: f64 = 45_000.0;
+/// Condition below which a repair visit is scheduled automatically.
+pub const REPAIR_THRESHOLD_PERCENT: f64 = 70.0;
+/// Workshop prices are expressed in kilometres of company running cost, so
+/// they follow diesel, toll and insurance prices of [`economy::EconomyState`].
+const RATE_REFERENCE_KM: f64 = 1_000.0;
+const RATE_REFERENCE_FUEL_LITERS_PER_100KM: f64 = 31.0;
+const TRUCK_SERVICE_KM: f64 = 1_050.0;
+const TRAILER_SERVICE_KM: f64 = 520.0;
+const TRUCK_REPAIR_KM_PER_POINT: f64 = 22.0;
+const TRAILER_REPAIR_KM_PER_POINT: f64 = 10.0;
+/// Towing is charged with the trip on which the breakdown happened.
+const BREAKDOWN_TOWING_KM: f64 = 1_500.0;
+/// Breakdown chance per 100 km of a freshly serviced asset.
+const BASE_HAZARD_PER_100KM: f64 = 0.0005;
+/// Condition points over which the hazard grows by a factor of e.
+const CONDITION_HAZARD_SCALE: f64 = 12.0;
+/// Kilometres past the service interval that add the base hazard again.
+const OVERDUE_HAZARD_KM: f64 = 5_000.0;
+const MAX_BREAKDOWN_PROBABILITY: f64 = 0.95;
+/// Bank days between scheduling a visit and the truck being in the workshop.
+const WORKSHOP_LEAD_DAYS: i64 = 1;
+/// Component weights of the save wear; the engine matters most.
+const SAVE_WEAR_WEIGHTS: [f64; 5] = [0.30, 0.20, 0.20, 0.15, 0.15];
+
+pub const VISIT_SERVICE: &str = "service";
+pub const VISIT_REPAIR: &str = "repair";
+pub const VISIT_BREAKDOWN: &str = "breakdown";
+
+const VISIT_SCHEDULED: &str = "scheduled";
+const VISIT_COMPLETED: &str = "completed";
+
+/// Workshop prices derived from the current running cost per kilometre.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WorkshopRates {
+    pub cost_per_km: f64,
+}
+
+impl WorkshopRates {
+    pub fn repair_cost_per_point(&self, kind: &str) -> f64 {
+        let km = if kind == "trailer" {
+            TRAILER_REPAIR_KM_PER_POINT
+        } else {
+            TRUCK_REPAIR_KM_PER_POINT
+        };
+        km * self.cost_per_km
+    }
+
+    pub fn service_cost(&self, kind: &str) -> i64 {
+        let km = if kind == "trailer" {
+            TRAILER_SERVICE_KM
+        } else {
+            TRUCK_SERVICE_KM
+        };
+        (km * self.cost_per_km).round() as i64
+    }
+
+    pub fn towing_cost(&self) -> i64 {
+        (BREAKDOWN_TOWING_KM * self.cost_per_km).round() as i64
+    }
+
+    /// Workshop bill for bringing an asset back to full condition, including
+    /// the regular service when it is due.
+    pub fn visit_cost(&self, kind: &str, condition_percent: f64, service_due_km: f64) -> i64 {
+        let repair = ((100.0 - condition_percent).max(0.0) * self.repair_cost_per_point(kind))
+            .round() as i64;
+        let service = if service_due_km <= 0.0 || repair == 0 {
+            self.service_cost(kind)
+        } else {
+            0
+        };
+        repair + service
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkshopVisit {
+    pub id: i64,
+    pub asset_id: String,
+    pub kind: String,
+    pub reason: String,
+    pub scheduled_bank_day: i64,
+    pub estimated_cost: i64,
+    pub actual_cost: Option<i64>,
+    pub status: String,
+    pub created_at_utc: String,
+    pub completed_at_utc: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceStatus {
+    pub asset_id: String,
+    pub kind: String,
+    pub condition_percent: f64,
+    pub odometer_km: f64,
+    pub service_interval_km: f64,
+    pub service_due_km: f64,
+    /// Breakdown chance over the next 100 km, in percent.
+    pub breakdown_risk_percent: f64,
+    pub breakdown_count: i64,
+    pub cargo_damage_percent: f64,
+    pub save_wear_percent: Option<f64>,
+    pub estimated_repair_cost: i64,
+    pub next_visit: Option<WorkshopVisit>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FleetWearReconciliation {
+    pub asset_id: String,
+    pub previous_condition: f64,
+    pub condition_percent: f64,
+    pub save_wear_percent: f64,
+    pub odometer_km: f64,
+    pub service_due_km: f64,
+    pub scheduled_visit: Option<WorkshopVisit>,
+}
+
+pub fn ensure_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS fleet_workshop_visits (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            asset_id TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            reason TEXT NOT NULL,
+            scheduled_bank_day INTEGER NOT NULL,
+            estimated_cost INTEGER NOT NULL,
+            actual_cost INTEGER,
+            status TEXT NOT NULL DEFAULT 'scheduled',
+            created_at_utc TEXT NOT NULL,
+            completed_at_utc TEXT
+        );
+        "#,
+    )
+    .map_err(|e| e.to_string())?;
+    create_indexes(
+        conn,
+        &[
+            "CREATE INDEX IF NOT EXISTS idx_fleet_workshop_visits_due ON fleet_workshop_visits(status, scheduled_bank_day)",
+        ],
+    )
+}
+
+/// Chance that an asset breaks down over `distance_km`.
+///
+/// The hazard per 100 km grows exponentially as the condition drops and
+/// rises by its base value for every 5,000 km the service is overdue.
+pub fn breakdown_probability(condition_percent: f64, service_due_km: f64, distance_km: f64) -> f64 {
+    if distance_km <= 0.0 {
+        return 0.0;
+    }
+    let wear = (100.0 - condition_percent).clamp(0.0, 100.0);
+    let overdue_km = (-service_due_km).max(0.0);
+    let hazard = (BASE_HAZARD_PER_100KM
+        * (wear / CONDITION_HAZARD_SCALE).exp()
+        * (1.0 + overdue_km / OVERDUE_HAZARD_KM))
+        .min(MAX_BREAKDOWN_PROBABILITY);
+    (1.0 - (1.0 - hazard).powf(distance_km / 100.0)).clamp(0.0, MAX_BREAKDOWN_PROBABILITY)
+}
+
+/// Loads the workshop prices from the running costs of a reference trip as
+/// estimated by [`economy::estimate_trip_costs`].
+pub fn workshop_rates(conn: &Connection) -> Result<WorkshopRates, String> {
+    let costs = economy::estimate_trip_costs(
+        conn,
+        RATE_REFERENCE_KM * RATE_REFERENCE_FUEL_LITERS_PER_100KM / 100.0,
+        RATE_REFERENCE_KM,
+        0,
+    )?;
+    Ok(WorkshopRates {
+        cost_per_km: costs.total_cost as f64 / RATE_REFERENCE_KM,
+    })
+}
+
+/// Uniform value in `[0, 1)` derived from `seed`.
+pub fn roll(seed: u64) -> f64 {
+    let mut hash = seed ^ 0x9E37_79B9_7F4A_7C15;
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xFF51_AFD7_ED55_8CCD);
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xC4CE_B9FE_1A85_EC53);
+    hash ^= hash >> 33;
+    (hash >> 11) as f64 / (1_u64 << 53) as f64
+}
+
+/// Schedules a workshop visit for `asset_id`, or returns the visit that is
+/// already scheduled for it.
+pub fn schedule_visit(
+    conn: &Connection,
+    asset_id: &str,
+    kind: &str,
+    reason: &str,
+    in_days: Option<i64>,
+) -> Result<WorkshopVisit, String> {
+    if let Some(existing) = pending_visit(conn, asset_id)? {
+        return Ok(existing);
+    }
+    let (asset_kind, condition, service_due): (String, f64, f64) = conn
+        .query_row(
+            "SELECT kind, condition_percent, service_due_km FROM fleet_assets WHERE asset_id = ?1",
+            params![asset_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Fleet asset {} not found", asset_id))?;
+    let bank_day = bank::load_state(conn)?.bank_day;
+    let estimated_cost = workshop_rates(conn)?.visit_cost(&asset_kind, condition, service_due);
+    conn.execute(
+        r#"
+        INSERT INTO fleet_workshop_visits (
+            asset_id, kind, reason, scheduled_bank_day, estimated_cost, status, created_at_utc
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+        "#,
+        params![
+            asset_id,
+            kind,
+            reason,
+            bank_day + in_days.unwrap_or(WORKSHOP_LEAD_DAYS).max(0),
+            estimated_cost,
+            VISIT_SCHEDULED,
+            Utc::now().to_rfc3339()
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    load_visit(conn, conn.last_insert_rowid())
+}
+
+/// Completes every visit scheduled up to `bank_day`.
+pub fn run_due_visits(conn: &Connection, bank_day: i64) -> Result<Vec<WorkshopVisit>, String> {
+    let due_ids = {
+        let mut stmt = conn
+            .prepare(
+                "SELECT id FROM fleet_workshop_visits WHERE status = ?1 AND scheduled_bank_day <= ?2 ORDER BY id",
+            )
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map(params![VISIT_SCHEDULED, bank_day], |row| {
+                row.get::<_, i64>(0)
+            })
+            .map_err(|e| e.to_string())?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?
+    };
+    due_ids
+        .into_iter()
+        .map(|visit_id| complete_visit(conn, visit_id))
+        .collect()
+}
+
+/// Repairs and services the asset and books the bill on the company account.
+///
+/// The repair reserve that trips already set aside for the asset is used
+/// first, so only the remainder is charged.
+pub fn complete_visit(conn: &Connection, visit_id: i64) -> Result<WorkshopVisit, String> {
+    let visit = load_visit(conn, visit_id)?;
+    if visit.status != VISIT_SCHEDULED {
+        return Err(format!(
+            "Workshop visit #{} is already {}",
+            visit_id, visit.status
+        ));
+    }
+    let (kind, condition, service_due, odometer, interval, reserve): (
+        String,
+        f64,
+        f64,
+        f64,
+        f64,
+        i64,
+    ) = conn
+        .query_row(
+            r#"
+            SELECT kind, condition_percent, service_due_km, odometer_km, service_interval_km,
+                   repair_reserve
+            FROM fleet_assets
+            WHERE asset_id = ?1
+            "#,
+            params![visit.asset_id],
+            |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                    row.get(5)?,
+                ))
+            },
+        )
+        .map_err(|e| e.to_string())?;
+    let actual_cost = workshop_rates(conn)?.visit_cost(&kind, condition, service_due);
+    let from_reserve = actual_cost.min(reserve.max(0));
+
+    if actual_cost > from_reserve {
+        ledger::post(
+            conn,
+            ledger::KIND_WORKSHOP,
+            from_reserve - actual_cost,
+            None,
+            &format!("Workshop {} of {}", visit.kind, visit.asset_id),
+        )?;
+    }
+    conn.execute(
+        r#"
+        UPDATE fleet_assets
+        SET
+            condition_percent = 100,
+            service_due_km = ?1,
+            last_service_odometer_km = ?2,
+            repair_reserve = repair_reserve - ?3
+        WHERE asset_id = ?4
+        "#,
+        params![interval, odometer, from_reserve, visit.asset_id],
+    )
+    .map_err(|e| e.to_string())?;
+    conn.execute(
+        r#"
+        UPDATE fleet_workshop_visits
+        SET status = ?1, actual_cost = ?2, completed_at_utc = ?3
+        WHERE id = ?4
+        "#,
+        params![
+            VISIT_COMPLETED,
+            actual_cost,
+            Utc::now().to_rfc3339(),
+            visit_id
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    events::record_event(
+        conn,
+        "fleet",
+        "Workshop visit completed",
+        &format!(
+            "{} left the workshop after a {} for EUR {}.",
+            visit.asset_id, visit.kind, actual_cost
+        ),
+        "low",
+    )?;
+    load_visit(conn, visit_id)
+}
+
+pub fn list_visits(
+    conn: &Connection,
+    include_completed: bool,
+    limit: usize,
+) -> Result<Vec<WorkshopVisit>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "{} WHERE ?1 OR status = ?2 ORDER BY id DESC LIMIT ?3",
+            VISIT_SELECT
+        ))
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(
+            params![include_completed, VISIT_SCHEDULED, limit as i64],
+            map_visit,
+        )
+        .map_err(|e| e.to_string())?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())
+}
+
+pub fn load_statuses(conn: &Connection) -> Result<Vec<MaintenanceStatus>, String> {
+    let rates = workshop_rates(conn)?;
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT asset_id, kind, condition_percent, odometer_km, service_interval_km,
+                   service_due_km, breakdown_count, cargo_damage_percent, save_wear_percent
+            FROM fleet_assets
+            ORDER BY kind DESC, asset_id
+            "#,
+        )
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, f64>(2)?,
+                row.get::<_, f64>(3)?,
+                row.get::<_, f64>(4)?,
+                row.get::<_, f64>(5)?,
+                row.get::<_, i64>(6)?,
+                row.get::<_, f64>(7)?,
+                row.get::<_, Option<f64>>(8)?,
+            ))
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    rows.into_iter()
+        .map(
+            |(
+                asset_id,
+                kind,
+                condition_percent,
+                odometer_km,
+                service_interval_km,
+                service_due_km,
+                breakdown_count,
+                cargo_damage_percent,
+                save_wear_percent,
+            )| {
+                Ok(MaintenanceStatus {
+                    next_visit: pending_visit(conn, &asset_id)?,
+                    breakdown_risk_percent: (breakdown_probability(
+                        condition_percent,
+                        service_due_km,
+                        100.0,
+                    ) * 10_000.0)
+                        .round()
+                        / 100.0,
+                    estimated_repair_cost: rates.visit_cost(
+                        &kind,
+                        condition_percent,
+                        service_due_km,
+                    ),
+                    asset_id,
+                    kind,
+                    condition_percent,
+                    odometer_km,
+                    service_interval_km,
+                    service_due_km,
+                    breakdown_count,
+                    cargo_damage_percent,
+                    save_wear_percent,
+                })
+            },
+        )
+        .collect()
+}
+
+/// Overwrites the player truck's career condition with the wear stored in
+/// the save, so repairs or damage done in game are reflected in costs.
+pub fn reconcile_save_wear(
+    conn: &Connection,
+    asset_id: &str,
+    truck: &ParsedTruck,
+) -> Result<FleetWearReconciliation, String> {
+    let (previous_condition, stored_odometer, reconciled_before): (f64, f64, bool) = conn
+        .query_row(
+            r#"
+            SELECT condition_percent, odometer_km, wear_reconciled_at_utc IS NOT NULL
+            FROM fleet_assets
+            WHERE asset_id = ?1
+            "#,
+            params![asset_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Fleet asset {} not found", asset_id))?;
+
+    let save_wear_percent = save_wear_percent(truck);
+    let condition_percent = ((100.0 - save_wear_percent) * 10.0).round() / 10.0;
+    let odometer_km = (truck.odometer as f64).max(stored_odometer);
+    // The first reconciliation only moves the odometer onto the save's
+    // scale; later ones count kilometres driven outside logbook trips.
+    let service_shift = if reconciled_before {
+        0.0
+    } else {
+        odometer_km - stored_odometer
+    };
+
+    conn.execute(
+        r#"
+        UPDATE fleet_assets
+        SET
+            condition_percent = ?1,
+            save_wear_percent = ?2,
+            last_service_odometer_km = last_service_odometer_km + ?3,
+            odometer_km = ?4,
+            service_due_km = last_service_odometer_km + ?3 + service_interval_km - ?4,
+            wear_reconciled_at_utc = ?5
+        WHERE asset_id = ?6
+        "#,
+        params![
+            condition_percent,
+            save_wear_percent,
+            service_shift,
+            odometer_km,
+            Utc::now().to_rfc3339(),
+            asset_id
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    let service_due_km: f64 = conn
+        .query_row(
+            "SELECT service_due_km FROM fleet_assets WHERE asset_id = ?1",
+            params![asset_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+
+    let scheduled_visit = if condition_percent < REPAIR_THRESHOLD_PERCENT {
+        Some(schedule_visit(
+            conn,
+            asset_id,
+            VISIT_REPAIR,
+            &format!("Save wear at {:.0}%", save_wear_percent),
+            None,
+        )?)
+    } else if service_due_km <= 0.0 {
+        Some(schedule_visit(
+            conn,
+            asset_id,
+            VISIT_SERVICE,
+            "Service interval reached",
+            None,
+        )?)
+    } else {
+        None
+    };
+
+    Ok(FleetWearReconciliation {
+        asset_id: asset_id.to_string(),
+        previous_condition,
+        condition_percent,
+        save_wear_percent,
+        odometer_km,
+        service_due_km,
+        scheduled_visit,
+    })
+}
+
+/// Weighted wear of a save truck in percent, including unfixable wear.
+pub fn save_wear_percent(truck: &ParsedTruck) -> f64 {
+    let wheels = if truck.wheels_wear.is_empty() {
+        0.0
+    } else {
+        truck
+            .wheels_wear
+            .iter()
+            .zip(
+                truck
+                    .wheels_wear_unfixable
+                    .iter()
+                    .chain(std::iter::repeat(&0.0)),
+            )
+            .map(|(wear, unfixable)| f64::from(wear + unfixable))
+            .sum::<f64>()
+            / truck.wheels_wear.len() as f64
+    };
+    let components = [
+        f64::from(truck.engine_wear + truck.engine_wear_unfixable),
+        f64::from(truck.transmission_wear + truck.transmission_wear_unfixable),
+        f64::from(truck.chassis_wear + truck.chassis_wear_unfixable),
+        f64::from(truck.cabin_wear + truck.cabin_wear_unfixable),
+        wheels,
+    ];
+    let wear = components
+        .iter()
+        .zip(SAVE_WEAR_WEIGHTS)
+        .map(|(wear, weight)| wear.clamp(0.0, 1.0) * weight)
+        .sum::<f64>();
+    (wear * 1_000.0).round() / 10.0
+}
+
+pub fn pending_visit(conn: &Connection, asset_id: &str) -> Result<Option<WorkshopVisit>, String> {
+    conn.query_row(
+        &format!(
+            "{} WHERE asset_id = ?1 AND status = ?2 ORDER BY scheduled_bank_day LIMIT 1",
+            VISIT_SELECT
+        ),
+        params![asset_id, VISIT_SCHEDULED],
+        map_visit,
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+fn load_visit(conn: &Connection, visit_id: i64) -> Result<WorkshopVisit, String> {
+    conn.query_row(
+        &format!("{} WHERE id = ?1", VISIT_SELECT),
+        params![visit_id],
+        map_visit,
+    )
+    .optional()
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| format!("Workshop visit #{} not found", visit_id))
+}
+
+const VISIT_SELECT: &str = r#"
+    SELECT id, asset_id, kind, reason, scheduled_bank_day, estimated_cost, actual_cost,
+           status, created_at_utc, completed_at_utc
+    FROM fleet_workshop_visits
+"#;
+
+fn map_visit(row: &rusqlite::Row<'_>) -> rusqlite::Result<WorkshopVisit> {
+    Ok(WorkshopVisit {
+        id: row.get(0)?,
+        asset_id: row.get(1)?,
+        kind: row.get(2)?,
+        reason: row.get(3)?,
+        scheduled_bank_day: row.get(4)?,
+        estimated_cost: row.get(5)?,
+        actual_cost: row.get(6)?,
+        status: row.get(7)?,
+        created_at_utc: row.get(8)?,
+        completed_at_utc: row.get(9)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::fleet::{self, PLAYER_TRUCK_ASSET_ID};
+    use crate::features::reputation;
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        reputation::ensure_tables(&conn).unwrap();
+        events::ensure_tables(&conn).unwrap();
+        bank::ensure_tables(&conn).unwrap();
+        economy::ensure_tables(&conn).unwrap();
+        fleet::ensure_tables(&conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn workshop_rates_follow_the_running_costs() {
+        let conn = setup();
+        let rates = workshop_rates(&conn).unwrap();
+        assert!(rates.cost_per_km > 0.0);
+        assert!(rates.service_cost("truck") > rates.service_cost("trailer"));
+
+        conn.execute(
+            "UPDATE economy_state SET diesel_price_per_liter = diesel_price_per_liter * 2",
+            [],
+        )
+        .unwrap();
+        let pricier = workshop_rates(&conn).unwrap();
+        assert!(pricier.towing_cost() > rates.towing_cost());
+        assert!(pricier.visit_cost("truck", 80.0, 100.0) > rates.visit_cost("truck", 80.0, 100.0));
+    }
+
+    #[test]
+    fn breakdown_probability_grows_with_wear_and_overdue_service() {
+        let fresh = breakdown_probability(100.0, 20_000.0, 500.0);
+        let worn = breakdown_probability(60.0, 20_000.0, 500.0);
+        let overdue = breakdown_probability(60.0, -10_000.0, 500.0);
+        assert!(fresh > 0.0 && fresh < 0.01);
+        assert!(worn > fresh);
+        assert!(overdue > worn * 2.0);
+        assert_eq!(breakdown_probability(60.0, 0.0, 0.0), 0.0);
+        assert!(breakdown_probability(0.0, -100_000.0, 5_000.0) <= MAX_BREAKDOWN_PROBABILITY);
+    }
+
+    #[test]
+    fn workshop_visit_uses_the_trip_reserve_before_charging_the_account() {
+        let conn = setup();
+        let outcome = fleet::apply_trip_wear(&conn, 1_500.0, 4, 20.0, u64::MAX).unwrap();
+        assert!(!outcome.breakdown);
+        assert!(outcome.repair_reserve > 0);
+
+        let visit =
+            schedule_visit(&conn, PLAYER_TRUCK_ASSET_ID, VISIT_REPAIR, "Test", Some(0)).unwrap();
+        let again =
+            schedule_visit(&conn, PLAYER_TRUCK_ASSET_ID, VISIT_SERVICE, "Test", None).unwrap();
+        assert_eq!(again.id, visit.id);
+
+        let reserve = || -> i64 {
+            conn.query_row(
+                "SELECT repair_reserve FROM fleet_assets WHERE asset_id = ?1",
+                params![PLAYER_TRUCK_ASSET_ID],
+                |row| row.get(0),
+            )
+            .unwrap()
+        };
+        let reserve_before = reserve();
+        assert!(reserve_before > 0);
+        let cash_before = bank::load_state(&conn).unwrap().cash_balance;
+        let done = run_due_visits(&conn, 0).unwrap();
+        assert_eq!(done.len(), 1);
+        assert_eq!(done[0].status, VISIT_COMPLETED);
+        let charged = cash_before - bank::load_state(&conn).unwrap().cash_balance;
+        let reserve_used = reserve_before - reserve();
+        assert_eq!(charged + reserve_used, done[0].actual_cost.unwrap());
+
+        let status = load_statuses(&conn)
+            .unwrap()
+            .into_iter()
+            .find(|status| status.asset_id == PLAYER_TRUCK_ASSET_ID)
+            .unwrap();
+        assert_eq!(status.condition_percent, 100.0);
+        assert_eq!(status.service_due_km, TRUCK_SERVICE_INTERVAL_KM);
+        assert!(status.next_visit.is_none());
+    }
+}
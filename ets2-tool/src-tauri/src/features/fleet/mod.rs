@@ -1,6 +1,13 @@
-use rusqlite::{Connection, params};
+pub mod maintenance;
+
+use rusqlite::{Connection, OptionalExtension, params};
 use serde::Serialize;
 
+use crate::features::fleet::maintenance::WorkshopVisit;
+use crate::shared::sqlite_schema::ensure_columns;
+
+pub const PLAYER_TRUCK_ASSET_ID: &str = "asset-player-truck";
+
 #[derive(Debug, Clone, Serialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct FleetAssetSummary {
@@ -26,11 +33,17 @@ pub struct FleetOverview {
     pub maintenance_risk: bool,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, PartialEq, Default)]
+#[derive(Debug, Clone, Serialize, PartialEq, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct WearOutcome {
     pub repair_reserve: i64,
     pub player_condition: f64,
+    pub breakdown_probability: f64,
+    pub breakdown: bool,
+    /// Towing cost included in `repair_reserve` when the truck broke down.
+    pub towing_cost: i64,
+    /// Workshop visit booked because of this trip, if none was booked yet.
+    pub scheduled_visit: Option<WorkshopVisit>,
 }
 
 pub fn ensure_tables(conn: &Connection) -> Result<(), String> {
@@ -129,7 +142,44 @@ pub fn ensure_tables(conn: &Connection) -> Result<(), String> {
         .map_err(|e| e.to_string())?;
     }
 
-    Ok(())
+    ensure_columns(
+        conn,
+        "fleet_assets",
+        &[
+            ("odometer_km", "REAL NOT NULL DEFAULT 0"),
+            ("service_interval_km", "REAL"),
+            ("last_service_odometer_km", "REAL"),
+            ("breakdown_count", "INTEGER NOT NULL DEFAULT 0"),
+            ("cargo_damage_percent", "REAL NOT NULL DEFAULT 0"),
+            ("repair_reserve", "INTEGER NOT NULL DEFAULT 0"),
+            ("save_wear_percent", "REAL"),
+            ("wear_reconciled_at_utc", "TEXT"),
+        ],
+    )?;
+    // Assets from before service intervals keep their remaining service km.
+    conn.execute(
+        r#"
+        UPDATE fleet_assets
+        SET service_interval_km = CASE kind WHEN 'trailer' THEN ?1 ELSE ?2 END
+        WHERE service_interval_km IS NULL
+        "#,
+        params![
+            maintenance::TRAILER_SERVICE_INTERVAL_KM,
+            maintenance::TRUCK_SERVICE_INTERVAL_KM
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    conn.execute(
+        r#"
+        UPDATE fleet_assets
+        SET last_service_odometer_km = odometer_km + service_due_km - service_interval_km
+        WHERE last_service_odometer_km IS NULL
+        "#,
+        [],
+    )
+    .map_err(|e| e.to_string())?;
+
+    maintenance::ensure_tables(conn)
 }
 
 pub fn load_assets(conn: &Connection, limit: usize) -> Result<Vec<FleetAssetSummary>, String> {
@@ -199,44 +249,175 @@ pub fn load_overview(conn: &Connection) -> Result<FleetOverview, String> {
     .map_err(|e| e.to_string())
 }
 
+/// Applies the wear of one trip to the player truck and its trailer.
+///
+/// Wear comes from distance, speeding and the cargo damage telemetry
+/// reported; the returned reserve is what that wear will cost in the
+/// workshop, plus towing if the trip ended in a breakdown.
 pub fn apply_trip_wear(
     conn: &Connection,
     distance_km: f64,
     speeding_events: i64,
+    cargo_damage_percent: f64,
+    seed: u64,
 ) -> Result<WearOutcome, String> {
+    let cargo_damage_percent = cargo_damage_percent.clamp(0.0, 100.0);
     let (current_condition, current_due): (f64, f64) = conn
         .query_row(
             r#"
             SELECT condition_percent, service_due_km
             FROM fleet_assets
-            WHERE asset_id = 'asset-player-truck'
+            WHERE asset_id = ?1
             "#,
-            [],
+            params![PLAYER_TRUCK_ASSET_ID],
             |row| Ok((row.get(0)?, row.get(1)?)),
         )
         .map_err(|e| e.to_string())?;
 
-    let wear = (distance_km * 0.0085) + (speeding_events as f64 * 0.55);
+    let breakdown_probability =
+        maintenance::breakdown_probability(current_condition, current_due, distance_km);
+    let breakdown = maintenance::roll(seed) < breakdown_probability;
+    let wear =
+        (distance_km * 0.0085) + (speeding_events as f64 * 0.55) + (cargo_damage_percent * 0.15);
     let next_condition = (current_condition - wear).clamp(45.0, 100.0);
-    let next_due = (current_due - distance_km).max(0.0);
-    let repair_reserve = if next_condition < 82.0 {
-        ((82.0 - next_condition) * 22.0).round() as i64
+    let rates = maintenance::workshop_rates(conn)?;
+    let truck_reserve = ((current_condition - next_condition)
+        * rates.repair_cost_per_point("truck"))
+    .round() as i64;
+    store_trip_wear(
+        conn,
+        PLAYER_TRUCK_ASSET_ID,
+        next_condition,
+        distance_km,
+        cargo_damage_percent,
+        truck_reserve,
+        breakdown,
+    )?;
+
+    let trailer_reserve = apply_trailer_wear(conn, &rates, distance_km, cargo_damage_percent)?;
+    let mut repair_reserve = truck_reserve + trailer_reserve;
+
+    let next_due: f64 = conn
+        .query_row(
+            "SELECT service_due_km FROM fleet_assets WHERE asset_id = ?1",
+            params![PLAYER_TRUCK_ASSET_ID],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+    let already_booked = maintenance::pending_visit(conn, PLAYER_TRUCK_ASSET_ID)?.is_some();
+    if breakdown {
+        repair_reserve += rates.towing_cost();
+    }
+    let scheduled_visit = if already_booked {
+        None
+    } else if breakdown {
+        Some(maintenance::schedule_visit(
+            conn,
+            PLAYER_TRUCK_ASSET_ID,
+            maintenance::VISIT_BREAKDOWN,
+            &format!("Broke down after {:.0} km", distance_km),
+            Some(0),
+        )?)
+    } else if next_condition < maintenance::REPAIR_THRESHOLD_PERCENT {
+        Some(maintenance::schedule_visit(
+            conn,
+            PLAYER_TRUCK_ASSET_ID,
+            maintenance::VISIT_REPAIR,
+            &format!("Condition dropped to {:.0}%", next_condition),
+            None,
+        )?)
+    } else if next_due <= 0.0 {
+        Some(maintenance::schedule_visit(
+            conn,
+            PLAYER_TRUCK_ASSET_ID,
+            maintenance::VISIT_SERVICE,
+            "Service interval reached",
+            None,
+        )?)
     } else {
-        0
+        None
+    };
+
+    Ok(WearOutcome {
+        repair_reserve,
+        player_condition: next_condition,
+        breakdown_probability,
+        breakdown,
+        towing_cost: if breakdown { rates.towing_cost() } else { 0 },
+        scheduled_visit,
+    })
+}
+
+fn apply_trailer_wear(
+    conn: &Connection,
+    rates: &maintenance::WorkshopRates,
+    distance_km: f64,
+    cargo_damage_percent: f64,
+) -> Result<i64, String> {
+    let trailer = conn
+        .query_row(
+            r#"
+            SELECT asset_id, condition_percent
+            FROM fleet_assets
+            WHERE kind = 'trailer'
+            ORDER BY CASE status WHEN 'player' THEN 0 WHEN 'ready' THEN 1 ELSE 2 END, asset_id
+            LIMIT 1
+            "#,
+            [],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, f64>(1)?)),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    let Some((asset_id, condition)) = trailer else {
+        return Ok(0);
     };
 
+    let wear = (distance_km * 0.006) + (cargo_damage_percent * 0.4);
+    let next_condition = (condition - wear).clamp(45.0, 100.0);
+    let reserve =
+        ((condition - next_condition) * rates.repair_cost_per_point("trailer")).round() as i64;
+    store_trip_wear(
+        conn,
+        &asset_id,
+        next_condition,
+        distance_km,
+        cargo_damage_percent,
+        reserve,
+        false,
+    )?;
+    Ok(reserve)
+}
+
+fn store_trip_wear(
+    conn: &Connection,
+    asset_id: &str,
+    condition_percent: f64,
+    distance_km: f64,
+    cargo_damage_percent: f64,
+    repair_reserve: i64,
+    breakdown: bool,
+) -> Result<(), String> {
     conn.execute(
         r#"
         UPDATE fleet_assets
-        SET condition_percent = ?1, service_due_km = ?2
-        WHERE asset_id = 'asset-player-truck'
+        SET
+            condition_percent = ?1,
+            odometer_km = odometer_km + ?2,
+            service_due_km = last_service_odometer_km + service_interval_km - (odometer_km + ?2),
+            cargo_damage_percent = ?3,
+            repair_reserve = repair_reserve + ?4,
+            breakdown_count = breakdown_count + ?5
+        WHERE asset_id = ?6
         "#,
-        params![next_condition, next_due],
+        params![
+            condition_percent,
+            distance_km.max(0.0),
+            cargo_damage_percent,
+            repair_reserve,
+            i64::from(breakdown),
+            asset_id
+        ],
     )
     .map_err(|e| e.to_string())?;
-
-    Ok(WearOutcome {
-        repair_reserve,
-        player_condition: next_condition,
-    })
+    Ok(())
 }
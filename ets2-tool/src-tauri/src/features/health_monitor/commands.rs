@@ -5,7 +5,9 @@ use crate::shared::current_profile::snapshot_resolved_save_context;
 use crate::shared::trace::TraceScope;
 use crate::state::{AppProfileState, DecryptCache, ProfileCache};
 
-use super::models::{SaveHealthFixResultDto, SaveHealthReportDto};
+use super::models::{
+    SaveHealthFixResultDto, SaveHealthReportDto, SaveSanitizePreviewDto, SaveSanitizeRequestDto,
+};
 use super::service;
 
 #[command]
@@ -85,3 +87,40 @@ pub fn apply_save_health_fix(
         decrypt_cache.inner(),
     )
 }
+
+#[command]
+pub async fn preview_save_mod_sanitizer(
+    request: Option<SaveSanitizeRequestDto>,
+    profile_state: State<'_, AppProfileState>,
+    decrypt_cache: State<'_, DecryptCache>,
+) -> Result<SaveSanitizePreviewDto, String> {
+    let mut trace = TraceScope::new("preview_save_mod_sanitizer");
+    let request = request.unwrap_or_default();
+    let result = service::preview_removed_mod_sanitizer(
+        &request,
+        profile_state.inner(),
+        decrypt_cache.inner(),
+    );
+    match result.as_ref() {
+        Ok(_) => trace.finish_ok(),
+        Err(error) => trace.finish_error(error),
+    }
+    result
+}
+
+#[command]
+pub fn apply_save_mod_sanitizer(
+    request: Option<SaveSanitizeRequestDto>,
+    confirmed: bool,
+    profile_state: State<'_, AppProfileState>,
+    profile_cache: State<'_, ProfileCache>,
+    decrypt_cache: State<'_, DecryptCache>,
+) -> Result<SaveHealthFixResultDto, String> {
+    service::apply_removed_mod_sanitizer(
+        &request.unwrap_or_default(),
+        confirmed,
+        profile_state.inner(),
+        profile_cache.inner(),
+        decrypt_cache.inner(),
+    )
+}
@@ -36,3 +36,56 @@ pub struct SaveHealthFixResultDto {
     pub applied: bool,
     pub message: String,
 }
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveSanitizeRequestDto {
+    /// Exact data paths or folder prefixes (ending in `/`) of removed mod content.
+    #[serde(default)]
+    pub removed_paths: Vec<String>,
+    #[serde(default)]
+    pub removed_cargo: Vec<String>,
+    #[serde(default)]
+    pub removed_trailer_definitions: Vec<String>,
+    #[serde(default)]
+    pub game_version: Option<String>,
+    /// Money credited to the player's bank for every owned truck the sanitizer removes.
+    #[serde(default)]
+    pub refund_per_truck: i64,
+    /// Money credited for every owned trailer the sanitizer removes.
+    #[serde(default)]
+    pub refund_per_trailer: i64,
+    /// `planHash` of the preview the user confirmed; applying fails if the plan differs.
+    #[serde(default)]
+    pub preview_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveSanitizeChangeDto {
+    pub action: String,
+    pub unit_class: String,
+    pub unit_id: String,
+    pub detail: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveSanitizePreviewDto {
+    pub save_path: String,
+    pub removed_paths: Vec<String>,
+    pub changes: Vec<SaveSanitizeChangeDto>,
+    pub replaced_accessories: usize,
+    pub removed_accessories: usize,
+    pub removed_trucks: usize,
+    pub removed_trailers: usize,
+    pub cleared_references: usize,
+    pub cleared_job_offers: usize,
+    pub refunded_money: i64,
+    pub warnings: Vec<String>,
+    pub can_apply: bool,
+    /// SHA-256 of the sanitized save content.
+    pub plan_hash: String,
+}
@@ -0,0 +1,847 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use sha2::{Digest, Sha256};
+
+use crate::features::ets2save::sii_codec::{join_lines, split_lines};
+use crate::features::truck_change::models::PowertrainCatalog;
+
+use super::models::{SaveSanitizeChangeDto, SaveSanitizePreviewDto};
+
+/// Arrays that list owned units. Entries pointing at removed units are dropped
+/// and the array is renumbered; every other reference is set to `null`, which
+/// keeps garage slot counts intact.
+const LIST_FIELDS: &[&str] = &["accessories", "trucks", "trailers", "trailer_defs"];
+const STRUCTURAL_CATEGORIES: &[&str] = &["chassis", "cabin", "body"];
+const REPLACEABLE_CATEGORIES: &[&str] = &["engine", "transmission", "paint_job"];
+const EMPTY_JOB_OFFER_FIELDS: &[(&str, &str)] = &[
+    ("target", "\"\""),
+    ("expiration_time", "nil"),
+    ("urgency", "nil"),
+    ("shortest_distance_km", "0"),
+    ("ferry_time", "0"),
+    ("ferry_price", "0"),
+    ("cargo", "null"),
+    ("company_truck", "\"\""),
+    ("trailer_variant", "null"),
+    ("trailer_definition", "null"),
+    ("units_count", "0"),
+];
+
+#[derive(Debug, Clone, Default)]
+pub struct SanitizerTargets {
+    pub paths: BTreeSet<String>,
+    pub cargo: BTreeSet<String>,
+    pub trailer_definitions: BTreeSet<String>,
+}
+
+impl SanitizerTargets {
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty() && self.cargo.is_empty() && self.trailer_definitions.is_empty()
+    }
+
+    fn path_removed(&self, path: &str) -> bool {
+        let normalized = normalize_data_path(path);
+        self.paths.iter().any(|target| {
+            if target.ends_with('/') {
+                normalized.starts_with(target.as_str())
+            } else {
+                normalized == *target
+            }
+        })
+    }
+}
+
+/// Sources the sanitizer may repair units from, and the refund for removed owned vehicles.
+#[derive(Debug, Clone, Default)]
+pub struct SanitizerOptions {
+    pub catalog: Option<PowertrainCatalog>,
+    /// Vehicle data paths the base game and its DLCs provide. Replacements are only taken
+    /// from these and from official catalog entries, never from other mods.
+    pub vanilla_paths: HashSet<String>,
+    pub refund_per_truck: i64,
+    pub refund_per_trailer: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct SanitizePlan {
+    pub content: String,
+    pub preview: SaveSanitizePreviewDto,
+}
+
+#[derive(Debug, Clone)]
+struct Unit {
+    class: String,
+    id: String,
+    start: usize,
+    end: usize,
+}
+
+pub fn normalize_data_path(path: &str) -> String {
+    let normalized = path.trim().trim_matches('"').replace('\\', "/");
+    let normalized = normalized.trim_start_matches('/').to_ascii_lowercase();
+    format!("/{}", normalized)
+}
+
+/// Builds the sanitized save and a preview of every change. Nothing is written here. Only
+/// units that reference a targeted path, cargo or trailer definition are touched; without
+/// targets the save is returned byte for byte.
+pub fn plan_sanitize(
+    content: &str,
+    targets: &SanitizerTargets,
+    options: &SanitizerOptions,
+) -> SanitizePlan {
+    if targets.is_empty() {
+        return unchanged_plan(content, targets, Vec::new());
+    }
+
+    let lines = split_lines(content);
+    let units = index_units(&lines);
+    let by_id = units
+        .iter()
+        .enumerate()
+        .map(|(position, unit)| (unit.id.clone(), position))
+        .collect::<HashMap<_, _>>();
+    let mut warnings = Vec::new();
+
+    let mut owners = HashMap::new();
+    for (position, unit) in units.iter().enumerate() {
+        if !is_vehicle_class(&unit.class) {
+            continue;
+        }
+        for accessory in array_values(&lines, unit, "accessories") {
+            if by_id.contains_key(&accessory) {
+                owners.insert(accessory, position);
+            }
+        }
+    }
+    let removed_definitions = units
+        .iter()
+        .filter(|unit| {
+            unit.class == "trailer_def"
+                && (targets.trailer_definitions.contains(&unit.id)
+                    || references_removed_path(&lines, unit, targets))
+        })
+        .map(|unit| unit.id.clone())
+        .chain(targets.trailer_definitions.iter().cloned())
+        .collect::<HashSet<_>>();
+
+    let local_paths = units
+        .iter()
+        .filter(|unit| is_accessory_class(&unit.class))
+        .filter_map(|unit| field_value(&lines, unit, "data_path"))
+        .map(|path| normalize_data_path(&path))
+        .filter(|path| !targets.path_removed(path))
+        .collect::<BTreeSet<_>>();
+
+    let mut removed = BTreeSet::new();
+    let mut reasons = HashMap::new();
+    let mut replacements = BTreeMap::new();
+    let mut unmappable = BTreeMap::new();
+
+    for (position, unit) in units.iter().enumerate() {
+        if !is_accessory_class(&unit.class) {
+            continue;
+        }
+        let Some(path) = field_value(&lines, unit, "data_path") else {
+            continue;
+        };
+        let path = normalize_data_path(&path);
+        if !targets.path_removed(&path) {
+            continue;
+        }
+        let Some(owner) = owners.get(&unit.id).copied() else {
+            removed.insert(position);
+            reasons.insert(position, format!("Orphaned accessory {}", path));
+            continue;
+        };
+
+        match split_vehicle_path(&path) {
+            Some((_, category)) if STRUCTURAL_CATEGORIES.contains(&category.as_str()) => {
+                unmappable
+                    .entry(owner)
+                    .or_insert_with(|| format!("Uses removed {} {}", category, path));
+            }
+            Some((folder, category)) if REPLACEABLE_CATEGORIES.contains(&category.as_str()) => {
+                match find_replacement(&folder, &category, targets, &local_paths, options) {
+                    Some(replacement) => {
+                        replacements.insert(position, (path, replacement));
+                    }
+                    None => {
+                        unmappable.entry(owner).or_insert_with(|| {
+                            format!("No vanilla {} replacement for {}", category, path)
+                        });
+                    }
+                }
+            }
+            _ => {
+                removed.insert(position);
+                reasons.insert(position, format!("Modded accessory {}", path));
+            }
+        }
+    }
+
+    for (position, unit) in units.iter().enumerate() {
+        if unit.class != "trailer" {
+            continue;
+        }
+        let Some(definition) = field_value(&lines, unit, "trailer_definition") else {
+            continue;
+        };
+        let definition = definition.to_ascii_lowercase();
+        if definition == "null" {
+            continue;
+        }
+        if removed_definitions.contains(&definition) {
+            unmappable
+                .entry(position)
+                .or_insert_with(|| format!("Trailer definition {} is unavailable", definition));
+        }
+    }
+
+    let mut queue = unmappable.keys().copied().collect::<Vec<_>>();
+    while let Some(position) = queue.pop() {
+        if !removed.insert(position) {
+            continue;
+        }
+        let unit = &units[position];
+        reasons.insert(
+            position,
+            unmappable
+                .get(&position)
+                .cloned()
+                .unwrap_or_else(|| "Coupled to a removed trailer".to_string()),
+        );
+        for accessory in array_values(&lines, unit, "accessories") {
+            if let Some(&accessory_position) = by_id.get(&accessory) {
+                removed.insert(accessory_position);
+                replacements.remove(&accessory_position);
+                reasons
+                    .entry(accessory_position)
+                    .or_insert_with(|| format!("Belongs to removed {}", unit.id));
+            }
+        }
+        if let Some(slave) = field_value(&lines, unit, "slave_trailer")
+            .and_then(|slave| by_id.get(&slave.to_ascii_lowercase()).copied())
+        {
+            queue.push(slave);
+        }
+    }
+
+    let kept_definitions = units
+        .iter()
+        .enumerate()
+        .filter(|(position, unit)| unit.class == "trailer" && !removed.contains(position))
+        .filter_map(|(_, unit)| field_value(&lines, unit, "trailer_definition"))
+        .map(|value| value.to_ascii_lowercase())
+        .collect::<HashSet<_>>();
+    for (position, unit) in units.iter().enumerate() {
+        if unit.class == "trailer_def"
+            && removed_definitions.contains(&unit.id)
+            && !kept_definitions.contains(&unit.id)
+        {
+            removed.insert(position);
+            reasons.insert(position, "Removed trailer definition".to_string());
+        }
+    }
+
+    let drop_ids = removed
+        .iter()
+        .map(|position| units[*position].id.clone())
+        .collect::<HashSet<_>>();
+
+    let mut blank_offers = BTreeSet::new();
+    for (position, unit) in units.iter().enumerate() {
+        let cargo = field_value(&lines, unit, "cargo").map(|value| value.to_ascii_lowercase());
+        let definition =
+            field_value(&lines, unit, "trailer_definition").map(|value| value.to_ascii_lowercase());
+        let uses_removed = cargo
+            .as_ref()
+            .is_some_and(|value| targets.cargo.contains(value))
+            || definition.as_ref().is_some_and(|value| {
+                removed_definitions.contains(value) || drop_ids.contains(value)
+            });
+        if !uses_removed || removed.contains(&position) {
+            continue;
+        }
+        match unit.class.as_str() {
+            "job_offer_data" => {
+                blank_offers.insert(position);
+            }
+            "player_job" => warnings.push(
+                "The active job uses removed cargo or trailers. Cancel it in game before loading this save with the mod removed."
+                    .to_string(),
+            ),
+            _ => {}
+        }
+    }
+
+    let mut refund = 0i64;
+    if let Some(player) = units.iter().find(|unit| unit.class == "player") {
+        let truck = field_value(&lines, player, "my_truck").map(|value| value.to_ascii_lowercase());
+        if truck.is_some_and(|value| drop_ids.contains(&value)) {
+            warnings.push(
+                "The player's current truck is removed. Pick another truck from a garage after loading."
+                    .to_string(),
+            );
+        }
+        let owned_removed = |field: &str| {
+            array_values(&lines, player, field)
+                .iter()
+                .filter(|id| drop_ids.contains(*id))
+                .count() as i64
+        };
+        refund = owned_removed("trucks") * options.refund_per_truck.max(0)
+            + owned_removed("trailers") * options.refund_per_trailer.max(0);
+    }
+    let bank = units.iter().position(|unit| unit.class == "bank");
+    if refund > 0 && bank.is_none() {
+        warnings.push(
+            "The save has no bank unit, so removed vehicles could not be refunded.".to_string(),
+        );
+        refund = 0;
+    }
+
+    let mut changes = Vec::new();
+    let mut output = Vec::with_capacity(lines.len());
+    let mut cursor = 0usize;
+    let mut cleared_references = 0usize;
+    for (position, unit) in units.iter().enumerate() {
+        output.extend_from_slice(&lines[cursor..unit.start]);
+        cursor = unit.end + 1;
+
+        if removed.contains(&position) {
+            changes.push(change(
+                removal_action(&unit.class),
+                unit,
+                reasons.get(&position).cloned().unwrap_or_default(),
+                None,
+                None,
+            ));
+            continue;
+        }
+
+        let mut unit_lines = lines[unit.start..=unit.end].to_vec();
+        if refund > 0 && bank == Some(position) {
+            let before = field_value(&lines, unit, "money_account")
+                .and_then(|value| value.parse::<i64>().ok())
+                .unwrap_or_default();
+            let after = before.saturating_add(refund);
+            set_field(&mut unit_lines, "money_account", &after.to_string());
+            changes.push(change(
+                "refund_removed_vehicles",
+                unit,
+                format!("{} credited for removed trucks and trailers", refund),
+                Some(before.to_string()),
+                Some(after.to_string()),
+            ));
+        }
+        if let Some((before, after)) = replacements.get(&position) {
+            set_field(&mut unit_lines, "data_path", &format!("\"{}\"", after));
+            changes.push(change(
+                "replace_accessory",
+                unit,
+                "Replaced with an installed equivalent".to_string(),
+                Some(before.clone()),
+                Some(after.clone()),
+            ));
+        }
+        if blank_offers.contains(&position) {
+            let before = field_value(&lines, unit, "cargo");
+            for (field, value) in EMPTY_JOB_OFFER_FIELDS {
+                set_field(&mut unit_lines, field, value);
+            }
+            changes.push(change(
+                "clear_job_offer",
+                unit,
+                "Job offer uses removed cargo or trailer".to_string(),
+                before,
+                None,
+            ));
+        }
+        let cleared = drop_references(&mut unit_lines, &drop_ids);
+        if cleared > 0 {
+            cleared_references += cleared;
+            changes.push(change(
+                "clear_references",
+                unit,
+                format!("{} reference(s) to removed units cleared", cleared),
+                None,
+                None,
+            ));
+        }
+        output.extend(unit_lines);
+    }
+    output.extend_from_slice(&lines[cursor.min(lines.len())..]);
+
+    let count_removed = |predicate: fn(&str) -> bool| {
+        removed
+            .iter()
+            .filter(|position| predicate(&units[**position].class))
+            .count()
+    };
+    if changes.is_empty() {
+        return unchanged_plan(content, targets, warnings);
+    }
+
+    let content = join_lines(&output);
+    let preview = SaveSanitizePreviewDto {
+        removed_paths: targets.paths.iter().cloned().collect(),
+        replaced_accessories: replacements.len(),
+        removed_accessories: count_removed(is_accessory_class),
+        removed_trucks: count_removed(|class| class == "vehicle"),
+        removed_trailers: count_removed(|class| class == "trailer"),
+        cleared_references,
+        cleared_job_offers: blank_offers.len(),
+        refunded_money: refund,
+        can_apply: true,
+        changes,
+        warnings,
+        plan_hash: content_hash(&content),
+        ..SaveSanitizePreviewDto::default()
+    };
+
+    SanitizePlan { content, preview }
+}
+
+fn unchanged_plan(
+    content: &str,
+    targets: &SanitizerTargets,
+    warnings: Vec<String>,
+) -> SanitizePlan {
+    SanitizePlan {
+        content: content.to_string(),
+        preview: SaveSanitizePreviewDto {
+            removed_paths: targets.paths.iter().cloned().collect(),
+            warnings,
+            plan_hash: content_hash(content),
+            ..SaveSanitizePreviewDto::default()
+        },
+    }
+}
+
+fn content_hash(content: &str) -> String {
+    format!("{:x}", Sha256::digest(content.as_bytes()))
+}
+
+/// Whether any quoted path field of the unit points at removed content.
+fn references_removed_path(lines: &[String], unit: &Unit, targets: &SanitizerTargets) -> bool {
+    lines[unit.start + 1..unit.end]
+        .iter()
+        .filter_map(|line| parse_field(line))
+        .filter_map(|(_, value)| value.strip_prefix('"')?.strip_suffix('"'))
+        .filter(|value| value.starts_with('/'))
+        .any(|value| targets.path_removed(value))
+}
+
+/// First base game part of the same truck and category: one the save already uses, then an
+/// official catalog entry, then any the game archives provide.
+fn find_replacement(
+    folder: &str,
+    category: &str,
+    targets: &SanitizerTargets,
+    local_paths: &BTreeSet<String>,
+    options: &SanitizerOptions,
+) -> Option<String> {
+    let prefix = format!("{}{}/", folder, category);
+    let mut candidates = local_paths
+        .iter()
+        .filter(|path| path.starts_with(&prefix) && options.vanilla_paths.contains(*path))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    if let Some(catalog) = options.catalog.as_ref() {
+        let mut official = match category {
+            "engine" => catalog
+                .engines
+                .iter()
+                .filter(|engine| engine.official)
+                .map(|engine| normalize_data_path(&engine.data_path))
+                .collect::<Vec<_>>(),
+            "transmission" => catalog
+                .transmissions
+                .iter()
+                .filter(|transmission| transmission.official)
+                .map(|transmission| normalize_data_path(&transmission.data_path))
+                .collect::<Vec<_>>(),
+            _ => Vec::new(),
+        };
+        official.retain(|path| path.starts_with(&prefix));
+        official.sort();
+        candidates.extend(official);
+    }
+    let mut archived = options
+        .vanilla_paths
+        .iter()
+        .filter(|path| path.starts_with(&prefix) && path.ends_with(".sii"))
+        .cloned()
+        .collect::<Vec<_>>();
+    archived.sort();
+    candidates.extend(archived);
+    if category == "paint_job" && folder.starts_with("/def/vehicle/truck/") {
+        candidates.push(format!("{}default.sii", prefix));
+    }
+
+    candidates
+        .into_iter()
+        .find(|candidate| !targets.path_removed(candidate))
+}
+
+/// `/def/vehicle/truck/scania.r/engine/dc13.sii` -> (`/def/vehicle/truck/scania.r/`, `engine`)
+fn split_vehicle_path(path: &str) -> Option<(String, String)> {
+    let segments = path.trim_matches('/').split('/').collect::<Vec<_>>();
+    if segments.len() < 6 || segments[0] != "def" || segments[1] != "vehicle" {
+        return None;
+    }
+    Some((
+        format!("/def/vehicle/{}/{}/", segments[2], segments[3]),
+        segments[4].to_string(),
+    ))
+}
+
+fn index_units(lines: &[String]) -> Vec<Unit> {
+    let mut units = Vec::new();
+    let mut open: Option<(String, String, usize)> = None;
+
+    for (index, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        if let Some(header) = trimmed.strip_suffix('{') {
+            if let Some((class, id)) = header.split_once(':') {
+                open = Some((
+                    class.trim().to_string(),
+                    id.trim().to_ascii_lowercase(),
+                    index,
+                ));
+            }
+        } else if trimmed == "}" {
+            if let Some((class, id, start)) = open.take() {
+                units.push(Unit {
+                    class,
+                    id,
+                    start,
+                    end: index,
+                });
+            }
+        }
+    }
+
+    units
+}
+
+fn parse_field(line: &str) -> Option<(&str, &str)> {
+    let trimmed = line.trim();
+    if trimmed.ends_with('{') {
+        return None;
+    }
+    let (key, value) = trimmed.split_once(':')?;
+    Some((key.trim(), value.trim()))
+}
+
+fn field_value(lines: &[String], unit: &Unit, field: &str) -> Option<String> {
+    lines[unit.start + 1..unit.end]
+        .iter()
+        .filter_map(|line| parse_field(line))
+        .find(|(key, _)| *key == field)
+        .map(|(_, value)| value.trim_matches('"').to_string())
+}
+
+fn array_values(lines: &[String], unit: &Unit, field: &str) -> Vec<String> {
+    let prefix = format!("{}[", field);
+    lines[unit.start + 1..unit.end]
+        .iter()
+        .filter_map(|line| parse_field(line))
+        .filter(|(key, _)| key.starts_with(&prefix))
+        .map(|(_, value)| value.to_ascii_lowercase())
+        .collect()
+}
+
+fn set_field(unit_lines: &mut [String], field: &str, value: &str) {
+    for line in unit_lines.iter_mut() {
+        if parse_field(line).is_some_and(|(key, _)| key == field) {
+            *line = format!("{}{}: {}", indentation(line), field, value);
+        }
+    }
+}
+
+fn drop_references(unit_lines: &mut Vec<String>, drop_ids: &HashSet<String>) -> usize {
+    let mut cleared = 0usize;
+    let mut renumber = BTreeSet::new();
+    let mut kept = Vec::with_capacity(unit_lines.len());
+
+    for line in unit_lines.drain(..) {
+        let Some((key, value)) = parse_field(&line) else {
+            kept.push(line);
+            continue;
+        };
+        if !drop_ids.contains(&value.to_ascii_lowercase()) {
+            kept.push(line);
+            continue;
+        }
+        cleared += 1;
+        let base = key.split('[').next().unwrap_or(key).to_string();
+        if key.contains('[') && LIST_FIELDS.contains(&base.as_str()) {
+            renumber.insert(base);
+            continue;
+        }
+        let replaced = format!("{}{}: null", indentation(&line), key);
+        kept.push(replaced);
+    }
+
+    for field in renumber {
+        let prefix = format!("{}[", field);
+        let mut next_index = 0usize;
+        for line in kept.iter_mut() {
+            let Some((key, value)) = parse_field(line) else {
+                continue;
+            };
+            if key.starts_with(&prefix) {
+                *line = format!("{}{}[{}]: {}", indentation(line), field, next_index, value);
+                next_index += 1;
+            }
+        }
+        set_field(&mut kept, &field, &next_index.to_string());
+    }
+
+    *unit_lines = kept;
+    cleared
+}
+
+fn indentation(line: &str) -> &str {
+    &line[..line.len() - line.trim_start().len()]
+}
+
+fn change(
+    action: &str,
+    unit: &Unit,
+    detail: String,
+    before: Option<String>,
+    after: Option<String>,
+) -> SaveSanitizeChangeDto {
+    SaveSanitizeChangeDto {
+        action: action.to_string(),
+        unit_class: unit.class.clone(),
+        unit_id: unit.id.clone(),
+        detail,
+        before,
+        after,
+    }
+}
+
+fn removal_action(class: &str) -> &'static str {
+    match class {
+        "vehicle" => "remove_truck",
+        "trailer" => "remove_trailer",
+        "trailer_def" => "remove_trailer_definition",
+        _ => "remove_accessory",
+    }
+}
+
+fn is_vehicle_class(class: &str) -> bool {
+    matches!(class, "vehicle" | "trailer")
+}
+
+fn is_accessory_class(class: &str) -> bool {
+    class.starts_with("vehicle_") && class.ends_with("accessory")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = r#"SiiNunit
+{
+player : _nameless.player {
+ my_truck: _nameless.truck.b
+ trucks: 2
+ trucks[0]: _nameless.truck.a
+ trucks[1]: _nameless.truck.b
+}
+garage : garage.berlin {
+ vehicles: 2
+ vehicles[0]: _nameless.truck.a
+ vehicles[1]: _nameless.truck.b
+}
+vehicle : _nameless.truck.a {
+ accessories: 3
+ accessories[0]: _nameless.acc.a_chassis
+ accessories[1]: _nameless.acc.a_engine
+ accessories[2]: _nameless.acc.a_horn
+}
+vehicle_accessory : _nameless.acc.a_chassis {
+ data_path: "/def/vehicle/truck/scania.r/chassis/4x2.sii"
+}
+vehicle_accessory : _nameless.acc.a_engine {
+ data_path: "/def/vehicle/truck/scania.r/engine/modded_v8.sii"
+}
+vehicle_addon_accessory : _nameless.acc.a_horn {
+ data_path: "/def/vehicle/truck/scania.r/accessory/horn/modded.sii"
+}
+vehicle : _nameless.truck.b {
+ accessories: 2
+ accessories[0]: _nameless.acc.b_chassis
+ accessories[1]: _nameless.acc.b_engine
+}
+vehicle_accessory : _nameless.acc.b_chassis {
+ data_path: "/def/vehicle/truck/custom.brand/chassis/6x4.sii"
+}
+vehicle_accessory : _nameless.acc.b_engine {
+ data_path: "/def/vehicle/truck/scania.r/engine/dc13.sii"
+}
+job_offer_data : _nameless.offer.a {
+ target: "tesco.berlin"
+ cargo: cargo.modded_goods
+ trailer_definition: trailer_def.scs.box
+}
+}
+"#;
+
+    fn options() -> SanitizerOptions {
+        SanitizerOptions {
+            vanilla_paths: ["/def/vehicle/truck/scania.r/engine/dc13.sii".to_string()]
+                .into_iter()
+                .collect(),
+            ..SanitizerOptions::default()
+        }
+    }
+
+    fn targets() -> SanitizerTargets {
+        SanitizerTargets {
+            paths: [
+                "/def/vehicle/truck/custom.brand/".to_string(),
+                "/def/vehicle/truck/scania.r/engine/modded_v8.sii".to_string(),
+                "/def/vehicle/truck/scania.r/accessory/horn/modded.sii".to_string(),
+            ]
+            .into_iter()
+            .collect(),
+            cargo: ["cargo.modded_goods".to_string()].into_iter().collect(),
+            trailer_definitions: BTreeSet::new(),
+        }
+    }
+
+    #[test]
+    fn replaces_removes_and_clears_references() {
+        let plan = plan_sanitize(FIXTURE, &targets(), &options());
+        let preview = &plan.preview;
+
+        assert_eq!(preview.replaced_accessories, 1);
+        assert_eq!(preview.removed_trucks, 1);
+        assert_eq!(preview.removed_accessories, 3);
+        assert_eq!(preview.cleared_job_offers, 1);
+        assert!(preview.can_apply);
+        assert_eq!(preview.warnings.len(), 1);
+
+        let content = &plan.content;
+        assert!(content.contains("data_path: \"/def/vehicle/truck/scania.r/engine/dc13.sii\""));
+        assert!(!content.contains("modded_v8"));
+        assert!(!content.contains("_nameless.acc.a_horn"));
+        assert!(!content.contains("vehicle : _nameless.truck.b"));
+        assert!(!content.contains("custom.brand"));
+        assert!(content.contains(" my_truck: null"));
+        assert!(content.contains(" trucks: 1\n trucks[0]: _nameless.truck.a"));
+        assert!(
+            content.contains(" vehicles: 2\n vehicles[0]: _nameless.truck.a\n vehicles[1]: null")
+        );
+        assert!(content.contains(" accessories: 2\n accessories[0]: _nameless.acc.a_chassis\n accessories[1]: _nameless.acc.a_engine\n}"));
+        assert!(content.contains(" cargo: null"));
+        assert!(content.contains(" target: \"\""));
+    }
+
+    #[test]
+    fn untouched_save_has_no_changes() {
+        let plan = plan_sanitize(FIXTURE, &SanitizerTargets::default(), &options());
+        assert!(!plan.preview.can_apply);
+        assert_eq!(plan.content, join_lines(&split_lines(FIXTURE)));
+    }
+
+    #[test]
+    fn empty_targets_leave_the_save_byte_identical() {
+        let content = FIXTURE.replace('\n', "\r\n").replace(
+            "accessories[2]: _nameless.acc.a_horn",
+            "accessories[2]: _nameless.acc.gone",
+        );
+        let content = content.trim_end();
+        let plan = plan_sanitize(content, &SanitizerTargets::default(), &options());
+        assert!(!plan.preview.can_apply);
+        assert!(plan.preview.changes.is_empty());
+        assert_eq!(plan.content.as_bytes(), content.as_bytes());
+    }
+
+    #[test]
+    fn units_without_removed_references_are_kept() {
+        let content = FIXTURE.replace(
+            "job_offer_data : _nameless.offer.a {",
+            "trailer : _nameless.trailer.a {\n trailer_definition: _nameless.def.missing\n}\njob_offer_data : _nameless.offer.a {",
+        );
+        let targets = SanitizerTargets {
+            paths: ["/def/vehicle/truck/scania.r/engine/modded_v8.sii".to_string()]
+                .into_iter()
+                .collect(),
+            ..SanitizerTargets::default()
+        };
+        let plan = plan_sanitize(&content, &targets, &options());
+
+        assert_eq!(plan.preview.replaced_accessories, 1);
+        assert_eq!(plan.preview.removed_trucks, 0);
+        assert_eq!(plan.preview.removed_trailers, 0);
+        assert!(plan.content.contains(" my_truck: _nameless.truck.b"));
+        assert!(
+            plan.content
+                .contains("trailer_definition: _nameless.def.missing")
+        );
+        assert!(plan.content.contains(" cargo: cargo.modded_goods"));
+    }
+
+    #[test]
+    fn replacements_only_use_base_game_parts() {
+        let content = FIXTURE.replace(
+            "/def/vehicle/truck/scania.r/engine/dc13.sii",
+            "/def/vehicle/truck/scania.r/engine/other_mod_v8.sii",
+        );
+        let targets = SanitizerTargets {
+            paths: ["/def/vehicle/truck/scania.r/engine/modded_v8.sii".to_string()]
+                .into_iter()
+                .collect(),
+            ..SanitizerTargets::default()
+        };
+
+        let plan = plan_sanitize(&content, &targets, &SanitizerOptions::default());
+        assert_eq!(plan.preview.replaced_accessories, 0);
+        assert!(!plan.content.contains("vehicle : _nameless.truck.a"));
+
+        let plan = plan_sanitize(&content, &targets, &options());
+        assert_eq!(plan.preview.replaced_accessories, 1);
+        assert!(
+            plan.content
+                .contains("data_path: \"/def/vehicle/truck/scania.r/engine/dc13.sii\"")
+        );
+    }
+
+    #[test]
+    fn refunds_removed_owned_vehicles_and_drops_removed_trailer_definitions() {
+        let content = FIXTURE
+            .replace(
+                " trucks[1]: _nameless.truck.b\n}",
+                " trucks[1]: _nameless.truck.b\n trailers: 1\n trailers[0]: _nameless.trailer.a\n}\nbank : _nameless.bank {\n money_account: 1000\n}",
+            )
+            .replace(
+                "job_offer_data : _nameless.offer.a {",
+                "trailer : _nameless.trailer.a {\n trailer_definition: trailer_def.modded.box\n}\njob_offer_data : _nameless.offer.a {",
+            );
+        let mut targets = targets();
+        targets
+            .trailer_definitions
+            .insert("trailer_def.modded.box".to_string());
+        let options = SanitizerOptions {
+            refund_per_truck: 50_000,
+            refund_per_trailer: 20_000,
+            ..options()
+        };
+
+        let plan = plan_sanitize(&content, &targets, &options);
+        assert_eq!(plan.preview.removed_trucks, 1);
+        assert_eq!(plan.preview.removed_trailers, 1);
+        assert_eq!(plan.preview.refunded_money, 70_000);
+        assert!(plan.content.contains(" money_account: 71000"));
+        assert!(!plan.content.contains("trailer_def.modded.box"));
+        assert!(plan.content.contains(" trailers: 0"));
+    }
+}
@@ -2,7 +2,7 @@ use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::mpsc;
-use std::sync::{Mutex, OnceLock};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::thread;
 use std::time::{Duration, UNIX_EPOCH};
 
@@ -14,8 +14,12 @@ use walkdir::WalkDir;
 use crate::features::backup::service as backup_service;
 use crate::features::logging::models::LogContext;
 use crate::features::logging::service as logging_service;
+use crate::features::mod_profile_manager::models::GameType;
+use crate::features::mod_profile_manager::save_dependencies::{self, ModContentIndex};
+use crate::features::truck_change::catalog::load_official_powertrain_catalog;
 use crate::shared::current_profile::{ResolvedSaveContext, snapshot_resolved_save_context};
 use crate::shared::decrypt::{decrypt_cached_with_cache, decrypt_if_needed};
+use crate::shared::ets2data;
 use crate::shared::paths::{game_sii_from_save, mod_directory_path};
 use crate::shared::sii_parser::{
     get_player_id, get_vehicle_ids, parse_trailer_defs_from_sii, parse_trailers_from_sii,
@@ -25,9 +29,16 @@ use crate::shared::trace::{TraceScope, lock_mutex};
 use crate::shared::user_log;
 use crate::state::{AppProfileState, DecryptCache, ProfileCache};
 
-use super::models::{SaveHealthFixResultDto, SaveHealthProblemDto, SaveHealthReportDto};
+use super::models::{
+    SaveHealthFixResultDto, SaveHealthProblemDto, SaveHealthReportDto, SaveSanitizePreviewDto,
+    SaveSanitizeRequestDto,
+};
+use super::sanitizer::{
+    SanitizePlan, SanitizerOptions, SanitizerTargets, normalize_data_path, plan_sanitize,
+};
 
 const FIX_SYNC_PLAYER_XP_LEVEL: &str = "sync_player_xp_level";
+const FIX_SANITIZE_REMOVED_MODS: &str = "sanitize_removed_mods";
 const COMMON_TOKENS: &[&str] = &[
     "accessory",
     "addon",
@@ -292,7 +303,7 @@ pub fn analyze_resolved_save_health(
                         "At least one accessory referenced by the active truck is not present in the save.",
                         "Restore a backup from before the accessory or truck mod change, or remove the broken truck setup in game.",
                         false,
-                        None,
+                        Some(FIX_SANITIZE_REMOVED_MODS.to_string()),
                         missing_accessories,
                     ));
                 }
@@ -352,7 +363,7 @@ pub fn analyze_resolved_save_health(
                         "The player trailer exists, but its trailer definition could not be resolved inside the save.",
                         "Restore a backup from before the trailer or cargo mod change.",
                         false,
-                        None,
+                        Some(FIX_SANITIZE_REMOVED_MODS.to_string()),
                         vec![trailer.trailer_definition.clone()],
                     ));
                 }
@@ -467,7 +478,7 @@ pub fn analyze_resolved_save_health(
                         "One or more custom truck, trailer, accessory or map assets referenced by the save were not found in the indexed local mods.",
                         "Restore a backup from before the mod change or reinstall the content that provided these assets.",
                         false,
-                        None,
+                        Some(FIX_SANITIZE_REMOVED_MODS.to_string()),
                         broken_asset_refs,
                     ));
                 }
@@ -611,6 +622,10 @@ pub fn apply_safe_fix(
         FIX_SYNC_PLAYER_XP_LEVEL => {
             apply_sync_player_xp_level_fix(profile_state, profile_cache, decrypt_cache)
         }
+        FIX_SANITIZE_REMOVED_MODS => Err(
+            "The removed mod sanitizer is not a safe fix. Preview it and apply the reviewed plan."
+                .to_string(),
+        ),
         _ => Err(format!("Unknown health fix `{}`.", fix_id)),
     }
 }
@@ -675,6 +690,257 @@ fn apply_sync_player_xp_level_fix(
     })
 }
 
+pub fn preview_removed_mod_sanitizer(
+    request: &SaveSanitizeRequestDto,
+    profile_state: &AppProfileState,
+    decrypt_cache: &DecryptCache,
+) -> Result<SaveSanitizePreviewDto, String> {
+    let (save_path, plan) = build_sanitize_plan(request, profile_state, decrypt_cache)?;
+    Ok(SaveSanitizePreviewDto {
+        save_path: save_path.display().to_string(),
+        ..plan.preview
+    })
+}
+
+pub fn apply_removed_mod_sanitizer(
+    request: &SaveSanitizeRequestDto,
+    confirmed: bool,
+    profile_state: &AppProfileState,
+    profile_cache: &ProfileCache,
+    decrypt_cache: &DecryptCache,
+) -> Result<SaveHealthFixResultDto, String> {
+    if !confirmed {
+        return Err("Safe fixes require explicit confirmation.".to_string());
+    }
+
+    let (save_path, plan) = build_sanitize_plan(request, profile_state, decrypt_cache)?;
+    if request.preview_hash.as_deref() != Some(plan.preview.plan_hash.as_str()) {
+        return Err(
+            "The save or the sanitizer plan changed since the preview. Preview it again before applying."
+                .to_string(),
+        );
+    }
+    if !plan.preview.can_apply {
+        return Ok(SaveHealthFixResultDto {
+            fix_id: FIX_SANITIZE_REMOVED_MODS.to_string(),
+            applied: false,
+            message: "No references to removed mods were found in the active save.".to_string(),
+        });
+    }
+
+    let backup_targets = backup_service::recommended_targets(&save_path);
+    backup_service::create_backup_for_targets(
+        profile_state,
+        "before health fix sanitize removed mods",
+        &backup_targets,
+    )?;
+
+    fs::write(&save_path, plan.content.as_bytes()).map_err(|error| error.to_string())?;
+    decrypt_cache.invalidate_path(&save_path);
+    profile_cache.invalidate_save_data();
+    profile_cache.invalidate_vehicle_data();
+
+    let preview = plan.preview;
+    let message = format!(
+        "Save sanitized: {} accessories replaced, {} accessories, {} trucks and {} trailers removed, {} job offers cleared, {} refunded.",
+        preview.replaced_accessories,
+        preview.removed_accessories,
+        preview.removed_trucks,
+        preview.removed_trailers,
+        preview.cleared_job_offers,
+        preview.refunded_money
+    );
+    let mut context = logging_service::resolve_active_context(profile_state);
+    context
+        .extra
+        .insert("fixId".to_string(), FIX_SANITIZE_REMOVED_MODS.to_string());
+    context
+        .extra
+        .insert("changes".to_string(), preview.changes.len().to_string());
+    let _ = logging_service::record_info("save_health_fix", &message, &context);
+
+    Ok(SaveHealthFixResultDto {
+        fix_id: FIX_SANITIZE_REMOVED_MODS.to_string(),
+        applied: true,
+        message,
+    })
+}
+
+fn build_sanitize_plan(
+    request: &SaveSanitizeRequestDto,
+    profile_state: &AppProfileState,
+    decrypt_cache: &DecryptCache,
+) -> Result<(PathBuf, SanitizePlan), String> {
+    let resolved = snapshot_resolved_save_context(profile_state)
+        .map_err(|error| format!("Failed to resolve active save context: {}", error))?;
+    let save_reference = resolved
+        .context
+        .save_reference
+        .clone()
+        .ok_or_else(|| "No active save was available for the safe fix.".to_string())?;
+    let selected_game =
+        lock_mutex("health_monitor.selected_game", &profile_state.selected_game)?.clone();
+    let save_path = game_sii_from_save(Path::new(&save_reference));
+    let content = decrypt_cached_with_cache(&save_path, decrypt_cache)?;
+
+    let mut warnings = Vec::new();
+    let mut targets = SanitizerTargets::default();
+    targets.paths.extend(
+        request
+            .removed_paths
+            .iter()
+            .filter(|path| !path.trim().is_empty())
+            .map(|path| normalize_data_path(path)),
+    );
+    targets.cargo.extend(
+        request
+            .removed_cargo
+            .iter()
+            .map(|cargo| cargo.trim().to_ascii_lowercase())
+            .filter(|cargo| !cargo.is_empty()),
+    );
+    targets.trailer_definitions.extend(
+        request
+            .removed_trailer_definitions
+            .iter()
+            .map(|definition| definition.trim().to_ascii_lowercase())
+            .filter(|definition| !definition.is_empty()),
+    );
+    let game = GameType::try_from(selected_game.as_str())?;
+    let vanilla = save_dependencies::vanilla_content_indexes(game);
+    match detect_removed_mod_content(
+        &content,
+        resolved.context.profile_reference.as_deref(),
+        game,
+        &vanilla,
+        decrypt_cache,
+    ) {
+        Ok(detected) => {
+            targets.paths.extend(detected.paths);
+            targets.cargo.extend(detected.cargo);
+            targets
+                .trailer_definitions
+                .extend(detected.trailer_definitions);
+        }
+        Err(message) => warnings.push(message),
+    }
+    if targets.is_empty() {
+        warnings.push(
+            "No removed mod content was detected. Pass the removed paths, cargo or trailer definitions explicitly."
+                .to_string(),
+        );
+    }
+
+    let catalog = match load_official_powertrain_catalog(
+        &ets2data::default_repo_root(),
+        &selected_game,
+        request.game_version.as_deref().unwrap_or("unknown"),
+    ) {
+        Ok(catalog) => Some(catalog),
+        Err(error) => {
+            crate::dev_log!("[health-monitor] sanitizer catalog unavailable: {}", error);
+            warnings.push(
+                "The powertrain catalog is unavailable. Engines and transmissions are only replaced with base game parts found in the game archives."
+                    .to_string(),
+            );
+            None
+        }
+    };
+    let options = SanitizerOptions {
+        catalog,
+        vanilla_paths: vanilla
+            .iter()
+            .flat_map(|index| index.paths.iter())
+            .filter(|path| path.starts_with("/def/vehicle/"))
+            .cloned()
+            .collect(),
+        refund_per_truck: request.refund_per_truck,
+        refund_per_trailer: request.refund_per_trailer,
+    };
+    if options.vanilla_paths.is_empty() {
+        warnings.push(
+            "The game archives could not be indexed. Removed parts are not replaced, their trucks are removed instead."
+                .to_string(),
+        );
+    }
+
+    let mut plan = plan_sanitize(&content, &targets, &options);
+    warnings.append(&mut plan.preview.warnings);
+    plan.preview.warnings = warnings;
+    Ok((save_path, plan))
+}
+
+/// Content the save uses that neither the base game nor any installed mod provides anymore.
+struct RemovedModContent {
+    paths: Vec<String>,
+    cargo: Vec<String>,
+    trailer_definitions: Vec<String>,
+}
+
+/// Custom asset paths, cargo and trailer definitions in the save that neither the base game
+/// nor any installed mod provides anymore. Local mods, Workshop items and HashFS archives are
+/// all indexed; if any of them cannot be read the detection gives up rather than flag content
+/// that may still exist.
+fn detect_removed_mod_content(
+    save_content: &str,
+    profile_reference: Option<&str>,
+    game: GameType,
+    vanilla: &[Arc<ModContentIndex>],
+    decrypt_cache: &DecryptCache,
+) -> Result<RemovedModContent, String> {
+    let active_mods = profile_reference
+        .map(|path| PathBuf::from(path).join("profile.sii"))
+        .and_then(|path| decrypt_cached_with_cache(&path, decrypt_cache).ok())
+        .map(|content| parse_active_mods(&content))
+        .unwrap_or_default();
+
+    if vanilla.is_empty() {
+        return Err(
+            "The game archives could not be indexed. Only explicitly listed content is sanitized."
+                .to_string(),
+        );
+    }
+    let (installed, unindexed) = save_dependencies::installed_content_indexes(game);
+    if unindexed > 0 {
+        return Err(format!(
+            "{} installed mod(s) could not be indexed. Only explicitly listed content is sanitized.",
+            unindexed
+        ));
+    }
+
+    let indexes = vanilla.iter().chain(installed.iter()).collect::<Vec<_>>();
+    let paths = extract_custom_save_references(save_content, &active_mods)
+        .into_iter()
+        .filter(|path| !indexes.iter().any(|index| index.paths.contains(path)))
+        .collect();
+
+    // Unit names only come from readable definition files; without any the base game would
+    // look like it provides no cargo at all.
+    let mut cargo = Vec::new();
+    let mut trailer_definitions = Vec::new();
+    if vanilla.iter().any(|index| !index.units.is_empty()) {
+        for reference in save_dependencies::extract_save_references(save_content) {
+            if indexes
+                .iter()
+                .any(|index| index.units.contains(&reference.value))
+            {
+                continue;
+            }
+            match reference.kind.as_str() {
+                "cargo" => cargo.push(reference.value),
+                "trailer_definition" => trailer_definitions.push(reference.value),
+                _ => {}
+            }
+        }
+    }
+
+    Ok(RemovedModContent {
+        paths,
+        cargo,
+        trailer_definitions,
+    })
+}
+
 fn finalize_report(
     generated_at_utc: String,
     profile_name: Option<String>,
@@ -17,8 +17,10 @@ pub mod language;
 pub mod logging;
 pub mod mod_profile_manager;
 pub mod profile_clone;
+pub mod profile_cloud;
 pub mod profile_controls;
 pub mod profile_manager;
+pub mod profile_merge;
 pub mod profile_move_mods;
 pub mod profile_rename;
 pub mod profile_sharing;
@@ -1,13 +1,18 @@
 use super::compare;
 use super::discovery::{ScanMode, load_manager_state, scan_inventory, scan_inventory_with_mode};
 use super::models::{
-    ApplySandboxResult, DiscoveredMod, GameType, ModPreset, ModSandbox, PresetCompareResult,
-    PresetModEntry, SandboxCollection, SandboxModPreset, SandboxPresetActivationResult,
-    SandboxPresetCheckResult, SteamWorkshopCache, SteamWorkshopMod, WorkshopInstallStatus,
-    WorkshopMod,
+    ApplySandboxResult, DiscoveredMod, GameType, ModDiskUsageReport, ModLibraryDuplicateReport,
+    ModLibraryInstallResult, ModLibraryMoveResult, ModLibraryRemoveResult, ModPreset,
+    ModRemovalImpact, ModSandbox, PortablePresetImportPreview, PortablePresetImportResult,
+    PresetCompareResult, PresetModEntry, SandboxCollection, SandboxModPreset,
+    SandboxPresetActivationResult, SandboxPresetCheckResult, SaveDependencyReport,
+    SteamWorkshopCache, SteamWorkshopMod, WorkshopChangeReport, WorkshopInstallStatus,
+    WorkshopMetadataCache, WorkshopMod,
 };
 use super::presets;
-use super::{launcher, sandbox, workshop_api};
+use super::{
+    launcher, library, preset_share, sandbox, save_dependencies, workshop_api, workshop_cache,
+};
 use crate::shared::user_log;
 use crate::state::{AppProfileState, DecryptCache, ProfileCache};
 use std::any::Any;
@@ -488,6 +493,80 @@ pub fn import_mod_preset(app: AppHandle) -> Result<Option<ModPreset>, String> {
     })
 }
 
+#[tauri::command]
+pub fn export_portable_mod_preset(
+    app: AppHandle,
+    profile_state: State<'_, AppProfileState>,
+    preset_id: String,
+) -> Result<String, String> {
+    log_user_event(
+        &format!("mod_profile_manager share preset | {}", preset_id),
+        "start",
+    );
+
+    catch_command("export_portable_mod_preset", || {
+        let _scan_guard = ScanGuard::acquire()?;
+        let exported =
+            preset_share::export_portable_preset(&app, profile_state.inner(), &preset_id)?;
+        if let Some(path) = exported.as_ref() {
+            log_user_event(
+                &format!("mod_profile_manager share preset success | {}", path),
+                "success",
+            );
+        }
+        Ok(exported.unwrap_or_default())
+    })
+}
+
+#[tauri::command]
+pub fn preview_portable_mod_preset(
+    app: AppHandle,
+    profile_state: State<'_, AppProfileState>,
+    source_path: Option<String>,
+) -> Result<Option<PortablePresetImportPreview>, String> {
+    catch_command("preview_portable_mod_preset", || {
+        let source_path = match source_path {
+            Some(path) => std::path::PathBuf::from(path),
+            None => match preset_share::pick_portable_preset(&app)? {
+                Some(path) => path,
+                None => return Ok(None),
+            },
+        };
+        let _scan_guard = ScanGuard::acquire()?;
+        preset_share::preview_portable_preset(&app, profile_state.inner(), &source_path).map(Some)
+    })
+}
+
+#[tauri::command]
+pub fn import_portable_mod_preset(
+    app: AppHandle,
+    profile_state: State<'_, AppProfileState>,
+    source_path: String,
+) -> Result<PortablePresetImportResult, String> {
+    log_user_event("mod_profile_manager import shared preset", "start");
+
+    catch_command("import_portable_mod_preset", || {
+        let _scan_guard = ScanGuard::acquire()?;
+        let result = preset_share::import_portable_preset(
+            &app,
+            profile_state.inner(),
+            std::path::Path::new(&source_path),
+        )?;
+        log_user_event(
+            &format!(
+                "mod_profile_manager import shared preset success | {} | exact={} mismatch={} unverified={} missing={}",
+                result.preset.name,
+                result.preview.exact_count,
+                result.preview.version_mismatch_count,
+                result.preview.unverified_count,
+                result.preview.missing_count
+            ),
+            "success",
+        );
+        Ok(result)
+    })
+}
+
 #[tauri::command]
 pub fn delete_mod_preset(app: AppHandle, preset_id: String) -> Result<(), String> {
     crate::dev_log!(
@@ -509,6 +588,171 @@ pub fn delete_mod_preset(app: AppHandle, preset_id: String) -> Result<(), String
     })
 }
 
+#[tauri::command]
+pub fn install_local_mod(
+    app: AppHandle,
+    game: String,
+    source_path: Option<String>,
+    replace_existing: Option<bool>,
+) -> Result<Option<ModLibraryInstallResult>, String> {
+    crate::dev_log!(
+        "[mod-profile-manager] install local mod requested game={} source={:?}",
+        game,
+        source_path
+    );
+    log_user_event("mod_profile_manager install mod", "start");
+
+    catch_command("install_local_mod", || {
+        let game = GameType::try_from(game.as_str())?;
+        let source_path = match source_path {
+            Some(path) => std::path::PathBuf::from(path),
+            None => match library::pick_mod_archive(&app)? {
+                Some(path) => path,
+                None => return Ok(None),
+            },
+        };
+        let result =
+            library::install_mod_archive(game, &source_path, replace_existing.unwrap_or(false))?;
+        log_user_event(
+            &format!(
+                "mod_profile_manager install success | {} | already_installed={}",
+                result.file_name, result.already_installed
+            ),
+            "success",
+        );
+        Ok(Some(result))
+    })
+}
+
+#[tauri::command]
+pub fn disable_local_mods(
+    game: String,
+    file_names: Vec<String>,
+) -> Result<ModLibraryMoveResult, String> {
+    log_user_event(
+        &format!(
+            "mod_profile_manager disable mods | count={}",
+            file_names.len()
+        ),
+        "start",
+    );
+    catch_command("disable_local_mods", || {
+        let game = GameType::try_from(game.as_str())?;
+        library::disable_mods(game, &file_names)
+    })
+}
+
+#[tauri::command]
+pub fn enable_local_mods(
+    game: String,
+    file_names: Vec<String>,
+) -> Result<ModLibraryMoveResult, String> {
+    log_user_event(
+        &format!(
+            "mod_profile_manager enable mods | count={}",
+            file_names.len()
+        ),
+        "start",
+    );
+    catch_command("enable_local_mods", || {
+        let game = GameType::try_from(game.as_str())?;
+        library::enable_mods(game, &file_names)
+    })
+}
+
+#[tauri::command]
+pub fn disable_unused_local_mods(
+    app: AppHandle,
+    profile_state: State<'_, AppProfileState>,
+    game: String,
+) -> Result<ModLibraryMoveResult, String> {
+    log_user_event("mod_profile_manager disable unused mods", "start");
+    catch_command("disable_unused_local_mods", || {
+        let game = GameType::try_from(game.as_str())?;
+        let _scan_guard = ScanGuard::acquire()?;
+        let result = library::disable_unused_mods(&app, profile_state.inner(), game)?;
+        log_user_event(
+            &format!(
+                "mod_profile_manager disable unused success | moved={} skipped={}",
+                result.moved.len(),
+                result.skipped.len()
+            ),
+            "success",
+        );
+        Ok(result)
+    })
+}
+
+#[tauri::command]
+pub fn find_duplicate_local_mods(game: String) -> Result<ModLibraryDuplicateReport, String> {
+    catch_command("find_duplicate_local_mods", || {
+        let game = GameType::try_from(game.as_str())?;
+        library::find_exact_duplicates(game)
+    })
+}
+
+#[tauri::command]
+pub fn remove_duplicate_local_mods(
+    game: String,
+    keep_paths: Option<Vec<String>>,
+) -> Result<ModLibraryRemoveResult, String> {
+    log_user_event("mod_profile_manager remove duplicates", "start");
+    catch_command("remove_duplicate_local_mods", || {
+        let game = GameType::try_from(game.as_str())?;
+        let result = library::remove_exact_duplicates(game, &keep_paths.unwrap_or_default())?;
+        log_user_event(
+            &format!(
+                "mod_profile_manager remove duplicates success | removed={} freed_bytes={}",
+                result.removed_paths.len(),
+                result.freed_bytes
+            ),
+            "success",
+        );
+        Ok(result)
+    })
+}
+
+#[tauri::command]
+pub fn get_mod_disk_usage(
+    app: AppHandle,
+    profile_state: State<'_, AppProfileState>,
+    game: String,
+) -> Result<ModDiskUsageReport, String> {
+    catch_command("get_mod_disk_usage", || {
+        let game = GameType::try_from(game.as_str())?;
+        let _scan_guard = ScanGuard::acquire()?;
+        library::disk_usage_report(&app, profile_state.inner(), game)
+    })
+}
+
+#[tauri::command]
+pub fn get_save_mod_dependencies(
+    app: AppHandle,
+    profile_state: State<'_, AppProfileState>,
+    game: String,
+) -> Result<SaveDependencyReport, String> {
+    catch_command("get_save_mod_dependencies", || {
+        let game = GameType::try_from(game.as_str())?;
+        let _scan_guard = ScanGuard::acquire()?;
+        log_user_event("mod_profile_manager save dependency report", "start");
+        save_dependencies::build_dependency_report(&app, profile_state.inner(), game)
+    })
+}
+
+#[tauri::command]
+pub fn check_mod_removal_impact(
+    app: AppHandle,
+    profile_state: State<'_, AppProfileState>,
+    game: String,
+    mod_ids: Vec<String>,
+) -> Result<ModRemovalImpact, String> {
+    catch_command("check_mod_removal_impact", || {
+        let game = GameType::try_from(game.as_str())?;
+        let _scan_guard = ScanGuard::acquire()?;
+        save_dependencies::check_removal_impact(&app, profile_state.inner(), game, &mod_ids)
+    })
+}
+
 #[tauri::command]
 pub fn select_manual_workshop_directory(
     app: AppHandle,
@@ -743,6 +987,33 @@ pub fn refresh_workshop_mod_cache(app: AppHandle) -> Result<SteamWorkshopCache,
     })
 }
 
+#[tauri::command]
+pub fn load_workshop_metadata_cache(app: AppHandle) -> Result<WorkshopMetadataCache, String> {
+    catch_command("load_workshop_metadata_cache", || {
+        workshop_cache::load_or_refresh_cache(&app)
+    })
+}
+
+#[tauri::command]
+pub fn refresh_workshop_metadata_cache(app: AppHandle) -> Result<WorkshopMetadataCache, String> {
+    crate::dev_log!("[workshop-cache] refresh command called");
+    catch_command("refresh_workshop_metadata_cache", || {
+        workshop_cache::refresh_cache(&app)
+    })
+}
+
+#[tauri::command]
+pub fn get_workshop_changes_since_last_launch(
+    app: AppHandle,
+    game: String,
+) -> Result<WorkshopChangeReport, String> {
+    catch_command("get_workshop_changes_since_last_launch", || {
+        let game = GameType::try_from(game.as_str())?;
+        let cache = workshop_cache::refresh_cache(&app)?;
+        Ok(workshop_cache::changes_since_last_launch(&cache, game))
+    })
+}
+
 #[tauri::command]
 pub fn check_workshop_mod_available(
     app: AppHandle,
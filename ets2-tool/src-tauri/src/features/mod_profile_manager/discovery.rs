@@ -5,6 +5,7 @@ use super::models::{
 };
 use super::presets;
 use super::steam_paths::discover_workshop_sources;
+use super::workshop_cache;
 use crate::shared::decrypt::decrypt_if_needed;
 use crate::shared::paths::mod_directory_path;
 use crate::shared::{logs, user_log};
@@ -13,7 +14,7 @@ use regex::Regex;
 use std::collections::{BTreeSet, HashSet};
 use std::fs::{self, File};
 use std::io::Read;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant, UNIX_EPOCH};
 use tauri::AppHandle;
 use walkdir::WalkDir;
@@ -276,10 +277,22 @@ pub fn scan_inventory_with_mode(
     apply_active_state(&mut scanned_mods, &active_mods, active_mods_reliably_known);
     sort_scanned_mods(&mut scanned_mods);
 
-    let mods = scanned_mods
+    let mut mods = scanned_mods
         .into_iter()
         .map(|item| item.mod_info)
         .collect::<Vec<_>>();
+    let workshop_cache = match mode {
+        ScanMode::Light => workshop_cache::load_cache(app),
+        ScanMode::Deep => workshop_cache::load_or_refresh_cache(app).map(Some),
+    };
+    match workshop_cache {
+        Ok(Some(cache)) => workshop_cache::enrich_discovered_mods(&cache, &mut mods),
+        Ok(None) => {}
+        Err(error) => record_warning(
+            &mut warnings,
+            format!("Workshop metadata cache unavailable: {}", error),
+        ),
+    }
     let logs = ModManagerLogPaths {
         technical_log_path: Some(logs::technical_log_path().display().to_string()),
         user_log_path: Some(user_log::user_log_path().display().to_string()),
@@ -352,6 +365,58 @@ pub fn scan_inventory_with_mode(
     })
 }
 
+/// File paths (normalized) of every scanned mod that is listed in the active mods of at
+/// least one of the given profiles. Fails if a profile's mod list cannot be read, since an
+/// unreadable profile might use any mod.
+pub fn mods_used_by_profiles(
+    app: &AppHandle,
+    game: GameType,
+    profile_paths: &[PathBuf],
+) -> Result<HashSet<String>, String> {
+    let mut warnings = Vec::new();
+    let mut scan_context = ScanContext::new(ScanMode::Deep);
+    let manual_workshop_path = presets::get_manual_workshop_path(app, game).ok().flatten();
+    let steam_discovery = discover_workshop_sources(game, manual_workshop_path.as_deref());
+    let mut scanned_mods = Vec::new();
+    if let Some(local_mod_folder) = mod_directory_path(game.as_str()) {
+        scanned_mods.extend(scan_local_mods(
+            &local_mod_folder,
+            &mut warnings,
+            &mut scan_context,
+        ));
+    }
+    scanned_mods.extend(scan_workshop_mods(
+        &steam_discovery.workshop_sources,
+        &mut warnings,
+        &mut scan_context,
+    ));
+
+    let mut used = HashSet::new();
+    for profile_path in profile_paths {
+        let content =
+            read_profile_content(profile_path.to_str(), &mut warnings).ok_or_else(|| {
+                format!(
+                    "The active mod list of profile {} could not be read.",
+                    profile_path.display()
+                )
+            })?;
+        let mut profile_mods = scanned_mods.clone();
+        apply_active_state(&mut profile_mods, &parse_active_mods(&content), true);
+        used.extend(
+            profile_mods
+                .iter()
+                .filter(|item| item.mod_info.enabled == Some(true))
+                .map(|item| {
+                    item.mod_info
+                        .file_path
+                        .replace('\\', "/")
+                        .to_ascii_lowercase()
+                }),
+        );
+    }
+    Ok(used)
+}
+
 fn resolve_game(
     profile_state: &AppProfileState,
     requested_game: Option<&str>,
@@ -974,6 +1039,8 @@ fn build_scanned_mod(
             manifest_present,
             duplicate_key,
             warnings,
+            workshop_updated_at: None,
+            workshop_size_bytes: None,
         },
         match_tokens,
     }
@@ -0,0 +1,811 @@
+use super::category_detector::detect_categories;
+use super::discovery::{mods_used_by_profiles, scan_inventory};
+use super::manifest_reader::{ManifestMetadata, parse_manifest_text};
+use super::models::{
+    DuplicateModEntry, GameType, ModCategory, ModDiskUsageEntry, ModDiskUsageReport,
+    ModLibraryDuplicateReport, ModLibraryInstallResult, ModLibraryMoveResult, ModLibraryMovedEntry,
+    ModLibraryRemoveResult, ModLibrarySkippedEntry, ModSource,
+};
+use crate::features::profile_cloud::logic::profile_roots;
+use crate::shared::paths::{disabled_mod_directory_path, mod_directory_path};
+use crate::state::AppProfileState;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+use tauri_plugin_dialog::DialogExt;
+use walkdir::WalkDir;
+use zip::ZipArchive;
+
+const HASHFS_MAGIC: &[u8; 4] = b"SCS#";
+const MAX_MANIFEST_BYTES: u64 = 512 * 1024;
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ArchiveInspection {
+    pub manifest_present: bool,
+    pub manifest: ManifestMetadata,
+    pub categories: Vec<ModCategory>,
+    pub warnings: Vec<String>,
+}
+
+pub fn pick_mod_archive(app: &AppHandle) -> Result<Option<PathBuf>, String> {
+    let file_path = app
+        .dialog()
+        .file()
+        .add_filter("ETS2/ATS mod", &["scs", "zip"])
+        .set_title("Install mod")
+        .blocking_pick_file();
+
+    let Some(file_path) = file_path else {
+        return Ok(None);
+    };
+
+    file_path
+        .into_path()
+        .map(Some)
+        .map_err(|_| "The selected path could not be resolved.".to_string())
+}
+
+pub fn install_mod_archive(
+    game: GameType,
+    source_path: &Path,
+    replace_existing: bool,
+) -> Result<ModLibraryInstallResult, String> {
+    if !source_path.is_file() {
+        return Err(format!("Mod file not found: {}", source_path.display()));
+    }
+    let file_name = source_path
+        .file_name()
+        .and_then(|value| value.to_str())
+        .map(str::to_string)
+        .ok_or_else(|| format!("Invalid mod file name: {}", source_path.display()))?;
+    if !is_mod_archive_name(&file_name) {
+        return Err("Only .scs and .zip mod archives can be installed.".to_string());
+    }
+
+    let inspection = inspect_mod_archive(source_path)?;
+    let mod_dir = resolve_mod_dir(game)?;
+    fs::create_dir_all(&mod_dir)
+        .map_err(|error| format!("Failed to create {}: {}", mod_dir.display(), error))?;
+
+    let source_hash = sha256_file(source_path)?;
+    let size_bytes = fs::metadata(source_path)
+        .map(|metadata| metadata.len())
+        .unwrap_or_default();
+    let target_path = mod_dir.join(&file_name);
+    let mut already_installed = false;
+    let mut replaced_path = None;
+    let mut replaces_existing = false;
+
+    if target_path.exists() {
+        if target_path.is_file() && sha256_file(&target_path)? == source_hash {
+            already_installed = true;
+        } else if !replace_existing {
+            return Err(format!(
+                "A different mod named '{}' already exists in the mod folder.",
+                file_name
+            ));
+        } else {
+            replaces_existing = true;
+        }
+    }
+
+    if !already_installed {
+        // Stage and verify the copy first so a failed install never leaves the old mod moved
+        // out of the mod folder.
+        let staging_path = mod_dir.join(format!("{}.installing", file_name));
+        fs::copy(source_path, &staging_path).map_err(|error| {
+            format!(
+                "Failed to copy {} into {}: {}",
+                source_path.display(),
+                mod_dir.display(),
+                error
+            )
+        })?;
+        if sha256_file(&staging_path)? != source_hash {
+            let _ = fs::remove_file(&staging_path);
+            return Err(
+                "The copied mod file does not match the source. Nothing was installed.".to_string(),
+            );
+        }
+        let mut moved_aside = None;
+        if replaces_existing {
+            let moved_to = resolve_disabled_dir(game)
+                .and_then(|disabled_dir| move_entry_into(&target_path, &disabled_dir))
+                .inspect_err(|_| {
+                    let _ = fs::remove_file(&staging_path);
+                })?;
+            moved_aside = Some(moved_to);
+        }
+        if let Err(error) = fs::rename(&staging_path, &target_path) {
+            let _ = fs::remove_file(&staging_path);
+            if let Some(moved_to) = &moved_aside {
+                let _ = fs::rename(moved_to, &target_path);
+            }
+            return Err(format!(
+                "Failed to finalize {}: {}",
+                target_path.display(),
+                error
+            ));
+        }
+        replaced_path = moved_aside.map(|path| path.display().to_string());
+    }
+
+    crate::dev_log!(
+        "[mod-library] install game={} file={} already_installed={} replaced={:?}",
+        game.as_str(),
+        file_name,
+        already_installed,
+        replaced_path
+    );
+
+    Ok(ModLibraryInstallResult {
+        game,
+        source_path: source_path.display().to_string(),
+        installed_path: target_path.display().to_string(),
+        file_name,
+        size_bytes,
+        sha256: source_hash,
+        manifest_present: inspection.manifest_present,
+        manifest_name: inspection
+            .manifest
+            .display_name
+            .clone()
+            .or_else(|| inspection.manifest.package_name.clone()),
+        version: inspection.manifest.version,
+        author: inspection.manifest.author,
+        categories: inspection.categories,
+        already_installed,
+        replaced_path,
+        warnings: inspection.warnings,
+    })
+}
+
+pub fn disable_mods(game: GameType, file_names: &[String]) -> Result<ModLibraryMoveResult, String> {
+    let mod_dir = resolve_mod_dir(game)?;
+    let disabled_dir = resolve_disabled_dir(game)?;
+    move_mods_between(game, &mod_dir, &disabled_dir, file_names)
+}
+
+pub fn enable_mods(game: GameType, file_names: &[String]) -> Result<ModLibraryMoveResult, String> {
+    let mod_dir = resolve_mod_dir(game)?;
+    let disabled_dir = resolve_disabled_dir(game)?;
+    let mut result = move_mods_between(game, &disabled_dir, &mod_dir, file_names)?;
+    // Keep the folder fields relative to the game, not to the move direction.
+    result.mod_folder_path = mod_dir.display().to_string();
+    result.disabled_folder_path = disabled_dir.display().to_string();
+    Ok(result)
+}
+
+/// Moves local mods that no profile of the game uses into the disabled folder. A mod only
+/// counts as unused if it is missing from the active mods of every profile, not just the
+/// current one.
+pub fn disable_unused_mods(
+    app: &AppHandle,
+    profile_state: &AppProfileState,
+    game: GameType,
+) -> Result<ModLibraryMoveResult, String> {
+    let inventory = scan_inventory(app, profile_state, Some(game.as_str()))?;
+    if !inventory.summary.active_mods_reliably_known {
+        return Err(
+            "The active mod list of the current profile is unknown. Select a profile first."
+                .to_string(),
+        );
+    }
+
+    let mut profile_paths = all_profile_dirs(game);
+    if let Some(current) = inventory.current_profile_path.as_deref() {
+        let current = PathBuf::from(current);
+        if !profile_paths.iter().any(|path| {
+            normalize_path(&path.display().to_string())
+                == normalize_path(&current.display().to_string())
+        }) {
+            profile_paths.push(current);
+        }
+    }
+    let used_by_any_profile = mods_used_by_profiles(app, game, &profile_paths)?;
+
+    let unused = inventory
+        .mods
+        .iter()
+        .filter(|item| item.source == ModSource::LocalModFolder && item.enabled == Some(false))
+        .filter(|item| !used_by_any_profile.contains(&normalize_path(&item.file_path)))
+        .filter_map(|item| {
+            Path::new(&item.file_path)
+                .file_name()
+                .and_then(|value| value.to_str())
+                .map(str::to_string)
+        })
+        .collect::<Vec<_>>();
+
+    disable_mods(game, &unused)
+}
+
+/// Every profile directory of the game, local and Steam Cloud.
+fn all_profile_dirs(game: GameType) -> Vec<PathBuf> {
+    profile_roots(game.as_str())
+        .into_iter()
+        .filter_map(|root| fs::read_dir(root.path).ok())
+        .flat_map(|entries| entries.flatten())
+        .map(|entry| entry.path())
+        .filter(|path| path.join("profile.sii").is_file())
+        .collect()
+}
+
+pub fn find_exact_duplicates(game: GameType) -> Result<ModLibraryDuplicateReport, String> {
+    let mod_dir = resolve_mod_dir(game)?;
+    let mut warnings = Vec::new();
+    let candidates = list_archive_files(&mod_dir, &mut warnings);
+    let scanned_files = candidates.len();
+    let groups = group_exact_duplicates(&candidates, &mut warnings);
+
+    let reclaimable_bytes = groups
+        .iter()
+        .map(|group| {
+            let size = fs::metadata(&group.file_paths[0])
+                .map(|metadata| metadata.len())
+                .unwrap_or_default();
+            size * (group.file_paths.len() as u64 - 1)
+        })
+        .sum();
+
+    Ok(ModLibraryDuplicateReport {
+        game,
+        mod_folder_path: mod_dir.display().to_string(),
+        scanned_files,
+        duplicate_groups: groups,
+        reclaimable_bytes,
+        warnings,
+    })
+}
+
+pub fn remove_exact_duplicates(
+    game: GameType,
+    keep_paths: &[String],
+) -> Result<ModLibraryRemoveResult, String> {
+    // Always re-hash right before deleting, a stale report must never remove a unique file.
+    let report = find_exact_duplicates(game)?;
+    let keep_set = keep_paths
+        .iter()
+        .map(|value| normalize_path(value))
+        .collect::<HashSet<_>>();
+    let mut result = ModLibraryRemoveResult {
+        game,
+        ..ModLibraryRemoveResult::default()
+    };
+
+    for group in &report.duplicate_groups {
+        let keep = group
+            .file_paths
+            .iter()
+            .find(|path| keep_set.contains(&normalize_path(path)))
+            .unwrap_or(&group.file_paths[0])
+            .clone();
+
+        for path in &group.file_paths {
+            if *path == keep {
+                continue;
+            }
+            let size = fs::metadata(path)
+                .map(|metadata| metadata.len())
+                .unwrap_or_default();
+            match fs::remove_file(path) {
+                Ok(()) => {
+                    result.freed_bytes += size;
+                    result.removed_paths.push(path.clone());
+                }
+                Err(error) => result.skipped.push(ModLibrarySkippedEntry {
+                    file_name: file_name_of(path),
+                    reason: format!("Failed to remove: {}", error),
+                }),
+            }
+        }
+        result.kept_paths.push(keep);
+    }
+
+    crate::dev_log!(
+        "[mod-library] duplicates removed game={} removed={} freed_bytes={}",
+        game.as_str(),
+        result.removed_paths.len(),
+        result.freed_bytes
+    );
+    Ok(result)
+}
+
+pub fn disk_usage_report(
+    app: &AppHandle,
+    profile_state: &AppProfileState,
+    game: GameType,
+) -> Result<ModDiskUsageReport, String> {
+    let inventory = scan_inventory(app, profile_state, Some(game.as_str()))?;
+    let mut warnings = inventory.warnings.clone();
+    let mut by_category: BTreeMap<ModCategory, ModDiskUsageEntry> = BTreeMap::new();
+    let mut local_bytes = 0u64;
+    let mut workshop_bytes = 0u64;
+
+    for item in &inventory.mods {
+        let size = entry_size(Path::new(&item.file_path));
+        match item.source {
+            ModSource::SteamWorkshop => workshop_bytes += size,
+            _ => local_bytes += size,
+        }
+        // Each mod is counted once, under its primary category, so the totals add up.
+        let category = item.categories.first().cloned().unwrap_or_default();
+        let entry = by_category
+            .entry(category.clone())
+            .or_insert_with(|| ModDiskUsageEntry {
+                category,
+                ..ModDiskUsageEntry::default()
+            });
+        entry.mod_count += 1;
+        entry.size_bytes += size;
+    }
+
+    let disabled_dir = disabled_mod_directory_path(game.as_str());
+    let mut disabled_bytes = 0u64;
+    let mut disabled_mod_count = 0usize;
+    if let Some(dir) = disabled_dir.as_ref().filter(|dir| dir.is_dir()) {
+        match fs::read_dir(dir) {
+            Ok(entries) => {
+                for entry in entries.flatten() {
+                    disabled_mod_count += 1;
+                    disabled_bytes += entry_size(&entry.path());
+                }
+            }
+            Err(error) => warnings.push(format!(
+                "Could not read disabled mod folder {}: {}",
+                dir.display(),
+                error
+            )),
+        }
+    }
+
+    let mut categories = by_category.into_values().collect::<Vec<_>>();
+    categories.sort_by(|left, right| right.size_bytes.cmp(&left.size_bytes));
+
+    Ok(ModDiskUsageReport {
+        game,
+        local_mod_folder_path: inventory.summary.local_mod_folder_path,
+        disabled_folder_path: disabled_dir.map(|path| path.display().to_string()),
+        local_bytes,
+        workshop_bytes,
+        disabled_bytes,
+        disabled_mod_count,
+        total_bytes: local_bytes + workshop_bytes + disabled_bytes,
+        categories,
+        warnings,
+    })
+}
+
+pub(crate) fn inspect_mod_archive(path: &Path) -> Result<ArchiveInspection, String> {
+    let file = File::open(path)
+        .map_err(|error| format!("Failed to open {}: {}", path.display(), error))?;
+    let mut archive = match ZipArchive::new(file) {
+        Ok(archive) => archive,
+        Err(zip_error) => {
+            if has_hashfs_magic(path) {
+                return Ok(ArchiveInspection {
+                    warnings: vec![
+                        "HashFS archive: manifest.sii could not be verified before install."
+                            .to_string(),
+                    ],
+                    categories: vec![ModCategory::Unknown],
+                    ..ArchiveInspection::default()
+                });
+            }
+            return Err(format!(
+                "{} is not a valid mod archive: {}",
+                path.display(),
+                zip_error
+            ));
+        }
+    };
+
+    let mut paths = Vec::with_capacity(archive.len());
+    let mut manifest_index = None;
+    let mut nested_manifest = false;
+    let mut nested_archives = 0usize;
+    for index in 0..archive.len() {
+        let Ok(entry) = archive.by_index(index) else {
+            continue;
+        };
+        let name = entry
+            .name()
+            .replace('\\', "/")
+            .trim_matches('/')
+            .to_ascii_lowercase();
+        if name == "manifest.sii" {
+            manifest_index = Some(index);
+        } else if name.ends_with("/manifest.sii") {
+            nested_manifest = true;
+        }
+        if name.ends_with(".scs") {
+            nested_archives += 1;
+        }
+        paths.push(name);
+    }
+
+    let Some(manifest_index) = manifest_index else {
+        if nested_archives > 0 {
+            return Err(
+                "The archive contains .scs files. Extract it and install the contained mod files instead."
+                    .to_string(),
+            );
+        }
+        if nested_manifest {
+            return Err(
+                "manifest.sii is inside a sub folder. The game only loads mods with manifest.sii at the archive root."
+                    .to_string(),
+            );
+        }
+        return Err("manifest.sii not found. This does not look like a game mod.".to_string());
+    };
+
+    let mut manifest_entry = archive
+        .by_index(manifest_index)
+        .map_err(|error| format!("manifest read failed: {}", error))?;
+    if manifest_entry.size() > MAX_MANIFEST_BYTES {
+        return Err("manifest.sii is unexpectedly large.".to_string());
+    }
+    let mut bytes = Vec::new();
+    manifest_entry
+        .read_to_end(&mut bytes)
+        .map_err(|error| format!("manifest read failed: {}", error))?;
+    let manifest = parse_manifest_text(&String::from_utf8_lossy(&bytes));
+
+    let mut warnings = Vec::new();
+    if manifest.display_name.is_none() && manifest.package_name.is_none() {
+        warnings.push("manifest.sii has no display_name.".to_string());
+    }
+    let categories = detect_categories(
+        &paths,
+        &manifest.categories,
+        &[
+            manifest.display_name.clone().unwrap_or_default(),
+            manifest.description.clone().unwrap_or_default(),
+        ],
+    );
+
+    Ok(ArchiveInspection {
+        manifest_present: true,
+        manifest,
+        categories,
+        warnings,
+    })
+}
+
+fn move_mods_between(
+    game: GameType,
+    from_dir: &Path,
+    to_dir: &Path,
+    file_names: &[String],
+) -> Result<ModLibraryMoveResult, String> {
+    let mut result = ModLibraryMoveResult {
+        game,
+        mod_folder_path: from_dir.display().to_string(),
+        disabled_folder_path: to_dir.display().to_string(),
+        ..ModLibraryMoveResult::default()
+    };
+    if file_names.is_empty() {
+        return Ok(result);
+    }
+    fs::create_dir_all(to_dir)
+        .map_err(|error| format!("Failed to create {}: {}", to_dir.display(), error))?;
+
+    for file_name in file_names {
+        if let Err(reason) = validate_entry_name(file_name) {
+            result.skipped.push(ModLibrarySkippedEntry {
+                file_name: file_name.clone(),
+                reason,
+            });
+            continue;
+        }
+        let source = from_dir.join(file_name);
+        if !source.exists() {
+            result.skipped.push(ModLibrarySkippedEntry {
+                file_name: file_name.clone(),
+                reason: "Not found.".to_string(),
+            });
+            continue;
+        }
+        if to_dir.join(file_name).exists() {
+            result.skipped.push(ModLibrarySkippedEntry {
+                file_name: file_name.clone(),
+                reason: "An entry with the same name already exists in the target folder."
+                    .to_string(),
+            });
+            continue;
+        }
+
+        let size_bytes = entry_size(&source);
+        match move_entry_into(&source, to_dir) {
+            Ok(target) => result.moved.push(ModLibraryMovedEntry {
+                file_name: file_name.clone(),
+                from_path: source.display().to_string(),
+                to_path: target.display().to_string(),
+                size_bytes,
+            }),
+            Err(reason) => result.skipped.push(ModLibrarySkippedEntry {
+                file_name: file_name.clone(),
+                reason,
+            }),
+        }
+    }
+
+    crate::dev_log!(
+        "[mod-library] move game={} from={} to={} moved={} skipped={}",
+        game.as_str(),
+        from_dir.display(),
+        to_dir.display(),
+        result.moved.len(),
+        result.skipped.len()
+    );
+    Ok(result)
+}
+
+fn move_entry_into(source: &Path, target_dir: &Path) -> Result<PathBuf, String> {
+    fs::create_dir_all(target_dir)
+        .map_err(|error| format!("Failed to create {}: {}", target_dir.display(), error))?;
+    let file_name = source
+        .file_name()
+        .ok_or_else(|| format!("Invalid path: {}", source.display()))?;
+    let mut target = target_dir.join(file_name);
+    if target.exists() {
+        let stamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+        target = target_dir.join(format!("{}_{}", stamp, file_name.to_string_lossy()));
+    }
+    fs::rename(source, &target).map_err(|error| {
+        format!(
+            "Failed to move {} to {}: {}",
+            source.display(),
+            target.display(),
+            error
+        )
+    })?;
+    Ok(target)
+}
+
+fn list_archive_files(dir: &Path, warnings: &mut Vec<String>) -> Vec<PathBuf> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(error) => {
+            warnings.push(format!("Could not read {}: {}", dir.display(), error));
+            return Vec::new();
+        }
+    };
+
+    let mut files = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|value| value.to_str())
+                .map(is_mod_archive_name)
+                .unwrap_or(false)
+        })
+        .collect::<Vec<_>>();
+    files.sort();
+    files
+}
+
+pub(crate) fn group_exact_duplicates(
+    files: &[PathBuf],
+    warnings: &mut Vec<String>,
+) -> Vec<DuplicateModEntry> {
+    let mut by_size: HashMap<u64, Vec<&PathBuf>> = HashMap::new();
+    for path in files {
+        if let Ok(metadata) = fs::metadata(path) {
+            by_size.entry(metadata.len()).or_default().push(path);
+        }
+    }
+
+    let mut by_hash: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for candidates in by_size.into_values().filter(|items| items.len() > 1) {
+        for path in candidates {
+            match sha256_file(path) {
+                Ok(hash) => by_hash
+                    .entry(hash)
+                    .or_default()
+                    .push(path.display().to_string()),
+                Err(error) => warnings.push(error),
+            }
+        }
+    }
+
+    by_hash
+        .into_iter()
+        .filter(|(_, paths)| paths.len() > 1)
+        .map(|(hash, mut paths)| {
+            paths.sort();
+            DuplicateModEntry {
+                mod_id: format!("sha256:{}", hash),
+                name: file_name_of(&paths[0]),
+                file_paths: paths,
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn sha256_file(path: &Path) -> Result<String, String> {
+    let mut file = File::open(path)
+        .map_err(|error| format!("Failed to open {}: {}", path.display(), error))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .map_err(|error| format!("Failed to read {}: {}", path.display(), error))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+pub(crate) fn entry_size(path: &Path) -> u64 {
+    if path.is_dir() {
+        return WalkDir::new(path)
+            .follow_links(false)
+            .into_iter()
+            .flatten()
+            .filter(|entry| entry.file_type().is_file())
+            .filter_map(|entry| entry.metadata().ok())
+            .map(|metadata| metadata.len())
+            .sum();
+    }
+    fs::metadata(path)
+        .map(|metadata| metadata.len())
+        .unwrap_or_default()
+}
+
+fn validate_entry_name(file_name: &str) -> Result<(), String> {
+    let trimmed = file_name.trim();
+    if trimmed.is_empty()
+        || trimmed == "."
+        || trimmed == ".."
+        || trimmed.contains(['/', '\\'])
+        || Path::new(trimmed).is_absolute()
+    {
+        return Err("Invalid mod file name.".to_string());
+    }
+    Ok(())
+}
+
+fn is_mod_archive_name(file_name: &str) -> bool {
+    let lowered = file_name.to_ascii_lowercase();
+    lowered.ends_with(".scs") || lowered.ends_with(".zip")
+}
+
+fn has_hashfs_magic(path: &Path) -> bool {
+    let mut magic = [0u8; 4];
+    File::open(path)
+        .and_then(|mut file| file.read_exact(&mut magic))
+        .map(|_| &magic == HASHFS_MAGIC)
+        .unwrap_or(false)
+}
+
+fn resolve_mod_dir(game: GameType) -> Result<PathBuf, String> {
+    mod_directory_path(game.as_str()).ok_or_else(|| {
+        format!(
+            "Mod folder for {} could not be resolved.",
+            game.display_name()
+        )
+    })
+}
+
+fn resolve_disabled_dir(game: GameType) -> Result<PathBuf, String> {
+    disabled_mod_directory_path(game.as_str()).ok_or_else(|| {
+        format!(
+            "Disabled mod folder for {} could not be resolved.",
+            game.display_name()
+        )
+    })
+}
+
+fn file_name_of(path: &str) -> String {
+    path.rsplit(['\\', '/']).next().unwrap_or(path).to_string()
+}
+
+fn normalize_path(value: &str) -> String {
+    value.trim().replace('\\', "/").to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    fn temp_dir(label: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("ets2_mod_library_{}_{}", label, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("create temp dir");
+        dir
+    }
+
+    fn write_zip(path: &Path, entries: &[(&str, &str)]) {
+        let file = File::create(path).expect("create zip");
+        let mut writer = zip::ZipWriter::new(file);
+        for (name, body) in entries {
+            writer
+                .start_file(*name, SimpleFileOptions::default())
+                .expect("start entry");
+            writer.write_all(body.as_bytes()).expect("write entry");
+        }
+        writer.finish().expect("finish zip");
+    }
+
+    #[test]
+    fn inspects_archive_with_root_manifest() {
+        let dir = temp_dir("manifest_ok");
+        let path = dir.join("trailer_pack.scs");
+        write_zip(
+            &path,
+            &[
+                (
+                    "manifest.sii",
+                    "SiiNunit\n{\nmod_package : .package_name\n{\n display_name: \"Trailer Pack\"\n version: \"1.2\"\n author: \"VTC\"\n}\n}\n",
+                ),
+                ("def/vehicle/trailer/test.sii", "SiiNunit {}"),
+            ],
+        );
+
+        let inspection = inspect_mod_archive(&path).expect("valid archive");
+        assert!(inspection.manifest_present);
+        assert_eq!(
+            inspection.manifest.display_name.as_deref(),
+            Some("Trailer Pack")
+        );
+        assert_eq!(inspection.manifest.version.as_deref(), Some("1.2"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn rejects_archive_with_nested_manifest() {
+        let dir = temp_dir("manifest_nested");
+        let path = dir.join("wrapped.zip");
+        write_zip(&path, &[("my_mod/manifest.sii", "SiiNunit {}")]);
+
+        let error = inspect_mod_archive(&path).expect_err("nested manifest must fail");
+        assert!(error.contains("sub folder"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn groups_only_byte_identical_files() {
+        let dir = temp_dir("duplicates");
+        let first = dir.join("a.scs");
+        let second = dir.join("b.scs");
+        let same_size_other = dir.join("c.scs");
+        fs::write(&first, b"identical").expect("write");
+        fs::write(&second, b"identical").expect("write");
+        fs::write(&same_size_other, b"different").expect("write");
+
+        let mut warnings = Vec::new();
+        let groups = group_exact_duplicates(
+            &[first.clone(), second.clone(), same_size_other],
+            &mut warnings,
+        );
+        assert!(warnings.is_empty());
+        assert_eq!(groups.len(), 1);
+        assert_eq!(
+            groups[0].file_paths,
+            vec![first.display().to_string(), second.display().to_string()]
+        );
+        assert!(groups[0].mod_id.starts_with("sha256:"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn rejects_entry_names_with_path_components() {
+        assert!(validate_entry_name("truck.scs").is_ok());
+        assert!(validate_entry_name("../truck.scs").is_err());
+        assert!(validate_entry_name("sub\\truck.scs").is_err());
+        assert!(validate_entry_name("..").is_err());
+    }
+}
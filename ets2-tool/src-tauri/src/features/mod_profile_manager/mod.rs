@@ -1,12 +1,17 @@
 mod category_detector;
 pub mod commands;
 mod compare;
-mod discovery;
+pub(crate) mod discovery;
 mod launcher;
+mod library;
 mod manifest_reader;
 pub mod models;
+mod preset_share;
 mod presets;
 mod sandbox;
-mod sii_mods;
-mod steam_paths;
+pub(crate) mod save_dependencies;
+pub(crate) mod sii_mods;
+pub(crate) mod steam_paths;
+mod vdf;
 mod workshop_api;
+mod workshop_cache;
@@ -98,6 +98,10 @@ pub struct DiscoveredMod {
     pub manifest_present: bool,
     pub duplicate_key: String,
     pub warnings: Vec<String>,
+    #[serde(default)]
+    pub workshop_updated_at: Option<String>,
+    #[serde(default)]
+    pub workshop_size_bytes: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -233,6 +237,265 @@ pub struct PresetCompareResult {
     pub load_order_source: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ModLibraryInstallResult {
+    pub game: GameType,
+    pub source_path: String,
+    pub installed_path: String,
+    pub file_name: String,
+    pub size_bytes: u64,
+    pub sha256: String,
+    pub manifest_present: bool,
+    pub manifest_name: Option<String>,
+    pub version: Option<String>,
+    pub author: Option<String>,
+    pub categories: Vec<ModCategory>,
+    pub already_installed: bool,
+    pub replaced_path: Option<String>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ModLibraryMovedEntry {
+    pub file_name: String,
+    pub from_path: String,
+    pub to_path: String,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ModLibrarySkippedEntry {
+    pub file_name: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ModLibraryMoveResult {
+    pub game: GameType,
+    pub mod_folder_path: String,
+    pub disabled_folder_path: String,
+    pub moved: Vec<ModLibraryMovedEntry>,
+    pub skipped: Vec<ModLibrarySkippedEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ModLibraryDuplicateReport {
+    pub game: GameType,
+    pub mod_folder_path: String,
+    pub scanned_files: usize,
+    pub duplicate_groups: Vec<DuplicateModEntry>,
+    pub reclaimable_bytes: u64,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ModLibraryRemoveResult {
+    pub game: GameType,
+    pub removed_paths: Vec<String>,
+    pub kept_paths: Vec<String>,
+    pub freed_bytes: u64,
+    pub skipped: Vec<ModLibrarySkippedEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ModDiskUsageEntry {
+    pub category: ModCategory,
+    pub mod_count: usize,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ModDiskUsageReport {
+    pub game: GameType,
+    pub local_mod_folder_path: Option<String>,
+    pub disabled_folder_path: Option<String>,
+    pub local_bytes: u64,
+    pub workshop_bytes: u64,
+    pub disabled_bytes: u64,
+    pub disabled_mod_count: usize,
+    pub total_bytes: u64,
+    pub categories: Vec<ModDiskUsageEntry>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PortablePresetMod {
+    pub load_order_index: i32,
+    pub name: String,
+    pub source: ModSource,
+    pub workshop_id: Option<String>,
+    pub app_id: Option<String>,
+    pub file_name: String,
+    pub size_bytes: Option<u64>,
+    pub sha256: Option<String>,
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PortableModPreset {
+    pub format: String,
+    pub format_version: u32,
+    pub name: String,
+    pub game: GameType,
+    pub exported_at: String,
+    pub notes: Option<String>,
+    pub preset_label: Option<String>,
+    #[serde(default = "default_load_order_unknown")]
+    pub load_order_source: String,
+    pub mods: Vec<PortablePresetMod>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PortablePresetModStatus {
+    pub entry: PortablePresetMod,
+    pub status: String,
+    pub local_mod_id: Option<String>,
+    pub local_path: Option<String>,
+    pub local_size_bytes: Option<u64>,
+    pub local_sha256: Option<String>,
+    pub local_version: Option<String>,
+    pub workshop_url: Option<String>,
+    pub steamcmd_command: Option<String>,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PortablePresetImportPreview {
+    pub source_path: String,
+    pub preset: PortableModPreset,
+    pub mods: Vec<PortablePresetModStatus>,
+    pub exact_count: usize,
+    pub version_mismatch_count: usize,
+    /// Mods found locally but without a hash in the shared preset to verify them against.
+    pub unverified_count: usize,
+    pub missing_count: usize,
+    pub bit_identical: bool,
+    pub steamcmd_commands: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PortablePresetImportResult {
+    pub preview: PortablePresetImportPreview,
+    pub preset: ModPreset,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WorkshopItemMetadata {
+    pub app_id: String,
+    pub workshop_id: String,
+    pub installed: bool,
+    pub library_path: String,
+    pub content_path: Option<String>,
+    pub size_bytes: Option<u64>,
+    pub time_updated: Option<i64>,
+    pub latest_time_updated: Option<i64>,
+    pub manifest_id: Option<String>,
+    pub needs_update: bool,
+    pub subscribed_by: Option<String>,
+    pub display_name: Option<String>,
+    pub package_name: Option<String>,
+    pub version: Option<String>,
+    pub author: Option<String>,
+    pub categories: Vec<String>,
+    pub first_seen_unix: i64,
+    pub removed_at_unix: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WorkshopAppState {
+    pub app_id: String,
+    pub library_path: String,
+    pub size_on_disk: Option<u64>,
+    pub time_last_updated: Option<i64>,
+    pub time_last_app_ran: Option<i64>,
+    pub needs_update: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WorkshopMetadataCache {
+    pub generated_at: String,
+    pub apps: Vec<WorkshopAppState>,
+    pub items: Vec<WorkshopItemMetadata>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WorkshopChangeReport {
+    pub game: GameType,
+    pub app_id: String,
+    pub last_launch_at: Option<String>,
+    pub cache_generated_at: String,
+    pub added: Vec<WorkshopItemMetadata>,
+    pub updated: Vec<WorkshopItemMetadata>,
+    pub removed: Vec<WorkshopItemMetadata>,
+    pub pending_update: Vec<WorkshopItemMetadata>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SaveModReference {
+    pub kind: String,
+    pub value: String,
+    pub unit_class: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SaveModDependency {
+    pub mod_id: String,
+    pub name: String,
+    pub file_path: String,
+    pub source: ModSource,
+    pub workshop_id: Option<String>,
+    pub reference_count: usize,
+    pub kinds: Vec<String>,
+    pub sample_references: Vec<SaveModReference>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SaveDependencyEntry {
+    pub save_path: String,
+    pub save_folder: String,
+    pub save_name: Option<String>,
+    pub reference_count: usize,
+    pub unattributed_count: usize,
+    pub dependencies: Vec<SaveModDependency>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ModSaveUsage {
+    pub mod_id: String,
+    pub name: String,
+    pub file_path: String,
+    pub source: ModSource,
+    pub workshop_id: Option<String>,
+    pub enabled: Option<bool>,
+    pub indexed: bool,
+    pub required_by_saves: Vec<String>,
+    /// `safe`, `required` or `unknown` (mod content could not be indexed)
+    pub removal_safety: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SaveDependencyReport {
+    pub game: GameType,
+    pub generated_at: String,
+    pub profile_path: Option<String>,
+    pub saves: Vec<SaveDependencyEntry>,
+    pub mods: Vec<ModSaveUsage>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ModRemovalImpact {
+    pub game: GameType,
+    pub mod_ids: Vec<String>,
+    pub affected_saves: Vec<SaveDependencyEntry>,
+    pub unknown_mod_ids: Vec<String>,
+    pub safe: bool,
+    pub warnings: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub(crate) struct ManualWorkshopPath {
     pub game: GameType,
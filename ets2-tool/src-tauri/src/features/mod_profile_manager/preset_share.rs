@@ -0,0 +1,537 @@
+use super::discovery::scan_inventory;
+use super::library::{entry_size, sha256_file};
+use super::models::{
+    DiscoveredMod, GameType, ModPreset, ModSource, PortableModPreset, PortablePresetImportPreview,
+    PortablePresetImportResult, PortablePresetMod, PortablePresetModStatus, PresetModEntry,
+};
+use super::presets;
+use super::workshop_api::{steamcmd_download_command, workshop_page_url};
+use crate::state::AppProfileState;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+use tauri_plugin_dialog::DialogExt;
+use uuid::Uuid;
+use walkdir::WalkDir;
+
+pub const PORTABLE_PRESET_FORMAT: &str = "ets2-tool.mod-preset";
+pub const PORTABLE_PRESET_FORMAT_VERSION: u32 = 1;
+
+pub fn export_portable_preset(
+    app: &AppHandle,
+    profile_state: &AppProfileState,
+    preset_id: &str,
+) -> Result<Option<String>, String> {
+    let preset = presets::find_preset(app, preset_id)?;
+    let inventory = scan_inventory(app, profile_state, Some(preset.game.as_str()))?;
+    let portable = build_portable_preset(&preset, &inventory.mods);
+
+    let file_path = app
+        .dialog()
+        .file()
+        .add_filter("Mod preset", &["json"])
+        .set_title("Share mod preset")
+        .set_file_name(&format!(
+            "{}.modpreset.json",
+            presets::sanitized_file_name(&preset.name)
+        ))
+        .blocking_save_file();
+
+    let Some(file_path) = file_path else {
+        return Ok(None);
+    };
+
+    let path = presets::file_path_to_path_buf(file_path)?;
+    let body = serde_json::to_string_pretty(&portable)
+        .map_err(|error| format!("Failed to serialize preset: {}", error))?;
+    fs::write(&path, body)
+        .map_err(|error| format!("Failed to write {}: {}", path.display(), error))?;
+    crate::dev_log!(
+        "[mod-preset-share] exported preset_id={} mods={} path={}",
+        preset.id,
+        portable.mods.len(),
+        path.display()
+    );
+    Ok(Some(path.display().to_string()))
+}
+
+pub fn pick_portable_preset(app: &AppHandle) -> Result<Option<PathBuf>, String> {
+    let file_path = app
+        .dialog()
+        .file()
+        .add_filter("Mod preset", &["json"])
+        .set_title("Import shared mod preset")
+        .blocking_pick_file();
+
+    match file_path {
+        Some(file_path) => presets::file_path_to_path_buf(file_path).map(Some),
+        None => Ok(None),
+    }
+}
+
+pub fn preview_portable_preset(
+    app: &AppHandle,
+    profile_state: &AppProfileState,
+    source_path: &Path,
+) -> Result<PortablePresetImportPreview, String> {
+    let content = fs::read_to_string(source_path)
+        .map_err(|error| format!("Failed to read {}: {}", source_path.display(), error))?;
+    let (portable, mut warnings) = parse_portable_preset(&content)?;
+    let inventory = scan_inventory(app, profile_state, Some(portable.game.as_str()))?;
+    warnings.extend(inventory.warnings);
+
+    let mods = portable
+        .mods
+        .iter()
+        .map(|entry| resolve_entry_status(entry, portable.game, &inventory.mods))
+        .collect::<Vec<_>>();
+    let exact_count = mods.iter().filter(|item| item.status == "exact").count();
+    let version_mismatch_count = mods
+        .iter()
+        .filter(|item| item.status == "version_mismatch")
+        .count();
+    let unverified_count = mods
+        .iter()
+        .filter(|item| item.status == "unverified")
+        .count();
+    let missing_count = mods.iter().filter(|item| item.status == "missing").count();
+    let steamcmd_commands = mods
+        .iter()
+        .filter(|item| item.status == "missing" || item.status == "version_mismatch")
+        .filter_map(|item| item.steamcmd_command.clone())
+        .collect();
+
+    Ok(PortablePresetImportPreview {
+        source_path: source_path.display().to_string(),
+        bit_identical: exact_count == mods.len(),
+        preset: portable,
+        mods,
+        exact_count,
+        version_mismatch_count,
+        unverified_count,
+        missing_count,
+        steamcmd_commands,
+        warnings,
+    })
+}
+
+pub fn import_portable_preset(
+    app: &AppHandle,
+    profile_state: &AppProfileState,
+    source_path: &Path,
+) -> Result<PortablePresetImportResult, String> {
+    let preview = preview_portable_preset(app, profile_state, source_path)?;
+    let timestamp = chrono::Local::now().to_rfc3339();
+    let mods = preview.mods.iter().map(preset_entry_from_status).collect();
+
+    let preset = presets::save_preset(
+        app,
+        ModPreset {
+            id: Uuid::new_v4().to_string(),
+            name: preview.preset.name.clone(),
+            game: preview.preset.game,
+            created_at: timestamp.clone(),
+            updated_at: timestamp,
+            mods,
+            notes: preview.preset.notes.clone(),
+            preset_label: preview.preset.preset_label.clone(),
+            load_order_source: preview.preset.load_order_source.clone(),
+        },
+    )?;
+    crate::dev_log!(
+        "[mod-preset-share] imported preset_id={} exact={} mismatch={} missing={}",
+        preset.id,
+        preview.exact_count,
+        preview.version_mismatch_count,
+        preview.missing_count
+    );
+    Ok(PortablePresetImportResult { preview, preset })
+}
+
+/// Accepts the portable format and, for older exports, the raw `ModPreset` JSON.
+pub(crate) fn parse_portable_preset(
+    content: &str,
+) -> Result<(PortableModPreset, Vec<String>), String> {
+    let value: serde_json::Value = serde_json::from_str(content)
+        .map_err(|error| format!("Failed to parse preset JSON: {}", error))?;
+
+    if value.get("format").and_then(|format| format.as_str()) == Some(PORTABLE_PRESET_FORMAT) {
+        let portable: PortableModPreset = serde_json::from_value(value)
+            .map_err(|error| format!("Invalid shared mod preset: {}", error))?;
+        if portable.format_version > PORTABLE_PRESET_FORMAT_VERSION {
+            return Err(format!(
+                "The preset was exported by a newer version (format {}).",
+                portable.format_version
+            ));
+        }
+        return Ok((portable, Vec::new()));
+    }
+
+    let legacy: ModPreset = serde_json::from_value(value)
+        .map_err(|error| format!("Failed to parse preset JSON: {}", error))?;
+    let mods = legacy
+        .mods
+        .iter()
+        .map(|entry| PortablePresetMod {
+            load_order_index: entry.load_order_index,
+            name: entry.name.clone(),
+            source: entry.source.clone(),
+            workshop_id: entry.workshop_id.clone(),
+            app_id: entry.app_id.clone(),
+            file_name: file_name_of(&entry.file_path),
+            size_bytes: None,
+            sha256: None,
+            version: None,
+        })
+        .collect();
+    Ok((
+        PortableModPreset {
+            format: PORTABLE_PRESET_FORMAT.to_string(),
+            format_version: PORTABLE_PRESET_FORMAT_VERSION,
+            name: legacy.name,
+            game: legacy.game,
+            exported_at: legacy.updated_at,
+            notes: legacy.notes,
+            preset_label: legacy.preset_label,
+            load_order_source: legacy.load_order_source,
+            mods,
+        },
+        vec![
+            "Legacy preset without content hashes. Mods can only be matched by id and file name."
+                .to_string(),
+        ],
+    ))
+}
+
+fn build_portable_preset(preset: &ModPreset, current_mods: &[DiscoveredMod]) -> PortableModPreset {
+    let mut entries = preset.mods.clone();
+    entries.sort_by_key(|entry| entry.load_order_index);
+
+    let mods = entries
+        .iter()
+        .map(|entry| {
+            let path = Path::new(&entry.file_path);
+            let present = path.exists();
+            let version = current_mods
+                .iter()
+                .find(|item| item.file_path == entry.file_path)
+                .and_then(|item| item.version.clone());
+            PortablePresetMod {
+                load_order_index: entry.load_order_index,
+                name: entry.name.clone(),
+                source: entry.source.clone(),
+                workshop_id: entry.workshop_id.clone(),
+                app_id: entry.app_id.clone(),
+                file_name: file_name_of(&entry.file_path),
+                size_bytes: present.then(|| entry_size(path)),
+                sha256: if present {
+                    content_hash(path).ok()
+                } else {
+                    None
+                },
+                version,
+            }
+        })
+        .collect();
+
+    PortableModPreset {
+        format: PORTABLE_PRESET_FORMAT.to_string(),
+        format_version: PORTABLE_PRESET_FORMAT_VERSION,
+        name: preset.name.clone(),
+        game: preset.game,
+        exported_at: chrono::Local::now().to_rfc3339(),
+        notes: preset.notes.clone(),
+        preset_label: preset.preset_label.clone(),
+        load_order_source: preset.load_order_source.clone(),
+        mods,
+    }
+}
+
+/// Preset entry for an imported mod. Matched mods keep the id discovery gave the local copy so
+/// the preset applies to it; missing ones fall back to their workshop id or file name.
+fn preset_entry_from_status(item: &PortablePresetModStatus) -> PresetModEntry {
+    PresetModEntry {
+        mod_id: item.local_mod_id.clone().unwrap_or_else(|| {
+            item.entry
+                .workshop_id
+                .as_ref()
+                .map(|id| format!("workshop:{}", id))
+                .unwrap_or_else(|| item.entry.file_name.clone())
+        }),
+        name: item.entry.name.clone(),
+        source: item.entry.source.clone(),
+        file_path: item
+            .local_path
+            .clone()
+            .unwrap_or_else(|| item.entry.file_name.clone()),
+        workshop_id: item.entry.workshop_id.clone(),
+        app_id: item.entry.app_id.clone(),
+        enabled: true,
+        load_order_index: item.entry.load_order_index,
+    }
+}
+
+fn resolve_entry_status(
+    entry: &PortablePresetMod,
+    game: GameType,
+    current_mods: &[DiscoveredMod],
+) -> PortablePresetModStatus {
+    let workshop_url = entry.workshop_id.as_deref().map(workshop_page_url);
+    let steamcmd_command = entry.workshop_id.as_deref().map(|id| {
+        let app_id = entry
+            .app_id
+            .as_deref()
+            .and_then(|value| value.parse::<u32>().ok())
+            .unwrap_or_else(|| game.app_id().parse().unwrap_or_default());
+        steamcmd_download_command(app_id, id)
+    });
+
+    let Some(current) = find_local_match(entry, current_mods) else {
+        return PortablePresetModStatus {
+            entry: entry.clone(),
+            status: "missing".to_string(),
+            workshop_url,
+            steamcmd_command,
+            reason: Some(if entry.workshop_id.is_some() {
+                "The workshop item is not downloaded on this machine.".to_string()
+            } else {
+                "No local mod with this file name was found.".to_string()
+            }),
+            ..PortablePresetModStatus::default()
+        };
+    };
+
+    let path = Path::new(&current.file_path);
+    let local_size = entry_size(path);
+    let size_matches = entry.size_bytes.is_none_or(|size| size == local_size);
+    // Only hash when the size already matches; a size mismatch is conclusive on its own.
+    let local_hash = if size_matches && entry.sha256.is_some() {
+        content_hash(path).ok()
+    } else {
+        None
+    };
+    let (status, reason) = classify_match(entry, local_size, local_hash.as_deref());
+
+    PortablePresetModStatus {
+        entry: entry.clone(),
+        status: status.to_string(),
+        local_mod_id: Some(current.id.clone()),
+        local_path: Some(current.file_path.clone()),
+        local_size_bytes: Some(local_size),
+        local_sha256: local_hash,
+        local_version: current.version.clone(),
+        workshop_url,
+        steamcmd_command,
+        reason,
+    }
+}
+
+pub(crate) fn classify_match(
+    entry: &PortablePresetMod,
+    local_size: u64,
+    local_hash: Option<&str>,
+) -> (&'static str, Option<String>) {
+    if let Some(expected) = entry.size_bytes {
+        if expected != local_size {
+            return (
+                "version_mismatch",
+                Some(format!(
+                    "Size differs: expected {} bytes, found {} bytes.",
+                    expected, local_size
+                )),
+            );
+        }
+    }
+
+    match (entry.sha256.as_deref(), local_hash) {
+        (Some(expected), Some(actual)) if expected.eq_ignore_ascii_case(actual) => ("exact", None),
+        (Some(_), Some(_)) => (
+            "version_mismatch",
+            Some("Content hash differs from the shared preset.".to_string()),
+        ),
+        (Some(_), None) => (
+            "version_mismatch",
+            Some("The local copy could not be hashed.".to_string()),
+        ),
+        (None, _) => (
+            "unverified",
+            Some("No hash in the shared preset; matched by identifier only.".to_string()),
+        ),
+    }
+}
+
+pub(crate) fn find_local_match<'a>(
+    entry: &PortablePresetMod,
+    current_mods: &'a [DiscoveredMod],
+) -> Option<&'a DiscoveredMod> {
+    if let Some(workshop_id) = entry.workshop_id.as_deref() {
+        let by_workshop = current_mods
+            .iter()
+            .filter(|item| item.workshop_id.as_deref() == Some(workshop_id))
+            .collect::<Vec<_>>();
+        if by_workshop.len() > 1 {
+            if let Some(item) = by_workshop
+                .iter()
+                .find(|item| file_name_of(&item.file_path).eq_ignore_ascii_case(&entry.file_name))
+            {
+                return Some(item);
+            }
+        }
+        if let Some(item) = by_workshop.first() {
+            return Some(item);
+        }
+    }
+
+    current_mods
+        .iter()
+        .filter(|item| entry.workshop_id.is_none() || item.source == ModSource::LocalModFolder)
+        .find(|item| file_name_of(&item.file_path).eq_ignore_ascii_case(&entry.file_name))
+}
+
+/// SHA-256 of an archive, or of the sorted (path, file hash) list for folder mods.
+pub(crate) fn content_hash(path: &Path) -> Result<String, String> {
+    if !path.is_dir() {
+        return sha256_file(path);
+    }
+
+    let mut files = WalkDir::new(path)
+        .follow_links(false)
+        .into_iter()
+        .flatten()
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .collect::<Vec<_>>();
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for file in files {
+        let relative = file
+            .strip_prefix(path)
+            .unwrap_or(&file)
+            .to_string_lossy()
+            .replace('\\', "/");
+        hasher.update(relative.as_bytes());
+        hasher.update([0u8]);
+        hasher.update(sha256_file(&file)?.as_bytes());
+        hasher.update([b'\n']);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn file_name_of(path: &str) -> String {
+    path.rsplit(['\\', '/']).next().unwrap_or(path).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn portable_entry(file_name: &str, workshop_id: Option<&str>) -> PortablePresetMod {
+        PortablePresetMod {
+            name: file_name.to_string(),
+            file_name: file_name.to_string(),
+            workshop_id: workshop_id.map(str::to_string),
+            app_id: Some("227300".to_string()),
+            size_bytes: Some(10),
+            sha256: Some("abc".to_string()),
+            ..PortablePresetMod::default()
+        }
+    }
+
+    fn local_mod(path: &str, workshop_id: Option<&str>, source: ModSource) -> DiscoveredMod {
+        DiscoveredMod {
+            file_path: path.to_string(),
+            workshop_id: workshop_id.map(str::to_string),
+            source,
+            ..DiscoveredMod::default()
+        }
+    }
+
+    #[test]
+    fn classifies_size_and_hash_differences() {
+        let entry = portable_entry("map.scs", None);
+        assert_eq!(classify_match(&entry, 10, Some("ABC")).0, "exact");
+        assert_eq!(classify_match(&entry, 11, None).0, "version_mismatch");
+        assert_eq!(
+            classify_match(&entry, 10, Some("def")).0,
+            "version_mismatch"
+        );
+
+        let legacy = PortablePresetMod {
+            sha256: None,
+            ..portable_entry("map.scs", None)
+        };
+        assert_eq!(classify_match(&legacy, 10, None).0, "unverified");
+    }
+
+    #[test]
+    fn steamcmd_command_uses_the_preset_game() {
+        let entry = PortablePresetMod {
+            app_id: None,
+            ..portable_entry("truck.scs", Some("42"))
+        };
+        let status = resolve_entry_status(&entry, GameType::Ats, &[]);
+        assert_eq!(status.status, "missing");
+        assert_eq!(
+            status.steamcmd_command.as_deref(),
+            Some("steamcmd +login anonymous +workshop_download_item 270880 42 +quit")
+        );
+    }
+
+    #[test]
+    fn matches_workshop_id_before_file_name() {
+        let mods = vec![
+            local_mod("C:/mod/rework.scs", None, ModSource::LocalModFolder),
+            local_mod(
+                "D:/workshop/123/universal.scs",
+                Some("123"),
+                ModSource::SteamWorkshop,
+            ),
+        ];
+        let entry = portable_entry("rework.scs", Some("123"));
+        let matched = find_local_match(&entry, &mods).expect("match");
+        assert_eq!(matched.file_path, "D:/workshop/123/universal.scs");
+
+        let local_only = portable_entry("REWORK.scs", None);
+        let matched = find_local_match(&local_only, &mods).expect("match");
+        assert_eq!(matched.file_path, "C:/mod/rework.scs");
+    }
+
+    #[test]
+    fn imported_entries_keep_the_matched_mod_id() {
+        let mut local = local_mod("C:/mod/rework.scs", None, ModSource::LocalModFolder);
+        local.id = "rework_local_key".to_string();
+        let entry = PortablePresetMod {
+            sha256: None,
+            ..portable_entry("rework.scs", None)
+        };
+        let matched = resolve_entry_status(&entry, GameType::Ets2, &[local]);
+        assert_eq!(
+            preset_entry_from_status(&matched).mod_id,
+            "rework_local_key"
+        );
+
+        let missing =
+            resolve_entry_status(&portable_entry("gone.scs", Some("77")), GameType::Ets2, &[]);
+        assert_eq!(preset_entry_from_status(&missing).mod_id, "workshop:77");
+    }
+
+    #[test]
+    fn reads_legacy_preset_json() {
+        let legacy = r#"{
+            "id": "1", "name": "Convoy", "game": "ets2",
+            "created_at": "2026-01-01", "updated_at": "2026-01-02",
+            "mods": [{
+                "mod_id": "local:x", "name": "X", "source": "LocalModFolder",
+                "file_path": "C:\\Users\\a\\Documents\\Euro Truck Simulator 2\\mod\\x.scs",
+                "workshop_id": null, "app_id": null, "enabled": true, "load_order_index": 0
+            }],
+            "notes": null, "preset_label": null
+        }"#;
+        let (portable, warnings) = parse_portable_preset(legacy).expect("legacy preset");
+        assert_eq!(portable.mods[0].file_name, "x.scs");
+        assert!(portable.mods[0].sha256.is_none());
+        assert_eq!(warnings.len(), 1);
+    }
+}
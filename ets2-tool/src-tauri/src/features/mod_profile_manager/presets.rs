@@ -201,7 +201,7 @@ fn settings_file_path(app: &AppHandle) -> Result<PathBuf, String> {
     storage_dir(app).map(|dir| dir.join(SETTINGS_FILE_NAME))
 }
 
-fn storage_dir(app: &AppHandle) -> Result<PathBuf, String> {
+pub(super) fn storage_dir(app: &AppHandle) -> Result<PathBuf, String> {
     let mut dir = app
         .path()
         .config_dir()
@@ -212,12 +212,14 @@ fn storage_dir(app: &AppHandle) -> Result<PathBuf, String> {
     Ok(dir)
 }
 
-fn file_path_to_path_buf(path: tauri_plugin_dialog::FilePath) -> Result<PathBuf, String> {
+pub(super) fn file_path_to_path_buf(
+    path: tauri_plugin_dialog::FilePath,
+) -> Result<PathBuf, String> {
     path.into_path()
         .map_err(|_| "The selected path could not be resolved.".to_string())
 }
 
-fn sanitized_file_name(value: &str) -> String {
+pub(super) fn sanitized_file_name(value: &str) -> String {
     let mut out = String::new();
     for character in value.chars() {
         if character.is_ascii_alphanumeric() || matches!(character, '-' | '_' | ' ') {
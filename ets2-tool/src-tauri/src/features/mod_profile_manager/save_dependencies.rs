@@ -0,0 +1,830 @@
+use super::discovery::scan_inventory;
+use super::models::{
+    DiscoveredMod, GameType, ModRemovalImpact, ModSaveUsage, SaveDependencyEntry,
+    SaveDependencyReport, SaveModDependency, SaveModReference,
+};
+use super::steam_paths::{discover_workshop_sources, find_game_install_dir};
+use crate::features::save_analysis::extract_save_asset_references;
+use crate::shared::decrypt::decrypt_if_needed;
+use crate::shared::extract_save_name::extract_save_name;
+use crate::shared::hashfs::{HashFsArchive, is_hashfs_archive};
+use crate::shared::paths::{game_sii_from_save, info_sii_from_save, mod_directory_path};
+use crate::state::AppProfileState;
+use chrono::Local;
+use once_cell::sync::Lazy;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use tauri::AppHandle;
+use walkdir::WalkDir;
+use zip::ZipArchive;
+
+const MAX_DEF_FILE_BYTES: u64 = 2 * 1024 * 1024;
+const MAX_SAMPLE_REFERENCES: usize = 20;
+
+/// Content indexes keyed by archive or folder path. An entry is reused while the
+/// fingerprint (size and newest modification time) of the mod is unchanged.
+static CONTENT_INDEX_CACHE: Lazy<Mutex<HashMap<PathBuf, CachedContentIndex>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+type CachedContentIndex = (ContentFingerprint, Arc<ModContentIndex>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ContentFingerprint {
+    total_bytes: u64,
+    newest_modified: Option<SystemTime>,
+    file_count: usize,
+}
+
+/// Files and unit names a single installed mod provides.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ModContentIndex {
+    pub paths: HashSet<String>,
+    pub units: HashSet<String>,
+    pub unreadable_archives: usize,
+}
+
+impl ModContentIndex {
+    pub fn indexed(&self) -> bool {
+        self.unreadable_archives == 0 && (!self.paths.is_empty() || !self.units.is_empty())
+    }
+
+    fn provides(&self, reference: &SaveModReference) -> bool {
+        match reference.kind.as_str() {
+            "truck_accessory" | "trailer_accessory" | "paint_job" | "asset" => {
+                self.paths.contains(&reference.value)
+            }
+            _ => self.units.contains(&reference.value),
+        }
+    }
+}
+
+pub fn build_dependency_report(
+    app: &AppHandle,
+    profile_state: &AppProfileState,
+    game: GameType,
+) -> Result<SaveDependencyReport, String> {
+    let inventory = scan_inventory(app, profile_state, Some(game.as_str()))?;
+    let Some(profile_path) = inventory.current_profile_path.clone() else {
+        return Err("No profile selected. Load a profile to analyze its saves.".to_string());
+    };
+
+    let mut warnings = Vec::new();
+    let indexes = inventory
+        .mods
+        .iter()
+        .map(|item| cached_content_index(Path::new(&item.file_path)))
+        .collect::<Vec<_>>();
+    for (item, index) in inventory.mods.iter().zip(&indexes) {
+        if !index.indexed() {
+            warnings.push(format!(
+                "{} could not be indexed (unreadable archive or no directory listing). Its save usage is unknown.",
+                item.name
+            ));
+        }
+    }
+    let vanilla = vanilla_content_indexes(game);
+    if vanilla.is_empty() {
+        warnings.push(
+            "The game's own archives could not be indexed. Mods that replace base game files may be reported as required."
+                .to_string(),
+        );
+    }
+
+    let saves = list_save_folders(Path::new(&profile_path))?
+        .iter()
+        .map(|save_dir| analyze_save(save_dir, &inventory.mods, &indexes, &vanilla))
+        .collect::<Vec<_>>();
+    let failed_saves = saves.iter().filter(|save| save.error.is_some()).count();
+    if failed_saves > 0 {
+        warnings.push(format!(
+            "{} save(s) could not be read. Unused mods are reported as unknown.",
+            failed_saves
+        ));
+    }
+
+    let mut mods = inventory
+        .mods
+        .iter()
+        .zip(&indexes)
+        .map(|(item, index)| build_mod_usage(item, index, &saves, failed_saves > 0))
+        .collect::<Vec<_>>();
+    mods.sort_by(|left, right| {
+        left.name
+            .to_ascii_lowercase()
+            .cmp(&right.name.to_ascii_lowercase())
+    });
+
+    crate::dev_log!(
+        "[mod-profile-manager] save dependency report game={} saves={} mods={} warnings={}",
+        game.as_str(),
+        saves.len(),
+        mods.len(),
+        warnings.len()
+    );
+
+    Ok(SaveDependencyReport {
+        game,
+        generated_at: Local::now().to_rfc3339(),
+        profile_path: Some(profile_path),
+        saves,
+        mods,
+        warnings,
+    })
+}
+
+pub fn check_removal_impact(
+    app: &AppHandle,
+    profile_state: &AppProfileState,
+    game: GameType,
+    mod_ids: &[String],
+) -> Result<ModRemovalImpact, String> {
+    let report = build_dependency_report(app, profile_state, game)?;
+    Ok(removal_impact_from_report(report, mod_ids))
+}
+
+fn removal_impact_from_report(
+    report: SaveDependencyReport,
+    mod_ids: &[String],
+) -> ModRemovalImpact {
+    let requested = mod_ids.iter().cloned().collect::<BTreeSet<_>>();
+    let unknown_mod_ids = requested
+        .iter()
+        .filter(|mod_id| {
+            report
+                .mods
+                .iter()
+                .find(|usage| &usage.mod_id == *mod_id)
+                .map(|usage| usage.removal_safety == "unknown")
+                .unwrap_or(true)
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let affected_saves = report
+        .saves
+        .into_iter()
+        .filter_map(|mut save| {
+            save.dependencies
+                .retain(|dependency| requested.contains(&dependency.mod_id));
+            if save.dependencies.is_empty() {
+                return None;
+            }
+            save.reference_count = save
+                .dependencies
+                .iter()
+                .map(|dependency| dependency.reference_count)
+                .sum();
+            Some(save)
+        })
+        .collect::<Vec<_>>();
+
+    ModRemovalImpact {
+        game: report.game,
+        mod_ids: requested.into_iter().collect(),
+        safe: affected_saves.is_empty() && unknown_mod_ids.is_empty(),
+        affected_saves,
+        unknown_mod_ids,
+        warnings: report.warnings,
+    }
+}
+
+fn build_mod_usage(
+    item: &DiscoveredMod,
+    index: &ModContentIndex,
+    saves: &[SaveDependencyEntry],
+    saves_incomplete: bool,
+) -> ModSaveUsage {
+    let required_by_saves = saves
+        .iter()
+        .filter(|save| {
+            save.dependencies
+                .iter()
+                .any(|dependency| dependency.mod_id == item.id)
+        })
+        .map(|save| {
+            save.save_name
+                .clone()
+                .unwrap_or_else(|| save.save_folder.clone())
+        })
+        .collect::<Vec<_>>();
+
+    let removal_safety = if !required_by_saves.is_empty() {
+        "required"
+    } else if !index.indexed() || saves_incomplete {
+        "unknown"
+    } else {
+        "safe"
+    };
+
+    ModSaveUsage {
+        mod_id: item.id.clone(),
+        name: item.name.clone(),
+        file_path: item.file_path.clone(),
+        source: item.source.clone(),
+        workshop_id: item.workshop_id.clone(),
+        enabled: item.enabled,
+        indexed: index.indexed(),
+        required_by_saves,
+        removal_safety: removal_safety.to_string(),
+    }
+}
+
+fn list_save_folders(profile_path: &Path) -> Result<Vec<PathBuf>, String> {
+    let save_root = profile_path.join("save");
+    let entries = fs::read_dir(&save_root)
+        .map_err(|error| format!("Failed to read {}: {}", save_root.display(), error))?;
+    let mut saves = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir() && game_sii_from_save(path).is_file())
+        .collect::<Vec<_>>();
+    saves.sort();
+    Ok(saves)
+}
+
+fn analyze_save(
+    save_dir: &Path,
+    mods: &[DiscoveredMod],
+    indexes: &[Arc<ModContentIndex>],
+    vanilla: &[Arc<ModContentIndex>],
+) -> SaveDependencyEntry {
+    let mut entry = SaveDependencyEntry {
+        save_path: save_dir.display().to_string(),
+        save_folder: save_dir
+            .file_name()
+            .map(|value| value.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        save_name: decrypt_if_needed(&info_sii_from_save(save_dir))
+            .ok()
+            .and_then(|content| extract_save_name(&content)),
+        ..SaveDependencyEntry::default()
+    };
+
+    match decrypt_if_needed(&game_sii_from_save(save_dir)) {
+        Ok(content) => {
+            let references = without_vanilla_references(extract_save_references(&content), vanilla);
+            let (dependencies, unattributed_count) =
+                attribute_references(&references, mods, indexes);
+            entry.reference_count = references.len();
+            entry.unattributed_count = unattributed_count;
+            entry.dependencies = dependencies;
+        }
+        Err(error) => {
+            crate::dev_log!(
+                "[mod-profile-manager] save dependency read failed path={} error={}",
+                save_dir.display(),
+                error
+            );
+            entry.error = Some(error);
+        }
+    }
+
+    entry
+}
+
+/// Collects every reference in `game.sii` a mod could provide: accessory and paint job
+/// data paths and other assets (via the save analyzer's extractor), cargo and trailer
+/// definitions, companies and cities.
+pub(crate) fn extract_save_references(content: &str) -> Vec<SaveModReference> {
+    let mut references = BTreeMap::<(String, String), String>::new();
+    for path in extract_save_asset_references(content) {
+        references
+            .entry((path_reference_kind(&path).to_string(), path))
+            .or_default();
+    }
+
+    let mut unit_class = String::new();
+    let mut push = |kind: &str, value: String, unit_class: &str| {
+        references
+            .entry((kind.to_string(), value))
+            .or_insert_with(|| unit_class.to_string());
+    };
+    for raw_line in content.lines() {
+        let line = raw_line.trim();
+        if line == "}" {
+            unit_class.clear();
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let key = key.trim();
+        let value = value.trim();
+
+        if let Some(unit_id) = value.strip_suffix('{') {
+            unit_class = key.to_string();
+            if let Some(company) = unit_id.trim().strip_prefix("company.volatile.") {
+                push_company_references(company, &unit_class, &mut push);
+            }
+            continue;
+        }
+
+        let field = key.split('[').next().unwrap_or(key);
+        if let Some(quoted) = value
+            .strip_prefix('"')
+            .and_then(|inner| inner.strip_suffix('"'))
+        {
+            if field == "target" && !quoted.is_empty() {
+                push_company_references(quoted, &unit_class, &mut push);
+            }
+            continue;
+        }
+
+        let token = value.to_ascii_lowercase();
+        if token.starts_with("cargo.") {
+            push("cargo", token, &unit_class);
+        } else if token.starts_with("trailer_def.") {
+            push("trailer_definition", token, &unit_class);
+        } else if let Some(company) = token.strip_prefix("company.volatile.") {
+            push_company_references(company, &unit_class, &mut push);
+        }
+    }
+
+    references
+        .into_iter()
+        .map(|((kind, value), unit_class)| SaveModReference {
+            kind,
+            value,
+            unit_class,
+        })
+        .collect()
+}
+
+/// Drops references the base game and its DLCs already provide. A mod that only replaces
+/// such a file is not required by the save: without it the game falls back to its own copy.
+fn without_vanilla_references(
+    references: Vec<SaveModReference>,
+    vanilla: &[Arc<ModContentIndex>],
+) -> Vec<SaveModReference> {
+    references
+        .into_iter()
+        .filter(|reference| !vanilla.iter().any(|index| index.provides(reference)))
+        .collect()
+}
+
+/// `tesco.berlin` refers to both the company definition and the city.
+fn push_company_references(
+    company_and_city: &str,
+    unit_class: &str,
+    push: &mut impl FnMut(&str, String, &str),
+) {
+    let normalized = company_and_city.trim().to_ascii_lowercase();
+    let Some((company, city)) = normalized.split_once('.') else {
+        return;
+    };
+    if company.is_empty() || city.is_empty() {
+        return;
+    }
+    push(
+        "company",
+        format!("company.permanent.{}", company),
+        unit_class,
+    );
+    push("city", format!("city.{}", city), unit_class);
+}
+
+fn path_reference_kind(path: &str) -> &'static str {
+    if path.contains("/paint_job/") {
+        "paint_job"
+    } else if path.starts_with("/def/vehicle/truck/") {
+        "truck_accessory"
+    } else if path.starts_with("/def/vehicle/trailer") {
+        "trailer_accessory"
+    } else {
+        "asset"
+    }
+}
+
+pub(crate) fn attribute_references(
+    references: &[SaveModReference],
+    mods: &[DiscoveredMod],
+    indexes: &[Arc<ModContentIndex>],
+) -> (Vec<SaveModDependency>, usize) {
+    let mut dependencies = BTreeMap::<usize, SaveModDependency>::new();
+    let mut unattributed_count = 0usize;
+
+    for reference in references {
+        let providers = indexes
+            .iter()
+            .enumerate()
+            .filter(|(_, index)| index.provides(reference))
+            .map(|(position, _)| position)
+            .collect::<Vec<_>>();
+        if providers.is_empty() {
+            unattributed_count += 1;
+            continue;
+        }
+
+        for position in providers {
+            let item = &mods[position];
+            let dependency = dependencies
+                .entry(position)
+                .or_insert_with(|| SaveModDependency {
+                    mod_id: item.id.clone(),
+                    name: item.name.clone(),
+                    file_path: item.file_path.clone(),
+                    source: item.source.clone(),
+                    workshop_id: item.workshop_id.clone(),
+                    ..SaveModDependency::default()
+                });
+            dependency.reference_count += 1;
+            if !dependency.kinds.contains(&reference.kind) {
+                dependency.kinds.push(reference.kind.clone());
+            }
+            if dependency.sample_references.len() < MAX_SAMPLE_REFERENCES {
+                dependency.sample_references.push(reference.clone());
+            }
+        }
+    }
+
+    let mut dependencies = dependencies.into_values().collect::<Vec<_>>();
+    dependencies.sort_by(|left, right| right.reference_count.cmp(&left.reference_count));
+    (dependencies, unattributed_count)
+}
+
+/// Indexes of every mod the game can load, gathered without the mod inventory: archives and
+/// folders in the local mod directory plus every Workshop item folder. The second value counts
+/// mods that could not be indexed.
+pub(crate) fn installed_content_indexes(game: GameType) -> (Vec<Arc<ModContentIndex>>, usize) {
+    let mut sources = Vec::new();
+    if let Some(entries) = mod_directory_path(game.as_str()).and_then(|dir| fs::read_dir(dir).ok())
+    {
+        sources.extend(
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir() || is_archive(path)),
+        );
+    }
+    for source in discover_workshop_sources(game, None)
+        .workshop_sources
+        .iter()
+        .filter(|source| source.exists)
+    {
+        if let Ok(entries) = fs::read_dir(&source.path) {
+            sources.extend(
+                entries
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .filter(|path| path.is_dir()),
+            );
+        }
+    }
+    sources.sort();
+
+    let indexes = sources
+        .iter()
+        .map(|path| cached_content_index(path))
+        .collect::<Vec<_>>();
+    let unindexed = indexes.iter().filter(|index| !index.indexed()).count();
+    (indexes, unindexed)
+}
+
+/// Index of the base game archives (`base.scs`, `def.scs`, DLC archives, ...) in the install
+/// directory; empty if the game cannot be found.
+pub(crate) fn vanilla_content_indexes(game: GameType) -> Vec<Arc<ModContentIndex>> {
+    let Some(game_dir) = find_game_install_dir(game) else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(&game_dir) else {
+        return Vec::new();
+    };
+    let mut archives = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && is_archive(path))
+        .collect::<Vec<_>>();
+    archives.sort();
+    archives
+        .iter()
+        .map(|path| cached_content_index(path))
+        .filter(|index| index.indexed())
+        .collect()
+}
+
+fn cached_content_index(path: &Path) -> Arc<ModContentIndex> {
+    let fingerprint = content_fingerprint(path);
+    let cached = CONTENT_INDEX_CACHE.lock().ok().and_then(|cache| {
+        cache
+            .get(path)
+            .filter(|(cached_fingerprint, _)| *cached_fingerprint == fingerprint)
+            .map(|(_, index)| Arc::clone(index))
+    });
+    if let Some(index) = cached {
+        return index;
+    }
+
+    let index = Arc::new(index_mod_content(path));
+    if let Ok(mut cache) = CONTENT_INDEX_CACHE.lock() {
+        cache.insert(path.to_path_buf(), (fingerprint, Arc::clone(&index)));
+    }
+    index
+}
+
+fn content_fingerprint(path: &Path) -> ContentFingerprint {
+    let mut fingerprint = ContentFingerprint {
+        total_bytes: 0,
+        newest_modified: None,
+        file_count: 0,
+    };
+    let files = WalkDir::new(path)
+        .follow_links(false)
+        .into_iter()
+        .flatten()
+        .filter(|entry| entry.file_type().is_file());
+    for metadata in files.filter_map(|entry| entry.metadata().ok()) {
+        fingerprint.total_bytes += metadata.len();
+        fingerprint.file_count += 1;
+        fingerprint.newest_modified = fingerprint.newest_modified.max(metadata.modified().ok());
+    }
+    fingerprint
+}
+
+pub(crate) fn index_mod_content(path: &Path) -> ModContentIndex {
+    let mut index = ModContentIndex::default();
+
+    if path.is_dir() {
+        for entry in WalkDir::new(path)
+            .follow_links(false)
+            .into_iter()
+            .flatten()
+            .filter(|entry| entry.file_type().is_file())
+        {
+            let file_path = entry.path();
+            if is_archive(file_path) {
+                if index_any_archive(file_path, &mut index).is_err() {
+                    index.unreadable_archives += 1;
+                }
+                continue;
+            }
+            let Ok(relative) = file_path.strip_prefix(path) else {
+                continue;
+            };
+            let normalized = normalize_content_path(&relative.to_string_lossy());
+            if is_definition_file(&normalized)
+                && entry
+                    .metadata()
+                    .map(|metadata| metadata.len() <= MAX_DEF_FILE_BYTES)
+                    .unwrap_or(false)
+            {
+                if let Ok(bytes) = fs::read(file_path) {
+                    collect_unit_names(&String::from_utf8_lossy(&bytes), &mut index.units);
+                }
+            }
+            index.paths.insert(normalized);
+        }
+    } else if index_any_archive(path, &mut index).is_err() {
+        index.unreadable_archives += 1;
+    }
+
+    index
+}
+
+fn index_any_archive(path: &Path, index: &mut ModContentIndex) -> Result<(), String> {
+    if is_hashfs_archive(path) {
+        index_hashfs_archive(path, index)
+    } else {
+        index_archive(path, index)
+    }
+}
+
+fn index_hashfs_archive(path: &Path, index: &mut ModContentIndex) -> Result<(), String> {
+    let archive = HashFsArchive::open(path)?;
+    let files = archive.list_files()?;
+    if files.is_empty() {
+        return Err(format!("{} has no directory listing.", path.display()));
+    }
+    for file in files {
+        let normalized = normalize_content_path(&file);
+        let definition = is_definition_file(&normalized)
+            .then(|| archive.read_file(&file).ok().flatten())
+            .flatten()
+            .filter(|bytes| bytes.len() as u64 <= MAX_DEF_FILE_BYTES);
+        if let Some(bytes) = definition {
+            collect_unit_names(&String::from_utf8_lossy(&bytes), &mut index.units);
+        }
+        index.paths.insert(normalized);
+    }
+    Ok(())
+}
+
+fn index_archive(path: &Path, index: &mut ModContentIndex) -> Result<(), String> {
+    let file = File::open(path)
+        .map_err(|error| format!("Failed to open {}: {}", path.display(), error))?;
+    let mut archive = ZipArchive::new(file)
+        .map_err(|error| format!("{} is not a zip archive: {}", path.display(), error))?;
+
+    for position in 0..archive.len() {
+        let Ok(mut entry) = archive.by_index(position) else {
+            continue;
+        };
+        if entry.is_dir() {
+            continue;
+        }
+        let normalized = normalize_content_path(entry.name());
+        if is_definition_file(&normalized) && entry.size() <= MAX_DEF_FILE_BYTES {
+            let mut bytes = Vec::new();
+            if entry.read_to_end(&mut bytes).is_ok() {
+                collect_unit_names(&String::from_utf8_lossy(&bytes), &mut index.units);
+            }
+        }
+        index.paths.insert(normalized);
+    }
+
+    Ok(())
+}
+
+/// Unit declarations look like `cargo_data : cargo.apples {` (brace optionally
+/// on the next line). Attribute lines never open a block.
+fn collect_unit_names(text: &str, units: &mut HashSet<String>) {
+    let mut lines = text.lines().map(str::trim).peekable();
+    while let Some(line) = lines.next() {
+        let Some((class, name)) = line.split_once(':') else {
+            continue;
+        };
+        let class = class.trim();
+        if class.is_empty()
+            || !class
+                .chars()
+                .all(|character| character.is_ascii_alphanumeric() || character == '_')
+        {
+            continue;
+        }
+        let (name, opens_block) = match name.trim().strip_suffix('{') {
+            Some(name) => (name.trim(), true),
+            None => (name.trim(), false),
+        };
+        if !name.contains('.') || name.contains(['"', ' ', '\t']) {
+            continue;
+        }
+        if opens_block || lines.peek().is_some_and(|next| next.starts_with('{')) {
+            units.insert(name.to_ascii_lowercase());
+        }
+    }
+}
+
+fn is_definition_file(path: &str) -> bool {
+    path.starts_with("/def/") && (path.ends_with(".sii") || path.ends_with(".sui"))
+}
+
+fn is_archive(path: &Path) -> bool {
+    path.extension()
+        .and_then(|value| value.to_str())
+        .map(|value| matches!(value.to_ascii_lowercase().as_str(), "scs" | "zip"))
+        .unwrap_or(false)
+}
+
+fn normalize_content_path(path: &str) -> String {
+    format!(
+        "/{}",
+        path.replace('\\', "/")
+            .trim_matches('/')
+            .to_ascii_lowercase()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAVE_FIXTURE: &str = r#"SiiNunit
+{
+vehicle_accessory : _nameless.1a.2b {
+ data_path: "/def/vehicle/truck/custom.truck/cabin/big.sii"
+}
+vehicle_paint_job_accessory : _nameless.1a.2c {
+ data_path: "/def/vehicle/truck/custom.truck/paint_job/flames.sii"
+}
+job_offer_data : _nameless.1a.2d {
+ target: "fancy_co.oslo"
+ cargo: cargo.apples
+ trailer_definition: trailer_def.krone.curtain
+}
+company : company.volatile.fancy_co.oslo {
+ permanent_data: company.permanent.fancy_co
+}
+}
+"#;
+
+    #[test]
+    fn extracts_paths_cargo_and_company_tokens() {
+        let references = extract_save_references(SAVE_FIXTURE);
+        let pairs = references
+            .iter()
+            .map(|reference| (reference.kind.as_str(), reference.value.as_str()))
+            .collect::<Vec<_>>();
+
+        assert!(pairs.contains(&("cargo", "cargo.apples")));
+        assert!(pairs.contains(&("city", "city.oslo")));
+        assert!(pairs.contains(&("company", "company.permanent.fancy_co")));
+        assert!(pairs.contains(&("trailer_definition", "trailer_def.krone.curtain")));
+        assert!(pairs.contains(&(
+            "truck_accessory",
+            "/def/vehicle/truck/custom.truck/cabin/big.sii"
+        )));
+        assert!(pairs.contains(&(
+            "paint_job",
+            "/def/vehicle/truck/custom.truck/paint_job/flames.sii"
+        )));
+        assert_eq!(
+            pairs.iter().filter(|(kind, _)| *kind == "company").count(),
+            1
+        );
+    }
+
+    #[test]
+    fn attributes_references_to_providing_mods() {
+        let root =
+            std::env::temp_dir().join(format!("ets2-tool-save-deps-{}", uuid::Uuid::new_v4()));
+        let cargo_mod = root.join("cargo_pack");
+        fs::create_dir_all(cargo_mod.join("def/cargo")).expect("create mod dir");
+        fs::write(
+            cargo_mod.join("def/cargo/apples.sii"),
+            "SiiNunit\n{\ncargo_data : cargo.apples\n{\n mass: 10\n}\n}\n",
+        )
+        .expect("write cargo def");
+        let truck_mod = root.join("truck");
+        fs::create_dir_all(truck_mod.join("def/vehicle/truck/custom.truck/cabin"))
+            .expect("create truck dir");
+        fs::write(
+            truck_mod.join("def/vehicle/truck/custom.truck/cabin/big.sii"),
+            "SiiNunit\n{\n}\n",
+        )
+        .expect("write accessory");
+
+        let mods = vec![
+            DiscoveredMod {
+                id: "cargo".to_string(),
+                name: "Cargo pack".to_string(),
+                file_path: cargo_mod.display().to_string(),
+                ..DiscoveredMod::default()
+            },
+            DiscoveredMod {
+                id: "truck".to_string(),
+                name: "Truck".to_string(),
+                file_path: truck_mod.display().to_string(),
+                ..DiscoveredMod::default()
+            },
+        ];
+        let indexes = mods
+            .iter()
+            .map(|item| Arc::new(index_mod_content(Path::new(&item.file_path))))
+            .collect::<Vec<_>>();
+        let references = extract_save_references(SAVE_FIXTURE);
+        let (dependencies, unattributed) = attribute_references(&references, &mods, &indexes);
+
+        let ids = dependencies
+            .iter()
+            .map(|dependency| dependency.mod_id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids.len(), 2);
+        assert!(ids.contains(&"cargo") && ids.contains(&"truck"));
+        assert_eq!(unattributed, references.len() - 2);
+
+        let saves = vec![SaveDependencyEntry {
+            save_folder: "1".to_string(),
+            dependencies,
+            ..SaveDependencyEntry::default()
+        }];
+        let usage = build_mod_usage(&mods[0], &indexes[0], &saves, false);
+        assert_eq!(usage.removal_safety, "required");
+
+        let report = SaveDependencyReport {
+            saves,
+            mods: vec![usage],
+            ..SaveDependencyReport::default()
+        };
+        let impact = removal_impact_from_report(report, &["cargo".to_string()]);
+        assert!(!impact.safe);
+        assert_eq!(impact.affected_saves.len(), 1);
+        assert_eq!(impact.affected_saves[0].dependencies.len(), 1);
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn references_the_base_game_provides_are_not_dependencies() {
+        let vanilla = ModContentIndex {
+            paths: HashSet::from(["/def/vehicle/truck/custom.truck/cabin/big.sii".to_string()]),
+            units: HashSet::from(["cargo.apples".to_string()]),
+            unreadable_archives: 0,
+        };
+        let references =
+            without_vanilla_references(extract_save_references(SAVE_FIXTURE), &[Arc::new(vanilla)]);
+        assert!(
+            references
+                .iter()
+                .all(|reference| reference.value != "cargo.apples"
+                    && reference.kind != "truck_accessory")
+        );
+        assert!(
+            references
+                .iter()
+                .any(|reference| reference.kind == "paint_job")
+        );
+    }
+}
@@ -853,7 +853,7 @@ fn active_mod_entry_regex() -> Result<Regex, String> {
         .map_err(|error| error.to_string())
 }
 
-fn workshop_mod_ref_to_id(mod_ref: &str) -> Option<u64> {
+pub(crate) fn workshop_mod_ref_to_id(mod_ref: &str) -> Option<u64> {
     mod_ref
         .strip_prefix("mod_workshop_package.")
         .or_else(|| mod_ref.strip_prefix("workshop_package."))
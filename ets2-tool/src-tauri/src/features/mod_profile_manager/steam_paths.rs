@@ -1,5 +1,5 @@
 use super::models::{GameType, WorkshopFolderSource};
-use regex::Regex;
+use super::vdf::{VdfValue, parse_vdf};
 use std::collections::BTreeSet;
 use std::env;
 use std::fs;
@@ -46,6 +46,19 @@ pub fn get_steam_library_dirs() -> Result<Vec<PathBuf>, String> {
     })
 }
 
+/// Install directory of the game in any Steam library, identified by its base archive.
+pub fn find_game_install_dir(game: GameType) -> Option<PathBuf> {
+    let folder = match game {
+        GameType::Ets2 => "Euro Truck Simulator 2",
+        GameType::Ats => "American Truck Simulator",
+    };
+    get_steam_library_dirs()
+        .ok()?
+        .into_iter()
+        .map(|library| library.join("steamapps").join("common").join(folder))
+        .find(|dir| dir.join("base.scs").is_file() || dir.join("def.scs").is_file())
+}
+
 pub fn discover_workshop_sources(game: GameType, manual_path: Option<&str>) -> SteamDiscovery {
     let mut warnings = Vec::new();
     let steam_root = find_steam_install_dir_with_warnings(&mut warnings);
@@ -249,143 +262,32 @@ fn read_library_folders(vdf_path: &Path) -> Result<Vec<LibraryFolderEntry>, Stri
 }
 
 fn parse_library_folders_vdf(content: &str) -> Vec<LibraryFolderEntry> {
-    let direct_value_regex = Regex::new(r#"^\s*"(\d+)"\s*"([^"]+)""#).ok();
-    let block_header_open_regex = Regex::new(r#"^\s*"(\d+)"\s*\{\s*$"#).ok();
-    let block_header_regex = Regex::new(r#"^\s*"(\d+)"\s*$"#).ok();
-    let path_regex = Regex::new(r#"^\s*"path"\s*"([^"]+)""#).ok();
-    let apps_open_regex = Regex::new(r#"^\s*"apps"\s*\{\s*$"#).ok();
-    let apps_regex = Regex::new(r#"^\s*"apps"\s*$"#).ok();
-    let app_entry_regex = Regex::new(r#"^\s*"(\d+)"\s*"([^"]*)""#).ok();
-
-    #[derive(Debug, Default)]
-    struct PendingLibrary {
-        path: Option<PathBuf>,
-        app_ids: BTreeSet<String>,
-    }
-
-    let mut libraries = Vec::new();
-    let mut current_library = None::<PendingLibrary>;
-    let mut pending_library_block = false;
-    let mut pending_apps_block = false;
-    let mut block_stack = Vec::<&str>::new();
-
-    for line in content.lines() {
-        let trimmed = line.trim();
-        if trimmed.is_empty() {
-            continue;
-        }
-
-        if let Some(regex) = direct_value_regex.as_ref() {
-            if let Some(captures) = regex.captures(trimmed) {
-                let inside_apps = block_stack.last().copied() == Some("apps");
-                if current_library.is_none() && !inside_apps {
-                    let path = captures
-                        .get(2)
-                        .map(|value| normalize_vdf_path(value.as_str()))
-                        .unwrap_or_default();
-                    if !path.as_os_str().is_empty() {
-                        libraries.push(LibraryFolderEntry {
-                            path,
-                            app_ids: BTreeSet::new(),
-                        });
-                    }
-                    continue;
-                }
-            }
-        }
-
-        if let Some(regex) = block_header_open_regex.as_ref() {
-            if regex.is_match(trimmed) {
-                current_library = Some(PendingLibrary::default());
-                block_stack.push("library");
-                continue;
-            }
-        }
-
-        if let Some(regex) = block_header_regex.as_ref() {
-            if regex.is_match(trimmed) {
-                pending_library_block = true;
-                continue;
-            }
-        }
-
-        if let Some(regex) = path_regex.as_ref() {
-            if let Some(captures) = regex.captures(trimmed) {
-                if let Some(library) = current_library.as_mut() {
-                    library.path = captures
-                        .get(1)
-                        .map(|value| normalize_vdf_path(value.as_str()));
-                }
-                continue;
-            }
-        }
-
-        if let Some(regex) = apps_open_regex.as_ref() {
-            if regex.is_match(trimmed) {
-                pending_apps_block = false;
-                block_stack.push("apps");
-                continue;
-            }
-        }
-
-        if let Some(regex) = apps_regex.as_ref() {
-            if regex.is_match(trimmed) {
-                pending_apps_block = true;
-                continue;
-            }
-        }
-
-        if let Some(regex) = app_entry_regex.as_ref() {
-            if let Some(captures) = regex.captures(trimmed) {
-                let inside_apps = block_stack.last().copied() == Some("apps");
-                if inside_apps {
-                    if let Some(library) = current_library.as_mut() {
-                        if let Some(app_id) =
-                            captures.get(1).map(|value| value.as_str().to_string())
-                        {
-                            library.app_ids.insert(app_id);
-                        }
-                    }
-                }
-                continue;
-            }
-        }
-
-        if trimmed == "{" {
-            if pending_library_block {
-                current_library = Some(PendingLibrary::default());
-                block_stack.push("library");
-                pending_library_block = false;
-                continue;
-            }
-            if pending_apps_block {
-                block_stack.push("apps");
-                pending_apps_block = false;
-                continue;
-            }
-            block_stack.push("other");
-            continue;
-        }
-
-        if trimmed == "}" {
-            if let Some(block) = block_stack.pop() {
-                if block == "library" {
-                    if let Some(library) = current_library.take() {
-                        if let Some(path) =
-                            library.path.filter(|value| !value.as_os_str().is_empty())
-                        {
-                            libraries.push(LibraryFolderEntry {
-                                path,
-                                app_ids: library.app_ids,
-                            });
-                        }
-                    }
-                }
-            }
-        }
-    }
+    let Ok(document) = parse_vdf(content) else {
+        return Vec::new();
+    };
+    let Some(root) = document.get("libraryfolders") else {
+        return Vec::new();
+    };
 
-    libraries
+    // Old files map "1" directly to a path, newer ones hold a block with "path" and "apps".
+    root.entries()
+        .iter()
+        .filter(|(key, _)| key.chars().all(|character| character.is_ascii_digit()))
+        .filter_map(|(_, value)| match value {
+            VdfValue::Text(path) => Some(LibraryFolderEntry {
+                path: normalize_vdf_path(path),
+                app_ids: BTreeSet::new(),
+            }),
+            VdfValue::Block(_) => value.text("path").map(|path| LibraryFolderEntry {
+                path: normalize_vdf_path(path),
+                app_ids: value
+                    .get("apps")
+                    .map(|apps| apps.entries().iter().map(|(id, _)| id.clone()).collect())
+                    .unwrap_or_default(),
+            }),
+        })
+        .filter(|entry| !entry.path.as_os_str().is_empty())
+        .collect()
 }
 
 fn push_library_entry(
@@ -0,0 +1,189 @@
+/// Minimal Valve KeyValues (VDF/ACF) reader. Keys are matched case-insensitively,
+/// which mirrors how Steam itself treats them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VdfValue {
+    Text(String),
+    Block(Vec<(String, VdfValue)>),
+}
+
+impl VdfValue {
+    pub fn get(&self, key: &str) -> Option<&VdfValue> {
+        match self {
+            Self::Block(entries) => entries
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(key))
+                .map(|(_, value)| value),
+            Self::Text(_) => None,
+        }
+    }
+
+    pub fn text(&self, key: &str) -> Option<&str> {
+        match self.get(key)? {
+            Self::Text(value) => Some(value.as_str()),
+            Self::Block(_) => None,
+        }
+    }
+
+    pub fn entries(&self) -> &[(String, VdfValue)] {
+        match self {
+            Self::Block(entries) => entries,
+            Self::Text(_) => &[],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Text(String),
+    Open,
+    Close,
+}
+
+/// Parses a document into its root block (`"AppWorkshop" { ... }` becomes one entry).
+pub fn parse_vdf(content: &str) -> Result<VdfValue, String> {
+    let tokens = tokenize(content)?;
+    let mut position = 0usize;
+    let root = parse_block(&tokens, &mut position, false)?;
+    Ok(VdfValue::Block(root))
+}
+
+fn parse_block(
+    tokens: &[Token],
+    position: &mut usize,
+    nested: bool,
+) -> Result<Vec<(String, VdfValue)>, String> {
+    let mut entries = Vec::new();
+    while *position < tokens.len() {
+        match &tokens[*position] {
+            Token::Close => {
+                if !nested {
+                    return Err("Unexpected '}' in VDF document.".to_string());
+                }
+                *position += 1;
+                return Ok(entries);
+            }
+            Token::Open => return Err("Unexpected '{' without key in VDF document.".to_string()),
+            Token::Text(key) => {
+                let key = key.clone();
+                *position += 1;
+                match tokens.get(*position) {
+                    Some(Token::Text(value)) => {
+                        entries.push((key, VdfValue::Text(value.clone())));
+                        *position += 1;
+                    }
+                    Some(Token::Open) => {
+                        *position += 1;
+                        let children = parse_block(tokens, position, true)?;
+                        entries.push((key, VdfValue::Block(children)));
+                    }
+                    _ => return Err(format!("Missing value for VDF key '{}'.", key)),
+                }
+            }
+        }
+    }
+
+    if nested {
+        return Err("Unterminated block in VDF document.".to_string());
+    }
+    Ok(entries)
+}
+
+fn tokenize(content: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = content.chars().peekable();
+
+    while let Some(character) = chars.next() {
+        match character {
+            '{' => tokens.push(Token::Open),
+            '}' => tokens.push(Token::Close),
+            '"' => {
+                let mut value = String::new();
+                let mut closed = false;
+                while let Some(next) = chars.next() {
+                    match next {
+                        '"' => {
+                            closed = true;
+                            break;
+                        }
+                        '\\' => match chars.next() {
+                            Some('n') => value.push('\n'),
+                            Some('t') => value.push('\t'),
+                            Some(other) => value.push(other),
+                            None => break,
+                        },
+                        other => value.push(other),
+                    }
+                }
+                if !closed {
+                    return Err("Unterminated string in VDF document.".to_string());
+                }
+                tokens.push(Token::Text(value));
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        break;
+                    }
+                }
+            }
+            // Conditionals like [$WIN32] are not used in the files we read.
+            '[' => {
+                for next in chars.by_ref() {
+                    if next == ']' {
+                        break;
+                    }
+                }
+            }
+            character if character.is_whitespace() => {}
+            _ => {
+                let mut value = String::from(character);
+                while let Some(next) = chars.peek() {
+                    if next.is_whitespace() || matches!(next, '{' | '}' | '"') {
+                        break;
+                    }
+                    value.push(*next);
+                    chars.next();
+                }
+                tokens.push(Token::Text(value));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_nested_blocks_and_escapes() {
+        let content = r#"
+// comment
+"AppWorkshop"
+{
+    "appid"     "227300"
+    "path"      "D:\\Steam"
+    "WorkshopItemsInstalled"
+    {
+        "123456"
+        {
+            "size"  "1024"
+        }
+    }
+}
+"#;
+        let root = parse_vdf(content).expect("valid vdf");
+        let workshop = root.get("appworkshop").expect("root block");
+        assert_eq!(workshop.text("AppID"), Some("227300"));
+        assert_eq!(workshop.text("path"), Some(r"D:\Steam"));
+        let installed = workshop.get("WorkshopItemsInstalled").expect("items");
+        assert_eq!(installed.entries()[0].0, "123456");
+        assert_eq!(installed.entries()[0].1.text("size"), Some("1024"));
+    }
+
+    #[test]
+    fn rejects_unterminated_block() {
+        assert!(parse_vdf(r#""root" { "a" "b" "#).is_err());
+    }
+}
@@ -0,0 +1,599 @@
+use super::manifest_reader::{ManifestMetadata, parse_manifest_text, read_plain_text_lossy};
+use super::models::{
+    DiscoveredMod, GameType, ModSource, WorkshopAppState, WorkshopChangeReport,
+    WorkshopItemMetadata, WorkshopMetadataCache,
+};
+use super::presets;
+use super::steam_paths;
+use super::vdf::{VdfValue, parse_vdf};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tauri::AppHandle;
+use zip::ZipArchive;
+
+const CACHE_FILE_NAME: &str = "workshop_metadata_cache.json";
+const MAX_MANIFEST_BYTES: u64 = 512 * 1024;
+/// A cache older than this is rebuilt even if no ACF file changed.
+const CACHE_MAX_AGE: Duration = Duration::from_secs(6 * 60 * 60);
+/// Removed items are kept this long so the change report can still list them.
+const REMOVED_ITEM_RETENTION_SECS: i64 = 30 * 24 * 60 * 60;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct AcfWorkshopItem {
+    pub workshop_id: String,
+    pub size_bytes: Option<u64>,
+    pub time_updated: Option<i64>,
+    pub manifest_id: Option<String>,
+    pub latest_time_updated: Option<i64>,
+    pub subscribed_by: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct AppWorkshopManifest {
+    pub app_id: String,
+    pub size_on_disk: Option<u64>,
+    pub time_last_updated: Option<i64>,
+    pub time_last_app_ran: Option<i64>,
+    pub needs_update: bool,
+    pub items: Vec<AcfWorkshopItem>,
+}
+
+pub(crate) fn parse_appworkshop_acf(content: &str) -> Result<AppWorkshopManifest, String> {
+    let document = parse_vdf(content)?;
+    let root = document
+        .get("AppWorkshop")
+        .ok_or_else(|| "appworkshop ACF has no AppWorkshop block.".to_string())?;
+
+    let mut items: BTreeMap<String, AcfWorkshopItem> = BTreeMap::new();
+    if let Some(installed) = root.get("WorkshopItemsInstalled") {
+        for (workshop_id, values) in installed.entries() {
+            let item = items
+                .entry(workshop_id.clone())
+                .or_insert_with(|| AcfWorkshopItem {
+                    workshop_id: workshop_id.clone(),
+                    ..AcfWorkshopItem::default()
+                });
+            item.size_bytes = parse_number(values, "size");
+            item.time_updated = parse_number(values, "timeupdated");
+            item.manifest_id = values.text("manifest").map(str::to_string);
+        }
+    }
+    // Details also list subscribed items that are not (yet) downloaded.
+    if let Some(details) = root.get("WorkshopItemDetails") {
+        for (workshop_id, values) in details.entries() {
+            let item = items
+                .entry(workshop_id.clone())
+                .or_insert_with(|| AcfWorkshopItem {
+                    workshop_id: workshop_id.clone(),
+                    ..AcfWorkshopItem::default()
+                });
+            item.latest_time_updated = parse_number(values, "latest_timeupdated");
+            item.subscribed_by = values.text("subscribedby").map(str::to_string);
+            if item.manifest_id.is_none() {
+                item.manifest_id = values.text("manifest").map(str::to_string);
+            }
+            if item.time_updated.is_none() {
+                item.time_updated = parse_number(values, "timeupdated");
+            }
+        }
+    }
+
+    Ok(AppWorkshopManifest {
+        app_id: root.text("appid").unwrap_or_default().to_string(),
+        size_on_disk: parse_number(root, "SizeOnDisk"),
+        time_last_updated: parse_number(root, "TimeLastUpdated"),
+        time_last_app_ran: parse_number(root, "TimeLastAppRan").filter(|value| *value > 0),
+        needs_update: root.text("NeedsUpdate").is_some_and(|value| value != "0"),
+        items: items.into_values().collect(),
+    })
+}
+
+pub fn load_cache(app: &AppHandle) -> Result<Option<WorkshopMetadataCache>, String> {
+    let path = cache_file_path(app)?;
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path)
+        .map_err(|error| format!("Failed to read {}: {}", path.display(), error))?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|error| format!("Failed to parse {}: {}", path.display(), error))
+}
+
+pub fn refresh_cache(app: &AppHandle) -> Result<WorkshopMetadataCache, String> {
+    let previous = load_cache(app).ok().flatten();
+    let libraries = steam_paths::get_steam_library_dirs()?;
+    let cache = build_cache(
+        &libraries,
+        previous.as_ref(),
+        chrono::Utc::now().timestamp(),
+    );
+
+    let path = cache_file_path(app)?;
+    let body = serde_json::to_string_pretty(&cache)
+        .map_err(|error| format!("Failed to serialize workshop cache: {}", error))?;
+    fs::write(&path, body)
+        .map_err(|error| format!("Failed to write {}: {}", path.display(), error))?;
+    crate::dev_log!(
+        "[workshop-cache] refreshed items={} apps={} path={}",
+        cache.items.len(),
+        cache.apps.len(),
+        path.display()
+    );
+    Ok(cache)
+}
+
+/// Returns the stored cache while it is fresh; rebuilds it when Steam rewrote one of the
+/// appworkshop ACF files since the last build or the cache is older than [`CACHE_MAX_AGE`].
+pub fn load_or_refresh_cache(app: &AppHandle) -> Result<WorkshopMetadataCache, String> {
+    let path = cache_file_path(app)?;
+    let cache_modified = fs::metadata(&path)
+        .and_then(|metadata| metadata.modified())
+        .ok();
+    let libraries = steam_paths::get_steam_library_dirs().unwrap_or_default();
+    let stale = cache_is_stale(
+        cache_modified,
+        &acf_modified_times(&libraries),
+        SystemTime::now(),
+    );
+    match load_cache(app)? {
+        Some(cache) if !stale => Ok(cache),
+        _ => refresh_cache(app),
+    }
+}
+
+fn acf_modified_times(libraries: &[PathBuf]) -> Vec<SystemTime> {
+    libraries
+        .iter()
+        .flat_map(|library| {
+            [GameType::Ets2, GameType::Ats].map(|game| {
+                library
+                    .join("steamapps")
+                    .join("workshop")
+                    .join(format!("appworkshop_{}.acf", game.app_id()))
+            })
+        })
+        .filter_map(|path| {
+            fs::metadata(path)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+        })
+        .collect()
+}
+
+pub(crate) fn cache_is_stale(
+    cache_modified: Option<SystemTime>,
+    acf_modified: &[SystemTime],
+    now: SystemTime,
+) -> bool {
+    let Some(cache_modified) = cache_modified else {
+        return true;
+    };
+    if now
+        .duration_since(cache_modified)
+        .is_ok_and(|age| age > CACHE_MAX_AGE)
+    {
+        return true;
+    }
+    acf_modified
+        .iter()
+        .any(|modified| *modified > cache_modified)
+}
+
+pub(crate) fn build_cache(
+    libraries: &[PathBuf],
+    previous: Option<&WorkshopMetadataCache>,
+    now_unix: i64,
+) -> WorkshopMetadataCache {
+    let mut apps = Vec::new();
+    let mut items: BTreeMap<(String, String), WorkshopItemMetadata> = BTreeMap::new();
+    let mut warnings = Vec::new();
+
+    for library in libraries {
+        for game in [GameType::Ets2, GameType::Ats] {
+            let app_id = game.app_id();
+            let acf_path = library
+                .join("steamapps")
+                .join("workshop")
+                .join(format!("appworkshop_{}.acf", app_id));
+            if !acf_path.is_file() {
+                continue;
+            }
+            let manifest = match read_plain_text_lossy(&acf_path)
+                .and_then(|content| parse_appworkshop_acf(&content))
+            {
+                Ok(manifest) => manifest,
+                Err(error) => {
+                    warnings.push(format!("{}: {}", acf_path.display(), error));
+                    continue;
+                }
+            };
+
+            apps.push(WorkshopAppState {
+                app_id: app_id.to_string(),
+                library_path: library.display().to_string(),
+                size_on_disk: manifest.size_on_disk,
+                time_last_updated: manifest.time_last_updated,
+                time_last_app_ran: manifest.time_last_app_ran,
+                needs_update: manifest.needs_update,
+            });
+
+            let content_root = library
+                .join("steamapps")
+                .join("workshop")
+                .join("content")
+                .join(app_id);
+            for acf_item in manifest.items {
+                let content_path = content_root.join(&acf_item.workshop_id);
+                let installed = content_path.is_dir() && acf_item.size_bytes.is_some();
+                let metadata = if installed {
+                    read_item_manifest(&content_path)
+                } else {
+                    ManifestMetadata::default()
+                };
+                let entry = WorkshopItemMetadata {
+                    app_id: app_id.to_string(),
+                    workshop_id: acf_item.workshop_id.clone(),
+                    installed,
+                    library_path: library.display().to_string(),
+                    content_path: installed.then(|| content_path.display().to_string()),
+                    size_bytes: acf_item.size_bytes,
+                    time_updated: acf_item.time_updated,
+                    latest_time_updated: acf_item.latest_time_updated,
+                    manifest_id: acf_item.manifest_id,
+                    needs_update: matches!(
+                        (acf_item.time_updated, acf_item.latest_time_updated),
+                        (Some(current), Some(latest)) if latest > current
+                    ),
+                    subscribed_by: acf_item.subscribed_by,
+                    display_name: metadata.display_name,
+                    package_name: metadata.package_name,
+                    version: metadata.version,
+                    author: metadata.author,
+                    categories: metadata.categories,
+                    first_seen_unix: 0,
+                    removed_at_unix: None,
+                };
+
+                let key = (entry.app_id.clone(), entry.workshop_id.clone());
+                // The same item can be listed by several libraries; the installed copy wins.
+                match items.get(&key) {
+                    Some(existing) if existing.installed && !entry.installed => {}
+                    _ => {
+                        items.insert(key, entry);
+                    }
+                }
+            }
+        }
+    }
+
+    let previous_items = previous
+        .map(|cache| {
+            cache
+                .items
+                .iter()
+                .map(|item| ((item.app_id.clone(), item.workshop_id.clone()), item))
+                .collect::<BTreeMap<_, _>>()
+        })
+        .unwrap_or_default();
+
+    for (key, item) in items.iter_mut() {
+        item.first_seen_unix = match previous_items.get(key) {
+            Some(old) if old.removed_at_unix.is_none() => old.first_seen_unix,
+            // Re-installed or new since the last refresh.
+            Some(_) => now_unix,
+            None if previous.is_some() => now_unix,
+            // First build: nothing is "new", use the Steam timestamp instead.
+            None => item.time_updated.unwrap_or(now_unix),
+        };
+    }
+
+    for (key, old) in previous_items {
+        if items.contains_key(&key) {
+            continue;
+        }
+        let removed_at = old.removed_at_unix.unwrap_or(now_unix);
+        if now_unix - removed_at > REMOVED_ITEM_RETENTION_SECS {
+            continue;
+        }
+        let mut removed = old.clone();
+        removed.installed = false;
+        removed.removed_at_unix = Some(removed_at);
+        items.insert(key, removed);
+    }
+
+    WorkshopMetadataCache {
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        apps,
+        items: items.into_values().collect(),
+        warnings,
+    }
+}
+
+pub(crate) fn changes_since_last_launch(
+    cache: &WorkshopMetadataCache,
+    game: GameType,
+) -> WorkshopChangeReport {
+    let app_id = game.app_id();
+    let last_launch = cache
+        .apps
+        .iter()
+        .filter(|state| state.app_id == app_id)
+        .filter_map(|state| state.time_last_app_ran)
+        .max();
+    let threshold = last_launch.unwrap_or(i64::MIN);
+
+    let mut report = WorkshopChangeReport {
+        game,
+        app_id: app_id.to_string(),
+        last_launch_at: last_launch.and_then(format_unix),
+        cache_generated_at: cache.generated_at.clone(),
+        ..WorkshopChangeReport::default()
+    };
+
+    for item in cache.items.iter().filter(|item| item.app_id == app_id) {
+        if let Some(removed_at) = item.removed_at_unix {
+            if removed_at > threshold {
+                report.removed.push(item.clone());
+            }
+            continue;
+        }
+        if item.first_seen_unix > threshold {
+            report.added.push(item.clone());
+        } else if item.time_updated.is_some_and(|updated| updated > threshold) {
+            report.updated.push(item.clone());
+        }
+        if item.needs_update {
+            report.pending_update.push(item.clone());
+        }
+    }
+
+    report
+}
+
+/// Fills manifest and Workshop fields from the local cache, without network access.
+pub fn enrich_discovered_mods(cache: &WorkshopMetadataCache, mods: &mut [DiscoveredMod]) {
+    let by_key = cache
+        .items
+        .iter()
+        .filter(|item| item.removed_at_unix.is_none())
+        .map(|item| ((item.app_id.as_str(), item.workshop_id.as_str()), item))
+        .collect::<BTreeMap<_, _>>();
+
+    for item in mods
+        .iter_mut()
+        .filter(|item| item.source == ModSource::SteamWorkshop)
+    {
+        let Some(workshop_id) = item.workshop_id.as_deref() else {
+            continue;
+        };
+        let app_id = item.app_id.as_deref().unwrap_or(GameType::Ets2.app_id());
+        let Some(cached) = by_key.get(&(app_id, workshop_id)) else {
+            continue;
+        };
+
+        if item.manifest_name.is_none() {
+            if let Some(name) = cached.display_name.clone() {
+                if item.name == workshop_id || item.name.is_empty() {
+                    item.name = name.clone();
+                }
+                item.manifest_name = Some(name);
+            }
+        }
+        if item.version.is_none() {
+            item.version = cached.version.clone();
+        }
+        if item.author.is_none() {
+            item.author = cached.author.clone();
+        }
+        item.workshop_updated_at = cached.time_updated.and_then(format_unix);
+        item.workshop_size_bytes = cached.size_bytes;
+        if cached.needs_update && !item.warnings.iter().any(|w| w.contains("update")) {
+            item.warnings
+                .push("Steam reports a newer version of this Workshop item.".to_string());
+        }
+    }
+}
+
+fn read_item_manifest(content_path: &Path) -> ManifestMetadata {
+    let root_manifest = content_path.join("manifest.sii");
+    if root_manifest.is_file() {
+        if let Ok(content) = read_plain_text_lossy(&root_manifest) {
+            return parse_manifest_text(&content);
+        }
+    }
+
+    // Workshop items usually ship the mod as universal.scs or per-version archives.
+    let Ok(entries) = fs::read_dir(content_path) else {
+        return ManifestMetadata::default();
+    };
+    let mut archives = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .and_then(|value| value.to_str())
+                .is_some_and(|value| matches!(value.to_ascii_lowercase().as_str(), "scs" | "zip"))
+        })
+        .collect::<Vec<_>>();
+    archives.sort();
+
+    for archive in archives {
+        if let Some(metadata) = read_archive_manifest(&archive) {
+            return metadata;
+        }
+    }
+    ManifestMetadata::default()
+}
+
+fn read_archive_manifest(path: &Path) -> Option<ManifestMetadata> {
+    let file = File::open(path).ok()?;
+    let mut archive = ZipArchive::new(file).ok()?;
+    let mut entry = archive.by_name("manifest.sii").ok()?;
+    if entry.size() > MAX_MANIFEST_BYTES {
+        return None;
+    }
+    let mut bytes = Vec::new();
+    entry.read_to_end(&mut bytes).ok()?;
+    Some(parse_manifest_text(&String::from_utf8_lossy(&bytes)))
+}
+
+fn parse_number<T: std::str::FromStr>(value: &VdfValue, key: &str) -> Option<T> {
+    value.text(key)?.trim().parse::<T>().ok()
+}
+
+fn format_unix(value: i64) -> Option<String> {
+    chrono::DateTime::<chrono::Utc>::from_timestamp(value, 0).map(|time| time.to_rfc3339())
+}
+
+fn cache_file_path(app: &AppHandle) -> Result<PathBuf, String> {
+    presets::storage_dir(app).map(|dir| dir.join(CACHE_FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACF: &str = r#"
+"AppWorkshop"
+{
+	"appid"		"227300"
+	"SizeOnDisk"		"3000"
+	"NeedsUpdate"		"0"
+	"TimeLastUpdated"		"1700000500"
+	"TimeLastAppRan"		"1700000000"
+	"WorkshopItemsInstalled"
+	{
+		"1001"
+		{
+			"size"		"1000"
+			"timeupdated"		"1690000000"
+			"manifest"		"111"
+		}
+		"1002"
+		{
+			"size"		"2000"
+			"timeupdated"		"1700000300"
+			"manifest"		"222"
+		}
+	}
+	"WorkshopItemDetails"
+	{
+		"1001"
+		{
+			"manifest"		"111"
+			"timeupdated"		"1690000000"
+			"timetouched"		"1700000000"
+			"subscribedby"		"76561198000000000"
+			"latest_timeupdated"		"1700000900"
+			"latest_manifest"		"333"
+		}
+	}
+}
+"#;
+
+    #[test]
+    fn parses_installed_items_and_details() {
+        let manifest = parse_appworkshop_acf(ACF).expect("valid acf");
+        assert_eq!(manifest.app_id, "227300");
+        assert_eq!(manifest.time_last_app_ran, Some(1_700_000_000));
+        assert_eq!(manifest.items.len(), 2);
+        let first = &manifest.items[0];
+        assert_eq!(first.workshop_id, "1001");
+        assert_eq!(first.size_bytes, Some(1000));
+        assert_eq!(first.latest_time_updated, Some(1_700_000_900));
+        assert_eq!(first.subscribed_by.as_deref(), Some("76561198000000000"));
+    }
+
+    fn cached_item(id: &str, time_updated: i64, first_seen: i64) -> WorkshopItemMetadata {
+        WorkshopItemMetadata {
+            app_id: "227300".to_string(),
+            workshop_id: id.to_string(),
+            installed: true,
+            time_updated: Some(time_updated),
+            first_seen_unix: first_seen,
+            ..WorkshopItemMetadata::default()
+        }
+    }
+
+    #[test]
+    fn reports_changes_after_last_launch() {
+        let mut removed = cached_item("3", 100, 100);
+        removed.removed_at_unix = Some(600);
+        let cache = WorkshopMetadataCache {
+            apps: vec![WorkshopAppState {
+                app_id: "227300".to_string(),
+                time_last_app_ran: Some(500),
+                ..WorkshopAppState::default()
+            }],
+            items: vec![
+                cached_item("1", 100, 100),
+                cached_item("2", 550, 100),
+                removed,
+                cached_item("4", 700, 700),
+            ],
+            ..WorkshopMetadataCache::default()
+        };
+
+        let report = changes_since_last_launch(&cache, GameType::Ets2);
+        assert_eq!(report.updated.len(), 1);
+        assert_eq!(report.updated[0].workshop_id, "2");
+        assert_eq!(report.removed[0].workshop_id, "3");
+        assert_eq!(report.added[0].workshop_id, "4");
+    }
+
+    #[test]
+    fn marks_unsubscribed_items_removed_and_prunes_old_ones() {
+        let mut long_gone = cached_item("9", 100, 100);
+        long_gone.removed_at_unix = Some(1_000);
+        let previous = WorkshopMetadataCache {
+            items: vec![cached_item("1", 100, 100), long_gone],
+            ..WorkshopMetadataCache::default()
+        };
+        let now = 1_000 + REMOVED_ITEM_RETENTION_SECS + 1;
+
+        let cache = build_cache(&[], Some(&previous), now);
+        assert_eq!(cache.items.len(), 1);
+        assert_eq!(cache.items[0].workshop_id, "1");
+        assert_eq!(cache.items[0].removed_at_unix, Some(now));
+        assert!(!cache.items[0].installed);
+    }
+
+    #[test]
+    fn cache_goes_stale_when_an_acf_changes_or_it_ages() {
+        let built = SystemTime::UNIX_EPOCH + Duration::from_secs(10_000);
+        let later = built + Duration::from_secs(60);
+        assert!(cache_is_stale(None, &[], later));
+        assert!(!cache_is_stale(Some(built), &[built], later));
+        assert!(cache_is_stale(Some(built), &[later], later));
+        assert!(cache_is_stale(Some(built), &[], built + CACHE_MAX_AGE * 2));
+    }
+
+    #[test]
+    fn enriches_workshop_mods_from_cache() {
+        let mut cached = cached_item("1001", 1_700_000_000, 0);
+        cached.display_name = Some("Scandinavia Rework".to_string());
+        cached.version = Some("2.1".to_string());
+        cached.size_bytes = Some(42);
+        let cache = WorkshopMetadataCache {
+            items: vec![cached],
+            ..WorkshopMetadataCache::default()
+        };
+        let mut mods = vec![DiscoveredMod {
+            name: "1001".to_string(),
+            source: ModSource::SteamWorkshop,
+            workshop_id: Some("1001".to_string()),
+            app_id: Some("227300".to_string()),
+            ..DiscoveredMod::default()
+        }];
+
+        enrich_discovered_mods(&cache, &mut mods);
+        assert_eq!(mods[0].name, "Scandinavia Rework");
+        assert_eq!(mods[0].version.as_deref(), Some("2.1"));
+        assert_eq!(mods[0].workshop_size_bytes, Some(42));
+        assert!(mods[0].workshop_updated_at.is_some());
+    }
+}
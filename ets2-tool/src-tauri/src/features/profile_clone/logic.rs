@@ -8,6 +8,8 @@ use walkdir::WalkDir;
 use zip::CompressionMethod;
 use zip::{ZipWriter, write::FileOptions};
 
+pub(crate) const BACKUP_DIR_NAME: &str = "Save Edit Tool Profile Backups";
+
 /// Hauptlogik
 pub fn clone_profile(
     source: &Path,
@@ -63,14 +65,18 @@ pub fn clone_profile(
 }
 
 fn create_zip_backup(source: &Path, parent: &Path) -> Result<(), String> {
-    let backup_root = parent.join("Save Edit Tool Profile Backups");
-    fs::create_dir_all(&backup_root).map_err(|e| e.to_string())?;
+    create_zip_backup_in(source, &parent.join(BACKUP_DIR_NAME)).map(|_| ())
+}
+
+/// Zipped Kopie von `source` in `backup_root`, liefert den Pfad des Archivs.
+pub(crate) fn create_zip_backup_in(source: &Path, backup_root: &Path) -> Result<PathBuf, String> {
+    fs::create_dir_all(backup_root).map_err(|e| e.to_string())?;
 
     let profile_name = source.file_name().unwrap().to_string_lossy();
     let timestamp = chrono::Local::now().format("%Y-%m-%d_%H-%M-%S");
     let zip_path = backup_root.join(format!("{}_{}.zip", profile_name, timestamp));
 
-    let file = File::create(zip_path).map_err(|e| e.to_string())?;
+    let file = File::create(&zip_path).map_err(|e| e.to_string())?;
     let mut zip = ZipWriter::new(file);
     let options: FileOptions<()> =
         FileOptions::default().compression_method(CompressionMethod::Deflated);
@@ -91,11 +97,11 @@ fn create_zip_backup(source: &Path, parent: &Path) -> Result<(), String> {
     }
 
     zip.finish().map_err(|e| e.to_string())?;
-    Ok(())
+    Ok(zip_path)
 }
 
 /// Rekursives Kopieren
-fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), String> {
+pub(crate) fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), String> {
     for entry in WalkDir::new(src) {
         let entry = entry.map_err(|e| e.to_string())?;
         let rel = entry.path().strip_prefix(src).map_err(|e| e.to_string())?;
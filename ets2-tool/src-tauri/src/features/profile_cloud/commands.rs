@@ -0,0 +1,41 @@
+use crate::dev_log;
+use crate::features::profile_cloud::logic::{self, ProfileConversion};
+use crate::models::profile_info::ProfileStorage;
+use crate::state::AppProfileState;
+use std::path::Path;
+use tauri::{State, command};
+
+#[command]
+pub fn convert_profile_storage(
+    profile_path: String,
+    target: ProfileStorage,
+    backup: bool,
+    keep_source: bool,
+    state: State<'_, AppProfileState>,
+) -> Result<ProfileConversion, String> {
+    let game = state.selected_game.lock().unwrap().clone();
+    dev_log!(
+        "Profil-Konvertierung: {} -> {:?} (Backup: {}, Quelle behalten: {})",
+        profile_path,
+        target,
+        backup,
+        keep_source
+    );
+    let conversion =
+        logic::convert_profile(Path::new(&profile_path), &game, target, backup, keep_source)?;
+
+    // Aktives Profil auf die Kopie umstellen, wenn die Quelle entfernt wurde.
+    if conversion.source_removed {
+        let mut current = state.current_profile.lock().unwrap();
+        if current.as_deref() == Some(profile_path.as_str()) {
+            *current = Some(conversion.target_path.clone());
+            let mut save = state.current_save.lock().unwrap();
+            if let Some(save_path) = save.as_deref() {
+                if let Some(rest) = save_path.strip_prefix(profile_path.as_str()) {
+                    *save = Some(format!("{}{}", conversion.target_path, rest));
+                }
+            }
+        }
+    }
+    Ok(conversion)
+}
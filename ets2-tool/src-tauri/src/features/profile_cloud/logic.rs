@@ -0,0 +1,332 @@
+use crate::features::mod_profile_manager::steam_paths::find_steam_install_dir;
+use crate::features::profile_clone::logic::{
+    BACKUP_DIR_NAME, copy_dir_recursive, create_zip_backup_in,
+};
+use crate::models::profile_info::ProfileStorage;
+use crate::shared::paths::get_base_path;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const LOCAL_PROFILES_DIR: &str = "profiles";
+const LOCAL_BACKUP_PROFILES_DIR: &str = "profiles.backup";
+/// Ordner im Dokumente-Verzeichnis, in dem das Spiel Steam-Cloud-Profile ablegt.
+///
+/// Das Spiel erkennt Cloud-Profile allein an diesem Ordner; ein Cloud-Schlüssel
+/// in profile.sii ist nicht dokumentiert, deshalb wird das Profil unverändert
+/// kopiert.
+const CLOUD_PROFILES_DIR: &str = "steam_profiles";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileRoot {
+    pub path: PathBuf,
+    pub storage: ProfileStorage,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileConversion {
+    pub source_path: String,
+    pub target_path: String,
+    pub storage: ProfileStorage,
+    pub backup_path: Option<String>,
+    pub source_removed: bool,
+}
+
+fn steam_app_id(game: &str) -> &'static str {
+    match game {
+        "ats" => "270880",
+        _ => "227300",
+    }
+}
+
+/// Alle Ordner, in denen Profile des Spiels liegen können.
+///
+/// Neben `profiles` und `profiles.backup` sind das `steam_profiles` und die
+/// `remote/profiles`-Ordner aller Steam-Accounts unter `userdata`.
+pub fn profile_roots(game: &str) -> Vec<ProfileRoot> {
+    let mut roots = Vec::new();
+    if let Some(base) = get_base_path(game) {
+        roots.push(ProfileRoot {
+            path: base.join(LOCAL_PROFILES_DIR),
+            storage: ProfileStorage::Local,
+        });
+        roots.push(ProfileRoot {
+            path: base.join(LOCAL_BACKUP_PROFILES_DIR),
+            storage: ProfileStorage::LocalBackup,
+        });
+        roots.push(ProfileRoot {
+            path: base.join(CLOUD_PROFILES_DIR),
+            storage: ProfileStorage::Cloud,
+        });
+    }
+    if let Some(steam_root) = find_steam_install_dir() {
+        roots.extend(
+            steam_remote_profile_dirs(&steam_root, steam_app_id(game))
+                .into_iter()
+                .map(|path| ProfileRoot {
+                    path,
+                    storage: ProfileStorage::Cloud,
+                }),
+        );
+    }
+    roots
+}
+
+fn steam_remote_profile_dirs(steam_root: &Path, app_id: &str) -> Vec<PathBuf> {
+    let Ok(accounts) = fs::read_dir(steam_root.join("userdata")) else {
+        return Vec::new();
+    };
+    accounts
+        .flatten()
+        .map(|account| account.path().join(app_id).join("remote").join("profiles"))
+        .filter(|path| path.is_dir())
+        .collect()
+}
+
+/// Leitet den Speicherort eines Profils aus seinem Elternordner ab.
+pub fn storage_of(profile_path: &Path) -> ProfileStorage {
+    let parent = profile_path.parent();
+    let parent_name = parent
+        .and_then(|parent| parent.file_name())
+        .and_then(|name| name.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let grandparent_name = parent
+        .and_then(|parent| parent.parent())
+        .and_then(|grandparent| grandparent.file_name())
+        .and_then(|name| name.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    match parent_name.as_str() {
+        CLOUD_PROFILES_DIR => ProfileStorage::Cloud,
+        LOCAL_PROFILES_DIR if grandparent_name == "remote" => ProfileStorage::Cloud,
+        LOCAL_BACKUP_PROFILES_DIR => ProfileStorage::LocalBackup,
+        _ => ProfileStorage::Local,
+    }
+}
+
+/// Kopiert ein Profil zwischen lokalem Ordner und Steam-Cloud-Ordner.
+///
+/// Vor dem Kopieren wird ein ZIP-Backup im Basisordner des Spiels angelegt.
+/// Die Quelle wird erst gelöscht, wenn `keep_source` nicht gesetzt ist und
+/// jede Datei der Kopie inhaltlich geprüft wurde; schlägt Kopie oder Prüfung
+/// fehl, wird die Kopie entfernt und die Quelle bleibt unberührt. Profile im
+/// Steam-`userdata`-Ordner werden nie gelöscht, weil Steam sie sonst wieder
+/// herunterlädt.
+pub fn convert_profile(
+    source: &Path,
+    game: &str,
+    target: ProfileStorage,
+    backup: bool,
+    keep_source: bool,
+) -> Result<ProfileConversion, String> {
+    let base = get_base_path(game).ok_or("Spielordner nicht gefunden".to_string())?;
+    convert_profile_in(
+        source,
+        &base,
+        target,
+        backup,
+        keep_source,
+        copy_dir_recursive,
+    )
+}
+
+fn convert_profile_in(
+    source: &Path,
+    base: &Path,
+    target: ProfileStorage,
+    backup: bool,
+    keep_source: bool,
+    copy: impl Fn(&Path, &Path) -> Result<(), String>,
+) -> Result<ProfileConversion, String> {
+    if !source.join("profile.sii").is_file() {
+        return Err("profile.sii nicht gefunden".to_string());
+    }
+    if target == ProfileStorage::LocalBackup {
+        return Err("Ziel muss lokal oder Steam Cloud sein".to_string());
+    }
+    let current = storage_of(source);
+    if current.is_cloud() == target.is_cloud() {
+        return Err("Profil liegt bereits am gewünschten Speicherort".to_string());
+    }
+
+    let folder_name = source
+        .file_name()
+        .ok_or("Ungültiger Profilpfad".to_string())?;
+    let target_root = base.join(if target.is_cloud() {
+        CLOUD_PROFILES_DIR
+    } else {
+        LOCAL_PROFILES_DIR
+    });
+    let target_dir = target_root.join(folder_name);
+    if target_dir.exists() {
+        return Err(format!(
+            "Zielprofil existiert bereits: {}",
+            target_dir.display()
+        ));
+    }
+
+    let backup_path = if backup {
+        Some(create_zip_backup_in(source, &base.join(BACKUP_DIR_NAME))?)
+    } else {
+        None
+    };
+
+    fs::create_dir_all(&target_root).map_err(|e| e.to_string())?;
+    if let Err(e) = copy(source, &target_dir).and_then(|_| verify_copy(source, &target_dir)) {
+        let _ = fs::remove_dir_all(&target_dir);
+        return Err(e);
+    }
+
+    let steam_managed = current.is_cloud() && !source.starts_with(base);
+    let source_removed = if keep_source || steam_managed {
+        false
+    } else {
+        fs::remove_dir_all(source)
+            .map_err(|e| format!("Profil kopiert, Quelle konnte nicht entfernt werden: {}", e))?;
+        true
+    };
+
+    Ok(ProfileConversion {
+        source_path: source.display().to_string(),
+        target_path: target_dir.display().to_string(),
+        storage: target,
+        backup_path: backup_path.map(|path| path.display().to_string()),
+        source_removed,
+    })
+}
+
+/// Prüft, dass jede Datei der Quelle mit gleichem Inhalt im Ziel liegt.
+fn verify_copy(source: &Path, target: &Path) -> Result<(), String> {
+    for entry in walkdir::WalkDir::new(source) {
+        let entry = entry.map_err(|e| e.to_string())?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let rel = entry
+            .path()
+            .strip_prefix(source)
+            .map_err(|e| e.to_string())?;
+        let expected = file_sha256(entry.path())?;
+        let copied = file_sha256(&target.join(rel))
+            .map_err(|_| format!("Datei fehlt in der Kopie: {}", rel.display()))?;
+        if copied != expected {
+            return Err(format!("Datei weicht von der Quelle ab: {}", rel.display()));
+        }
+    }
+    Ok(())
+}
+
+fn file_sha256(path: &Path) -> Result<Vec<u8>, String> {
+    let mut file = fs::File::open(path).map_err(|e| e.to_string())?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher).map_err(|e| e.to_string())?;
+    Ok(hasher.finalize().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn storage_follows_parent_folder() {
+        let base = Path::new("/docs/Euro Truck Simulator 2");
+        assert_eq!(
+            storage_of(&base.join("profiles").join("4142")),
+            ProfileStorage::Local
+        );
+        assert_eq!(
+            storage_of(&base.join("steam_profiles").join("4142")),
+            ProfileStorage::Cloud
+        );
+        assert_eq!(
+            storage_of(&base.join("profiles.backup").join("4142")),
+            ProfileStorage::LocalBackup
+        );
+        assert_eq!(
+            storage_of(Path::new("/steam/userdata/42/227300/remote/profiles/4142")),
+            ProfileStorage::Cloud
+        );
+    }
+
+    fn temp_game_dir() -> PathBuf {
+        let base =
+            std::env::temp_dir().join(format!("ets2-profile-cloud-test-{}", uuid::Uuid::new_v4()));
+        let profile = base.join(LOCAL_PROFILES_DIR).join("4142");
+        fs::create_dir_all(profile.join("save").join("1")).unwrap();
+        fs::write(profile.join("profile.sii"), "SiiNunit\n{\n}\n").unwrap();
+        fs::write(profile.join("save").join("1").join("game.sii"), "SiiNunit").unwrap();
+        base
+    }
+
+    #[test]
+    fn failed_copy_is_rolled_back_and_keeps_the_source() {
+        let base = temp_game_dir();
+        let source = base.join(LOCAL_PROFILES_DIR).join("4142");
+        let target = base.join(CLOUD_PROFILES_DIR).join("4142");
+
+        let error = convert_profile_in(
+            &source,
+            &base,
+            ProfileStorage::Cloud,
+            false,
+            false,
+            |src, dst| {
+                fs::create_dir_all(dst).map_err(|e| e.to_string())?;
+                fs::copy(src.join("profile.sii"), dst.join("profile.sii"))
+                    .map_err(|e| e.to_string())?;
+                Err("Datenträger voll".to_string())
+            },
+        )
+        .unwrap_err();
+        assert_eq!(error, "Datenträger voll");
+        assert!(!target.exists());
+        assert!(source.join("save").join("1").join("game.sii").is_file());
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn unverified_copy_never_removes_the_source() {
+        let base = temp_game_dir();
+        let source = base.join(LOCAL_PROFILES_DIR).join("4142");
+        let target = base.join(CLOUD_PROFILES_DIR).join("4142");
+
+        let error = convert_profile_in(
+            &source,
+            &base,
+            ProfileStorage::Cloud,
+            false,
+            false,
+            |src, dst| {
+                copy_dir_recursive(src, dst)?;
+                fs::write(dst.join("save").join("1").join("game.sii"), "SiiNuni!")
+                    .map_err(|e| e.to_string())
+            },
+        )
+        .unwrap_err();
+        assert!(error.contains("game.sii"));
+        assert!(!target.exists());
+        assert!(source.join("profile.sii").is_file());
+
+        let conversion = convert_profile_in(
+            &source,
+            &base,
+            ProfileStorage::Cloud,
+            false,
+            false,
+            copy_dir_recursive,
+        )
+        .unwrap();
+        assert!(conversion.source_removed);
+        assert!(!source.exists());
+        assert_eq!(
+            fs::read_to_string(target.join("profile.sii")).unwrap(),
+            "SiiNunit\n{\n}\n"
+        );
+
+        let _ = fs::remove_dir_all(&base);
+    }
+}
@@ -1,6 +1,8 @@
 use crate::dev_log;
+use crate::features::profile_cloud::logic::profile_roots;
 use crate::models::cached_profile::CachedProfile;
 use crate::models::profile_info::ProfileInfo;
+use crate::models::profile_info::ProfileStorage;
 use crate::models::profile_info::SaveKind;
 use crate::models::save_info::SaveInfo;
 use crate::shared::current_profile::set_current_profile;
@@ -278,13 +280,13 @@ pub fn find_ets2_profiles(state: State<'_, AppProfileState>) -> Vec<ProfileInfo>
     let base_opt = get_base_path(&game_key);
 
     if let Some(base) = base_opt {
-        let folders = vec![
-            base.join("profiles"),
-            base.join("profiles.backup"),
-            base.clone(),
-        ];
+        let mut folders: Vec<(PathBuf, ProfileStorage)> = profile_roots(&game_key)
+            .into_iter()
+            .map(|root| (root.path, root.storage))
+            .collect();
+        folders.push((base.clone(), ProfileStorage::Local));
 
-        for folder in folders {
+        for (folder, storage) in folders {
             if !folder.exists() {
                 continue;
             }
@@ -321,6 +323,8 @@ pub fn find_ets2_profiles(state: State<'_, AppProfileState>) -> Vec<ProfileInfo>
                         avatar,
                         success: false,
                         message: None,
+                        storage,
+                        steam_cloud: storage.is_cloud(),
                     };
 
                     let from_sii = text.as_ref().and_then(|t| extract_profile_name(t));
@@ -351,9 +355,10 @@ pub fn find_ets2_profiles(state: State<'_, AppProfileState>) -> Vec<ProfileInfo>
                         info.success = true;
                         info.message = Some("OK".into());
                         dev_log!(
-                            "Profil gefunden: {} ({})",
+                            "Profil gefunden: {} ({}, {:?})",
                             info.path,
-                            info.name.as_ref().unwrap()
+                            info.name.as_ref().unwrap(),
+                            info.storage
                         );
                     } else {
                         info.message = Some("profile_name nicht gefunden".into());
@@ -0,0 +1,79 @@
+use crate::dev_log;
+use crate::features::backup::service as backup_service;
+use crate::features::profile_merge::logic::{self, MergeComponent, MergePreviewItem};
+use crate::state::{AppProfileState, ProfileCache};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::{State, command};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergePreview {
+    pub items: Vec<MergePreviewItem>,
+    /// Muss beim Anwenden mitgeschickt werden
+    pub plan_hash: String,
+}
+
+#[command]
+pub fn preview_profile_merge(
+    source_profile_path: String,
+    target_profile_path: String,
+    components: Vec<MergeComponent>,
+) -> Result<MergePreview, String> {
+    let plan = logic::plan_merge(
+        Path::new(&source_profile_path),
+        Path::new(&target_profile_path),
+        &components,
+    )?;
+    Ok(MergePreview {
+        plan_hash: plan.hash(),
+        items: plan.items,
+    })
+}
+
+//* Übernimmt die gewählten Teile des Quellprofils; vorher wird ein Backup der betroffenen Zieldateien angelegt *//
+#[command]
+pub fn apply_profile_merge(
+    source_profile_path: String,
+    target_profile_path: String,
+    components: Vec<MergeComponent>,
+    plan_hash: String,
+    profile_state: State<'_, AppProfileState>,
+    profile_cache: State<'_, ProfileCache>,
+) -> Result<Vec<MergePreviewItem>, String> {
+    let plan = logic::plan_merge(
+        Path::new(&source_profile_path),
+        Path::new(&target_profile_path),
+        &components,
+    )?;
+    plan.verify_hash(&plan_hash)?;
+    if plan.writes.is_empty() {
+        return Ok(plan.items);
+    }
+
+    dev_log!(
+        "Profil-Merge von '{}' nach '{}': {:?}",
+        source_profile_path,
+        target_profile_path,
+        components
+    );
+
+    let existing: Vec<PathBuf> = plan
+        .writes
+        .iter()
+        .map(|write| write.path.clone())
+        .filter(|path| path.exists())
+        .collect();
+    if !existing.is_empty() {
+        backup_service::create_backup_for_targets(
+            profile_state.inner(),
+            "before profile merge",
+            &existing,
+        )
+        .map_err(|e| format!("Backup vor dem Profil-Merge fehlgeschlagen: {}", e))?;
+    }
+
+    logic::apply_plan(&plan, &plan_hash)?;
+    profile_cache.invalidate_save_config();
+    Ok(plan.items)
+}
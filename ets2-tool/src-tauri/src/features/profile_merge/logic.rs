@@ -0,0 +1,486 @@
+use crate::features::settings::config_file::CfgDocument;
+use crate::shared::decrypt::decrypt_if_needed;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CONTROLS_FILE: &str = "controls.sii";
+const CONFIG_FILES: &[&str] = &["config.cfg", "config_local.cfg"];
+const PROFILE_SII: &str = "profile.sii";
+const MODS_ARRAYS: &[&str] = &["active_mods"];
+/// Statistiken aus profile.sii. Errungenschaften liegen bei Steam und werden
+/// nicht berührt.
+const STATS_ARRAYS: &[&str] = &["cached_stats", "cached_discovery"];
+const PAINT_PRESETS_FILE: &str = "paint_presets.sii";
+const RADIO_FILE: &str = "live_streams.sii";
+const GPS_BOOKMARKS_FILE: &str = "gps_bookmarks.sii";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeComponent {
+    Controls,
+    Config,
+    Mods,
+    PaintPresets,
+    RadioStations,
+    GpsBookmarks,
+    Stats,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergePreviewItem {
+    pub component: MergeComponent,
+    pub available: bool,
+    pub files: Vec<String>,
+    pub changes: Vec<String>,
+    /// Teile, die im Ziel fehlen und deshalb nicht übernommen werden
+    pub skipped: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PlannedWrite {
+    pub path: PathBuf,
+    pub content: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MergePlan {
+    pub items: Vec<MergePreviewItem>,
+    pub writes: Vec<PlannedWrite>,
+}
+
+impl MergePlan {
+    /// Fingerabdruck aller geplanten Schreibvorgänge. Die Vorschau liefert
+    /// ihn aus, beim Anwenden muss der neu berechnete Plan ihn bestätigen.
+    pub fn hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        for write in &self.writes {
+            let path = write.path.to_string_lossy();
+            hasher.update((path.len() as u64).to_le_bytes());
+            hasher.update(path.as_bytes());
+            hasher.update((write.content.len() as u64).to_le_bytes());
+            hasher.update(&write.content);
+        }
+        hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    pub fn verify_hash(&self, expected_hash: &str) -> Result<(), String> {
+        if self.hash() != expected_hash {
+            return Err(
+                "Profile haben sich seit der Vorschau geändert; bitte Vorschau neu laden.".into(),
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Berechnet alle Änderungen, ohne etwas zu schreiben. Vorschau und Anwenden
+/// nutzen denselben Plan.
+pub fn plan_merge(
+    source: &Path,
+    target: &Path,
+    components: &[MergeComponent],
+) -> Result<MergePlan, String> {
+    if !source.join(PROFILE_SII).is_file() {
+        return Err("Quell-profile.sii nicht gefunden".into());
+    }
+    if !target.join(PROFILE_SII).is_file() {
+        return Err("Ziel-profile.sii nicht gefunden".into());
+    }
+    if source == target {
+        return Err("Quell- und Zielprofil sind identisch.".into());
+    }
+
+    let mut plan = MergePlan::default();
+    // Alle Array-Komponenten ändern dieselbe profile.sii.
+    let mut profile_sii: Option<(String, bool)> = None;
+
+    for &component in components {
+        if plan.items.iter().any(|item| item.component == component) {
+            continue;
+        }
+        let item = match component {
+            MergeComponent::Controls => {
+                plan_file(&mut plan, source, target, component, CONTROLS_FILE)?
+            }
+            MergeComponent::PaintPresets => {
+                plan_file(&mut plan, source, target, component, PAINT_PRESETS_FILE)?
+            }
+            MergeComponent::RadioStations => {
+                plan_file(&mut plan, source, target, component, RADIO_FILE)?
+            }
+            MergeComponent::GpsBookmarks => {
+                plan_file(&mut plan, source, target, component, GPS_BOOKMARKS_FILE)?
+            }
+            MergeComponent::Config => plan_config(&mut plan, source, target)?,
+            MergeComponent::Mods | MergeComponent::Stats => {
+                let keys = if component == MergeComponent::Mods {
+                    MODS_ARRAYS
+                } else {
+                    STATS_ARRAYS
+                };
+                if profile_sii.is_none() {
+                    profile_sii = Some((decrypt_if_needed(&target.join(PROFILE_SII))?, false));
+                }
+                let (content, dirty) = profile_sii.as_mut().expect("profile.sii loaded");
+                let source_content = decrypt_if_needed(&source.join(PROFILE_SII))?;
+                plan_arrays(component, &source_content, content, dirty, keys)?
+            }
+        };
+        plan.items.push(item);
+    }
+
+    if let Some((content, true)) = profile_sii {
+        plan.writes.push(PlannedWrite {
+            path: target.join(PROFILE_SII),
+            content: content.into_bytes(),
+        });
+    }
+    Ok(plan)
+}
+
+/// Schreibt einen Plan, wenn er noch dem Stand der Vorschau (`expected_hash`)
+/// entspricht. Backups legt der Aufrufer vorher an.
+pub fn apply_plan(plan: &MergePlan, expected_hash: &str) -> Result<(), String> {
+    plan.verify_hash(expected_hash)?;
+    for write in &plan.writes {
+        fs::write(&write.path, &write.content)
+            .map_err(|e| format!("Fehler beim Schreiben von {}: {}", write.path.display(), e))?;
+    }
+    Ok(())
+}
+
+/// Ganze Datei 1:1 übernehmen (kein Decrypt), wie beim Kopieren der Controls.
+/// Fehlt die Datei im Quellprofil, meldet die Vorschau die Komponente als
+/// nicht verfügbar.
+fn plan_file(
+    plan: &mut MergePlan,
+    source: &Path,
+    target: &Path,
+    component: MergeComponent,
+    file_name: &str,
+) -> Result<MergePreviewItem, String> {
+    let source_file = source.join(file_name);
+    if !source_file.is_file() {
+        return Ok(unavailable(component));
+    }
+    let target_file = target.join(file_name);
+    let content = fs::read(&source_file).map_err(|e| e.to_string())?;
+    let changes = match fs::read(&target_file) {
+        Ok(existing) if existing == content => Vec::new(),
+        Ok(_) => vec![format!("{} wird ersetzt", file_name)],
+        Err(_) => vec![format!("{} wird angelegt", file_name)],
+    };
+    if !changes.is_empty() {
+        plan.writes.push(PlannedWrite {
+            path: target_file,
+            content,
+        });
+    }
+    Ok(MergePreviewItem {
+        component,
+        available: true,
+        files: vec![file_name.to_string()],
+        changes,
+        skipped: Vec::new(),
+    })
+}
+
+/// Variablen aus config.cfg/config_local.cfg übernehmen; Kommentare und
+/// zusätzliche Variablen im Ziel bleiben erhalten.
+fn plan_config(
+    plan: &mut MergePlan,
+    source: &Path,
+    target: &Path,
+) -> Result<MergePreviewItem, String> {
+    let mut item = unavailable(MergeComponent::Config);
+    for file_name in CONFIG_FILES {
+        let source_file = source.join(file_name);
+        if !source_file.is_file() {
+            continue;
+        }
+        item.available = true;
+        item.files.push(file_name.to_string());
+
+        let source_doc = CfgDocument::parse(&decrypt_if_needed(&source_file)?);
+        let target_file = target.join(file_name);
+        let mut target_doc = if target_file.is_file() {
+            CfgDocument::parse(&decrypt_if_needed(&target_file)?)
+        } else {
+            CfgDocument::parse("")
+        };
+        let changed: Vec<String> = source_doc
+            .vars()
+            .into_iter()
+            .filter(|(name, value)| target_doc.set(name, value))
+            .map(|(name, _)| format!("{}: {}", file_name, name))
+            .collect();
+        if !changed.is_empty() {
+            plan.writes.push(PlannedWrite {
+                path: target_file,
+                content: target_doc.render().into_bytes(),
+            });
+        }
+        item.changes.extend(changed);
+    }
+    Ok(item)
+}
+
+fn plan_arrays(
+    component: MergeComponent,
+    source_content: &str,
+    target_content: &mut String,
+    dirty: &mut bool,
+    keys: &[&str],
+) -> Result<MergePreviewItem, String> {
+    let mut item = unavailable(component);
+    for key in keys {
+        let Some(values) = extract_array(source_content, key) else {
+            continue;
+        };
+        item.available = true;
+        item.files.push(format!("{}:{}", PROFILE_SII, key));
+        match extract_array(target_content, key) {
+            None => {
+                item.skipped.push(format!("{}: fehlt im Zielprofil", key));
+                continue;
+            }
+            Some(existing) if existing == values => continue,
+            Some(_) => {}
+        }
+        *target_content = replace_array(target_content, key, &values)?;
+        *dirty = true;
+        item.changes
+            .push(format!("{}: {} Einträge", key, values.len()));
+    }
+    Ok(item)
+}
+
+fn unavailable(component: MergeComponent) -> MergePreviewItem {
+    MergePreviewItem {
+        component,
+        available: false,
+        files: Vec::new(),
+        changes: Vec::new(),
+        skipped: Vec::new(),
+    }
+}
+
+/// Liest ein SII-Array (`key: N` gefolgt von `key[i]: wert`) als Rohwerte.
+/// Liefert `None`, wenn die Zählzeile fehlt.
+pub(crate) fn extract_array(content: &str, key: &str) -> Option<Vec<String>> {
+    let count = Regex::new(&format!(r"(?m)^\s*{}:\s*\d+", regex::escape(key))).ok()?;
+    if !count.is_match(content) {
+        return None;
+    }
+    let item = Regex::new(&format!(
+        r"(?m)^\s*{}\[\d*\]:\s*(.*?)\s*$",
+        regex::escape(key)
+    ))
+    .ok()?;
+    Some(
+        item.captures_iter(content)
+            .map(|cap| cap[1].to_string())
+            .collect(),
+    )
+}
+
+/// Ersetzt ein SII-Array durch die übergebenen Rohwerte. Zeilenenden und
+/// Einrückung des Ziels bleiben erhalten.
+pub(crate) fn replace_array(content: &str, key: &str, values: &[String]) -> Result<String, String> {
+    let re_count =
+        Regex::new(&format!(r"^\s*{}:\s*\d+", regex::escape(key))).map_err(|e| e.to_string())?;
+    let re_item =
+        Regex::new(&format!(r"^\s*{}\[\d*\]:", regex::escape(key))).map_err(|e| e.to_string())?;
+    let newline = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+
+    let mut lines = Vec::new();
+    let mut injected = false;
+    for line in content.lines() {
+        if re_item.is_match(line) {
+            continue;
+        }
+        if re_count.is_match(line) && !injected {
+            let indentation = line.split(key).next().unwrap_or("");
+            lines.push(format!("{}{}: {}", indentation, key, values.len()));
+            for (i, value) in values.iter().enumerate() {
+                lines.push(format!("{}{}[{}]: {}", indentation, key, i, value));
+            }
+            injected = true;
+        } else {
+            lines.push(line.to_string());
+        }
+    }
+    if !injected {
+        return Err(format!(
+            "Konnte '{}' Zeile im Zielprofil nicht finden.",
+            key
+        ));
+    }
+
+    let mut out = lines.join(newline);
+    if content.ends_with('\n') {
+        out.push_str(newline);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(dir: &Path, mods: &[&str], extra_files: &[(&str, &str)]) {
+        fs::create_dir_all(dir).unwrap();
+        let mut sii = String::from("SiiNunit\r\n{\r\nuser_profile : .x {\r\n active_mods: ");
+        sii.push_str(&format!("{}\r\n", mods.len()));
+        for (i, entry) in mods.iter().enumerate() {
+            sii.push_str(&format!(" active_mods[{}]: \"{}\"\r\n", i, entry));
+        }
+        sii.push_str(
+            " cached_stats: 2\r\n cached_stats[0]: 10\r\n cached_stats[1]: 20\r\n}\r\n}\r\n",
+        );
+        fs::write(dir.join(PROFILE_SII), sii).unwrap();
+        for (name, content) in extra_files {
+            fs::write(dir.join(name), content).unwrap();
+        }
+    }
+
+    #[test]
+    fn arrays_are_replaced_in_place_with_original_line_endings() {
+        let content = "a {\r\n active_mods: 1\r\n active_mods[0]: \"old|Old\"\r\n name: x\r\n}\r\n";
+        let values = vec!["\"a|A\"".to_string(), "\"b|B\"".to_string()];
+        let updated = replace_array(content, "active_mods", &values).unwrap();
+        assert_eq!(
+            updated,
+            "a {\r\n active_mods: 2\r\n active_mods[0]: \"a|A\"\r\n active_mods[1]: \"b|B\"\r\n name: x\r\n}\r\n"
+        );
+        assert_eq!(extract_array(&updated, "active_mods"), Some(values));
+        assert_eq!(extract_array(&updated, "cached_stats"), None);
+    }
+
+    #[test]
+    fn plan_merges_selected_components_and_previews_before_writing() {
+        let root = std::env::temp_dir().join(format!("profile-merge-{}", std::process::id()));
+        let source = root.join("master");
+        let target = root.join("event");
+        profile(
+            &source,
+            &["m1|One", "m2|Two"],
+            &[
+                (CONTROLS_FILE, "controls-master"),
+                (
+                    "config.cfg",
+                    "uset g_lang \"de_de\"\nuset r_gamma \"1.2\"\n",
+                ),
+            ],
+        );
+        profile(
+            &target,
+            &[],
+            &[(
+                "config.cfg",
+                "# keep me\nuset g_lang \"en_us\"\nuset x_local \"1\"\n",
+            )],
+        );
+
+        let components = [
+            MergeComponent::Controls,
+            MergeComponent::Config,
+            MergeComponent::Mods,
+            MergeComponent::Stats,
+        ];
+        let plan = plan_merge(&source, &target, &components).unwrap();
+        assert_eq!(plan.items.len(), 4);
+        assert!(plan.items[3].changes.is_empty());
+        assert_eq!(plan.items[1].changes.len(), 2);
+        assert_eq!(plan.writes.len(), 3);
+        assert!(!target.join(CONTROLS_FILE).exists());
+        let previewed = plan.hash();
+
+        fs::write(source.join(CONTROLS_FILE), "controls-changed").unwrap();
+        let changed = plan_merge(&source, &target, &components).unwrap();
+        assert!(apply_plan(&changed, &previewed).is_err());
+        assert!(!target.join(CONTROLS_FILE).exists());
+        fs::write(source.join(CONTROLS_FILE), "controls-master").unwrap();
+
+        let plan = plan_merge(&source, &target, &components).unwrap();
+        apply_plan(&plan, &previewed).unwrap();
+        let config = fs::read_to_string(target.join("config.cfg")).unwrap();
+        assert!(config.starts_with("# keep me\nuset g_lang \"de_de\"\nuset x_local \"1\"\n"));
+        let sii = fs::read_to_string(target.join(PROFILE_SII)).unwrap();
+        assert_eq!(extract_array(&sii, "active_mods").unwrap().len(), 2);
+        assert!(
+            plan_merge(&source, &target, &components)
+                .unwrap()
+                .writes
+                .is_empty()
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn paint_radio_and_gps_files_are_previewed_and_copied() {
+        let root = std::env::temp_dir().join(format!("profile-merge-files-{}", std::process::id()));
+        let source = root.join("master");
+        let target = root.join("event");
+        profile(
+            &source,
+            &[],
+            &[
+                (PAINT_PRESETS_FILE, "paint-master"),
+                (RADIO_FILE, "radio-master"),
+            ],
+        );
+        profile(&target, &[], &[(RADIO_FILE, "radio-event")]);
+
+        let components = [
+            MergeComponent::PaintPresets,
+            MergeComponent::RadioStations,
+            MergeComponent::GpsBookmarks,
+        ];
+        let plan = plan_merge(&source, &target, &components).unwrap();
+        assert_eq!(
+            plan.items[0].changes,
+            vec![format!("{} wird angelegt", PAINT_PRESETS_FILE)]
+        );
+        assert_eq!(
+            plan.items[1].changes,
+            vec![format!("{} wird ersetzt", RADIO_FILE)]
+        );
+        assert!(!plan.items[2].available);
+        assert_eq!(plan.writes.len(), 2);
+        assert!(!target.join(PAINT_PRESETS_FILE).exists());
+
+        fs::write(source.join(GPS_BOOKMARKS_FILE), "gps-master").unwrap();
+        let plan = plan_merge(&source, &target, &components).unwrap();
+        assert!(plan.items[2].available);
+        apply_plan(&plan, &plan.hash()).unwrap();
+        for (file, content) in [
+            (PAINT_PRESETS_FILE, "paint-master"),
+            (RADIO_FILE, "radio-master"),
+            (GPS_BOOKMARKS_FILE, "gps-master"),
+        ] {
+            assert_eq!(fs::read_to_string(target.join(file)).unwrap(), content);
+        }
+        assert!(
+            plan_merge(&source, &target, &components)
+                .unwrap()
+                .writes
+                .is_empty()
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -1,8 +1,8 @@
 use crate::dev_log;
+use crate::features::profile_merge::logic::{extract_array, replace_array};
 use crate::shared::current_profile::require_current_profile;
 use crate::shared::decrypt::decrypt_if_needed;
 use crate::state::AppProfileState;
-use regex::Regex;
 use std::fs;
 use std::path::Path;
 use tauri::command;
@@ -46,7 +46,7 @@ pub fn copy_mods_to_profile(
     let source_content = decrypt_if_needed(&source_sii).map_err(|e| e.to_string())?;
 
     // 4. Extract Mods from Source
-    let mods = extract_mods(&source_content);
+    let mods = extract_array(&source_content, "active_mods").unwrap_or_default();
     dev_log!("Gefundene Mods im Quellprofil: {}", mods.len());
 
     // 5. Find profile.sii in Target
@@ -59,7 +59,7 @@ pub fn copy_mods_to_profile(
     let target_content = decrypt_if_needed(&target_sii).map_err(|e| e.to_string())?;
 
     // 7. Inject Mods into Target Content
-    let new_target_content = inject_mods(&target_content, &mods)?;
+    let new_target_content = replace_array(&target_content, "active_mods", &mods)?;
 
     // 8. Write back to Target
     fs::write(&target_sii, new_target_content)
@@ -68,61 +68,3 @@ pub fn copy_mods_to_profile(
     dev_log!("Mods erfolgreich übertragen.");
     Ok(format!("Erfolgreich {} Mods übertragen.", mods.len()))
 }
-
-fn extract_mods(content: &str) -> Vec<String> {
-    // Regex to find lines like: active_mods[0]: "mod_id|mod_name"
-    // We capture the content inside the quotes.
-    let re = Regex::new(r#"active_mods\[\d+\]:\s*"(.*)""#).unwrap();
-    let mut mods = Vec::new();
-
-    for cap in re.captures_iter(content) {
-        if let Some(m) = cap.get(1) {
-            mods.push(m.as_str().to_string());
-        }
-    }
-    mods
-}
-
-fn inject_mods(content: &str, mods: &[String]) -> Result<String, String> {
-    let mut new_lines = Vec::new();
-    let mut injected = false;
-
-    // Regex to identify the count line: active_mods: 123
-    let re_count = Regex::new(r"^\s*active_mods:\s*\d+").unwrap();
-    // Regex to identify existing mod lines to remove them
-    let re_mod_line = Regex::new(r"^\s*active_mods\[\d+\]:").unwrap();
-
-    for line in content.lines() {
-        // Skip existing mod lines in target
-        if re_mod_line.is_match(line) {
-            continue;
-        }
-
-        if re_count.is_match(line) {
-            // Found the count line. Replace it and append new mods.
-            // Preserve indentation from the found line
-            let indentation = line.split("active_mods").next().unwrap_or("");
-
-            // 1. Write new count
-            new_lines.push(format!("{}active_mods: {}", indentation, mods.len()));
-
-            // 2. Write new mod lines
-            for (i, mod_str) in mods.iter().enumerate() {
-                new_lines.push(format!(
-                    "{}active_mods[{}]: \"{}\"",
-                    indentation, i, mod_str
-                ));
-            }
-            injected = true;
-        } else {
-            new_lines.push(line.to_string());
-        }
-    }
-
-    if !injected {
-        return Err("Konnte 'active_mods' Zeile im Zielprofil nicht finden.".into());
-    }
-
-    // Reconstruct file with CRLF
-    Ok(new_lines.join("\r\n"))
-}
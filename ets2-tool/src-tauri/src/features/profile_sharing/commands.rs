@@ -1,6 +1,6 @@
 use super::models::{
-    ProfileShareContext, ProfileShareExportResult, ProfileShareImportPreview,
-    ProfileShareImportResult,
+    ProfileImportMode, ProfileShareContext, ProfileShareExportResult, ProfileShareImportPreview,
+    ProfileShareImportResult, ProfileShareSigningKey,
 };
 use super::service;
 use crate::state::AppProfileState;
@@ -35,18 +35,31 @@ pub async fn pick_shared_profile_export_directory(
 pub fn export_shared_profile(
     profile_path: String,
     export_dir_override: Option<String>,
+    sign: Option<bool>,
     profile_state: State<'_, AppProfileState>,
 ) -> Result<ProfileShareExportResult, String> {
-    service::export_shared_profile(&profile_path, export_dir_override, profile_state.inner())
+    service::export_shared_profile(
+        &profile_path,
+        export_dir_override,
+        sign.unwrap_or(false),
+        profile_state.inner(),
+    )
+}
+
+#[tauri::command]
+pub fn get_profile_share_signing_key() -> Result<ProfileShareSigningKey, String> {
+    service::get_profile_share_signing_key()
 }
 
 #[tauri::command]
 pub fn inspect_shared_profile_archive(
+    app: AppHandle,
     archive_path: String,
     profile_name_override: Option<String>,
     profile_state: State<'_, AppProfileState>,
 ) -> Result<ProfileShareImportPreview, String> {
     service::inspect_shared_profile_archive(
+        &app,
         &archive_path,
         profile_name_override,
         profile_state.inner(),
@@ -57,7 +70,17 @@ pub fn inspect_shared_profile_archive(
 pub fn import_shared_profile(
     archive_path: String,
     profile_name_override: Option<String>,
+    mode: Option<ProfileImportMode>,
+    target_profile_path: Option<String>,
+    trust_signer: Option<bool>,
     profile_state: State<'_, AppProfileState>,
 ) -> Result<ProfileShareImportResult, String> {
-    service::import_shared_profile(&archive_path, profile_name_override, profile_state.inner())
+    service::import_shared_profile(
+        &archive_path,
+        profile_name_override,
+        mode.unwrap_or_default(),
+        target_profile_path,
+        trust_signer.unwrap_or(false),
+        profile_state.inner(),
+    )
 }
@@ -0,0 +1,512 @@
+//! Kompatibilitaetspruefung eines Profilarchivs gegen den empfangenden Rechner:
+//! Mods, DLC-abhaengige Inhalte der Saves und Save-Formatversion.
+
+use super::models::{
+    DlcRequirement, ModAvailability, ProfileCompatibilityReport, SaveVersionCheck, SharedProfileMod,
+};
+use crate::features::mod_profile_manager::models::DiscoveredMod;
+use crate::features::profile_merge::logic::extract_array;
+use regex::Regex;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// Inhalt eines Saves aus dem Archiv (entschluesselt).
+pub struct ArchiveSave {
+    pub slot: String,
+    pub info_text: Option<String>,
+    pub game_text: Option<String>,
+}
+
+/// Staedte der SCS-Karten-DLCs von ETS2 (Stadt-Token aus `def/city/`).
+/// Staedte des Grundspiels fehlen bewusst, auch wenn ein DLC ihr Land erweitert.
+const CITY_DLC: &[(&str, &[&str])] = &[
+    (
+        "dlc_north",
+        &[
+            "aalborg",
+            "bergen",
+            "esbjerg",
+            "frederikshv",
+            "gedser",
+            "goteborg",
+            "helsingborg",
+            "hirtshals",
+            "jonkoping",
+            "kalmar",
+            "karlskrona",
+            "kobenhavn",
+            "kristiansand",
+            "linkoping",
+            "malmo",
+            "nynashamn",
+            "odense",
+            "orebro",
+            "oslo",
+            "stavanger",
+            "stockholm",
+            "sodertalje",
+            "trelleborg",
+            "uppsala",
+            "vasteraas",
+            "vaxjo",
+        ],
+    ),
+    (
+        "dlc_east",
+        &[
+            "gdansk", "olsztyn", "warszawa", "lodz", "lublin", "krakow", "katowice", "kosice",
+            "bystrica", "debrecen", "szeged", "pecs",
+        ],
+    ),
+    (
+        "dlc_fr",
+        &[
+            "ajaccio",
+            "alban",
+            "bastia",
+            "bonifacio",
+            "bordeaux",
+            "bourges",
+            "brest",
+            "calvi",
+            "civaux",
+            "clermont",
+            "golfech",
+            "larochelle",
+            "laurent",
+            "lehavre",
+            "lemans",
+            "lile_rousse",
+            "limoges",
+            "marseille",
+            "montpellier",
+            "nantes",
+            "nice",
+            "paluel",
+            "porto_vecchi",
+            "rennes",
+            "roscoff",
+            "toulouse",
+        ],
+    ),
+    (
+        "dlc_it",
+        &[
+            "ancona",
+            "bari",
+            "bologna",
+            "cagliari",
+            "cassino",
+            "catania",
+            "catanzaro",
+            "firenze",
+            "genova",
+            "livorno",
+            "messina",
+            "napoli",
+            "olbia",
+            "palermo",
+            "parma",
+            "pescara",
+            "roma",
+            "sangiovanni",
+            "sassari",
+            "suzzara",
+            "taranto",
+            "terni",
+        ],
+    ),
+    (
+        "dlc_balt",
+        &[
+            "daugavpils",
+            "helsinki",
+            "kaliningrad",
+            "kaunas",
+            "klaipeda",
+            "kotka",
+            "kouvola",
+            "kunda",
+            "lahti",
+            "liepaja",
+            "loviisa",
+            "luga",
+            "naantali",
+            "narva",
+            "olkiluoto",
+            "paldiski",
+            "panevezys",
+            "parnu",
+            "pori",
+            "pskov",
+            "rezekne",
+            "riga",
+            "siauliai",
+            "sosnovy_bor",
+            "st_petersburg",
+            "tallinn",
+            "tampere",
+            "tartu",
+            "turku",
+            "utena",
+            "valmiera",
+            "ventspils",
+            "vilnius",
+            "vyborg",
+        ],
+    ),
+    (
+        "dlc_balkan_e",
+        &[
+            "bacau",
+            "brasov",
+            "bucuresti",
+            "burgas",
+            "calarasi",
+            "cernavoda",
+            "cluj_napoca",
+            "constanta",
+            "craiova",
+            "edirne",
+            "galati",
+            "hamzabeyli",
+            "hunedoara",
+            "iasi",
+            "istanbul",
+            "kapikule",
+            "karlovo",
+            "kozloduy",
+            "mangalia",
+            "pernik",
+            "pirdop",
+            "pitesti",
+            "pleven",
+            "plovdiv",
+            "resita",
+            "ruse",
+            "sofia",
+            "targu_mures",
+            "tekirdag",
+            "timisoara",
+            "varna",
+            "veli_tarnovo",
+        ],
+    ),
+    (
+        "dlc_iberia",
+        &[
+            "a_coruna",
+            "albacete",
+            "algeciras",
+            "almaraz",
+            "almeria",
+            "badajoz",
+            "bailen",
+            "barcelona",
+            "beja",
+            "bilbao",
+            "burgos",
+            "ciudad_real",
+            "coimbra",
+            "cordoba",
+            "el_ejido",
+            "evora",
+            "faro",
+            "gijon",
+            "granada",
+            "guarda",
+            "huelva",
+            "leon",
+            "lisboa",
+            "lleida",
+            "madrid",
+            "malaga",
+            "mengibar",
+            "murcia",
+            "navia",
+            "o_barco",
+            "olhao",
+            "pamplona",
+            "ponte_de_sor",
+            "port_sagunt",
+            "porto",
+            "puertollano",
+            "salamanca",
+            "santander",
+            "setubal",
+            "sevilla",
+            "sines",
+            "soria",
+            "tarragona",
+            "teruel",
+            "valencia",
+            "valladolid",
+            "vandellos",
+            "vigo",
+            "zaragoza",
+        ],
+    ),
+    (
+        "dlc_balkan_w",
+        &[
+            "banja_luka",
+            "beograd",
+            "doboj",
+            "dubrovnik",
+            "kragujevac",
+            "mostar",
+            "nis",
+            "novi_sad",
+            "osijek",
+            "ploce",
+            "podgorica",
+            "pula",
+            "rijeka",
+            "sarajevo",
+            "split",
+            "subotica",
+            "tuzla",
+            "zadar",
+            "zagreb",
+            "zenica",
+        ],
+    ),
+];
+
+/// Trailer-Marken, deren Auflieger in SCS-DLCs ausgeliefert werden.
+const TRAILER_DLC: &[(&str, &str)] = &[
+    ("feldbinder", "dlc_feldbinder"),
+    ("kassbohrer", "dlc_kassbohrer"),
+    ("krone", "dlc_krone"),
+    ("schwarzmuller", "dlc_schwarzmuller"),
+    ("tirsan", "dlc_tirsan"),
+    ("wielton", "dlc_wielton"),
+];
+
+fn city_dlc(city: &str) -> Option<&'static str> {
+    CITY_DLC
+        .iter()
+        .find(|(_, cities)| cities.contains(&city))
+        .map(|(dlc, _)| *dlc)
+}
+
+pub fn compare_mods(
+    archive_mods: &[SharedProfileMod],
+    local_mods: Option<&[DiscoveredMod]>,
+) -> Vec<ModAvailability> {
+    archive_mods
+        .iter()
+        .map(|item| {
+            let installed =
+                local_mods.map(|local| local.iter().any(|local_mod| mod_matches(item, local_mod)));
+            ModAvailability {
+                mod_ref: item.mod_ref.clone(),
+                name: item.name.clone(),
+                workshop_id: item.workshop_id.clone(),
+                installed,
+            }
+        })
+        .collect()
+}
+
+fn mod_matches(item: &SharedProfileMod, local: &DiscoveredMod) -> bool {
+    if let Some(workshop_id) = item.workshop_id.as_deref() {
+        return local.workshop_id.as_deref() == Some(workshop_id);
+    }
+    let wanted = item.mod_ref.to_ascii_lowercase();
+    let stem = Path::new(&local.file_path)
+        .file_stem()
+        .and_then(|value| value.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    stem == wanted
+        || local.id.eq_ignore_ascii_case(&item.mod_ref)
+        || item
+            .name
+            .as_deref()
+            .is_some_and(|name| name.eq_ignore_ascii_case(&local.name))
+}
+
+/// Sammelt DLC-Anforderungen aus allen Saves. `installed_dlc` ist `None`,
+/// wenn die Spielinstallation nicht gefunden wurde.
+pub fn required_dlc(
+    saves: &[ArchiveSave],
+    installed_dlc: Option<&BTreeSet<String>>,
+) -> Vec<DlcRequirement> {
+    let trailer_brand = Regex::new(r"trailer_def\.([a-z0-9_]+)\.").expect("valid regex");
+    let mut reasons: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+
+    for save in saves {
+        if let Some(info) = save.info_text.as_deref() {
+            // dependencies[i]: "dlc|dlc_north|Beyond the Baltic Sea"
+            for value in extract_array(info, "dependencies").unwrap_or_default() {
+                let value = value.trim_matches('"');
+                let mut parts = value.split('|');
+                if let (Some("dlc"), Some(dlc)) = (parts.next(), parts.next()) {
+                    reasons
+                        .entry(dlc.to_string())
+                        .or_default()
+                        .insert(format!("{}: Save-Abhaengigkeit", save.slot));
+                }
+            }
+        }
+        let Some(game) = save.game_text.as_deref() else {
+            continue;
+        };
+        for city in extract_array(game, "visited_cities").unwrap_or_default() {
+            let city = city.trim_matches('"');
+            if let Some(dlc) = city_dlc(city) {
+                reasons
+                    .entry(dlc.to_string())
+                    .or_default()
+                    .insert(format!("Stadt {}", city));
+            }
+        }
+        for caps in trailer_brand.captures_iter(game) {
+            let brand = &caps[1];
+            if let Some((_, dlc)) = TRAILER_DLC.iter().find(|(known, _)| *known == brand) {
+                reasons
+                    .entry(dlc.to_string())
+                    .or_default()
+                    .insert(format!("Trailer {}", brand));
+            }
+        }
+    }
+
+    reasons
+        .into_iter()
+        .map(|(dlc, reasons)| DlcRequirement {
+            installed: installed_dlc.map(|installed| installed.contains(&dlc)),
+            dlc,
+            reasons: reasons.into_iter().collect(),
+        })
+        .collect()
+}
+
+/// Liest `version` aus einer info.sii.
+pub fn save_format_version(info_text: &str) -> Option<u32> {
+    let re = Regex::new(r"(?m)^\s*version\s*:\s*(\d+)").ok()?;
+    re.captures(info_text)?[1].parse().ok()
+}
+
+/// Vergleicht Spielversionen wie `1.53.1.0s` numerisch Teil fuer Teil.
+fn compare_game_versions(left: &str, right: &str) -> Ordering {
+    let parts = |value: &str| -> Vec<u32> {
+        value
+            .split('.')
+            .map(|part| {
+                part.chars()
+                    .take_while(char::is_ascii_digit)
+                    .collect::<String>()
+                    .parse()
+                    .unwrap_or(0)
+            })
+            .collect()
+    };
+    parts(left).cmp(&parts(right))
+}
+
+/// `archive_game_version` stammt aus dem Manifest, `local_game_version` aus
+/// der lokalen Spielinstallation und `local_save_version` aus den lokalen
+/// Saves. Das Save-Format wird auch ohne Spielversion im Manifest verglichen.
+pub fn check_save_version(
+    saves: &[ArchiveSave],
+    archive_game_version: Option<&str>,
+    local_game_version: Option<&str>,
+    local_save_version: Option<u32>,
+) -> SaveVersionCheck {
+    let archive_max = saves
+        .iter()
+        .filter_map(|save| save.info_text.as_deref().and_then(save_format_version))
+        .max();
+    let newer_game = matches!(
+        (archive_game_version, local_game_version),
+        (Some(archive), Some(local)) if compare_game_versions(archive, local) == Ordering::Greater
+    );
+    let newer_format = matches!(
+        (archive_max, local_save_version),
+        (Some(archive), Some(local)) if archive > local
+    );
+    SaveVersionCheck {
+        archive_version: archive_max,
+        local_version: local_save_version,
+        archive_game_version: archive_game_version.map(str::to_string),
+        local_game_version: local_game_version.map(str::to_string),
+        newer_than_local: newer_game || newer_format,
+    }
+}
+
+pub fn build_report(
+    mods: Vec<ModAvailability>,
+    dlc: Vec<DlcRequirement>,
+    save_version: SaveVersionCheck,
+    existing_profile_path: Option<String>,
+) -> ProfileCompatibilityReport {
+    let missing_mods = mods
+        .iter()
+        .filter(|item| item.installed == Some(false))
+        .count();
+    let missing_dlc = dlc
+        .iter()
+        .filter(|item| item.installed == Some(false))
+        .count();
+    ProfileCompatibilityReport {
+        compatible: missing_mods == 0 && missing_dlc == 0 && !save_version.newer_than_local,
+        missing_mods,
+        missing_dlc,
+        mods,
+        dlc,
+        save_version,
+        existing_profile_path,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dlc_requirements_come_from_cities_trailers_and_dependencies() {
+        let saves = vec![ArchiveSave {
+            slot: "autosave".to_string(),
+            info_text: Some(
+                "save_container : _nameless.1 {\n version: 80\n dependencies: 1\n dependencies[0]: \"dlc|dlc_it|Italia\"\n}\n"
+                    .to_string(),
+            ),
+            game_text: Some(
+                " visited_cities: 3\n visited_cities[0]: berlin\n visited_cities[1]: aalborg\n visited_cities[2]: a_coruna\n trailer_definition: trailer_def.krone.curtain\n trailer_definition: trailer_def.scs.box\n trailer_definition: trailer_def.mymod.box\n"
+                    .to_string(),
+            ),
+        }];
+        let installed = BTreeSet::from(["dlc_north".to_string()]);
+        let dlc = required_dlc(&saves, Some(&installed));
+        let summary: Vec<(&str, Option<bool>)> = dlc
+            .iter()
+            .map(|item| (item.dlc.as_str(), item.installed))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("dlc_iberia", Some(false)),
+                ("dlc_it", Some(false)),
+                ("dlc_krone", Some(false)),
+                ("dlc_north", Some(true)),
+            ]
+        );
+
+        let version = check_save_version(&saves, Some("1.53.1.0s"), Some("1.53.0.12s"), None);
+        assert_eq!(version.archive_version, Some(80));
+        assert!(version.newer_than_local);
+        assert!(
+            !check_save_version(&saves, Some("1.9.0"), Some("1.53.0"), Some(80)).newer_than_local
+        );
+        assert!(!check_save_version(&saves, None, Some("1.53.0"), None).newer_than_local);
+        assert!(check_save_version(&saves, None, None, Some(79)).newer_than_local);
+        assert!(!check_save_version(&saves, None, None, Some(81)).newer_than_local);
+
+        let report = build_report(Vec::new(), dlc, version, None);
+        assert!(!report.compatible);
+        assert_eq!(report.missing_dlc, 3);
+    }
+}
@@ -0,0 +1,418 @@
+//! Hashes, Gesamtgroesse und optionale Ed25519-Signatur fuer Manifest v2.
+//!
+//! Eine Signatur gilt nur als gueltig, wenn ihr Schluessel der eigene oder
+//! ein hinterlegter bekannter Exporteur ist; der Schluessel im Manifest
+//! allein beweist nur, dass das Archiv seit dem Signieren unveraendert ist.
+
+use super::models::{
+    ArchiveIntegrityReport, IntegrityMismatch, ProfileShareSigningKey, SharedProfileEntry,
+    SharedProfileManifest, SharedProfileMod, SharedProfileSignature, SignatureStatus,
+    TrustedProfileSigner,
+};
+use crate::features::mod_profile_manager::sii_mods::{
+    parse_active_mod_values_from_profile_text, workshop_mod_ref_to_id,
+};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use rand_core::{OsRng, RngCore};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+const SIGNATURE_ALGORITHM: &str = "ed25519";
+const SIGNING_KEY_FILE: &str = "profile_share_signing.key";
+const TRUSTED_SIGNERS_FILE: &str = "profile_share_trusted_signers.json";
+
+pub fn entry_for(path: &str, bytes: &[u8]) -> SharedProfileEntry {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    SharedProfileEntry {
+        path: path.to_string(),
+        size: bytes.len() as u64,
+        sha256: format!("{:x}", hasher.finalize()),
+    }
+}
+
+/// Aktive Mods aus einer entschluesselten profile.sii, Workshop-Mods mit ID.
+pub fn active_mods_from_profile(profile_text: &str) -> Vec<SharedProfileMod> {
+    parse_active_mod_values_from_profile_text(profile_text)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|value| {
+            let (mod_ref, name) = match value.split_once('|') {
+                Some((mod_ref, name)) => (mod_ref.to_string(), Some(name.to_string())),
+                None => (value, None),
+            };
+            SharedProfileMod {
+                workshop_id: workshop_mod_ref_to_id(&mod_ref).map(|id| id.to_string()),
+                mod_ref,
+                name,
+            }
+        })
+        .collect()
+}
+
+fn config_file_path(file_name: &str) -> Result<PathBuf, String> {
+    let dir = dirs::config_dir()
+        .ok_or("Konfigurationsverzeichnis nicht gefunden".to_string())?
+        .join("save-edit-tool");
+    fs::create_dir_all(&dir).map_err(|error| error.to_string())?;
+    Ok(dir.join(file_name))
+}
+
+fn signing_key_path() -> Result<PathBuf, String> {
+    config_file_path(SIGNING_KEY_FILE)
+}
+
+/// Laedt den Exporter-Schluessel oder legt beim ersten Aufruf einen neuen an.
+pub fn load_or_create_signing_key() -> Result<SigningKey, String> {
+    let path = signing_key_path()?;
+    if let Ok(content) = fs::read_to_string(&path) {
+        let seed: [u8; 32] = STANDARD
+            .decode(content.trim())
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or("Der gespeicherte Signaturschluessel ist beschaedigt.".to_string())?;
+        return Ok(SigningKey::from_bytes(&seed));
+    }
+
+    let mut seed = [0u8; 32];
+    OsRng.fill_bytes(&mut seed);
+    fs::write(&path, STANDARD.encode(seed)).map_err(|error| {
+        format!(
+            "Signaturschluessel konnte nicht gespeichert werden: {}",
+            error
+        )
+    })?;
+    Ok(SigningKey::from_bytes(&seed))
+}
+
+/// Hinterlegte Exporteur-Schluessel; eine fehlende Datei ist eine leere Liste.
+pub fn load_trusted_signers() -> Result<Vec<TrustedProfileSigner>, String> {
+    let path = config_file_path(TRUSTED_SIGNERS_FILE)?;
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|error| format!("Liste bekannter Signierer ist beschaedigt: {}", error)),
+        Err(_) => Ok(Vec::new()),
+    }
+}
+
+/// Hinterlegt einen Exporteur-Schluessel als vertrauenswuerdig.
+pub fn trust_signer(public_key: &str) -> Result<TrustedProfileSigner, String> {
+    let key = decode_verifying_key(public_key)
+        .ok_or("Ungueltiger oeffentlicher Schluessel".to_string())?;
+    let mut signers = load_trusted_signers()?;
+    if let Some(existing) = signers
+        .iter()
+        .find(|signer| signer.public_key == public_key)
+    {
+        return Ok(existing.clone());
+    }
+    let signer = TrustedProfileSigner {
+        public_key: public_key.to_string(),
+        fingerprint: fingerprint(&key),
+        trusted_at: chrono::Utc::now().to_rfc3339(),
+    };
+    signers.push(signer.clone());
+    let json = serde_json::to_string_pretty(&signers).map_err(|error| error.to_string())?;
+    fs::write(config_file_path(TRUSTED_SIGNERS_FILE)?, json).map_err(|error| {
+        format!(
+            "Liste bekannter Signierer konnte nicht gespeichert werden: {}",
+            error
+        )
+    })?;
+    Ok(signer)
+}
+
+/// Eigener Schluessel (falls schon angelegt) und alle hinterlegten Schluessel.
+pub fn trusted_keys() -> Result<Vec<VerifyingKey>, String> {
+    let mut keys: Vec<VerifyingKey> = load_trusted_signers()?
+        .iter()
+        .filter_map(|signer| decode_verifying_key(&signer.public_key))
+        .collect();
+    if signing_key_path()?.is_file() {
+        keys.push(load_or_create_signing_key()?.verifying_key());
+    }
+    Ok(keys)
+}
+
+fn decode_verifying_key(public_key: &str) -> Option<VerifyingKey> {
+    STANDARD
+        .decode(public_key)
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+}
+
+pub fn fingerprint(key: &VerifyingKey) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(key.as_bytes());
+    let digest = format!("{:x}", hasher.finalize());
+    digest.as_bytes()[..16]
+        .chunks(4)
+        .map(|chunk| String::from_utf8_lossy(chunk).to_uppercase())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+pub fn signing_key_info(key: &SigningKey) -> ProfileShareSigningKey {
+    let verifying = key.verifying_key();
+    ProfileShareSigningKey {
+        public_key: STANDARD.encode(verifying.as_bytes()),
+        fingerprint: fingerprint(&verifying),
+    }
+}
+
+/// Signiert wird das Manifest-JSON ohne das Feld `signature`.
+fn signing_payload(manifest: &SharedProfileManifest) -> Result<Vec<u8>, String> {
+    let mut unsigned = manifest.clone();
+    unsigned.signature = None;
+    serde_json::to_vec(&unsigned).map_err(|error| error.to_string())
+}
+
+pub fn sign_manifest(manifest: &mut SharedProfileManifest, key: &SigningKey) -> Result<(), String> {
+    let signature = key.sign(&signing_payload(manifest)?);
+    manifest.signature = Some(SharedProfileSignature {
+        algorithm: SIGNATURE_ALGORITHM.to_string(),
+        public_key: STANDARD.encode(key.verifying_key().as_bytes()),
+        signature: STANDARD.encode(signature.to_bytes()),
+    });
+    Ok(())
+}
+
+fn verify_signature(
+    manifest: &SharedProfileManifest,
+    trusted: &[VerifyingKey],
+) -> (SignatureStatus, Option<String>) {
+    let Some(signature) = manifest.signature.as_ref() else {
+        return (SignatureStatus::Unsigned, None);
+    };
+    let Some(key) = decode_verifying_key(&signature.public_key) else {
+        return (SignatureStatus::Invalid, None);
+    };
+    let valid = signature.algorithm == SIGNATURE_ALGORITHM
+        && STANDARD
+            .decode(&signature.signature)
+            .ok()
+            .and_then(|bytes| Signature::from_slice(&bytes).ok())
+            .zip(signing_payload(manifest).ok())
+            .is_some_and(|(sig, payload)| key.verify(&payload, &sig).is_ok());
+    let status = if !valid {
+        SignatureStatus::Invalid
+    } else if trusted.contains(&key) {
+        SignatureStatus::Valid
+    } else {
+        SignatureStatus::UnknownSigner
+    };
+    (status, Some(fingerprint(&key)))
+}
+
+/// Vergleicht die tatsaechlichen Archiveintraege mit dem Manifest.
+/// `unreadable` enthaelt Eintraege, die nicht vollstaendig gelesen werden
+/// konnten oder ausserhalb des Profilordners liegen; `trusted` sind die
+/// Schluessel, deren Signaturen als gueltig gelten.
+pub fn build_report(
+    manifest: Option<&SharedProfileManifest>,
+    actual: &[SharedProfileEntry],
+    unreadable: Vec<IntegrityMismatch>,
+    trusted: &[VerifyingKey],
+) -> ArchiveIntegrityReport {
+    let actual_total_size = actual.iter().map(|entry| entry.size).sum();
+    let (signature_status, signer_fingerprint) = manifest
+        .map(|manifest| verify_signature(manifest, trusted))
+        .unwrap_or((SignatureStatus::Unsigned, None));
+    let signer_public_key = manifest
+        .and_then(|manifest| manifest.signature.as_ref())
+        .filter(|_| {
+            matches!(
+                signature_status,
+                SignatureStatus::Valid | SignatureStatus::UnknownSigner
+            )
+        })
+        .map(|signature| signature.public_key.clone());
+    let mut report = ArchiveIntegrityReport {
+        manifest_version: manifest.map(|manifest| manifest.archive_version),
+        checked: false,
+        verified: false,
+        checked_files: 0,
+        expected_total_size: manifest.and_then(|manifest| manifest.total_size),
+        actual_total_size,
+        mismatches: unreadable,
+        signature_status,
+        signer_fingerprint,
+        signer_public_key,
+        source_app_version: manifest.and_then(|manifest| manifest.source_app_version.clone()),
+        source_game_version: manifest.and_then(|manifest| manifest.source_game_version.clone()),
+        active_mods: manifest
+            .map(|manifest| manifest.active_mods.clone())
+            .unwrap_or_default(),
+    };
+
+    let Some(manifest) = manifest.filter(|manifest| !manifest.entries.is_empty()) else {
+        // Ab Version 2 gehoeren die Hashes zum Format; fehlen sie, wurde das
+        // Manifest veraendert.
+        if report.manifest_version.is_some_and(|version| version >= 2) {
+            report.mismatches.push(IntegrityMismatch {
+                path: "profile_share_manifest.json".to_string(),
+                kind: "manifest".to_string(),
+                expected: None,
+                actual: None,
+            });
+        }
+        report.verified =
+            report.mismatches.is_empty() && signature_status != SignatureStatus::Invalid;
+        return report;
+    };
+
+    report.checked = true;
+    let mut remaining: BTreeMap<&str, &SharedProfileEntry> = actual
+        .iter()
+        .map(|entry| (entry.path.as_str(), entry))
+        .collect();
+    for expected in &manifest.entries {
+        let Some(found) = remaining.remove(expected.path.as_str()) else {
+            if !report
+                .mismatches
+                .iter()
+                .any(|item| item.path == expected.path)
+            {
+                report.mismatches.push(IntegrityMismatch {
+                    path: expected.path.clone(),
+                    kind: "missing".to_string(),
+                    expected: Some(expected.sha256.clone()),
+                    actual: None,
+                });
+            }
+            continue;
+        };
+        report.checked_files += 1;
+        if found.size != expected.size {
+            report.mismatches.push(IntegrityMismatch {
+                path: expected.path.clone(),
+                kind: "size".to_string(),
+                expected: Some(expected.size.to_string()),
+                actual: Some(found.size.to_string()),
+            });
+        } else if found.sha256 != expected.sha256 {
+            report.mismatches.push(IntegrityMismatch {
+                path: expected.path.clone(),
+                kind: "hash".to_string(),
+                expected: Some(expected.sha256.clone()),
+                actual: Some(found.sha256.clone()),
+            });
+        }
+    }
+    for path in remaining.into_keys() {
+        report.mismatches.push(IntegrityMismatch {
+            path: path.to_string(),
+            kind: "unexpected".to_string(),
+            expected: None,
+            actual: None,
+        });
+    }
+
+    let total_matches = report
+        .expected_total_size
+        .is_none_or(|expected| expected == report.actual_total_size);
+    report.verified = report.mismatches.is_empty()
+        && total_matches
+        && signature_status != SignatureStatus::Invalid;
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(entries: Vec<SharedProfileEntry>) -> SharedProfileManifest {
+        SharedProfileManifest {
+            archive_format: "ets2-tool.profile-share".to_string(),
+            archive_version: 2,
+            exported_at: "2026-10-19T12:00:00+02:00".to_string(),
+            game: "ets2".to_string(),
+            profile_name: "Event".to_string(),
+            source_profile_folder: "4576656E74".to_string(),
+            profile_root: "profile".to_string(),
+            source_app_version: Some("1.0.0".to_string()),
+            source_game_version: None,
+            total_size: Some(entries.iter().map(|entry| entry.size).sum()),
+            entries,
+            active_mods: Vec::new(),
+            signature: None,
+        }
+    }
+
+    #[test]
+    fn report_detects_tampered_truncated_and_extra_files() {
+        let original = vec![
+            entry_for("profile.sii", b"profile"),
+            entry_for("controls.sii", b"controls"),
+            entry_for("save/1/game.sii", b"game data"),
+        ];
+        let manifest = manifest(original.clone());
+        let clean = build_report(Some(&manifest), &original, Vec::new(), &[]);
+        assert!(clean.checked && clean.verified);
+        assert_eq!(clean.checked_files, 3);
+
+        let actual = vec![
+            entry_for("profile.sii", b"profilX"),
+            entry_for("save/1/game.sii", b"game"),
+            entry_for("extra.sii", b"x"),
+        ];
+        let report = build_report(Some(&manifest), &actual, Vec::new(), &[]);
+        assert!(!report.verified);
+        let kinds: Vec<(&str, &str)> = report
+            .mismatches
+            .iter()
+            .map(|item| (item.path.as_str(), item.kind.as_str()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("profile.sii", "hash"),
+                ("controls.sii", "missing"),
+                ("save/1/game.sii", "size"),
+                ("extra.sii", "unexpected"),
+            ]
+        );
+    }
+
+    #[test]
+    fn signature_covers_the_whole_manifest() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let entries = vec![entry_for("profile.sii", b"profile")];
+        let mut signed = manifest(entries.clone());
+        sign_manifest(&mut signed, &key).unwrap();
+
+        let trusted = [key.verifying_key()];
+        let report = build_report(Some(&signed), &entries, Vec::new(), &trusted);
+        assert_eq!(report.signature_status, SignatureStatus::Valid);
+        assert_eq!(
+            report.signer_fingerprint,
+            Some(signing_key_info(&key).fingerprint)
+        );
+
+        signed.entries[0].sha256 = entry_for("profile.sii", b"other").sha256;
+        let report = build_report(Some(&signed), &entries, Vec::new(), &trusted);
+        assert_eq!(report.signature_status, SignatureStatus::Invalid);
+        assert!(!report.verified);
+    }
+
+    #[test]
+    fn a_key_only_named_in_the_manifest_is_an_unknown_signer() {
+        let ours = SigningKey::from_bytes(&[7u8; 32]);
+        let stranger = SigningKey::from_bytes(&[9u8; 32]);
+        let entries = vec![entry_for("profile.sii", b"profile")];
+        let mut signed = manifest(entries.clone());
+        sign_manifest(&mut signed, &stranger).unwrap();
+
+        let report = build_report(Some(&signed), &entries, Vec::new(), &[ours.verifying_key()]);
+        assert_eq!(report.signature_status, SignatureStatus::UnknownSigner);
+        assert_eq!(
+            report.signer_public_key,
+            Some(signing_key_info(&stranger).public_key)
+        );
+    }
+}
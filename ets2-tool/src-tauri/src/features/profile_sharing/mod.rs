@@ -1,3 +1,5 @@
 pub mod commands;
+mod compatibility;
+mod integrity;
 mod models;
 mod service;
@@ -22,6 +22,7 @@ pub struct ProfileShareExportResult {
     pub archive_path: String,
     pub export_dir: String,
     pub exported_files: usize,
+    pub signer_fingerprint: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -37,6 +38,8 @@ pub struct ProfileShareImportPreview {
     pub has_manifest: bool,
     pub file_count: usize,
     pub profile_name_conflict: bool,
+    pub integrity: ArchiveIntegrityReport,
+    pub compatibility: ProfileCompatibilityReport,
 }
 
 #[derive(Debug, Serialize)]
@@ -47,9 +50,74 @@ pub struct ProfileShareImportResult {
     pub archive_path: String,
     pub imported_files: usize,
     pub import_target_dir: String,
+    pub mode: ProfileImportMode,
+    pub backup_path: Option<String>,
+    pub merged_saves: Vec<String>,
+    pub signature_status: SignatureStatus,
+    pub signer_fingerprint: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Wie ein Archiv importiert wird, wenn es das Profil schon gibt.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfileImportMode {
+    /// Neues Profil; bei Namenskonflikt mit neuem Namen
+    #[default]
+    Rename,
+    /// Vorhandenes Profil nach Backup ersetzen
+    Replace,
+    /// Nur die Saves in ein vorhandenes Profil uebernehmen
+    MergeSaves,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModAvailability {
+    pub mod_ref: String,
+    pub name: Option<String>,
+    pub workshop_id: Option<String>,
+    /// `None`, wenn der lokale Mod-Scan nicht moeglich war
+    pub installed: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DlcRequirement {
+    /// Name des DLC-Archivs ohne Endung, z. B. `dlc_north`
+    pub dlc: String,
+    pub reasons: Vec<String>,
+    /// `None`, wenn die Spielinstallation nicht gefunden wurde
+    pub installed: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveVersionCheck {
+    /// Hoechste Save-Formatversion (`version` aus info.sii) im Archiv
+    pub archive_version: Option<u32>,
+    /// Hoechste Save-Formatversion der lokalen Profile
+    pub local_version: Option<u32>,
+    /// Spielversion des Exportrechners laut Manifest
+    pub archive_game_version: Option<String>,
+    /// Spielversion der lokalen Installation
+    pub local_game_version: Option<String>,
+    pub newer_than_local: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileCompatibilityReport {
+    pub compatible: bool,
+    pub missing_mods: usize,
+    pub missing_dlc: usize,
+    pub mods: Vec<ModAvailability>,
+    pub dlc: Vec<DlcRequirement>,
+    pub save_version: SaveVersionCheck,
+    /// Vorhandenes Profil gleichen Namens (Ziel fuer Ersetzen/Saves mergen)
+    pub existing_profile_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SharedProfileManifest {
     pub archive_format: String,
@@ -59,4 +127,104 @@ pub struct SharedProfileManifest {
     pub profile_name: String,
     pub source_profile_folder: String,
     pub profile_root: String,
+    // Ab Version 2
+    #[serde(default)]
+    pub source_app_version: Option<String>,
+    /// Spielversion des Exportrechners; fehlt bei aelteren Archiven und wird
+    /// dann nicht serialisiert, damit deren Signatur gueltig bleibt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_game_version: Option<String>,
+    #[serde(default)]
+    pub total_size: Option<u64>,
+    #[serde(default)]
+    pub entries: Vec<SharedProfileEntry>,
+    #[serde(default)]
+    pub active_mods: Vec<SharedProfileMod>,
+    #[serde(default)]
+    pub signature: Option<SharedProfileSignature>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedProfileEntry {
+    /// Pfad relativ zu `profile_root`, immer mit `/`
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedProfileMod {
+    pub mod_ref: String,
+    pub name: Option<String>,
+    pub workshop_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedProfileSignature {
+    pub algorithm: String,
+    /// Base64, 32 Byte
+    pub public_key: String,
+    /// Base64, 64 Byte ueber das Manifest ohne Signatur
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureStatus {
+    Unsigned,
+    /// Gueltig und von einem vertrauenswuerdigen Schluessel signiert
+    Valid,
+    /// Gueltig, aber der Schluessel ist weder der eigene noch hinterlegt
+    UnknownSigner,
+    Invalid,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityMismatch {
+    pub path: String,
+    /// missing, unexpected, size, hash, unreadable oder manifest
+    pub kind: String,
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveIntegrityReport {
+    pub manifest_version: Option<u32>,
+    /// `false` bei Archiven ohne Hashes (Version 1 oder ohne Manifest)
+    pub checked: bool,
+    pub verified: bool,
+    pub checked_files: usize,
+    pub expected_total_size: Option<u64>,
+    pub actual_total_size: u64,
+    pub mismatches: Vec<IntegrityMismatch>,
+    pub signature_status: SignatureStatus,
+    pub signer_fingerprint: Option<String>,
+    /// Base64; nur gesetzt, wenn die Signatur gueltig ist
+    pub signer_public_key: Option<String>,
+    pub source_app_version: Option<String>,
+    pub source_game_version: Option<String>,
+    pub active_mods: Vec<SharedProfileMod>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileShareSigningKey {
+    pub public_key: String,
+    pub fingerprint: String,
+}
+
+/// Hinterlegter Schluessel eines bekannten Exporteurs.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustedProfileSigner {
+    /// Base64, 32 Byte
+    pub public_key: String,
+    pub fingerprint: String,
+    pub trusted_at: String,
 }
@@ -1,14 +1,28 @@
+use super::compatibility::{self, ArchiveSave};
+use super::integrity;
 use super::models::{
+    ArchiveIntegrityReport, IntegrityMismatch, ProfileCompatibilityReport, ProfileImportMode,
     ProfileShareContext, ProfileShareExportResult, ProfileShareImportPreview,
-    ProfileShareImportResult, SharedProfileManifest,
+    ProfileShareImportResult, ProfileShareSigningKey, SharedProfileEntry, SharedProfileManifest,
+    SignatureStatus,
 };
 use crate::dev_log;
+use crate::features::career::plugin_installer::{ScsGame, find_game_installation};
+use crate::features::mod_profile_manager::discovery::{ScanMode, scan_inventory_with_mode};
+use crate::features::mod_profile_manager::models::GameType;
+use crate::features::mod_profile_manager::steam_paths::find_game_install_dir;
+use crate::features::profile_clone::logic::{
+    BACKUP_DIR_NAME, copy_dir_recursive, create_zip_backup_in,
+};
 use crate::shared::decrypt::decrypt_if_needed;
 use crate::shared::extract::extract_profile_name;
+use crate::shared::hashfs::{HashFsArchive, is_hashfs_archive};
 use crate::shared::hex_float::{decode_hex_folder_name, text_to_hex};
 use crate::shared::paths::get_base_path;
 use crate::state::AppProfileState;
 use chrono::Local;
+use ed25519_dalek::SigningKey;
+use std::collections::BTreeSet;
 use std::fs::{self, File};
 use std::io::{Read, Write};
 use std::path::{Component, Path, PathBuf};
@@ -20,7 +34,7 @@ use zip::write::FileOptions;
 use zip::{CompressionMethod, ZipArchive, ZipWriter};
 
 const ARCHIVE_FORMAT: &str = "ets2-tool.profile-share";
-const ARCHIVE_VERSION: u32 = 1;
+const ARCHIVE_VERSION: u32 = 2;
 const ARCHIVE_ROOT: &str = "profile";
 const MANIFEST_NAME: &str = "profile_share_manifest.json";
 
@@ -108,9 +122,15 @@ pub fn pick_shared_profile_export_directory(
         .transpose()
 }
 
+pub fn get_profile_share_signing_key() -> Result<ProfileShareSigningKey, String> {
+    let key = integrity::load_or_create_signing_key()?;
+    Ok(integrity::signing_key_info(&key))
+}
+
 pub fn export_shared_profile(
     profile_path: &str,
     export_dir_override: Option<String>,
+    sign: bool,
     profile_state: &AppProfileState,
 ) -> Result<ProfileShareExportResult, String> {
     let profile_dir = require_profile_dir(profile_path)?;
@@ -123,7 +143,16 @@ pub fn export_shared_profile(
     let archive_name = ensure_unique_archive_name(&export_dir, &profile_name);
     let archive_path = export_dir.join(&archive_name);
     let temp_archive_path = export_dir.join(format!(".{}.tmp", Uuid::new_v4().simple()));
-    let manifest = SharedProfileManifest {
+    let signing_key = if sign {
+        Some(integrity::load_or_create_signing_key()?)
+    } else {
+        None
+    };
+    let active_mods = decrypt_if_needed(&profile_dir.join("profile.sii"))
+        .map(|content| integrity::active_mods_from_profile(&content))
+        .unwrap_or_default();
+    let source_game_version = installed_game_version(&selected_game);
+    let mut manifest = SharedProfileManifest {
         archive_format: ARCHIVE_FORMAT.to_string(),
         archive_version: ARCHIVE_VERSION,
         exported_at: Local::now().to_rfc3339(),
@@ -135,6 +164,12 @@ pub fn export_shared_profile(
             .unwrap_or_default()
             .to_string(),
         profile_root: ARCHIVE_ROOT.to_string(),
+        source_app_version: Some(env!("CARGO_PKG_VERSION").to_string()),
+        source_game_version,
+        total_size: None,
+        entries: Vec::new(),
+        active_mods,
+        signature: None,
     };
 
     dev_log!(
@@ -143,16 +178,21 @@ pub fn export_shared_profile(
         archive_path.display()
     );
 
-    let export_result = write_profile_archive(&profile_dir, &temp_archive_path, &manifest)
-        .and_then(|exported_files| {
-            fs::rename(&temp_archive_path, &archive_path).map_err(|error| {
-                format!(
-                    "Das ZIP-Archiv konnte nicht in den Zielordner verschoben werden: {}",
-                    error
-                )
-            })?;
-            Ok(exported_files)
-        });
+    let export_result = write_profile_archive(
+        &profile_dir,
+        &temp_archive_path,
+        &mut manifest,
+        signing_key.as_ref(),
+    )
+    .and_then(|exported_files| {
+        fs::rename(&temp_archive_path, &archive_path).map_err(|error| {
+            format!(
+                "Das ZIP-Archiv konnte nicht in den Zielordner verschoben werden: {}",
+                error
+            )
+        })?;
+        Ok(exported_files)
+    });
     if export_result.is_err() && temp_archive_path.exists() {
         let _ = fs::remove_file(&temp_archive_path);
     }
@@ -170,10 +210,14 @@ pub fn export_shared_profile(
         archive_path: archive_path.display().to_string(),
         export_dir: export_dir.display().to_string(),
         exported_files,
+        signer_fingerprint: signing_key
+            .as_ref()
+            .map(|key| integrity::signing_key_info(key).fingerprint),
     })
 }
 
 pub fn inspect_shared_profile_archive(
+    app: &AppHandle,
     archive_path: &str,
     profile_name_override: Option<String>,
     profile_state: &AppProfileState,
@@ -188,6 +232,14 @@ pub fn inspect_shared_profile_archive(
         &inspection.detected_profile_name,
         profile_name_override,
     );
+    let compatibility = analyze_compatibility(
+        app,
+        profile_state,
+        &selected_game,
+        &mut archive,
+        &inspection,
+        &import_target_dir,
+    )?;
 
     Ok(ProfileShareImportPreview {
         archive_path: archive_path.display().to_string(),
@@ -200,12 +252,17 @@ pub fn inspect_shared_profile_archive(
         has_manifest: inspection.has_manifest,
         file_count: inspection.file_count,
         profile_name_conflict: import_plan.profile_name_conflict,
+        integrity: inspection.integrity,
+        compatibility,
     })
 }
 
 pub fn import_shared_profile(
     archive_path: &str,
     profile_name_override: Option<String>,
+    mode: ProfileImportMode,
+    target_profile_path: Option<String>,
+    trust_signer: bool,
     profile_state: &AppProfileState,
 ) -> Result<ProfileShareImportResult, String> {
     let archive_path = require_archive_path(archive_path)?;
@@ -219,7 +276,45 @@ pub fn import_shared_profile(
     })?;
 
     let mut archive = open_archive(&archive_path)?;
-    let inspection = inspect_archive(&mut archive)?;
+    let mut inspection = inspect_archive(&mut archive)?;
+    if !inspection.integrity.verified {
+        return Err(integrity_error_message(&inspection.integrity));
+    }
+    if inspection.integrity.signature_status == SignatureStatus::UnknownSigner {
+        // Unbekannte Signierer werden gemeldet; hinterlegt wird der Schluessel
+        // nur auf ausdruecklichen Wunsch.
+        match inspection
+            .integrity
+            .signer_public_key
+            .as_deref()
+            .filter(|_| trust_signer)
+        {
+            Some(public_key) => {
+                integrity::trust_signer(public_key)?;
+                inspection.integrity.signature_status = SignatureStatus::Valid;
+            }
+            None => dev_log!(
+                "[profile_sharing] unbekannter Signierer {:?}",
+                inspection.integrity.signer_fingerprint
+            ),
+        }
+    }
+    if mode != ProfileImportMode::Rename {
+        let target_dir = resolve_existing_target(
+            &import_root,
+            target_profile_path.as_deref(),
+            &inspection.detected_profile_name,
+        )?;
+        return import_into_existing_profile(
+            &mut archive,
+            &archive_path,
+            &inspection,
+            &import_root,
+            &target_dir,
+            mode,
+            &selected_game,
+        );
+    }
     let import_plan = plan_import_target(
         &import_root,
         &inspection.detected_profile_name,
@@ -292,6 +387,11 @@ pub fn import_shared_profile(
             archive_path: archive_path.display().to_string(),
             imported_files,
             import_target_dir: import_root.display().to_string(),
+            mode: ProfileImportMode::Rename,
+            backup_path: None,
+            merged_saves: Vec::new(),
+            signature_status: inspection.integrity.signature_status,
+            signer_fingerprint: inspection.integrity.signer_fingerprint.clone(),
         })
     })();
 
@@ -302,11 +402,314 @@ pub fn import_shared_profile(
     import_result
 }
 
+fn resolve_existing_target(
+    import_root: &Path,
+    target_profile_path: Option<&str>,
+    detected_profile_name: &str,
+) -> Result<PathBuf, String> {
+    let target = match target_profile_path.map(str::trim) {
+        Some(path) if !path.is_empty() => PathBuf::from(path),
+        _ => import_root.join(text_to_hex(detected_profile_name.trim())),
+    };
+    if !target.join("profile.sii").is_file() {
+        return Err(
+            "Das Zielprofil zum Ersetzen oder Zusammenfuehren wurde nicht gefunden.".to_string(),
+        );
+    }
+    Ok(target)
+}
+
+/// Ersetzt ein vorhandenes Profil (nach ZIP-Backup) oder uebernimmt nur die
+/// Saves aus dem Archiv in das vorhandene Profil.
+fn import_into_existing_profile<R: Read + std::io::Seek>(
+    archive: &mut ZipArchive<R>,
+    archive_path: &Path,
+    inspection: &ArchiveInspection,
+    import_root: &Path,
+    target_dir: &Path,
+    mode: ProfileImportMode,
+    selected_game: &str,
+) -> Result<ProfileShareImportResult, String> {
+    let staging_dir = import_root.join(format!(".profile_import_{}", Uuid::new_v4().simple()));
+    fs::create_dir_all(&staging_dir).map_err(|error| {
+        format!(
+            "Der temporaere Importordner konnte nicht erstellt werden: {}",
+            error
+        )
+    })?;
+
+    dev_log!(
+        "[profile_sharing] import {:?} archive={} target={}",
+        mode,
+        archive_path.display(),
+        target_dir.display()
+    );
+
+    let result = (|| -> Result<ProfileShareImportResult, String> {
+        let imported_files =
+            extract_profile_from_archive(archive, &inspection.archive_root, &staging_dir)?;
+        let profile_name = resolve_profile_name(target_dir)?;
+
+        let (backup_path, merged_saves) = match mode {
+            ProfileImportMode::MergeSaves => (None, merge_saves(&staging_dir, target_dir)?),
+            _ => {
+                let backup_root = get_base_path(selected_game)
+                    .map(|base| base.join(BACKUP_DIR_NAME))
+                    .ok_or(
+                        "Das Spielbasisverzeichnis konnte nicht aufgeloest werden.".to_string(),
+                    )?;
+                let backup = create_zip_backup_in(target_dir, &backup_root)?;
+                if inspection.detected_profile_name != profile_name {
+                    rewrite_profile_name(&staging_dir.join("profile.sii"), &profile_name)?;
+                }
+                replace_profile_dir(&staging_dir, target_dir)?;
+                (Some(backup.display().to_string()), Vec::new())
+            }
+        };
+
+        Ok(ProfileShareImportResult {
+            profile_name,
+            profile_path: target_dir.display().to_string(),
+            archive_path: archive_path.display().to_string(),
+            imported_files,
+            import_target_dir: import_root.display().to_string(),
+            mode,
+            backup_path,
+            merged_saves,
+            signature_status: inspection.integrity.signature_status,
+            signer_fingerprint: inspection.integrity.signer_fingerprint.clone(),
+        })
+    })();
+
+    if staging_dir.exists() {
+        let _ = fs::remove_dir_all(&staging_dir);
+    }
+    result
+}
+
+/// Tauscht den Profilordner aus; schlaegt das fehl, wird das alte Profil
+/// zurueckgeschoben.
+fn replace_profile_dir(staging_dir: &Path, target_dir: &Path) -> Result<(), String> {
+    let parked =
+        target_dir.with_file_name(format!(".profile_replaced_{}", Uuid::new_v4().simple()));
+    fs::rename(target_dir, &parked).map_err(|error| {
+        format!(
+            "Das vorhandene Profil konnte nicht ersetzt werden: {}",
+            error
+        )
+    })?;
+    if let Err(error) = fs::rename(staging_dir, target_dir) {
+        let _ = fs::rename(&parked, target_dir);
+        return Err(format!(
+            "Der Import konnte nicht in den Profilordner verschoben werden: {}",
+            error
+        ));
+    }
+    let _ = fs::remove_dir_all(&parked);
+    Ok(())
+}
+
+/// Kopiert jeden Save-Slot des Archivs in das Zielprofil. Belegte Slots
+/// werden nicht ueberschrieben; der Save bekommt den naechsten freien
+/// numerischen Slot.
+fn merge_saves(staging_dir: &Path, target_dir: &Path) -> Result<Vec<String>, String> {
+    let source_saves = staging_dir.join("save");
+    let Ok(entries) = fs::read_dir(&source_saves) else {
+        return Err("Das Archiv enthaelt keine Saves.".to_string());
+    };
+    let target_saves = target_dir.join("save");
+    fs::create_dir_all(&target_saves).map_err(|error| error.to_string())?;
+
+    let mut merged = Vec::new();
+    for entry in entries.flatten() {
+        if !entry.path().is_dir() {
+            continue;
+        }
+        let slot = entry.file_name().to_string_lossy().to_string();
+        let target_slot = if target_saves.join(&slot).exists() {
+            (1..)
+                .map(|index| index.to_string())
+                .find(|candidate| !target_saves.join(candidate).exists())
+                .unwrap_or_default()
+        } else {
+            slot
+        };
+        copy_dir_recursive(&entry.path(), &target_saves.join(&target_slot))?;
+        merged.push(target_slot);
+    }
+    if merged.is_empty() {
+        return Err("Das Archiv enthaelt keine Saves.".to_string());
+    }
+    Ok(merged)
+}
+
+fn analyze_compatibility<R: Read + std::io::Seek>(
+    app: &AppHandle,
+    profile_state: &AppProfileState,
+    selected_game: &str,
+    archive: &mut ZipArchive<R>,
+    inspection: &ArchiveInspection,
+    import_root: &Path,
+) -> Result<ProfileCompatibilityReport, String> {
+    let archive_mods = if inspection.integrity.active_mods.is_empty() {
+        read_archive_text(
+            archive,
+            &archive_entry_path(&inspection.archive_root, "profile.sii"),
+        )
+        .map(|content| integrity::active_mods_from_profile(&content))
+        .unwrap_or_default()
+    } else {
+        inspection.integrity.active_mods.clone()
+    };
+    let local_mods = if archive_mods.is_empty() {
+        None
+    } else {
+        scan_inventory_with_mode(app, profile_state, Some(selected_game), ScanMode::Light)
+            .map_err(|error| dev_log!("[profile_sharing] mod scan failed: {}", error))
+            .ok()
+            .map(|inventory| inventory.mods)
+    };
+
+    let saves = read_archive_saves(archive, &inspection.archive_root)?;
+    let installed_dlc = installed_dlc(selected_game);
+    let existing_profile = import_root.join(text_to_hex(inspection.detected_profile_name.trim()));
+
+    Ok(compatibility::build_report(
+        compatibility::compare_mods(&archive_mods, local_mods.as_deref()),
+        compatibility::required_dlc(&saves, installed_dlc.as_ref()),
+        compatibility::check_save_version(
+            &saves,
+            inspection.integrity.source_game_version.as_deref(),
+            installed_game_version(selected_game).as_deref(),
+            local_save_format_version(import_root),
+        ),
+        existing_profile
+            .join("profile.sii")
+            .is_file()
+            .then(|| existing_profile.display().to_string()),
+    ))
+}
+
+fn archive_entry_path(archive_root: &str, relative: &str) -> String {
+    if archive_root.is_empty() {
+        relative.to_string()
+    } else {
+        format!("{}/{}", archive_root, relative)
+    }
+}
+
+/// Liest einen Archiveintrag und entschluesselt ihn bei Bedarf.
+fn read_archive_text<R: Read + std::io::Seek>(
+    archive: &mut ZipArchive<R>,
+    name: &str,
+) -> Option<String> {
+    let mut file = archive.by_name(name).ok()?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes).ok()?;
+    drop(file);
+
+    let temp_path = std::env::temp_dir().join(format!(
+        "ets2_tool_profile_share_{}.sii",
+        Uuid::new_v4().simple()
+    ));
+    fs::write(&temp_path, &bytes).ok()?;
+    let content = decrypt_if_needed(&temp_path).ok();
+    let _ = fs::remove_file(&temp_path);
+    content
+}
+
+fn read_archive_saves<R: Read + std::io::Seek>(
+    archive: &mut ZipArchive<R>,
+    archive_root: &str,
+) -> Result<Vec<ArchiveSave>, String> {
+    let save_prefix = archive_entry_path(archive_root, "save/");
+    let mut slots = BTreeSet::new();
+    for index in 0..archive.len() {
+        let file = archive.by_index(index).map_err(|error| error.to_string())?;
+        let normalized = normalize_archive_name(file.name());
+        if let Some((slot, "info.sii" | "game.sii")) = normalized
+            .strip_prefix(&save_prefix)
+            .and_then(|rest| rest.split_once('/'))
+        {
+            slots.insert(slot.to_string());
+        }
+    }
+
+    Ok(slots
+        .into_iter()
+        .map(|slot| ArchiveSave {
+            info_text: read_archive_text(archive, &format!("{}{}/info.sii", save_prefix, slot)),
+            game_text: read_archive_text(archive, &format!("{}{}/game.sii", save_prefix, slot)),
+            slot,
+        })
+        .collect())
+}
+
+/// Hoechste Save-Formatversion (`version` aus info.sii) aller Saves unter
+/// `profiles_root`.
+fn local_save_format_version(profiles_root: &Path) -> Option<u32> {
+    fs::read_dir(profiles_root)
+        .ok()?
+        .flatten()
+        .filter_map(|profile| fs::read_dir(profile.path().join("save")).ok())
+        .flat_map(|saves| saves.flatten())
+        .map(|save| save.path().join("info.sii"))
+        .filter(|info| info.is_file())
+        .filter_map(|info| decrypt_if_needed(&info).ok())
+        .filter_map(|text| compatibility::save_format_version(&text))
+        .max()
+}
+
+/// DLC-Archive (`dlc_*.scs`) der lokalen Spielinstallation.
+fn installed_dlc(selected_game: &str) -> Option<BTreeSet<String>> {
+    let game = if selected_game == "ats" {
+        ScsGame::Ats
+    } else {
+        ScsGame::Ets2
+    };
+    let install = find_game_installation(game).ok()?;
+    let entries = fs::read_dir(&install.install_root).ok()?;
+    Some(
+        entries
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_ascii_lowercase();
+                name.strip_suffix(".scs")
+                    .filter(|stem| stem.starts_with("dlc_"))
+                    .map(str::to_string)
+            })
+            .collect(),
+    )
+}
+
+/// Versionsdateien in den Grundarchiven der Installation (`core.scs` ab 1.47).
+const GAME_VERSION_ARCHIVES: [&str; 2] = ["core.scs", "base.scs"];
+
+/// Spielversion der lokalen Installation, gelesen aus `version.txt` im
+/// Grundarchiv.
+fn installed_game_version(selected_game: &str) -> Option<String> {
+    let game = if selected_game == "ats" {
+        GameType::Ats
+    } else {
+        GameType::Ets2
+    };
+    let install_dir = find_game_install_dir(game)?;
+    GAME_VERSION_ARCHIVES
+        .iter()
+        .map(|name| install_dir.join(name))
+        .filter(|path| is_hashfs_archive(path))
+        .filter_map(|path| HashFsArchive::open(&path).ok())
+        .filter_map(|archive| archive.read_file("version.txt").ok().flatten())
+        .map(|bytes| String::from_utf8_lossy(&bytes).trim().to_string())
+        .find(|version| !version.is_empty())
+}
+
 struct ArchiveInspection {
     detected_profile_name: String,
     archive_root: String,
     has_manifest: bool,
     file_count: usize,
+    integrity: ArchiveIntegrityReport,
 }
 
 struct ImportPlan {
@@ -482,10 +885,13 @@ fn sanitize_filename_component(value: &str) -> String {
     }
 }
 
+/// Schreibt erst die Profildateien und zuletzt das Manifest, damit Hashes und
+/// Gesamtgröße darin vollständig sind.
 fn write_profile_archive(
     profile_dir: &Path,
     archive_path: &Path,
-    manifest: &SharedProfileManifest,
+    manifest: &mut SharedProfileManifest,
+    signing_key: Option<&SigningKey>,
 ) -> Result<usize, String> {
     let file = File::create(archive_path)
         .map_err(|error| format!("Die ZIP-Datei konnte nicht erstellt werden: {}", error))?;
@@ -493,25 +899,6 @@ fn write_profile_archive(
     let options: FileOptions<()> =
         FileOptions::default().compression_method(CompressionMethod::Deflated);
 
-    let manifest_json = serde_json::to_vec_pretty(manifest).map_err(|error| {
-        format!(
-            "Das Export-Manifest konnte nicht erstellt werden: {}",
-            error
-        )
-    })?;
-    zip.start_file(MANIFEST_NAME, options).map_err(|error| {
-        format!(
-            "Das Export-Manifest konnte nicht in das Archiv geschrieben werden: {}",
-            error
-        )
-    })?;
-    zip.write_all(&manifest_json).map_err(|error| {
-        format!(
-            "Das Export-Manifest konnte nicht geschrieben werden: {}",
-            error
-        )
-    })?;
-
     let mut exported_files = 0usize;
     for entry in WalkDir::new(profile_dir) {
         let entry = entry.map_err(|error| error.to_string())?;
@@ -525,11 +912,8 @@ fn write_profile_archive(
                 error
             )
         })?;
-        let archive_name = format!(
-            "{}/{}",
-            ARCHIVE_ROOT,
-            relative.to_string_lossy().replace('\\', "/")
-        );
+        let relative_name = relative.to_string_lossy().replace('\\', "/");
+        let archive_name = format!("{}/{}", ARCHIVE_ROOT, relative_name);
 
         zip.start_file(archive_name, options).map_err(|error| {
             format!(
@@ -550,6 +934,9 @@ fn write_profile_archive(
                 error
             )
         })?;
+        manifest
+            .entries
+            .push(integrity::entry_for(&relative_name, &data));
         exported_files += 1;
     }
 
@@ -559,6 +946,29 @@ fn write_profile_archive(
         );
     }
 
+    manifest.total_size = Some(manifest.entries.iter().map(|entry| entry.size).sum());
+    if let Some(key) = signing_key {
+        integrity::sign_manifest(manifest, key)?;
+    }
+    let manifest_json = serde_json::to_vec_pretty(&*manifest).map_err(|error| {
+        format!(
+            "Das Export-Manifest konnte nicht erstellt werden: {}",
+            error
+        )
+    })?;
+    zip.start_file(MANIFEST_NAME, options).map_err(|error| {
+        format!(
+            "Das Export-Manifest konnte nicht in das Archiv geschrieben werden: {}",
+            error
+        )
+    })?;
+    zip.write_all(&manifest_json).map_err(|error| {
+        format!(
+            "Das Export-Manifest konnte nicht geschrieben werden: {}",
+            error
+        )
+    })?;
+
     zip.finish().map_err(|error| {
         format!(
             "Das ZIP-Archiv konnte nicht abgeschlossen werden: {}",
@@ -575,15 +985,84 @@ fn inspect_archive<R: Read + std::io::Seek>(
     let (archive_root, file_count) = locate_archive_root(archive, manifest.as_ref())?;
     let detected_profile_name =
         read_profile_name_from_archive(archive, &archive_root, manifest.as_ref())?;
+    let (entries, unreadable) = hash_archive_entries(archive, &archive_root)?;
+    let integrity = integrity::build_report(
+        manifest.as_ref(),
+        &entries,
+        unreadable,
+        &integrity::trusted_keys()?,
+    );
 
     Ok(ArchiveInspection {
         detected_profile_name,
         archive_root,
         has_manifest: manifest.is_some(),
         file_count,
+        integrity,
     })
 }
 
+/// Liest jeden Eintrag unter `archive_root` vollständig; CRC-Fehler und
+/// abgeschnittene Einträge landen als `unreadable`, Einträge ausserhalb des
+/// Profilordners als `unexpected` im Bericht.
+fn hash_archive_entries<R: Read + std::io::Seek>(
+    archive: &mut ZipArchive<R>,
+    archive_root: &str,
+) -> Result<(Vec<SharedProfileEntry>, Vec<IntegrityMismatch>), String> {
+    let prefix = if archive_root.is_empty() {
+        String::new()
+    } else {
+        format!("{}/", archive_root)
+    };
+    let mut entries = Vec::new();
+    let mut unreadable = Vec::new();
+
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index).map_err(|error| error.to_string())?;
+        let normalized = normalize_archive_name(file.name());
+        if normalized == MANIFEST_NAME || file.is_dir() {
+            continue;
+        }
+        let Some(relative) = normalized.strip_prefix(&prefix) else {
+            unreadable.push(IntegrityMismatch {
+                path: normalized,
+                kind: "unexpected".to_string(),
+                expected: None,
+                actual: None,
+            });
+            continue;
+        };
+        let relative = relative.to_string();
+        let mut data = Vec::new();
+        match file.read_to_end(&mut data) {
+            Ok(_) => entries.push(integrity::entry_for(&relative, &data)),
+            Err(error) => unreadable.push(IntegrityMismatch {
+                path: relative,
+                kind: "unreadable".to_string(),
+                expected: None,
+                actual: Some(error.to_string()),
+            }),
+        }
+    }
+
+    Ok((entries, unreadable))
+}
+
+fn integrity_error_message(report: &ArchiveIntegrityReport) -> String {
+    if report.signature_status == SignatureStatus::Invalid {
+        return "Die Signatur des Profilarchivs ist ungueltig. Das Archiv wurde nach dem Export veraendert.".to_string();
+    }
+    if let Some(first) = report.mismatches.first() {
+        return format!(
+            "Das Profilarchiv ist beschaedigt oder veraendert ({} Abweichungen, z. B. {}: {}).",
+            report.mismatches.len(),
+            first.path,
+            first.kind
+        );
+    }
+    "Die Gesamtgroesse des Profilarchivs stimmt nicht mit dem Manifest ueberein.".to_string()
+}
+
 fn read_manifest<R: Read + std::io::Seek>(
     archive: &mut ZipArchive<R>,
 ) -> Result<Option<SharedProfileManifest>, String> {
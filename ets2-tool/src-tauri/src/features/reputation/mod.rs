@@ -64,10 +64,21 @@ pub fn apply_trip_outcome(
     conn: &Connection,
     distance_km: f64,
     speeding_events: i64,
+    driving_score: Option<f64>,
+    hos_penalty: i64,
 ) -> Result<ReputationState, String> {
     let current = load_state(conn)?;
     let distance_bonus = if distance_km >= 200.0 { 2 } else { 0 };
-    let score_delta = (4 + distance_bonus - speeding_events * 2).max(-12);
+    // Scores around 70 are neutral; clean driving earns up to +3, rough up to -4.
+    // The driving score already rates speeding, so the flat per-event penalty
+    // only applies to trips without one.
+    let driving_bonus = match driving_score {
+        Some(score) => ((score - 70.0) / 10.0).round().clamp(-4.0, 3.0) as i64,
+        None => -speeding_events * 2,
+    };
+    // The tachograph's penalty is settled on top of the driving score, which
+    // only rates rest compliance as one of its categories.
+    let score_delta = (4 + distance_bonus + driving_bonus - hos_penalty.max(0)).max(-12);
     let xp_gain = ((distance_km * 3.5).round() as i64 - speeding_events * 15).max(40);
     let next_score = (current.score + score_delta).max(0);
     let next_xp = (current.xp_points + xp_gain).max(0);
@@ -86,6 +97,18 @@ pub fn apply_trip_outcome(
     load_state(conn)
 }
 
+/// Deducts reputation outside of a trip settlement, e.g. for a missed loan
+/// installment.
+pub fn apply_penalty(conn: &Connection, points: i64) -> Result<ReputationState, String> {
+    conn.execute(
+        "UPDATE reputation_state SET score = MAX(score - ?1, 0) WHERE id = 1",
+        params![points.max(0)],
+    )
+    .map_err(|e| e.to_string())?;
+
+    load_state(conn)
+}
+
 fn level_from_xp(xp_points: i64) -> i64 {
     (xp_points / 750) + 1
 }
@@ -99,3 +122,58 @@ fn label_from_score(score: i64) -> &'static str {
         _ => "Starting",
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conn() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        ensure_tables(&conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn driving_score_sets_the_bonus_instead_of_speeding_events() {
+        let clean = apply_trip_outcome(&conn(), 250.0, 2, Some(100.0), 0).unwrap();
+        // 4 base + 2 distance + 3 capped driving bonus.
+        assert_eq!(clean.score, 51);
+        assert_eq!(clean.xp_points, 875 - 30);
+        assert_eq!(clean.completed_jobs, 1);
+
+        let neutral = apply_trip_outcome(&conn(), 120.0, 0, Some(70.0), 0).unwrap();
+        assert_eq!(neutral.score, 46);
+
+        let rough = apply_trip_outcome(&conn(), 120.0, 0, Some(20.0), 0).unwrap();
+        assert_eq!(rough.score, 42);
+
+        let unscored = apply_trip_outcome(&conn(), 120.0, 3, None, 0).unwrap();
+        assert_eq!(unscored.score, 40);
+        assert_eq!(unscored.xp_points, 375);
+    }
+
+    #[test]
+    fn hos_penalty_is_deducted_and_the_loss_capped() {
+        let fined = apply_trip_outcome(&conn(), 250.0, 0, Some(80.0), 5).unwrap();
+        assert_eq!(fined.score, 42 + 4 + 2 + 1 - 5);
+
+        let capped = apply_trip_outcome(&conn(), 50.0, 0, Some(0.0), 40).unwrap();
+        assert_eq!(capped.score, 42 - 12);
+
+        let negative = apply_trip_outcome(&conn(), 50.0, 0, Some(70.0), -10).unwrap();
+        assert_eq!(negative.score, 46);
+    }
+
+    #[test]
+    fn outcomes_accumulate_on_the_stored_state() {
+        let conn = conn();
+        apply_trip_outcome(&conn, 300.0, 0, Some(90.0), 0).unwrap();
+        let second = apply_trip_outcome(&conn, 300.0, 0, Some(90.0), 3).unwrap();
+
+        assert_eq!(second.score, 42 + 8 + 5);
+        assert_eq!(second.xp_points, 2 * 1_050);
+        assert_eq!(second.level, 3);
+        assert_eq!(second.completed_jobs, 2);
+        assert_eq!(load_state(&conn).unwrap(), second);
+    }
+}
@@ -5,3 +5,5 @@ pub mod models;
 pub mod quicksave;
 pub mod reader;
 mod service;
+
+pub(crate) use service::extract_save_asset_references;
@@ -2011,35 +2011,37 @@ fn extract_custom_save_references(
     save_content: &str,
     active_mods: &[ActiveModEntry],
 ) -> Vec<String> {
+    extract_save_asset_references(save_content)
+        .into_iter()
+        .filter(|path| looks_like_custom_reference(path, active_mods))
+        .collect()
+}
+
+/// Every data path and asset path a save refers to, normalized like indexed mod paths.
+pub(crate) fn extract_save_asset_references(save_content: &str) -> Vec<String> {
     let mut references = BTreeSet::new();
     let Some(data_path_re) = DATA_PATH_RE.as_ref() else {
         crate::dev_log!(
-            "[diagnostics] regex compile failed in extract_custom_save_references.data_path"
+            "[diagnostics] regex compile failed in extract_save_asset_references.data_path"
         );
         return Vec::new();
     };
     let Some(asset_re) = ASSET_RE.as_ref() else {
         crate::dev_log!(
-            "[diagnostics] regex compile failed in extract_custom_save_references.asset_re"
+            "[diagnostics] regex compile failed in extract_save_asset_references.asset_re"
         );
         return Vec::new();
     };
 
     for capture in data_path_re.captures_iter(save_content) {
         if let Some(value) = capture.get(1) {
-            let normalized = normalize_indexed_path(value.as_str());
-            if looks_like_custom_reference(&normalized, active_mods) {
-                references.insert(normalized);
-            }
+            references.insert(normalize_indexed_path(value.as_str()));
         }
     }
 
     for capture in asset_re.captures_iter(save_content) {
         if let Some(value) = capture.get(1) {
-            let normalized = normalize_indexed_path(value.as_str());
-            if looks_like_custom_reference(&normalized, active_mods) {
-                references.insert(normalized);
-            }
+            references.insert(normalize_indexed_path(value.as_str()));
         }
     }
 
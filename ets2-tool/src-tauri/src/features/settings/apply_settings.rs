@@ -1,3 +1,5 @@
+use super::config_file::CfgDocument;
+use super::cvar_catalog::{self, CvarScope};
 use crate::dev_log;
 use crate::features::backup::service as backup_service;
 use crate::features::logging::service as logging_service;
@@ -8,6 +10,7 @@ use crate::state::{AppProfileState, ProfileCache};
 use regex::Regex;
 use serde::Deserialize;
 use serde_json::Value;
+use std::collections::BTreeMap;
 use std::path::Path;
 use tauri::State;
 
@@ -36,7 +39,7 @@ fn value_to_string(v: &Value) -> String {
     }
 }
 
-fn write_text_with_auto_backup(
+pub(crate) fn write_text_with_auto_backup(
     profile_state: &AppProfileState,
     path: &Path,
     action: &str,
@@ -129,21 +132,21 @@ pub fn apply_setting(
             let content = fs::read_to_string(&path)
                 .map_err(|e| format!("Fehler beim Lesen der Config: {}", e))?;
 
-            // 3. Regex: Sucht nach 'uset KEY "WERT"'
-            // Wir bauen den Regex dynamisch basierend auf dem Key
-            let re_str = format!(r#"(uset {}\s*)"?[\d\.]+"?"#, regex::escape(config_key));
-            let re = Regex::new(&re_str).map_err(|e| e.to_string())?;
-
-            if !re.is_match(&content) {
+            // 3. Eintrag suchen, Wert prüfen und setzen
+            let mut doc = CfgDocument::parse(&content);
+            if doc.get(config_key).is_none() {
                 return Err(format!(
                     "Eintrag '{}' in config.cfg nicht gefunden.",
                     config_key
                 ));
             }
+            let values = BTreeMap::from([(config_key.to_string(), val_str.clone())]);
+            if cvar_catalog::apply_values(&mut doc, &values, CvarScope::Base)?.is_empty() {
+                return Ok(());
+            }
 
-            // 4. Ersetzen
-            let replacement = format!(r#"${{1}}"{}"#, val_str);
-            let new_content = re.replace(&content, replacement).to_string();
+            // 4. Rendern (Kommentare und unbekannte Zeilen bleiben erhalten)
+            let new_content = doc.render();
 
             // 5. Schreiben
             write_text_with_auto_backup(
@@ -0,0 +1,203 @@
+use super::apply_settings::write_text_with_auto_backup;
+use super::config_file::CfgDocument;
+use super::cvar_catalog::{self, ConfigPreset, CvarCatalog, CvarDefinition, CvarDiff, CvarScope};
+use crate::dev_log;
+use crate::shared::decrypt::decrypt_if_needed;
+use crate::shared::paths::{ets2_base_config_path, quicksave_config_path};
+use crate::state::{AppProfileState, ProfileCache};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tauri::{State, command};
+
+/// Welche Config-Datei bearbeitet wird.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigTarget {
+    /// Globale config.cfg im Basis-Verzeichnis des Spiels
+    Base,
+    /// config.cfg im Profilordner
+    Profile,
+    /// config_local.cfg im Profilordner
+    ProfileLocal,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigEntry {
+    pub name: String,
+    pub value: String,
+    pub is_default: bool,
+    pub definition: Option<CvarDefinition>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigFileView {
+    pub path: String,
+    pub entries: Vec<ConfigEntry>,
+    pub diff: Vec<CvarDiff>,
+}
+
+impl ConfigTarget {
+    fn scope(self) -> CvarScope {
+        match self {
+            ConfigTarget::Base => CvarScope::Base,
+            ConfigTarget::Profile | ConfigTarget::ProfileLocal => CvarScope::Profile,
+        }
+    }
+}
+
+fn target_path(target: ConfigTarget, profile_path: Option<&str>) -> Result<PathBuf, String> {
+    let path = match target {
+        ConfigTarget::Base => ets2_base_config_path()
+            .ok_or("Konnte Pfad zur globalen config.cfg nicht finden.".to_string())?,
+        ConfigTarget::Profile | ConfigTarget::ProfileLocal => {
+            let profile = profile_path
+                .filter(|path| !path.trim().is_empty())
+                .ok_or("Kein Profilpfad angegeben.".to_string())?;
+            match target {
+                ConfigTarget::Profile => quicksave_config_path(profile),
+                _ => Path::new(profile).join("config_local.cfg"),
+            }
+        }
+    };
+    if !path.exists() {
+        return Err(format!("Datei nicht gefunden: {:?}", path));
+    }
+    Ok(path)
+}
+
+fn load_document(path: &Path) -> Result<CfgDocument, String> {
+    Ok(CfgDocument::parse(&decrypt_if_needed(path)?))
+}
+
+fn write_document(
+    target: ConfigTarget,
+    path: &Path,
+    doc: &CfgDocument,
+    reason: &str,
+    profile_state: &AppProfileState,
+    profile_cache: &ProfileCache,
+) -> Result<(), String> {
+    write_text_with_auto_backup(
+        profile_state,
+        path,
+        "config_editor_write",
+        reason,
+        "A config file was updated by the config editor.",
+        &doc.render(),
+    )?;
+    match target {
+        ConfigTarget::Base => profile_cache.invalidate_base_config(),
+        ConfigTarget::Profile | ConfigTarget::ProfileLocal => {
+            profile_cache.invalidate_save_config()
+        }
+    }
+    Ok(())
+}
+
+#[command]
+pub fn get_cvar_catalog() -> CvarCatalog {
+    cvar_catalog::catalog().clone()
+}
+
+//* Liest eine Config-Datei mit Katalog-Metadaten und Abweichungen vom Standard *//
+#[command]
+pub fn read_config_file(
+    target: ConfigTarget,
+    profile_path: Option<String>,
+) -> Result<ConfigFileView, String> {
+    let path = target_path(target, profile_path.as_deref())?;
+    let doc = load_document(&path)?;
+
+    let entries = doc
+        .vars()
+        .into_iter()
+        .map(|(name, value)| {
+            let definition = cvar_catalog::find_cvar(&name).cloned();
+            ConfigEntry {
+                is_default: definition
+                    .as_ref()
+                    .is_some_and(|cvar| cvar.is_default(&value)),
+                name,
+                value,
+                definition,
+            }
+        })
+        .collect();
+
+    Ok(ConfigFileView {
+        path: path.display().to_string(),
+        entries,
+        diff: cvar_catalog::diff_against_defaults(&doc),
+    })
+}
+
+//* Schreibt geprüfte Werte; Kommentare und unbekannte Zeilen bleiben erhalten *//
+#[command]
+pub fn write_config_values(
+    target: ConfigTarget,
+    profile_path: Option<String>,
+    values: BTreeMap<String, String>,
+    profile_state: State<'_, AppProfileState>,
+    profile_cache: State<'_, ProfileCache>,
+) -> Result<Vec<String>, String> {
+    let path = target_path(target, profile_path.as_deref())?;
+    let mut doc = load_document(&path)?;
+    let changed = cvar_catalog::apply_values(&mut doc, &values, target.scope())?;
+    if changed.is_empty() {
+        return Ok(changed);
+    }
+
+    write_document(
+        target,
+        &path,
+        &doc,
+        "before config editor change",
+        profile_state.inner(),
+        profile_cache.inner(),
+    )?;
+    dev_log!("Config {:?} geändert: {:?}", target, changed);
+    Ok(changed)
+}
+
+#[command]
+pub fn list_config_presets() -> Vec<ConfigPreset> {
+    cvar_catalog::catalog().presets.clone()
+}
+
+//* Wendet ein Preset an; vorher wird automatisch ein Backup angelegt *//
+#[command]
+pub fn apply_config_preset(
+    target: ConfigTarget,
+    profile_path: Option<String>,
+    preset_id: String,
+    profile_state: State<'_, AppProfileState>,
+    profile_cache: State<'_, ProfileCache>,
+) -> Result<Vec<String>, String> {
+    let preset = cvar_catalog::find_preset(&preset_id)
+        .ok_or(format!("Unbekanntes Preset: {}", preset_id))?;
+    let path = target_path(target, profile_path.as_deref())?;
+    let mut doc = load_document(&path)?;
+    let changed = cvar_catalog::apply_values(&mut doc, &preset.values, target.scope())?;
+    if changed.is_empty() {
+        return Ok(changed);
+    }
+
+    write_document(
+        target,
+        &path,
+        &doc,
+        &format!("before config preset {}", preset.id),
+        profile_state.inner(),
+        profile_cache.inner(),
+    )?;
+    dev_log!(
+        "Preset '{}' auf {:?} angewendet: {:?}",
+        preset.id,
+        target,
+        changed
+    );
+    Ok(changed)
+}
@@ -0,0 +1,212 @@
+//! Zeilentreuer Parser/Writer für `config.cfg` und `config_local.cfg`.
+//!
+//! Nur `uset`/`set`-Zeilen werden verstanden; Kommentare, Leerzeilen und
+//! unbekannte Zeilen bleiben unverändert stehen. Unveränderte Variablen werden
+//! byte-genau zurückgeschrieben.
+
+#[derive(Debug, Clone, PartialEq)]
+enum CfgLine {
+    Var {
+        raw: String,
+        indent: String,
+        command: String,
+        name: String,
+        value: String,
+        trailing: String,
+        dirty: bool,
+    },
+    Other(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CfgDocument {
+    lines: Vec<CfgLine>,
+    newline: &'static str,
+    trailing_newline: bool,
+}
+
+impl CfgDocument {
+    pub fn parse(content: &str) -> Self {
+        let newline = if content.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+        let trailing_newline = content.ends_with('\n');
+        let body = content
+            .strip_suffix('\n')
+            .map(|rest| rest.strip_suffix('\r').unwrap_or(rest))
+            .unwrap_or(content);
+        let lines = if body.is_empty() && content.is_empty() {
+            Vec::new()
+        } else {
+            body.split('\n')
+                .map(|line| parse_line(line.strip_suffix('\r').unwrap_or(line)))
+                .collect()
+        };
+        Self {
+            lines,
+            newline,
+            trailing_newline,
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.lines.iter().rev().find_map(|line| match line {
+            CfgLine::Var {
+                name: line_name,
+                value,
+                ..
+            } if line_name.eq_ignore_ascii_case(name) => Some(value.as_str()),
+            _ => None,
+        })
+    }
+
+    pub fn get_i64(&self, name: &str) -> Option<i64> {
+        self.get(name).and_then(|value| value.trim().parse().ok())
+    }
+
+    /// Alle Variablen in Dateireihenfolge; bei Duplikaten gewinnt die letzte.
+    pub fn vars(&self) -> Vec<(String, String)> {
+        let mut vars: Vec<(String, String)> = Vec::new();
+        for line in &self.lines {
+            if let CfgLine::Var { name, value, .. } = line {
+                match vars
+                    .iter_mut()
+                    .find(|(existing, _)| existing.eq_ignore_ascii_case(name))
+                {
+                    Some(entry) => entry.1 = value.clone(),
+                    None => vars.push((name.clone(), value.clone())),
+                }
+            }
+        }
+        vars
+    }
+
+    /// Setzt eine Variable. Fehlt sie, wird eine `uset`-Zeile angehängt.
+    /// Liefert `true`, wenn sich der Inhalt geändert hat.
+    pub fn set(&mut self, name: &str, new_value: &str) -> bool {
+        let existing = self.lines.iter_mut().rev().find_map(|line| match line {
+            CfgLine::Var {
+                name: line_name,
+                value,
+                dirty,
+                ..
+            } if line_name.eq_ignore_ascii_case(name) => Some((value, dirty)),
+            _ => None,
+        });
+        match existing {
+            Some((value, _)) if value == new_value => false,
+            Some((value, dirty)) => {
+                *value = new_value.to_string();
+                *dirty = true;
+                true
+            }
+            None => {
+                self.lines.push(CfgLine::Var {
+                    raw: String::new(),
+                    indent: String::new(),
+                    command: "uset".to_string(),
+                    name: name.to_string(),
+                    value: new_value.to_string(),
+                    trailing: String::new(),
+                    dirty: true,
+                });
+                true
+            }
+        }
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = self
+            .lines
+            .iter()
+            .map(|line| match line {
+                CfgLine::Other(raw) => raw.clone(),
+                CfgLine::Var {
+                    raw, dirty: false, ..
+                } => raw.clone(),
+                CfgLine::Var {
+                    indent,
+                    command,
+                    name,
+                    value,
+                    trailing,
+                    ..
+                } => format!("{indent}{command} {name} \"{value}\"{trailing}"),
+            })
+            .collect::<Vec<_>>()
+            .join(self.newline);
+        if self.trailing_newline || self.lines.is_empty() {
+            out.push_str(self.newline);
+        }
+        out
+    }
+}
+
+fn parse_line(raw: &str) -> CfgLine {
+    let trimmed = raw.trim_start();
+    let indent = &raw[..raw.len() - trimmed.len()];
+    let mut parts = trimmed.splitn(2, char::is_whitespace);
+    let command = parts.next().unwrap_or_default();
+    if !matches!(command, "uset" | "set") {
+        return CfgLine::Other(raw.to_string());
+    }
+    let rest = parts.next().unwrap_or_default().trim_start();
+    let mut parts = rest.splitn(2, char::is_whitespace);
+    let name = parts.next().unwrap_or_default();
+    if name.is_empty() {
+        return CfgLine::Other(raw.to_string());
+    }
+    let (value, trailing) = split_value(parts.next().unwrap_or_default().trim_start());
+    CfgLine::Var {
+        raw: raw.to_string(),
+        indent: indent.to_string(),
+        command: command.to_string(),
+        name: name.to_string(),
+        value: value.to_string(),
+        trailing: trailing.to_string(),
+        dirty: false,
+    }
+}
+
+/// Trennt den (ggf. gequoteten) Wert vom Rest der Zeile, z. B. einem Kommentar.
+fn split_value(rest: &str) -> (&str, &str) {
+    match rest.strip_prefix('"') {
+        Some(quoted) => quoted.split_once('"').unwrap_or((quoted, "")),
+        None => {
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            rest.split_at(end)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "# prism3d variable config data\r\n\r\nuset g_traffic \"1\"\r\n  uset g_console 0 # comment\r\nbind foo bar\r\nuset g_lang \"de_de\"\r\n";
+
+    #[test]
+    fn round_trips_untouched_content_byte_for_byte() {
+        let doc = CfgDocument::parse(SAMPLE);
+        assert_eq!(doc.render(), SAMPLE);
+        assert_eq!(doc.get("g_traffic"), Some("1"));
+        assert_eq!(doc.get_i64("g_console"), Some(0));
+        assert_eq!(doc.get("G_LANG"), Some("de_de"));
+        assert_eq!(doc.vars().len(), 3);
+    }
+
+    #[test]
+    fn set_rewrites_only_the_changed_line_and_appends_missing_vars() {
+        let mut doc = CfgDocument::parse(SAMPLE);
+        assert!(!doc.set("g_traffic", "1"));
+        assert!(doc.set("g_console", "1"));
+        assert!(doc.set("g_max_convoy_size", "128"));
+        let rendered = doc.render();
+        assert!(rendered.contains("\r\n  uset g_console \"1\" # comment\r\nbind foo bar\r\n"));
+        assert!(rendered.contains("uset g_traffic \"1\"\r\n"));
+        assert!(rendered.ends_with("uset g_max_convoy_size \"128\"\r\n"));
+        assert!(rendered.starts_with("# prism3d variable config data\r\n"));
+    }
+}
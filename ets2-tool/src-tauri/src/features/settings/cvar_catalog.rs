@@ -0,0 +1,337 @@
+//! Katalog bekannter `uset`-Variablen und eingebauter Config-Presets.
+//!
+//! Die Daten liegen in `data/ets2/cvars.json`; neue Variablen oder Presets
+//! brauchen dort nur einen Eintrag und keine Codeänderung.
+
+use super::config_file::CfgDocument;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+const EMBEDDED_CVARS_JSON: &str = include_str!("../../../../data/ets2/cvars.json");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CvarKind {
+    Bool,
+    Int,
+    Float,
+    String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CvarScope {
+    Base,
+    Profile,
+    Any,
+}
+
+impl CvarScope {
+    /// Ob eine Variable dieses Geltungsbereichs in eine Datei von `file` gehört.
+    pub fn allows(self, file: CvarScope) -> bool {
+        self == CvarScope::Any || file == CvarScope::Any || self == file
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            CvarScope::Base => "globale config.cfg",
+            CvarScope::Profile => "Profil-Config",
+            CvarScope::Any => "jede Config",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CvarDefinition {
+    pub name: String,
+    pub kind: CvarKind,
+    /// Fehlt, wenn das Spiel den Wert selbst bestimmt (z. B. die Auflösung).
+    #[serde(default)]
+    pub default: Option<String>,
+    #[serde(default)]
+    pub min: Option<f64>,
+    #[serde(default)]
+    pub max: Option<f64>,
+    pub scope: CvarScope,
+    pub category: String,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigPreset {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub values: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CvarCatalog {
+    pub cvars: Vec<CvarDefinition>,
+    pub presets: Vec<ConfigPreset>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CvarDiff {
+    pub name: String,
+    pub value: String,
+    pub default: String,
+    pub category: String,
+}
+
+pub fn catalog() -> &'static CvarCatalog {
+    static CATALOG: OnceLock<CvarCatalog> = OnceLock::new();
+
+    CATALOG.get_or_init(|| {
+        serde_json::from_str(EMBEDDED_CVARS_JSON).expect("cvar catalog must be valid JSON")
+    })
+}
+
+pub fn find_cvar(name: &str) -> Option<&'static CvarDefinition> {
+    catalog()
+        .cvars
+        .iter()
+        .find(|cvar| cvar.name.eq_ignore_ascii_case(name))
+}
+
+pub fn find_preset(id: &str) -> Option<&'static ConfigPreset> {
+    catalog()
+        .presets
+        .iter()
+        .find(|preset| preset.id.eq_ignore_ascii_case(id))
+}
+
+impl CvarDefinition {
+    /// Prüft Typ und Wertebereich und liefert den Wert in der Form, in der er
+    /// in die Config geschrieben wird (`true` → `1`).
+    pub fn normalize(&self, value: &str) -> Result<String, String> {
+        let value = value.trim();
+        match self.kind {
+            CvarKind::Bool => match value.to_ascii_lowercase().as_str() {
+                "1" | "true" => Ok("1".to_string()),
+                "0" | "false" => Ok("0".to_string()),
+                _ => Err(format!(
+                    "{} erwartet 0 oder 1, erhalten: {}",
+                    self.name, value
+                )),
+            },
+            CvarKind::Int => {
+                let parsed: i64 = value.parse().map_err(|_| {
+                    format!("{} erwartet eine Ganzzahl, erhalten: {}", self.name, value)
+                })?;
+                self.check_range(parsed as f64)?;
+                Ok(parsed.to_string())
+            }
+            CvarKind::Float => {
+                let parsed: f64 = value
+                    .parse()
+                    .ok()
+                    .filter(|parsed: &f64| parsed.is_finite())
+                    .ok_or_else(|| {
+                        format!("{} erwartet eine Zahl, erhalten: {}", self.name, value)
+                    })?;
+                self.check_range(parsed)?;
+                Ok(value.to_string())
+            }
+            CvarKind::String => {
+                if value.contains('"') {
+                    return Err(format!(
+                        "{} darf keine Anführungszeichen enthalten",
+                        self.name
+                    ));
+                }
+                Ok(value.to_string())
+            }
+        }
+    }
+
+    fn check_range(&self, value: f64) -> Result<(), String> {
+        if self.min.is_some_and(|min| value < min) || self.max.is_some_and(|max| value > max) {
+            return Err(format!(
+                "{} liegt außerhalb des erlaubten Bereichs ({} – {})",
+                self.name,
+                self.min
+                    .map(|min| min.to_string())
+                    .unwrap_or_else(|| "-".into()),
+                self.max
+                    .map(|max| max.to_string())
+                    .unwrap_or_else(|| "-".into()),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Vergleicht numerisch, damit `1` und `1.0` als gleich gelten. Ohne
+    /// bekannten Standardwert gilt kein Wert als Standard.
+    pub fn is_default(&self, value: &str) -> bool {
+        let Some(default) = self.default.as_deref() else {
+            return false;
+        };
+        match self.kind {
+            CvarKind::String => value == default,
+            _ => match (value.trim().parse::<f64>(), default.parse::<f64>()) {
+                (Ok(value), Ok(default)) => (value - default).abs() < f64::EPSILON,
+                _ => value.trim() == default,
+            },
+        }
+    }
+}
+
+/// Alle bekannten Variablen des Dokuments, die vom Standardwert abweichen.
+/// Variablen ohne Standardwert werden übersprungen.
+pub fn diff_against_defaults(doc: &CfgDocument) -> Vec<CvarDiff> {
+    doc.vars()
+        .into_iter()
+        .filter_map(|(name, value)| {
+            let cvar = find_cvar(&name)?;
+            let default = cvar.default.clone()?;
+            (!cvar.is_default(&value)).then(|| CvarDiff {
+                name: cvar.name.clone(),
+                value,
+                default,
+                category: cvar.category.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Setzt geprüfte Werte in einem Dokument des Geltungsbereichs `file`.
+/// Katalogisierte Variablen müssen zum Geltungsbereich der Datei passen.
+/// Unbekannte Variablen dürfen nur vorhandene Zeilen ändern, damit nicht
+/// katalogisierte Tweaks möglich bleiben, aber keine Tippfehler angehängt
+/// werden. Liefert die Namen der tatsächlich geänderten Variablen.
+pub fn apply_values(
+    doc: &mut CfgDocument,
+    values: &BTreeMap<String, String>,
+    file: CvarScope,
+) -> Result<Vec<String>, String> {
+    let mut normalized = Vec::with_capacity(values.len());
+    for (name, value) in values {
+        let value = match find_cvar(name) {
+            Some(cvar) if !cvar.scope.allows(file) => {
+                return Err(format!(
+                    "{} gehört in die {}, nicht in die {}",
+                    name,
+                    cvar.scope.label(),
+                    file.label()
+                ));
+            }
+            Some(cvar) => cvar.normalize(value)?,
+            None if doc.get(name).is_none() => {
+                return Err(format!("Unbekannte Variable: {}", name));
+            }
+            None if value.contains('"') => {
+                return Err(format!("{} darf keine Anführungszeichen enthalten", name));
+            }
+            None => value.trim().to_string(),
+        };
+        normalized.push((name, value));
+    }
+
+    Ok(normalized
+        .into_iter()
+        .filter(|(name, value)| doc.set(name, value))
+        .map(|(name, _)| name.clone())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catalog_values_are_validated_and_presets_reference_known_cvars() {
+        let convoy = find_cvar("g_max_convoy_size").unwrap();
+        assert_eq!(convoy.normalize(" 64 ").unwrap(), "64");
+        assert!(convoy.normalize("256").is_err());
+        assert!(convoy.normalize("abc").is_err());
+        assert_eq!(
+            find_cvar("g_console").unwrap().normalize("true").unwrap(),
+            "1"
+        );
+
+        let traffic = find_cvar("g_traffic").unwrap();
+        for value in ["NaN", "inf", "-inf", "infinity"] {
+            assert!(traffic.normalize(value).is_err(), "{value} accepted");
+        }
+
+        for preset in &catalog().presets {
+            for (name, value) in &preset.values {
+                let cvar = find_cvar(name).expect("preset uses unknown cvar");
+                cvar.normalize(value).expect("preset value out of range");
+            }
+        }
+    }
+
+    #[test]
+    fn diff_reports_only_known_cvars_that_differ_from_default() {
+        let mut doc = CfgDocument::parse(
+            "uset g_traffic \"1.0\"\nuset g_console \"1\"\nuset x_unknown \"5\"\n",
+        );
+        let diff = diff_against_defaults(&doc);
+        assert_eq!(diff.len(), 1);
+        assert_eq!(diff[0].name, "g_console");
+
+        let resolution = CfgDocument::parse("uset r_mode_width \"2560\"\n");
+        assert!(diff_against_defaults(&resolution).is_empty());
+
+        let changed = apply_values(
+            &mut doc,
+            &find_preset("convoy").unwrap().values,
+            CvarScope::Base,
+        )
+        .unwrap();
+        assert_eq!(changed, vec!["g_max_convoy_size", "g_traffic"]);
+        assert_eq!(doc.get("g_traffic"), Some("0.5"));
+    }
+
+    #[test]
+    fn values_respect_the_file_scope_and_unknown_vars_are_never_appended() {
+        let mut doc = CfgDocument::parse("uset x_unknown \"5\"\n");
+        let values =
+            |name: &str, value: &str| BTreeMap::from([(name.to_string(), value.to_string())]);
+
+        let base_only = catalog()
+            .cvars
+            .iter()
+            .find(|cvar| cvar.scope == CvarScope::Base)
+            .unwrap();
+        assert!(
+            apply_values(
+                &mut doc,
+                &values(&base_only.name, base_only.default.as_deref().unwrap()),
+                CvarScope::Profile
+            )
+            .is_err()
+        );
+
+        assert!(
+            apply_values(
+                &mut doc,
+                &values("g_trailer_stability", "0.8"),
+                CvarScope::Base
+            )
+            .is_err()
+        );
+        assert_eq!(
+            apply_values(
+                &mut doc,
+                &values("g_trailer_stability", "0.8"),
+                CvarScope::Profile
+            )
+            .unwrap(),
+            vec!["g_trailer_stability"]
+        );
+
+        assert!(apply_values(&mut doc, &values("x_typo", "1"), CvarScope::Base).is_err());
+        assert_eq!(doc.get("x_typo"), None);
+        assert_eq!(
+            apply_values(&mut doc, &values("x_unknown", "6"), CvarScope::Base).unwrap(),
+            vec!["x_unknown"]
+        );
+    }
+}
@@ -1,4 +1,5 @@
 // Make sure these imports match your project structure
+use super::config_file::CfgDocument;
 use crate::dev_log;
 use crate::models::global_config_info::BaseGameConfig;
 use crate::models::save_game_config::SaveGameConfig;
@@ -7,7 +8,6 @@ use crate::shared::paths::ets2_base_config_path;
 use crate::shared::paths::quicksave_config_path;
 use crate::shared::trace::TraceScope;
 use crate::state::ProfileCache;
-use regex::Regex;
 use std::fs;
 use tauri::{State, command};
 
@@ -46,21 +46,16 @@ pub fn read_base_config(cache: State<'_, ProfileCache>) -> Result<BaseGameConfig
     let content =
         fs::read_to_string(&path).map_err(|e| format!("Fehler beim Lesen der Datei: {}", e))?;
 
-    // Sicherstellen, dass Regex korrekt funktioniert
-    let re = |pat: &str| Regex::new(pat).unwrap();
+    let doc = CfgDocument::parse(&content);
     let data = BaseGameConfig {
-        max_convoy_size: re(r#"uset g_max_convoy_size\s*"?(\d+)"??"#)
-            .captures(&content)
-            .and_then(|c| c[1].parse().ok()),
-        traffic: re(r#"uset g_traffic\s*"?(\d+)"??"#)
-            .captures(&content)
-            .and_then(|c| c[1].parse().ok()),
-        developer: re(r#"uset g_developer\s*"?(\d+)"??"#)
-            .captures(&content)
-            .and_then(|c| c[1].parse().ok()),
-        console: re(r#"uset g_console\s*"?(\d+)"??"#)
-            .captures(&content)
-            .and_then(|c| c[1].parse().ok()),
+        max_convoy_size: doc.get_i64("g_max_convoy_size"),
+        // g_traffic ist ein Faktor (z. B. "0.5"), das Modell kennt nur Ganzzahlen
+        traffic: doc
+            .get("g_traffic")
+            .and_then(|value| value.trim().parse::<f64>().ok())
+            .map(|value| value as i64),
+        developer: doc.get_i64("g_developer"),
+        console: doc.get_i64("g_console"),
     };
 
     dev_log!(
@@ -109,14 +104,9 @@ pub fn read_save_config(
 
     let content = decrypt_if_needed(&path)?;
 
-    // Sicherstellen, dass Regex korrekt funktioniert
-    let re = |pat: &str| Regex::new(pat).unwrap();
+    let doc = CfgDocument::parse(&content);
     let data = SaveGameConfig {
-        // Der Regex-Pattern sucht nach dem genauen String in der config.cfg
-        // c[1] ist korrekt für die erste Capture-Gruppe (die Ziffern).
-        factor_parking_doubles: re(r#"uset g_simple_parking_doubles\s*"?(\d+)"??"#)
-            .captures(&content)
-            .and_then(|c| c[1].parse().ok()),
+        factor_parking_doubles: doc.get_i64("g_simple_parking_doubles"),
     };
     dev_log!(
         "Gefundene Daten: uset g_simple_parking_doubles {:?}",
@@ -1,2 +1,5 @@
 pub mod apply_settings;
+pub mod config_editor;
+pub mod config_file;
+pub mod cvar_catalog;
 pub mod game_config;
@@ -0,0 +1,1339 @@
+//! Opt-in local telemetry server for external dashboards and overlays.
+//!
+//! WebSocket clients connect to `ws://<bind_address>:<websocket_port>` and
+//! receive JSON messages. UDP clients send a `subscribe` datagram to
+//! `<bind_address>:<udp_port>` and get compact datagrams back until their
+//! subscription expires. Both transports accept the same request:
+//!
+//! `{"type":"subscribe","fields":["speed_kph","job.cargo"],"maxRateHz":5,"jobEvents":true}`
+//!
+//! Field paths use the snapshot's own field names; an empty list means all
+//! fields. The requested rate is capped by the server-wide `max_rate_hz`.
+//!
+//! Every WebSocket client gets its own writer thread fed by a bounded queue,
+//! so a slow client never stalls the telemetry loop; a client whose queue is
+//! full is dropped. Browser connections are only accepted from local pages, the
+//! app's own overlay and the hosts listed in `allowed_hosts`.
+//!
+//! Clients on other machines (a tablet on the LAN) must connect from an address
+//! listed in `allowed_hosts` and present the pairing token: WebSocket clients as
+//! `?token=<pairing token>` in the request URL, UDP clients as a `token` field in
+//! their request. UDP requests are rate-limited per source address.
+
+use std::collections::HashMap;
+use std::io::{ErrorKind, Read, Write};
+use std::net::{IpAddr, Shutdown, SocketAddr, TcpListener, TcpStream, UdpSocket};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+use sha1::{Digest, Sha1};
+
+pub const DEFAULT_WEBSOCKET_PORT: u16 = 30_420;
+pub const DEFAULT_UDP_PORT: u16 = 30_421;
+const DEFAULT_MAX_RATE_HZ: f64 = 10.0;
+const MAX_RATE_HZ_LIMIT: f64 = 60.0;
+const BROADCAST_PROTOCOL_VERSION: u32 = 1;
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+const MAX_HANDSHAKE_BYTES: usize = 8 * 1024;
+const MAX_CLIENT_FRAME_BYTES: u64 = 64 * 1024;
+const UDP_SUBSCRIPTION_TTL: Duration = Duration::from_secs(30);
+const SOCKET_POLL_INTERVAL: Duration = Duration::from_millis(200);
+const CLIENT_WRITE_TIMEOUT: Duration = Duration::from_millis(250);
+/// Frames a WebSocket client may have queued before it counts as lagging.
+const CLIENT_QUEUE_FRAMES: usize = 32;
+/// Origins a browser page may connect from on top of `allowed_hosts`; clients
+/// without an `Origin` header (native dashboards) are always accepted.
+const ALLOWED_ORIGIN_HOSTS: &[&str] = &["localhost", "127.0.0.1", "[::1]", "tauri.localhost"];
+/// UDP datagrams one source address may send per `UDP_RATE_WINDOW`.
+const UDP_REQUESTS_PER_WINDOW: u32 = 10;
+const UDP_RATE_WINDOW: Duration = Duration::from_secs(10);
+const MAX_UDP_CLIENTS: usize = 32;
+
+/// Fields UDP clients receive when they subscribe without a field list. The
+/// full snapshot carries long strings that do not belong in a datagram.
+const COMPACT_FIELDS: &[&str] = &[
+    "sequence",
+    "simulation_timestamp",
+    "speed_kph",
+    "engine_rpm",
+    "gear",
+    "fuel_liters",
+    "fuel_capacity_liters",
+    "odometer_km",
+    "paused",
+    "engine_enabled",
+    "job_event_sequence",
+    "job.cargo",
+    "job.source_city",
+    "job.destination_city",
+    "job.planned_distance_km",
+    "job.delivery_time_min",
+    "job.game_time_min",
+    "job.cargo_damage",
+];
+
+static SERVER: Mutex<Option<BroadcastServer>> = Mutex::new(None);
+static LAST_ERROR: Mutex<Option<String>> = Mutex::new(None);
+static NEXT_CLIENT_ID: AtomicU64 = AtomicU64::new(1);
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BroadcastConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_bind_address")]
+    pub bind_address: String,
+    #[serde(default = "default_websocket_port")]
+    pub websocket_port: u16,
+    #[serde(default)]
+    pub udp_enabled: bool,
+    #[serde(default = "default_udp_port")]
+    pub udp_port: u16,
+    #[serde(default = "default_max_rate_hz")]
+    pub max_rate_hz: f64,
+    /// Remote addresses and browser origin hosts allowed besides this machine.
+    #[serde(default)]
+    pub allowed_hosts: Vec<String>,
+    /// Secret every client on another machine must present. Generated when the
+    /// server is bound to a non-loopback address without one.
+    #[serde(default)]
+    pub pairing_token: String,
+}
+
+impl Default for BroadcastConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind_address: default_bind_address(),
+            websocket_port: DEFAULT_WEBSOCKET_PORT,
+            udp_enabled: false,
+            udp_port: DEFAULT_UDP_PORT,
+            max_rate_hz: DEFAULT_MAX_RATE_HZ,
+            allowed_hosts: Vec::new(),
+            pairing_token: String::new(),
+        }
+    }
+}
+
+impl BroadcastConfig {
+    /// Whether the server is only reachable from this machine.
+    pub fn is_loopback_only(&self) -> bool {
+        let address = self.bind_address.trim();
+        address.eq_ignore_ascii_case("localhost")
+            || address
+                .trim_matches(|c| c == '[' || c == ']')
+                .parse::<IpAddr>()
+                .is_ok_and(|ip| ip.is_loopback())
+    }
+
+    /// Generates a pairing token when the server is reachable from other
+    /// machines and none is set yet.
+    pub fn ensure_pairing_token(&mut self) {
+        if !self.is_loopback_only() && self.pairing_token.trim().is_empty() {
+            self.pairing_token = uuid::Uuid::new_v4().simple().to_string();
+        }
+    }
+}
+
+fn default_bind_address() -> String {
+    "127.0.0.1".to_string()
+}
+
+fn default_websocket_port() -> u16 {
+    DEFAULT_WEBSOCKET_PORT
+}
+
+fn default_udp_port() -> u16 {
+    DEFAULT_UDP_PORT
+}
+
+fn default_max_rate_hz() -> f64 {
+    DEFAULT_MAX_RATE_HZ
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BroadcastStatus {
+    pub running: bool,
+    pub websocket_address: Option<String>,
+    pub udp_address: Option<String>,
+    pub websocket_clients: usize,
+    pub udp_clients: usize,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ClientRequest {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    fields: Option<Vec<String>>,
+    #[serde(default)]
+    max_rate_hz: Option<f64>,
+    #[serde(default)]
+    job_events: Option<bool>,
+    #[serde(default)]
+    token: Option<String>,
+}
+
+/// Who besides this machine may use the server.
+#[derive(Debug, Clone, Default)]
+struct AccessPolicy {
+    /// Lowercased `allowed_hosts` plus a specific bind address.
+    hosts: Vec<String>,
+    pairing_token: String,
+}
+
+impl AccessPolicy {
+    fn from_config(config: &BroadcastConfig) -> Self {
+        let mut hosts: Vec<String> = config
+            .allowed_hosts
+            .iter()
+            .map(|host| host.trim().to_ascii_lowercase())
+            .filter(|host| !host.is_empty())
+            .collect();
+        // A page served from the machine's own LAN address is as local as one
+        // served from localhost.
+        let bind = config.bind_address.trim().to_ascii_lowercase();
+        let unspecified = bind
+            .trim_matches(|c| c == '[' || c == ']')
+            .parse::<IpAddr>()
+            .is_ok_and(|ip| ip.is_unspecified());
+        if !bind.is_empty() && !unspecified {
+            hosts.push(bind);
+        }
+        Self {
+            hosts,
+            pairing_token: config.pairing_token.trim().to_string(),
+        }
+    }
+
+    fn host_allowed(&self, host: &str) -> bool {
+        ALLOWED_ORIGIN_HOSTS.contains(&host) || self.hosts.iter().any(|known| known == host)
+    }
+
+    /// Loopback peers are always accepted. Any other peer must be listed in
+    /// `allowed_hosts` by address and present the pairing token.
+    fn peer_allowed(&self, peer: IpAddr, token: Option<&str>) -> bool {
+        let peer = peer.to_canonical();
+        if peer.is_loopback() {
+            return true;
+        }
+        let listed = self.hosts.iter().any(|host| {
+            host.trim_matches(|c| c == '[' || c == ']')
+                .parse::<IpAddr>()
+                .is_ok_and(|ip| ip == peer)
+        });
+        listed
+            && !self.pairing_token.is_empty()
+            && token.is_some_and(|token| tokens_match(token.trim(), &self.pairing_token))
+    }
+
+    /// Requests without an `Origin` header come from native clients. Browser
+    /// pages are only accepted from local and allowed hosts.
+    fn origin_allowed(&self, request: &str) -> bool {
+        let origin = request.lines().skip(1).find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.trim()
+                .eq_ignore_ascii_case("origin")
+                .then(|| value.trim().to_ascii_lowercase())
+        });
+        let Some(origin) = origin else {
+            return true;
+        };
+        let Some((scheme, rest)) = origin.split_once("://") else {
+            return false;
+        };
+        if !matches!(scheme, "http" | "https" | "tauri") {
+            return false;
+        }
+        let host = match rest.strip_prefix('[') {
+            Some(_) => rest.split_inclusive(']').next().unwrap_or(rest),
+            None => rest.split([':', '/']).next().unwrap_or(rest),
+        };
+        self.host_allowed(host)
+    }
+}
+
+/// Compares without stopping at the first differing byte.
+fn tokens_match(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// The `token` query parameter of a WebSocket handshake's request line.
+fn request_token(request: &str) -> Option<String> {
+    let target = request.lines().next()?.split_whitespace().nth(1)?;
+    let (_, query) = target.split_once('?')?;
+    query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("token="))
+        .map(str::to_string)
+}
+
+/// Fixed-window request counter per source address.
+#[derive(Default)]
+struct UdpRateLimiter {
+    windows: HashMap<IpAddr, (Instant, u32)>,
+}
+
+impl UdpRateLimiter {
+    fn allow(&mut self, address: IpAddr, now: Instant) -> bool {
+        self.windows
+            .retain(|_, (started, _)| now.duration_since(*started) < UDP_RATE_WINDOW);
+        let (_, count) = self.windows.entry(address).or_insert((now, 0));
+        *count += 1;
+        *count <= UDP_REQUESTS_PER_WINDOW
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Subscription {
+    fields: Vec<String>,
+    min_interval: Duration,
+    job_events: bool,
+    last_sent: Option<Instant>,
+}
+
+impl Subscription {
+    fn new(fields: Vec<String>, rate_hz: f64, server_rate_hz: f64) -> Self {
+        Self {
+            fields,
+            min_interval: rate_interval(rate_hz, server_rate_hz),
+            job_events: true,
+            last_sent: None,
+        }
+    }
+
+    fn apply(&mut self, request: &ClientRequest, server_rate_hz: f64) {
+        if let Some(fields) = request.fields.as_ref() {
+            self.fields = fields
+                .iter()
+                .map(|field| field.trim().to_string())
+                .filter(|field| !field.is_empty())
+                .collect();
+        }
+        if let Some(rate_hz) = request.max_rate_hz {
+            self.min_interval = rate_interval(rate_hz, server_rate_hz);
+        }
+        if let Some(job_events) = request.job_events {
+            self.job_events = job_events;
+        }
+    }
+
+    fn take_due(&mut self, now: Instant) -> bool {
+        if self
+            .last_sent
+            .is_some_and(|last_sent| now.duration_since(last_sent) < self.min_interval)
+        {
+            return false;
+        }
+        self.last_sent = Some(now);
+        true
+    }
+
+    fn filter(&self, data: &Value) -> Value {
+        if self.fields.is_empty() {
+            return data.clone();
+        }
+        let mut output = Value::Object(Map::new());
+        for field in &self.fields {
+            copy_field_path(data, &mut output, field);
+        }
+        output
+    }
+}
+
+fn rate_interval(rate_hz: f64, server_rate_hz: f64) -> Duration {
+    let server_rate_hz = server_rate_hz.clamp(0.1, MAX_RATE_HZ_LIMIT);
+    let rate_hz = if rate_hz.is_finite() && rate_hz > 0.0 {
+        rate_hz.min(server_rate_hz)
+    } else {
+        server_rate_hz
+    };
+    Duration::from_secs_f64(1.0 / rate_hz)
+}
+
+fn copy_field_path(source: &Value, target: &mut Value, path: &str) {
+    let parts = path.split('.').collect::<Vec<_>>();
+    let mut current = source;
+    for part in &parts {
+        match current.get(part) {
+            Some(value) => current = value,
+            None => return,
+        }
+    }
+
+    let mut slot = target;
+    for (index, part) in parts.iter().enumerate() {
+        let Some(object) = slot.as_object_mut() else {
+            return;
+        };
+        if index + 1 == parts.len() {
+            object.insert((*part).to_string(), current.clone());
+            return;
+        }
+        slot = object
+            .entry((*part).to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+    }
+}
+
+enum Transport {
+    WebSocket {
+        outbox: SyncSender<Vec<u8>>,
+        socket: TcpStream,
+    },
+    Udp {
+        address: SocketAddr,
+        expires_at: Instant,
+    },
+}
+
+struct Client {
+    id: u64,
+    transport: Transport,
+    subscription: Subscription,
+}
+
+struct BroadcastServer {
+    stop: Arc<AtomicBool>,
+    clients: Arc<Mutex<Vec<Client>>>,
+    udp_socket: Option<Arc<UdpSocket>>,
+    websocket_address: Option<SocketAddr>,
+    udp_address: Option<SocketAddr>,
+}
+
+impl BroadcastServer {
+    fn shutdown(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Ok(mut clients) = self.clients.lock() {
+            for client in clients.drain(..) {
+                if let Transport::WebSocket { outbox, socket } = client.transport {
+                    // The writer thread sends the close frame and shuts the
+                    // socket down once it has drained the queue.
+                    if outbox.try_send(encode_frame(OPCODE_CLOSE, &[])).is_err() {
+                        let _ = socket.shutdown(Shutdown::Both);
+                    }
+                }
+            }
+        }
+    }
+
+    fn sink(&self) -> ClientSink {
+        ClientSink {
+            clients: self.clients.clone(),
+            udp_socket: self.udp_socket.clone(),
+        }
+    }
+
+    fn status(&self) -> BroadcastStatus {
+        let (websocket_clients, udp_clients) = self
+            .clients
+            .lock()
+            .map(|clients| {
+                clients
+                    .iter()
+                    .fold((0, 0), |(ws, udp), client| match client.transport {
+                        Transport::WebSocket { .. } => (ws + 1, udp),
+                        Transport::Udp { .. } => (ws, udp + 1),
+                    })
+            })
+            .unwrap_or_default();
+        BroadcastStatus {
+            running: true,
+            websocket_address: self.websocket_address.map(|value| value.to_string()),
+            udp_address: self.udp_address.map(|value| value.to_string()),
+            websocket_clients,
+            udp_clients,
+            last_error: last_error(),
+        }
+    }
+}
+
+/// Handles to the client list taken out of `SERVER`, so publishing never holds
+/// the server lock while it walks the clients.
+struct ClientSink {
+    clients: Arc<Mutex<Vec<Client>>>,
+    udp_socket: Option<Arc<UdpSocket>>,
+}
+
+impl ClientSink {
+    /// Queues a message for every client `build` returns one for. Nothing here
+    /// blocks on a socket: WebSocket frames go to the client's writer thread and
+    /// a client whose queue is full or closed is dropped.
+    fn send<F>(&self, mut build: F)
+    where
+        F: FnMut(&mut Subscription, Instant) -> Option<Vec<u8>>,
+    {
+        let Ok(mut clients) = self.clients.lock() else {
+            return;
+        };
+        let now = Instant::now();
+        clients.retain_mut(|client| {
+            if matches!(client.transport, Transport::Udp { expires_at, .. } if expires_at <= now) {
+                return false;
+            }
+            let Some(message) = build(&mut client.subscription, now) else {
+                return true;
+            };
+            match &client.transport {
+                Transport::WebSocket { outbox, socket } => {
+                    match outbox.try_send(encode_frame(OPCODE_TEXT, &message)) {
+                        Ok(()) => true,
+                        Err(TrySendError::Full(_)) => {
+                            crate::dev_log!(
+                                "[telemetry] broadcast client {} dropped: queue full",
+                                client.id
+                            );
+                            let _ = socket.shutdown(Shutdown::Both);
+                            false
+                        }
+                        Err(TrySendError::Disconnected(_)) => false,
+                    }
+                }
+                Transport::Udp { address, .. } => match self.udp_socket.as_ref() {
+                    Some(socket) => socket.send_to(&message, *address).is_ok(),
+                    None => false,
+                },
+            }
+        });
+    }
+}
+
+pub fn default_config_path() -> PathBuf {
+    dirs::config_local_dir()
+        .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")))
+        .join("SimNexus")
+        .join("telemetry_broadcast.json")
+}
+
+pub fn load_config() -> Result<BroadcastConfig, String> {
+    let path = default_config_path();
+    if !path.exists() {
+        return Ok(BroadcastConfig::default());
+    }
+
+    let raw = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&raw).map_err(|e| e.to_string())
+}
+
+pub fn save_config(config: &BroadcastConfig) -> Result<(), String> {
+    let path = default_config_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    let raw = serde_json::to_string_pretty(config).map_err(|e| e.to_string())?;
+    std::fs::write(path, format!("{raw}\n")).map_err(|e| e.to_string())
+}
+
+/// Stops any running server and starts a new one for `config`. A disabled
+/// config only stops the server.
+pub fn restart(config: &BroadcastConfig) -> Result<BroadcastStatus, String> {
+    stop();
+    set_last_error(None);
+    if !config.enabled {
+        return Ok(status());
+    }
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let clients = Arc::new(Mutex::new(Vec::new()));
+    let policy = Arc::new(AccessPolicy::from_config(config));
+
+    let listener = TcpListener::bind((config.bind_address.as_str(), config.websocket_port))
+        .map_err(|error| {
+            format!(
+                "Telemetry broadcast could not listen on {}:{}: {}",
+                config.bind_address, config.websocket_port, error
+            )
+        })?;
+    listener
+        .set_nonblocking(true)
+        .map_err(|error| error.to_string())?;
+    let websocket_address = listener.local_addr().ok();
+
+    let (udp_socket, udp_address) = if config.udp_enabled {
+        let socket =
+            UdpSocket::bind((config.bind_address.as_str(), config.udp_port)).map_err(|error| {
+                format!(
+                    "Telemetry broadcast could not bind UDP {}:{}: {}",
+                    config.bind_address, config.udp_port, error
+                )
+            })?;
+        socket
+            .set_read_timeout(Some(SOCKET_POLL_INTERVAL))
+            .map_err(|error| error.to_string())?;
+        let address = socket.local_addr().ok();
+        (Some(Arc::new(socket)), address)
+    } else {
+        (None, None)
+    };
+
+    {
+        let stop = stop.clone();
+        let clients = clients.clone();
+        let policy = policy.clone();
+        let rate_hz = config.max_rate_hz;
+        std::thread::spawn(move || accept_loop(listener, stop, clients, policy, rate_hz));
+    }
+    if let Some(socket) = udp_socket.clone() {
+        let stop = stop.clone();
+        let clients = clients.clone();
+        let rate_hz = config.max_rate_hz;
+        std::thread::spawn(move || udp_loop(socket, stop, clients, policy, rate_hz));
+    }
+
+    crate::dev_log!(
+        "[telemetry] broadcast started ws={:?} udp={:?}",
+        websocket_address,
+        udp_address
+    );
+
+    let server = BroadcastServer {
+        stop,
+        clients,
+        udp_socket,
+        websocket_address,
+        udp_address,
+    };
+    let status = server.status();
+    if let Ok(mut guard) = SERVER.lock() {
+        *guard = Some(server);
+    }
+    Ok(status)
+}
+
+pub fn stop() {
+    let server = SERVER.lock().ok().and_then(|mut guard| guard.take());
+    if let Some(server) = server {
+        server.shutdown();
+        crate::dev_log!("[telemetry] broadcast stopped");
+    }
+}
+
+pub fn status() -> BroadcastStatus {
+    match SERVER.lock().ok().as_ref().and_then(|guard| guard.as_ref()) {
+        Some(server) => server.status(),
+        None => BroadcastStatus {
+            last_error: last_error(),
+            ..Default::default()
+        },
+    }
+}
+
+/// Sends a telemetry snapshot to every client whose rate limit allows it.
+/// Cheap no-op while the server is stopped.
+pub fn publish_snapshot<T: Serialize>(snapshot: &T) {
+    let Some(sink) = active_sink() else {
+        return;
+    };
+    let Ok(data) = serde_json::to_value(snapshot) else {
+        return;
+    };
+
+    sink.send(|subscription, now| {
+        if !subscription.take_due(now) {
+            return None;
+        }
+        let message = json!({ "type": "telemetry", "data": subscription.filter(&data) });
+        serde_json::to_vec(&message).ok()
+    });
+}
+
+/// Sends a job event to every client that asked for job events. Job events
+/// are rare and bypass the per-client rate limit.
+pub fn publish_job_event<T: Serialize>(event: &T) {
+    let Some(sink) = active_sink() else {
+        return;
+    };
+    let Ok(data) = serde_json::to_value(event) else {
+        return;
+    };
+    let Ok(message) = serde_json::to_vec(&json!({ "type": "job_event", "data": data })) else {
+        return;
+    };
+
+    sink.send(|subscription, _| subscription.job_events.then(|| message.clone()));
+}
+
+fn active_sink() -> Option<ClientSink> {
+    SERVER
+        .lock()
+        .ok()
+        .and_then(|guard| guard.as_ref().map(BroadcastServer::sink))
+}
+
+fn last_error() -> Option<String> {
+    LAST_ERROR.lock().ok().and_then(|guard| guard.clone())
+}
+
+fn set_last_error(error: Option<String>) {
+    if let Ok(mut guard) = LAST_ERROR.lock() {
+        *guard = error;
+    }
+}
+
+fn accept_loop(
+    listener: TcpListener,
+    stop: Arc<AtomicBool>,
+    clients: Arc<Mutex<Vec<Client>>>,
+    policy: Arc<AccessPolicy>,
+    rate_hz: f64,
+) {
+    while !stop.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((stream, peer)) => {
+                let stop = stop.clone();
+                let clients = clients.clone();
+                let policy = policy.clone();
+                std::thread::spawn(move || {
+                    if let Err(error) =
+                        serve_websocket(stream, peer, stop, clients, &policy, rate_hz)
+                    {
+                        crate::dev_log!("[telemetry] broadcast client {} closed: {}", peer, error);
+                    }
+                });
+            }
+            Err(error) if error.kind() == ErrorKind::WouldBlock => {
+                std::thread::sleep(SOCKET_POLL_INTERVAL / 4);
+            }
+            Err(error) => {
+                set_last_error(Some(error.to_string()));
+                std::thread::sleep(SOCKET_POLL_INTERVAL);
+            }
+        }
+    }
+}
+
+fn serve_websocket(
+    mut stream: TcpStream,
+    peer: SocketAddr,
+    stop: Arc<AtomicBool>,
+    clients: Arc<Mutex<Vec<Client>>>,
+    policy: &AccessPolicy,
+    rate_hz: f64,
+) -> Result<(), String> {
+    stream
+        .set_nonblocking(false)
+        .map_err(|error| error.to_string())?;
+    stream
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .map_err(|error| error.to_string())?;
+    stream
+        .set_write_timeout(Some(CLIENT_WRITE_TIMEOUT))
+        .map_err(|error| error.to_string())?;
+    let _ = stream.set_nodelay(true);
+
+    let request = read_handshake(&mut stream)?;
+    if !policy.peer_allowed(peer.ip(), request_token(&request).as_deref()) {
+        let _ = stream
+            .write_all(b"HTTP/1.1 403 Forbidden\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+        return Err("Client is not allowed or sent no valid pairing token".to_string());
+    }
+    if !policy.origin_allowed(&request) {
+        let _ = stream
+            .write_all(b"HTTP/1.1 403 Forbidden\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+        return Err("WebSocket origin is not allowed".to_string());
+    }
+    let Some(key) = websocket_key(&request) else {
+        let _ = stream.write_all(
+            b"HTTP/1.1 426 Upgrade Required\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        );
+        return Err("Request was not a WebSocket upgrade".to_string());
+    };
+    let response = format!(
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+        websocket_accept_key(&key)
+    );
+    stream
+        .write_all(response.as_bytes())
+        .map_err(|error| error.to_string())?;
+
+    let hello = json!({
+        "type": "hello",
+        "protocol": BROADCAST_PROTOCOL_VERSION,
+        "maxRateHz": rate_hz,
+    });
+    stream
+        .write_all(&encode_frame(OPCODE_TEXT, hello.to_string().as_bytes()))
+        .map_err(|error| error.to_string())?;
+
+    let id = NEXT_CLIENT_ID.fetch_add(1, Ordering::Relaxed);
+    let writer = stream.try_clone().map_err(|error| error.to_string())?;
+    let socket = stream.try_clone().map_err(|error| error.to_string())?;
+    let (outbox, queue) = mpsc::sync_channel(CLIENT_QUEUE_FRAMES);
+    std::thread::spawn(move || write_client_frames(writer, queue));
+    clients
+        .lock()
+        .map_err(|_| "Broadcast client lock poisoned".to_string())?
+        .push(Client {
+            id,
+            transport: Transport::WebSocket { outbox, socket },
+            subscription: Subscription::new(Vec::new(), rate_hz, rate_hz),
+        });
+
+    stream
+        .set_read_timeout(Some(SOCKET_POLL_INTERVAL))
+        .map_err(|error| error.to_string())?;
+    let result = read_client_frames(&mut stream, &stop, &clients, id, rate_hz);
+
+    if let Ok(mut guard) = clients.lock() {
+        guard.retain(|client| client.id != id);
+    }
+    let _ = stream.shutdown(Shutdown::Both);
+    result
+}
+
+/// Writes queued frames until the queue closes, a close frame went out or the
+/// client stops reading.
+fn write_client_frames(mut stream: TcpStream, queue: Receiver<Vec<u8>>) {
+    for frame in queue {
+        let closing = frame.first() == Some(&(0x80 | OPCODE_CLOSE));
+        if stream.write_all(&frame).is_err() || closing {
+            break;
+        }
+    }
+    let _ = stream.shutdown(Shutdown::Both);
+}
+
+fn read_client_frames(
+    stream: &mut TcpStream,
+    stop: &AtomicBool,
+    clients: &Mutex<Vec<Client>>,
+    id: u64,
+    rate_hz: f64,
+) -> Result<(), String> {
+    let mut reader = FrameReader::default();
+    while !stop.load(Ordering::Relaxed) {
+        let frame = match reader.read(stream) {
+            Ok(Some(frame)) => frame,
+            Ok(None) => return Ok(()),
+            Err(error)
+                if error.kind() == ErrorKind::WouldBlock || error.kind() == ErrorKind::TimedOut =>
+            {
+                continue;
+            }
+            Err(error) => return Err(error.to_string()),
+        };
+
+        match frame.opcode {
+            OPCODE_TEXT => {
+                let Ok(request) = serde_json::from_slice::<ClientRequest>(&frame.payload) else {
+                    continue;
+                };
+                let mut guard = clients
+                    .lock()
+                    .map_err(|_| "Broadcast client lock poisoned".to_string())?;
+                if let Some(client) = guard
+                    .iter_mut()
+                    .find(|client| client.id == id && request.kind == "subscribe")
+                {
+                    client.subscription.apply(&request, rate_hz);
+                }
+            }
+            OPCODE_PING | OPCODE_CLOSE => {
+                // Replies go through the client's queue so they cannot
+                // interleave with a broadcast frame.
+                let reply = if frame.opcode == OPCODE_PING {
+                    encode_frame(OPCODE_PONG, &frame.payload)
+                } else {
+                    encode_frame(OPCODE_CLOSE, &[])
+                };
+                let guard = clients
+                    .lock()
+                    .map_err(|_| "Broadcast client lock poisoned".to_string())?;
+                if let Some(Transport::WebSocket { outbox, .. }) = guard
+                    .iter()
+                    .find(|client| client.id == id)
+                    .map(|client| &client.transport)
+                {
+                    let _ = outbox.try_send(reply);
+                }
+                if frame.opcode == OPCODE_CLOSE {
+                    return Ok(());
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn udp_loop(
+    socket: Arc<UdpSocket>,
+    stop: Arc<AtomicBool>,
+    clients: Arc<Mutex<Vec<Client>>>,
+    policy: Arc<AccessPolicy>,
+    rate_hz: f64,
+) {
+    let mut buffer = [0u8; 2048];
+    let mut limiter = UdpRateLimiter::default();
+    while !stop.load(Ordering::Relaxed) {
+        let (size, address) = match socket.recv_from(&mut buffer) {
+            Ok(value) => value,
+            Err(error)
+                if error.kind() == ErrorKind::WouldBlock || error.kind() == ErrorKind::TimedOut =>
+            {
+                continue;
+            }
+            Err(error) => {
+                set_last_error(Some(error.to_string()));
+                std::thread::sleep(SOCKET_POLL_INTERVAL);
+                continue;
+            }
+        };
+        if !limiter.allow(address.ip(), Instant::now()) {
+            continue;
+        }
+        let Ok(request) = serde_json::from_slice::<ClientRequest>(&buffer[..size]) else {
+            continue;
+        };
+        if !policy.peer_allowed(address.ip(), request.token.as_deref()) {
+            continue;
+        }
+        let Ok(mut guard) = clients.lock() else {
+            continue;
+        };
+        handle_udp_request(&mut guard, address, &request, rate_hz);
+    }
+}
+
+fn handle_udp_request(
+    clients: &mut Vec<Client>,
+    address: SocketAddr,
+    request: &ClientRequest,
+    rate_hz: f64,
+) {
+    let existing = clients.iter().position(|client| {
+        matches!(client.transport, Transport::Udp { address: known, .. } if known == address)
+    });
+
+    match request.kind.as_str() {
+        "subscribe" => {
+            let expires_at = Instant::now() + UDP_SUBSCRIPTION_TTL;
+            match existing {
+                Some(index) => {
+                    let client = &mut clients[index];
+                    client.transport = Transport::Udp {
+                        address,
+                        expires_at,
+                    };
+                    client.subscription.apply(request, rate_hz);
+                }
+                None => {
+                    let udp_clients = clients
+                        .iter()
+                        .filter(|client| matches!(client.transport, Transport::Udp { .. }))
+                        .count();
+                    if udp_clients >= MAX_UDP_CLIENTS {
+                        return;
+                    }
+                    let fields = COMPACT_FIELDS.iter().map(|field| field.to_string());
+                    let mut subscription = Subscription::new(fields.collect(), rate_hz, rate_hz);
+                    subscription.apply(request, rate_hz);
+                    clients.push(Client {
+                        id: NEXT_CLIENT_ID.fetch_add(1, Ordering::Relaxed),
+                        transport: Transport::Udp {
+                            address,
+                            expires_at,
+                        },
+                        subscription,
+                    });
+                }
+            }
+        }
+        "unsubscribe" => {
+            if let Some(index) = existing {
+                clients.remove(index);
+            }
+        }
+        _ => {}
+    }
+}
+
+const OPCODE_TEXT: u8 = 0x1;
+const OPCODE_CLOSE: u8 = 0x8;
+const OPCODE_PING: u8 = 0x9;
+const OPCODE_PONG: u8 = 0xA;
+
+struct Frame {
+    opcode: u8,
+    payload: Vec<u8>,
+}
+
+fn read_handshake(stream: &mut TcpStream) -> Result<String, String> {
+    let mut request = Vec::new();
+    let mut byte = [0u8; 1];
+    while !request.ends_with(b"\r\n\r\n") {
+        if request.len() >= MAX_HANDSHAKE_BYTES {
+            return Err("WebSocket handshake is too large".to_string());
+        }
+        let read = stream.read(&mut byte).map_err(|error| error.to_string())?;
+        if read == 0 {
+            return Err("Connection closed during handshake".to_string());
+        }
+        request.push(byte[0]);
+    }
+    Ok(String::from_utf8_lossy(&request).to_string())
+}
+
+fn websocket_key(request: &str) -> Option<String> {
+    let mut lines = request.lines();
+    let request_line = lines.next()?;
+    if !request_line.starts_with("GET ") {
+        return None;
+    }
+    let mut upgrade = false;
+    let mut key = None;
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let name = name.trim().to_ascii_lowercase();
+        let value = value.trim();
+        if name == "upgrade" && value.eq_ignore_ascii_case("websocket") {
+            upgrade = true;
+        } else if name == "sec-websocket-key" && !value.is_empty() {
+            key = Some(value.to_string());
+        }
+    }
+    if upgrade { key } else { None }
+}
+
+fn websocket_accept_key(key: &str) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(key.as_bytes());
+    hasher.update(WEBSOCKET_GUID.as_bytes());
+    base64::engine::general_purpose::STANDARD.encode(hasher.finalize())
+}
+
+/// Server frames are never masked and never fragmented.
+fn encode_frame(opcode: u8, payload: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(payload.len() + 10);
+    frame.push(0x80 | opcode);
+    match payload.len() {
+        len if len < 126 => frame.push(len as u8),
+        len if len <= u16::MAX as usize => {
+            frame.push(126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(payload);
+    frame
+}
+
+/// Collects client bytes across reads. A read that times out in the middle of
+/// a frame keeps what arrived so far instead of losing the frame boundary.
+#[derive(Default)]
+struct FrameReader {
+    buffer: Vec<u8>,
+}
+
+impl FrameReader {
+    /// Returns the next complete frame, `Ok(None)` once the peer closed the
+    /// connection, or the read error (timeouts included) with the partial
+    /// frame kept for the next call.
+    fn read<R: Read>(&mut self, reader: &mut R) -> std::io::Result<Option<Frame>> {
+        let mut chunk = [0u8; 4096];
+        loop {
+            if let Some((frame, used)) = parse_frame(&self.buffer)? {
+                self.buffer.drain(..used);
+                return Ok(Some(frame));
+            }
+            let read = reader.read(&mut chunk)?;
+            if read == 0 {
+                return Ok(None);
+            }
+            self.buffer.extend_from_slice(&chunk[..read]);
+        }
+    }
+}
+
+/// Parses one client frame from the start of `bytes`; `None` while it is still
+/// incomplete. Continuation frames are returned as-is and ignored by the
+/// caller; subscription requests fit comfortably in a single frame.
+fn parse_frame(bytes: &[u8]) -> std::io::Result<Option<(Frame, usize)>> {
+    let Some(head) = bytes.get(..2) else {
+        return Ok(None);
+    };
+    let opcode = head[0] & 0x0F;
+    let masked = head[1] & 0x80 != 0;
+    let (length, mut offset) = match head[1] & 0x7F {
+        126 => match bytes.get(2..4) {
+            Some(extended) => (u16::from_be_bytes([extended[0], extended[1]]) as u64, 4),
+            None => return Ok(None),
+        },
+        127 => match bytes.get(2..10) {
+            Some(extended) => {
+                let mut value = [0u8; 8];
+                value.copy_from_slice(extended);
+                (u64::from_be_bytes(value), 10)
+            }
+            None => return Ok(None),
+        },
+        length => (length as u64, 2),
+    };
+    if length > MAX_CLIENT_FRAME_BYTES {
+        return Err(std::io::Error::new(
+            ErrorKind::InvalidData,
+            "WebSocket frame is too large",
+        ));
+    }
+
+    let mut mask = [0u8; 4];
+    if masked {
+        let Some(key) = bytes.get(offset..offset + 4) else {
+            return Ok(None);
+        };
+        mask.copy_from_slice(key);
+        offset += 4;
+    }
+    let end = offset + length as usize;
+    let Some(payload) = bytes.get(offset..end) else {
+        return Ok(None);
+    };
+    let mut payload = payload.to_vec();
+    if masked {
+        for (index, byte) in payload.iter_mut().enumerate() {
+            *byte ^= mask[index % 4];
+        }
+    }
+    Ok(Some((Frame { opcode, payload }, end)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn masked_client_frame(opcode: u8, payload: &[u8]) -> Vec<u8> {
+        let mask = [0x12, 0x34, 0x56, 0x78];
+        let mut frame = vec![0x80 | opcode, 0x80 | payload.len() as u8];
+        frame.extend_from_slice(&mask);
+        frame.extend(
+            payload
+                .iter()
+                .enumerate()
+                .map(|(index, byte)| byte ^ mask[index % 4]),
+        );
+        frame
+    }
+
+    #[test]
+    fn computes_rfc6455_accept_key() {
+        assert_eq!(
+            websocket_accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+        let request = "GET / HTTP/1.1\r\nHost: localhost\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\r\n";
+        assert_eq!(
+            websocket_key(request).as_deref(),
+            Some("dGhlIHNhbXBsZSBub25jZQ==")
+        );
+        assert_eq!(websocket_key("GET / HTTP/1.1\r\nHost: x\r\n\r\n"), None);
+    }
+
+    #[test]
+    fn decodes_masked_client_frames_and_encodes_long_server_frames() {
+        let bytes = masked_client_frame(OPCODE_TEXT, br#"{"type":"subscribe"}"#);
+        let (frame, used) = parse_frame(&bytes).unwrap().unwrap();
+        assert_eq!(frame.opcode, OPCODE_TEXT);
+        assert_eq!(frame.payload, br#"{"type":"subscribe"}"#);
+        assert_eq!(used, bytes.len());
+
+        let payload = vec![b'x'; 300];
+        let encoded = encode_frame(OPCODE_TEXT, &payload);
+        assert_eq!(&encoded[..4], &[0x81, 126, 0x01, 0x2C]);
+        let (decoded, _) = parse_frame(&encoded).unwrap().unwrap();
+        assert_eq!(decoded.payload.len(), 300);
+    }
+
+    /// Hands out at most `chunk` bytes per read and times out between chunks,
+    /// like a socket with a read timeout.
+    struct TrickleReader {
+        bytes: Vec<u8>,
+        position: usize,
+        chunk: usize,
+        starved: bool,
+    }
+
+    impl Read for TrickleReader {
+        fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+            self.starved = !self.starved;
+            if self.starved {
+                return Err(std::io::Error::new(ErrorKind::WouldBlock, "no data yet"));
+            }
+            let end = (self.position + self.chunk)
+                .min(self.bytes.len())
+                .min(self.position + buffer.len());
+            let read = end - self.position;
+            buffer[..read].copy_from_slice(&self.bytes[self.position..end]);
+            self.position = end;
+            Ok(read)
+        }
+    }
+
+    #[test]
+    fn partial_frames_survive_read_timeouts() {
+        let mut bytes = masked_client_frame(OPCODE_TEXT, br#"{"type":"subscribe"}"#);
+        bytes.extend(masked_client_frame(OPCODE_PING, b"hi"));
+        let mut source = TrickleReader {
+            bytes,
+            position: 0,
+            chunk: 3,
+            starved: false,
+        };
+        let mut reader = FrameReader::default();
+        let mut frames = Vec::new();
+        loop {
+            match reader.read(&mut source) {
+                Ok(Some(frame)) => frames.push(frame),
+                Ok(None) => break,
+                Err(error) => assert_eq!(error.kind(), ErrorKind::WouldBlock),
+            }
+        }
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].payload, br#"{"type":"subscribe"}"#);
+        assert_eq!(frames[1].opcode, OPCODE_PING);
+        assert_eq!(frames[1].payload, b"hi");
+    }
+
+    #[test]
+    fn only_local_and_overlay_origins_are_accepted() {
+        let policy = AccessPolicy::from_config(&BroadcastConfig::default());
+        let origin_allowed = |request: &str| policy.origin_allowed(request);
+        let request = |origin: &str| format!("GET / HTTP/1.1\r\nOrigin: {origin}\r\n\r\n");
+        assert!(origin_allowed("GET / HTTP/1.1\r\nHost: localhost\r\n\r\n"));
+        assert!(origin_allowed(&request("http://localhost:5173")));
+        assert!(origin_allowed(&request("http://127.0.0.1")));
+        assert!(origin_allowed(&request("http://[::1]:8080")));
+        assert!(origin_allowed(&request("tauri://localhost")));
+        assert!(origin_allowed(&request("https://tauri.localhost")));
+        assert!(!origin_allowed(&request("https://example.com")));
+        assert!(!origin_allowed(&request("http://localhost.example.com")));
+        assert!(!origin_allowed(&request("null")));
+    }
+
+    #[test]
+    fn lagging_websocket_clients_are_dropped() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let _peer = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (socket, _) = listener.accept().unwrap();
+        let (outbox, _queue) = mpsc::sync_channel(1);
+        let sink = ClientSink {
+            clients: Arc::new(Mutex::new(vec![Client {
+                id: 1,
+                transport: Transport::WebSocket { outbox, socket },
+                subscription: Subscription::new(Vec::new(), 10.0, 10.0),
+            }])),
+            udp_socket: None,
+        };
+
+        sink.send(|_, _| Some(b"first".to_vec()));
+        assert_eq!(sink.clients.lock().unwrap().len(), 1);
+        sink.send(|_, _| Some(b"second".to_vec()));
+        assert!(sink.clients.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn subscription_filters_nested_fields_and_caps_rate() {
+        let request: ClientRequest = serde_json::from_str(
+            r#"{"type":"subscribe","fields":["speed_kph","job.cargo","missing"],"maxRateHz":100}"#,
+        )
+        .unwrap();
+        let mut subscription = Subscription::new(Vec::new(), 10.0, 10.0);
+        subscription.apply(&request, 10.0);
+
+        let data = json!({
+            "speed_kph": 82.5,
+            "gear": 9,
+            "job": { "cargo": "Apples", "income": 12500 }
+        });
+        assert_eq!(
+            subscription.filter(&data),
+            json!({ "speed_kph": 82.5, "job": { "cargo": "Apples" } })
+        );
+
+        let start = Instant::now();
+        assert!(subscription.take_due(start));
+        assert!(!subscription.take_due(start + Duration::from_millis(50)));
+        assert!(subscription.take_due(start + Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn lan_clients_need_an_allowed_address_and_the_pairing_token() {
+        let mut config = BroadcastConfig {
+            bind_address: "0.0.0.0".to_string(),
+            allowed_hosts: vec!["192.168.1.40".to_string(), "Tablet.Local".to_string()],
+            ..Default::default()
+        };
+        config.ensure_pairing_token();
+        assert!(!config.pairing_token.is_empty());
+        let policy = AccessPolicy::from_config(&config);
+        let token = config.pairing_token.as_str();
+        let tablet: IpAddr = "192.168.1.40".parse().unwrap();
+        let stranger: IpAddr = "192.168.1.99".parse().unwrap();
+
+        assert!(policy.peer_allowed("127.0.0.1".parse().unwrap(), None));
+        assert!(policy.peer_allowed("::ffff:127.0.0.1".parse().unwrap(), None));
+        assert!(policy.peer_allowed(tablet, Some(token)));
+        assert!(!policy.peer_allowed(tablet, None));
+        assert!(!policy.peer_allowed(tablet, Some("wrong")));
+        assert!(!policy.peer_allowed(stranger, Some(token)));
+
+        let handshake =
+            format!("GET /?token={token} HTTP/1.1\r\nOrigin: http://tablet.local:8080\r\n\r\n");
+        assert_eq!(request_token(&handshake).as_deref(), Some(token));
+        assert!(policy.origin_allowed(&handshake));
+        assert!(!policy.origin_allowed("GET / HTTP/1.1\r\nOrigin: http://evil.example\r\n\r\n"));
+
+        let lan_bound = AccessPolicy::from_config(&BroadcastConfig {
+            bind_address: "192.168.1.10".to_string(),
+            ..Default::default()
+        });
+        assert!(
+            lan_bound.origin_allowed("GET / HTTP/1.1\r\nOrigin: http://192.168.1.10:5173\r\n\r\n")
+        );
+        assert!(!lan_bound.peer_allowed(tablet, Some(token)));
+    }
+
+    #[test]
+    fn udp_requests_are_rate_limited_per_source() {
+        let mut limiter = UdpRateLimiter::default();
+        let source: IpAddr = "192.168.1.40".parse().unwrap();
+        let other: IpAddr = "192.168.1.41".parse().unwrap();
+        let start = Instant::now();
+        for _ in 0..UDP_REQUESTS_PER_WINDOW {
+            assert!(limiter.allow(source, start));
+        }
+        assert!(!limiter.allow(source, start));
+        assert!(limiter.allow(other, start));
+        assert!(limiter.allow(source, start + UDP_RATE_WINDOW));
+    }
+
+    #[test]
+    fn udp_subscribers_are_capped() {
+        let request: ClientRequest = serde_json::from_str(r#"{"type":"subscribe"}"#).unwrap();
+        let mut clients = Vec::new();
+        for port in 0..=MAX_UDP_CLIENTS as u16 {
+            let address = SocketAddr::from(([127, 0, 0, 1], 40_000 + port));
+            handle_udp_request(&mut clients, address, &request, 10.0);
+        }
+        assert_eq!(clients.len(), MAX_UDP_CLIENTS);
+    }
+}
@@ -0,0 +1,28 @@
+use tauri::command;
+
+use crate::features::telemetry::broadcast::{self, BroadcastConfig, BroadcastStatus};
+
+#[command]
+pub fn telemetry_broadcast_get_config() -> Result<BroadcastConfig, String> {
+    broadcast::load_config()
+}
+
+#[command]
+pub fn telemetry_broadcast_set_config(
+    mut config: BroadcastConfig,
+) -> Result<BroadcastStatus, String> {
+    crate::dev_log!(
+        "[telemetry] command: telemetry_broadcast_set_config enabled={} ws_port={} udp={}",
+        config.enabled,
+        config.websocket_port,
+        config.udp_enabled
+    );
+    config.ensure_pairing_token();
+    broadcast::save_config(&config)?;
+    broadcast::restart(&config)
+}
+
+#[command]
+pub fn telemetry_broadcast_get_status() -> Result<BroadcastStatus, String> {
+    Ok(broadcast::status())
+}
@@ -33,4 +33,8 @@ pub struct TelemetryJobEventPayload {
     pub route_time: i64,
     pub job_income: i64,
     pub job_delivered_revenue: i64,
+    /// In-game minute at the time of the event.
+    pub game_time_min: u32,
+    /// Cargo damage as reported by the SDK, 0..1.
+    pub cargo_damage: f64,
 }
@@ -1,3 +1,5 @@
+pub mod broadcast;
+pub mod commands;
 pub mod events;
 pub mod scs_shared_mem;
 pub(crate) mod simnexus_protocol;
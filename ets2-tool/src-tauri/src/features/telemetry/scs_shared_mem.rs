@@ -22,6 +22,7 @@ mod platform {
     };
 
     use crate::events::{EVT_SYSTEM_STATUS, EVT_TELEMETRY_JOB_EVENT};
+    use crate::features::career::service as career_service;
     use crate::features::ets2save::link_service;
     use crate::features::telemetry::events::{SystemStatusPayload, TelemetryJobEventPayload};
     use crate::features::telemetry::simnexus_protocol::{
@@ -111,6 +112,8 @@ mod platform {
                     route_time: payload.job_delivery_time_min as i64,
                     job_income: payload.job_income,
                     job_delivered_revenue: payload.job_income,
+                    game_time_min: payload.game_time_min,
+                    cargo_damage: payload.job_cargo_damage,
                 },
                 payload.job_event_sequence,
             )))
@@ -200,12 +203,37 @@ mod platform {
                             let app_clone = app_for_thread.clone();
                             let pool_clone = pool.clone();
                             tauri::async_runtime::spawn(async move {
-                                let _ = link_service::handle_telemetry_job_event(
+                                let Ok(Some(link)) = link_service::handle_telemetry_job_event(
                                     &app_clone,
                                     &pool_clone,
                                     &event,
                                 )
-                                .await;
+                                .await
+                                else {
+                                    return;
+                                };
+                                if let Err(error) =
+                                    career_service::settle_dispatcher_job(&app_clone, &link, &event)
+                                {
+                                    crate::dev_log!(
+                                        "[dispatcher] settlement for {} failed: {}",
+                                        link.vtc_job_id,
+                                        error
+                                    );
+                                }
+                                if let Err(error) = career_service::continue_dispatcher_chain(
+                                    &app_clone,
+                                    &pool_clone,
+                                    &link,
+                                )
+                                .await
+                                {
+                                    crate::dev_log!(
+                                        "[dispatcher] chain continuation for {} failed: {}",
+                                        link.vtc_job_id,
+                                        error
+                                    );
+                                }
                             });
                         }
                     }
@@ -7,7 +7,9 @@ pub(crate) const SHARED_MEMORY_NAME: &str = "Local\\SimNexusTelemetryV3";
 pub(crate) const LEGACY_SHARED_MEMORY_NAME: &str = "Local\\SimNexusTelemetry";
 pub(crate) const BRIDGE_MAGIC: [u8; 8] = *b"SNXTLM03";
 pub(crate) const BRIDGE_PROTOCOL_VERSION: u32 = 3;
-pub(crate) const PAYLOAD_REVISION: u32 = 3;
+pub(crate) const PAYLOAD_REVISION: u32 = 4;
+/// Oldest payload revision still accepted; revision 4 added world placement.
+pub(crate) const MIN_PAYLOAD_REVISION: u32 = 3;
 pub(crate) const HEARTBEAT_STALE_AFTER_MS: u64 = 2_000;
 
 #[repr(C)]
@@ -78,7 +80,16 @@ pub(crate) struct TelemetryDataV3 {
     pub(crate) destination_company_id: [u8; 64],
     pub(crate) job_market: [u8; 32],
 
-    pub(crate) reserved: [u8; 584],
+    pub(crate) world_x: f64,
+    pub(crate) world_y: f64,
+    pub(crate) world_z: f64,
+    pub(crate) heading: f32,
+    pub(crate) pitch: f32,
+    pub(crate) roll: f32,
+    pub(crate) placement_valid: u8,
+    pub(crate) reserved_placement: [u8; 3],
+
+    pub(crate) reserved: [u8; 544],
 }
 
 impl Default for TelemetryDataV3 {
@@ -114,11 +125,17 @@ pub(crate) fn heartbeat_age_ms(payload: &TelemetryDataV3) -> u64 {
     unix_timestamp_ms().saturating_sub(payload.heartbeat_timestamp_ms)
 }
 
+/// Revision 4+ DLLs publish the truck world placement once the game has sent
+/// the placement channel at least once.
+pub(crate) fn has_world_placement(payload: &TelemetryDataV3) -> bool {
+    payload.payload_revision >= 4 && payload.placement_valid != 0
+}
+
 pub(crate) fn validate_liveness(payload: &TelemetryDataV3) -> Result<u64, String> {
-    if payload.payload_revision != PAYLOAD_REVISION {
+    if !(MIN_PAYLOAD_REVISION..=PAYLOAD_REVISION).contains(&payload.payload_revision) {
         return Err(format!(
-            "Payload revision mismatch: DLL={} App={}",
-            payload.payload_revision, PAYLOAD_REVISION
+            "Payload revision mismatch: DLL={} App={}..={}",
+            payload.payload_revision, MIN_PAYLOAD_REVISION, PAYLOAD_REVISION
         ));
     }
     if payload.plugin_initialized == 0 {
@@ -192,8 +209,9 @@ pub(crate) unsafe fn read_consistent(base: *const u8) -> Result<Option<BridgeSna
 
 pub(crate) fn layout_diagnostic() -> String {
     format!(
-        "protocol={} header_size={} payload_size={} align={} off(heartbeat)={} off(telemetry_timestamp)={} off(frame_id)={} off(job_active)={} off(job_event)={} off(job_id)={}",
+        "protocol={} revision={} header_size={} payload_size={} align={} off(heartbeat)={} off(telemetry_timestamp)={} off(frame_id)={} off(job_active)={} off(job_event)={} off(job_id)={} off(world_x)={}",
         BRIDGE_PROTOCOL_VERSION,
+        PAYLOAD_REVISION,
         size_of::<BridgeHeader>(),
         size_of::<TelemetryDataV3>(),
         std::mem::align_of::<TelemetryDataV3>(),
@@ -202,7 +220,8 @@ pub(crate) fn layout_diagnostic() -> String {
         offset_of!(TelemetryDataV3, frame_id),
         offset_of!(TelemetryDataV3, job_active),
         offset_of!(TelemetryDataV3, job_event),
-        offset_of!(TelemetryDataV3, job_id)
+        offset_of!(TelemetryDataV3, job_id),
+        offset_of!(TelemetryDataV3, world_x)
     )
 }
 
@@ -269,6 +288,10 @@ mod tests {
         assert_eq!(offset_of!(TelemetryDataV3, build_id), 136);
         assert_eq!(offset_of!(TelemetryDataV3, dll_path), 216);
         assert_eq!(offset_of!(TelemetryDataV3, job_id), 728);
+        assert_eq!(offset_of!(TelemetryDataV3, world_x), 1464);
+        assert_eq!(offset_of!(TelemetryDataV3, heading), 1488);
+        assert_eq!(offset_of!(TelemetryDataV3, placement_valid), 1500);
+        assert_eq!(offset_of!(TelemetryDataV3, reserved), 1504);
     }
 
     #[test]
@@ -287,6 +310,26 @@ mod tests {
         assert!(validate_liveness(&snapshot.payload).unwrap() <= 10);
     }
 
+    #[test]
+    fn placement_requires_revision_four() {
+        let mut payload = TelemetryDataV3 {
+            heartbeat_timestamp_ms: unix_timestamp_ms(),
+            payload_revision: MIN_PAYLOAD_REVISION,
+            plugin_initialized: 1,
+            sdk_connected: 1,
+            placement_valid: 1,
+            ..Default::default()
+        };
+        assert!(validate_liveness(&payload).is_ok());
+        assert!(!has_world_placement(&payload));
+
+        payload.payload_revision = PAYLOAD_REVISION;
+        assert!(has_world_placement(&payload));
+
+        payload.payload_revision = PAYLOAD_REVISION + 1;
+        assert!(validate_liveness(&payload).is_err());
+    }
+
     #[test]
     fn rejects_in_progress_write() {
         let mapping = synthetic_mapping(9);
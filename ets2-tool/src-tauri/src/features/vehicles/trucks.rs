@@ -7,7 +7,10 @@ use crate::shared::trace::TraceScope;
 use crate::state::{AppProfileState, DecryptCache, ProfileCache};
 use tauri::command;
 
-fn player_truck_from_content(content: &str, trucks: &[ParsedTruck]) -> Option<ParsedTruck> {
+pub(crate) fn player_truck_from_content(
+    content: &str,
+    trucks: &[ParsedTruck],
+) -> Option<ParsedTruck> {
     let player_id = get_player_id(content)?;
     let (player_truck_id_opt, _) = get_vehicle_ids(content, &player_id);
     let player_truck_id = player_truck_id_opt?;
@@ -162,6 +162,17 @@ fn main() {
                     }
                 }
             }
+            match features::telemetry::broadcast::load_config() {
+                Ok(config) if config.enabled => {
+                    if let Err(error) = features::telemetry::broadcast::restart(&config) {
+                        crate::dev_log!("[telemetry] broadcast start failed: {}", error);
+                    }
+                }
+                Ok(_) => {}
+                Err(error) => {
+                    crate::dev_log!("[telemetry] broadcast config load failed: {}", error);
+                }
+            }
             crate::dev_log!("[app] setup start telemetry bridge + background threads");
             crate::dev_log!("[trace] START telemetry_bridge_startup");
             features::career::service::start_background(handle, runtime);
@@ -192,6 +203,11 @@ fn main() {
             // Read Base and Save Config.cfg
             features::settings::game_config::read_base_config,
             features::settings::game_config::read_save_config,
+            features::settings::config_editor::get_cvar_catalog,
+            features::settings::config_editor::read_config_file,
+            features::settings::config_editor::write_config_values,
+            features::settings::config_editor::list_config_presets,
+            features::settings::config_editor::apply_config_preset,
             // Profile Manager
             features::profile_manager::commands::find_ets2_profiles,
             features::profile_manager::commands::load_profile,
@@ -228,6 +244,8 @@ fn main() {
             features::backup::commands::restore_backup,
             features::health_monitor::commands::get_active_save_health,
             features::health_monitor::commands::apply_save_health_fix,
+            features::health_monitor::commands::preview_save_mod_sanitizer,
+            features::health_monitor::commands::apply_save_mod_sanitizer,
             // Save Analysis+
             features::save_analysis::reader::read_all_save_data,
             // features::save_analysis::reader::read_money,
@@ -247,7 +265,19 @@ fn main() {
             features::mod_profile_manager::commands::compare_mod_preset,
             features::mod_profile_manager::commands::export_mod_preset,
             features::mod_profile_manager::commands::import_mod_preset,
+            features::mod_profile_manager::commands::export_portable_mod_preset,
+            features::mod_profile_manager::commands::preview_portable_mod_preset,
+            features::mod_profile_manager::commands::import_portable_mod_preset,
             features::mod_profile_manager::commands::delete_mod_preset,
+            features::mod_profile_manager::commands::install_local_mod,
+            features::mod_profile_manager::commands::disable_local_mods,
+            features::mod_profile_manager::commands::enable_local_mods,
+            features::mod_profile_manager::commands::disable_unused_local_mods,
+            features::mod_profile_manager::commands::find_duplicate_local_mods,
+            features::mod_profile_manager::commands::remove_duplicate_local_mods,
+            features::mod_profile_manager::commands::get_mod_disk_usage,
+            features::mod_profile_manager::commands::get_save_mod_dependencies,
+            features::mod_profile_manager::commands::check_mod_removal_impact,
             features::mod_profile_manager::commands::select_manual_workshop_directory,
             features::mod_profile_manager::commands::clear_manual_workshop_directory,
             features::mod_profile_manager::commands::fetch_workshop_mod,
@@ -274,6 +304,9 @@ fn main() {
             features::mod_profile_manager::commands::load_steam_workshop_mod_cache,
             features::mod_profile_manager::commands::refresh_workshop_mod_cache,
             features::mod_profile_manager::commands::check_workshop_mod_available,
+            features::mod_profile_manager::commands::load_workshop_metadata_cache,
+            features::mod_profile_manager::commands::refresh_workshop_metadata_cache,
+            features::mod_profile_manager::commands::get_workshop_changes_since_last_launch,
             features::mod_profile_manager::commands::load_sandbox_mod_presets,
             features::mod_profile_manager::commands::check_sandbox_preset_mods,
             features::mod_profile_manager::commands::activate_sandbox_mod_preset,
@@ -307,12 +340,16 @@ fn main() {
             // FEATURE: PROFILE CLONE + Rename
             features::profile_clone::commands::clone_profile_command,
             features::profile_clone::commands::validate_clone_target,
+            features::profile_cloud::commands::convert_profile_storage,
             features::profile_rename::commands::profile_rename,
             features::profile_move_mods::commands::copy_mods_to_profile,
+            features::profile_merge::commands::preview_profile_merge,
+            features::profile_merge::commands::apply_profile_merge,
             features::profile_sharing::commands::get_profile_share_context,
             features::profile_sharing::commands::pick_shared_profile_import_archive,
             features::profile_sharing::commands::pick_shared_profile_export_directory,
             features::profile_sharing::commands::export_shared_profile,
+            features::profile_sharing::commands::get_profile_share_signing_key,
             features::profile_sharing::commands::inspect_shared_profile_archive,
             features::profile_sharing::commands::import_shared_profile,
             // Language Management
@@ -334,6 +371,9 @@ fn main() {
             // Hub (UI navigation)
             features::hub::commands::hub_get_mode,
             features::hub::commands::hub_set_mode,
+            features::telemetry::commands::telemetry_broadcast_get_config,
+            features::telemetry::commands::telemetry_broadcast_set_config,
+            features::telemetry::commands::telemetry_broadcast_get_status,
             // Career (background + logbook)
             features::career::commands::career_get_status,
             features::career::commands::career_get_overview,
@@ -345,6 +385,32 @@ fn main() {
             features::career::commands::career_scan_profile_job_history,
             features::career::commands::career_export_analytics_csv,
             features::career::commands::career_list_trips,
+            features::career::commands::career_list_driving_scores,
+            features::career::commands::career_get_driving_score_thresholds,
+            features::career::commands::career_set_driving_score_thresholds,
+            features::career::commands::career_get_settlement_rules,
+            features::career::commands::career_set_settlement_rules,
+            features::career::commands::career_get_hours_of_service,
+            features::career::commands::career_list_trip_hos_violations,
+            features::career::commands::career_get_bank_overview,
+            features::career::commands::career_take_loan,
+            features::career::commands::career_repay_loan,
+            features::career::commands::career_get_loan_schedule,
+            features::career::commands::career_list_bank_ledger,
+            features::career::commands::career_get_fleet_maintenance,
+            features::career::commands::career_list_workshop_visits,
+            features::career::commands::career_schedule_workshop_visit,
+            features::career::commands::career_complete_workshop_visit,
+            features::career::commands::career_reconcile_fleet_wear,
+            features::career::commands::career_hire_driver,
+            features::career::commands::career_dismiss_employee,
+            features::career::commands::career_list_employee_jobs,
+            features::career::commands::career_get_trip_track,
+            features::career::commands::career_export_trip_track,
+            features::career::commands::career_start_telemetry_recording,
+            features::career::commands::career_stop_telemetry_recording,
+            features::career::commands::career_start_telemetry_replay,
+            features::career::commands::career_stop_telemetry_replay,
             features::career::commands::career_generate_jobs,
             features::career::commands::career_accept_job,
             features::career::commands::career_complete_job,
@@ -362,6 +428,11 @@ fn main() {
             features::career::commands::dispatcher_get_offers,
             features::career::commands::dispatcher_cancel_offer,
             features::career::commands::dispatcher_respond_to_counter,
+            features::career::commands::dispatcher_get_offer_negotiation,
+            features::career::commands::dispatcher_create_chain,
+            features::career::commands::dispatcher_get_chain,
+            features::career::commands::dispatcher_get_chains,
+            features::career::commands::dispatcher_cancel_chain,
             features::career::commands::dispatcher_get_dispatcher_overview,
             features::career::commands::dispatcher_generate_universal_jobs,
             features::career::commands::dispatcher_get_generation_status,
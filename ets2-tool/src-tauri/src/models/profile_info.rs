@@ -1,4 +1,4 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Serialize)]
 pub struct ProfileInfo {
@@ -7,6 +7,24 @@ pub struct ProfileInfo {
     pub avatar: Option<String>,
     pub success: bool,
     pub message: Option<String>,
+    pub storage: ProfileStorage,
+    pub steam_cloud: bool,
+}
+
+/// Where a profile lives. Cloud profiles are synced by Steam and have to be
+/// converted to local ones before the game picks up edits reliably.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfileStorage {
+    Local,
+    LocalBackup,
+    Cloud,
+}
+
+impl ProfileStorage {
+    pub fn is_cloud(self) -> bool {
+        self == Self::Cloud
+    }
 }
 
 #[derive(Debug, Serialize, PartialEq, Clone)]
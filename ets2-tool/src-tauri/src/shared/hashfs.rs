@@ -0,0 +1,666 @@
+//! Reader for SCS HashFS archives (`.scs`), versions 1 and 2.
+//!
+//! Entries are addressed by the CityHash64 of their path (without leading slash). Paths can
+//! only be listed by walking the directory entries from the root, so listing and reading both
+//! go through the hash table.
+use flate2::read::ZlibDecoder;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const HASHFS_MAGIC: u32 = 0x2353_4353; // "SCS#"
+const HASH_METHOD_CITY: u32 = 0x5954_4943; // "CITY"
+const V1_ENTRY_SIZE: usize = 32;
+const V2_ENTRY_SIZE: usize = 16;
+const V1_FLAG_DIRECTORY: u32 = 0x1;
+const V1_FLAG_COMPRESSED: u32 = 0x2;
+const V2_FLAG_DIRECTORY: u16 = 0x1;
+const V2_META_PLAIN: u8 = 0x80;
+const V2_META_DIRECTORY: u8 = 0x81;
+const V2_META_COMPRESSED: u8 = 0x10;
+/// Upper bound for a single decompressed entry; guards against corrupt size fields.
+const MAX_ENTRY_BYTES: u64 = 256 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct EntryLocation {
+    offset: u64,
+    size: u64,
+    compressed_size: u64,
+    compressed: bool,
+    directory: bool,
+}
+
+#[derive(Debug)]
+pub struct HashFsArchive {
+    path: PathBuf,
+    version: u16,
+    entries: HashMap<u64, EntryLocation>,
+    /// Handle opened by `open`, shared by every entry read.
+    file: Mutex<File>,
+}
+
+/// True if the file starts with the HashFS magic.
+pub fn is_hashfs_archive(path: &Path) -> bool {
+    let Ok(mut file) = File::open(path) else {
+        return false;
+    };
+    let mut magic = [0u8; 4];
+    file.read_exact(&mut magic).is_ok() && u32::from_le_bytes(magic) == HASHFS_MAGIC
+}
+
+impl HashFsArchive {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let mut file = File::open(path)
+            .map_err(|error| format!("Failed to open {}: {}", path.display(), error))?;
+        let mut header = [0u8; 12];
+        file.read_exact(&mut header)
+            .map_err(|error| format!("{} is too short for HashFS: {}", path.display(), error))?;
+        if read_u32(&header, 0) != HASHFS_MAGIC {
+            return Err(format!("{} is not a HashFS archive.", path.display()));
+        }
+        let version = read_u16(&header, 4);
+        let salt = read_u16(&header, 6);
+        if read_u32(&header, 8) != HASH_METHOD_CITY {
+            return Err(format!(
+                "{} uses an unsupported HashFS hash method.",
+                path.display()
+            ));
+        }
+        if salt != 0 {
+            return Err(format!(
+                "{} uses a salted HashFS index, which is not supported.",
+                path.display()
+            ));
+        }
+
+        let entries = match version {
+            1 => read_v1_entries(&mut file)?,
+            2 => read_v2_entries(&mut file)?,
+            other => {
+                return Err(format!(
+                    "{} uses HashFS version {}, which is not supported.",
+                    path.display(),
+                    other
+                ));
+            }
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            version,
+            entries,
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Reads a file entry; `Ok(None)` if the archive has no file at that path.
+    pub fn read_file(&self, path: &str) -> Result<Option<Vec<u8>>, String> {
+        match self.entries.get(&hash_path(path)) {
+            Some(location) if !location.directory => self.read_entry(location).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// All file paths in the archive as `/path`, in the case the listing stores them. Entries
+    /// are hashed by that exact path, so callers must pass it back unchanged to `read_file`.
+    /// Archives without directory listings (some mods strip them) only yield what is
+    /// reachable from the root.
+    pub fn list_files(&self) -> Result<Vec<String>, String> {
+        let mut files = Vec::new();
+        let mut pending = vec![String::new()];
+        let mut visited = 0usize;
+        while let Some(directory) = pending.pop() {
+            visited += 1;
+            if visited > self.entries.len() + 1 {
+                return Err(format!(
+                    "{} has a cyclic directory listing.",
+                    self.path.display()
+                ));
+            }
+            let Some(location) = self.entries.get(&hash_path(&directory)) else {
+                continue;
+            };
+            if !location.directory {
+                continue;
+            }
+            let listing = self.read_entry(location)?;
+            let children = if self.version == 1 {
+                parse_v1_listing(&listing)
+            } else {
+                parse_v2_listing(&listing)?
+            };
+            for (name, is_directory) in children {
+                let child = if directory.is_empty() {
+                    name
+                } else {
+                    format!("{}/{}", directory, name)
+                };
+                if is_directory {
+                    pending.push(child);
+                } else {
+                    files.push(format!("/{}", child));
+                }
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+
+    fn read_entry(&self, location: &EntryLocation) -> Result<Vec<u8>, String> {
+        if location.size > MAX_ENTRY_BYTES || location.compressed_size > MAX_ENTRY_BYTES {
+            return Err(format!(
+                "{} contains an entry that is too large.",
+                self.path.display()
+            ));
+        }
+        let mut file = self
+            .file
+            .lock()
+            .map_err(|_| format!("{} reader is poisoned.", self.path.display()))?;
+        file.seek(SeekFrom::Start(location.offset))
+            .map_err(|error| format!("Failed to seek in {}: {}", self.path.display(), error))?;
+        let mut raw = vec![0u8; location.compressed_size as usize];
+        file.read_exact(&mut raw)
+            .map_err(|error| format!("Failed to read {}: {}", self.path.display(), error))?;
+        if !location.compressed {
+            return Ok(raw);
+        }
+        inflate(&raw, location.size).map_err(|error| {
+            format!(
+                "Failed to inflate entry in {}: {}",
+                self.path.display(),
+                error
+            )
+        })
+    }
+}
+
+fn read_v1_entries(file: &mut File) -> Result<HashMap<u64, EntryLocation>, String> {
+    let mut rest = [0u8; 8];
+    file.read_exact(&mut rest)
+        .map_err(|error| error.to_string())?;
+    let entry_count = read_u32(&rest, 0) as usize;
+    let table_offset = read_u32(&rest, 4) as u64;
+    let table = read_at(file, table_offset, entry_count * V1_ENTRY_SIZE)?;
+
+    Ok(table
+        .chunks_exact(V1_ENTRY_SIZE)
+        .map(|entry| {
+            let flags = read_u32(entry, 16);
+            (
+                read_u64(entry, 0),
+                EntryLocation {
+                    offset: read_u64(entry, 8),
+                    size: read_u32(entry, 24) as u64,
+                    compressed_size: read_u32(entry, 28) as u64,
+                    compressed: flags & V1_FLAG_COMPRESSED != 0,
+                    directory: flags & V1_FLAG_DIRECTORY != 0,
+                },
+            )
+        })
+        .collect())
+}
+
+/// Version 2 keeps a zlib-compressed entry table (hash, metadata index/count, flags) and a
+/// zlib-compressed metadata table of `u32` words. Plain and directory metadata hold the
+/// compressed size (24 bit) with a flags byte, the size, an unused word and the offset in
+/// 16-byte blocks.
+fn read_v2_entries(file: &mut File) -> Result<HashMap<u64, EntryLocation>, String> {
+    let mut rest = [0u8; 40];
+    file.read_exact(&mut rest)
+        .map_err(|error| error.to_string())?;
+    let entry_count = read_u32(&rest, 0) as usize;
+    let entry_table_size = read_u32(&rest, 4) as usize;
+    let metadata_count = read_u32(&rest, 8) as usize;
+    let metadata_table_size = read_u32(&rest, 12) as usize;
+    let entry_table_offset = read_u64(&rest, 16);
+    let metadata_table_offset = read_u64(&rest, 24);
+
+    let entry_table = inflate(
+        &read_at(file, entry_table_offset, entry_table_size)?,
+        (entry_count * V2_ENTRY_SIZE) as u64,
+    )?;
+    let metadata = inflate(
+        &read_at(file, metadata_table_offset, metadata_table_size)?,
+        (metadata_count * 4) as u64,
+    )?;
+    let words = metadata
+        .chunks_exact(4)
+        .map(|word| u32::from_le_bytes([word[0], word[1], word[2], word[3]]))
+        .collect::<Vec<_>>();
+
+    let mut entries = HashMap::with_capacity(entry_count);
+    for entry in entry_table.chunks_exact(V2_ENTRY_SIZE) {
+        let hash = read_u64(entry, 0);
+        let metadata_index = read_u32(entry, 8) as usize;
+        let metadata_entries = read_u16(entry, 12) as usize;
+        let directory = read_u16(entry, 14) & V2_FLAG_DIRECTORY != 0;
+
+        for position in metadata_index..metadata_index + metadata_entries {
+            let Some(descriptor) = words.get(position) else {
+                break;
+            };
+            let meta_type = (descriptor >> 24) as u8;
+            if meta_type != V2_META_PLAIN && meta_type != V2_META_DIRECTORY {
+                // Textures (image, mip and sample metadata) are not needed here.
+                continue;
+            }
+            let start = (descriptor & 0x00ff_ffff) as usize;
+            let Some(fields) = words.get(start..start + 4) else {
+                break;
+            };
+            entries.insert(
+                hash,
+                EntryLocation {
+                    offset: fields[3] as u64 * 16,
+                    size: (fields[1] & 0x0fff_ffff) as u64,
+                    compressed_size: (fields[0] & 0x00ff_ffff) as u64,
+                    compressed: ((fields[0] >> 24) as u8) & V2_META_COMPRESSED != 0,
+                    directory: directory || meta_type == V2_META_DIRECTORY,
+                },
+            );
+            break;
+        }
+    }
+    Ok(entries)
+}
+
+/// Version 1 listings are text, one name per line; subdirectories start with `*`.
+fn parse_v1_listing(listing: &[u8]) -> Vec<(String, bool)> {
+    String::from_utf8_lossy(listing)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| match line.strip_prefix('*') {
+            Some(directory) => (directory.to_string(), true),
+            None => (line.to_string(), false),
+        })
+        .collect()
+}
+
+/// Version 2 listings are a `u32` count, one length byte per name, then the names;
+/// subdirectories start with `/`.
+fn parse_v2_listing(listing: &[u8]) -> Result<Vec<(String, bool)>, String> {
+    if listing.len() < 4 {
+        return Err("Truncated HashFS directory listing.".to_string());
+    }
+    let count = read_u32(listing, 0) as usize;
+    let lengths = listing
+        .get(4..4 + count)
+        .ok_or_else(|| "Truncated HashFS directory listing.".to_string())?;
+    let mut position = 4 + count;
+    let mut children = Vec::with_capacity(count);
+    for length in lengths {
+        let name = listing
+            .get(position..position + *length as usize)
+            .ok_or_else(|| "Truncated HashFS directory listing.".to_string())?;
+        position += *length as usize;
+        let name = String::from_utf8_lossy(name).to_string();
+        children.push(match name.strip_prefix('/') {
+            Some(directory) => (directory.to_string(), true),
+            None => (name, false),
+        });
+    }
+    Ok(children)
+}
+
+fn read_at(file: &mut File, offset: u64, length: usize) -> Result<Vec<u8>, String> {
+    if length as u64 > MAX_ENTRY_BYTES {
+        return Err("HashFS table is too large.".to_string());
+    }
+    file.seek(SeekFrom::Start(offset))
+        .map_err(|error| error.to_string())?;
+    let mut buffer = vec![0u8; length];
+    file.read_exact(&mut buffer)
+        .map_err(|error| format!("Truncated HashFS archive: {}", error))?;
+    Ok(buffer)
+}
+
+fn inflate(compressed: &[u8], expected_size: u64) -> Result<Vec<u8>, String> {
+    let mut output = Vec::with_capacity(expected_size.min(MAX_ENTRY_BYTES) as usize);
+    ZlibDecoder::new(compressed)
+        .take(MAX_ENTRY_BYTES)
+        .read_to_end(&mut output)
+        .map_err(|error| error.to_string())?;
+    Ok(output)
+}
+
+/// Hash key of an archive path: CityHash64 of the path without leading slash.
+pub fn hash_path(path: &str) -> u64 {
+    let normalized = path.replace('\\', "/");
+    city_hash64(normalized.trim_start_matches('/').as_bytes())
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([
+        bytes[offset],
+        bytes[offset + 1],
+        bytes[offset + 2],
+        bytes[offset + 3],
+    ])
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> u64 {
+    let mut word = [0u8; 8];
+    word.copy_from_slice(&bytes[offset..offset + 8]);
+    u64::from_le_bytes(word)
+}
+
+// CityHash64 (v1.1), as used by HashFS for its path index.
+const K0: u64 = 0xc3a5_c85c_97cb_3127;
+const K1: u64 = 0xb492_b66f_be98_f273;
+const K2: u64 = 0x9ae1_6a3b_2f90_404f;
+
+fn fetch64(bytes: &[u8], offset: usize) -> u64 {
+    read_u64(bytes, offset)
+}
+
+fn fetch32(bytes: &[u8], offset: usize) -> u64 {
+    read_u32(bytes, offset) as u64
+}
+
+fn rotate(value: u64, shift: u32) -> u64 {
+    value.rotate_right(shift)
+}
+
+fn shift_mix(value: u64) -> u64 {
+    value ^ (value >> 47)
+}
+
+fn hash_len16_mul(u: u64, v: u64, mul: u64) -> u64 {
+    let mut a = (u ^ v).wrapping_mul(mul);
+    a ^= a >> 47;
+    let mut b = (v ^ a).wrapping_mul(mul);
+    b ^= b >> 47;
+    b.wrapping_mul(mul)
+}
+
+fn hash_len16(u: u64, v: u64) -> u64 {
+    hash_len16_mul(u, v, 0x9ddf_ea08_eb38_2d69)
+}
+
+fn hash_len0_to16(s: &[u8]) -> u64 {
+    let len = s.len();
+    if len >= 8 {
+        let mul = K2.wrapping_add(len as u64 * 2);
+        let a = fetch64(s, 0).wrapping_add(K2);
+        let b = fetch64(s, len - 8);
+        let c = rotate(b, 37).wrapping_mul(mul).wrapping_add(a);
+        let d = rotate(a, 25).wrapping_add(b).wrapping_mul(mul);
+        return hash_len16_mul(c, d, mul);
+    }
+    if len >= 4 {
+        let mul = K2.wrapping_add(len as u64 * 2);
+        let a = fetch32(s, 0);
+        return hash_len16_mul((len as u64).wrapping_add(a << 3), fetch32(s, len - 4), mul);
+    }
+    if len > 0 {
+        let a = s[0] as u32;
+        let b = s[len >> 1] as u32;
+        let c = s[len - 1] as u32;
+        let y = a.wrapping_add(b << 8);
+        let z = (len as u32).wrapping_add(c << 2);
+        return shift_mix((y as u64).wrapping_mul(K2) ^ (z as u64).wrapping_mul(K0))
+            .wrapping_mul(K2);
+    }
+    K2
+}
+
+fn hash_len17_to32(s: &[u8]) -> u64 {
+    let len = s.len();
+    let mul = K2.wrapping_add(len as u64 * 2);
+    let a = fetch64(s, 0).wrapping_mul(K1);
+    let b = fetch64(s, 8);
+    let c = fetch64(s, len - 8).wrapping_mul(mul);
+    let d = fetch64(s, len - 16).wrapping_mul(K2);
+    hash_len16_mul(
+        rotate(a.wrapping_add(b), 43)
+            .wrapping_add(rotate(c, 30))
+            .wrapping_add(d),
+        a.wrapping_add(rotate(b.wrapping_add(K2), 18))
+            .wrapping_add(c),
+        mul,
+    )
+}
+
+fn hash_len33_to64(s: &[u8]) -> u64 {
+    let len = s.len();
+    let mul = K2.wrapping_add(len as u64 * 2);
+    let a = fetch64(s, 0).wrapping_mul(K2);
+    let b = fetch64(s, 8);
+    let c = fetch64(s, len - 24);
+    let d = fetch64(s, len - 32);
+    let e = fetch64(s, 16).wrapping_mul(K2);
+    let f = fetch64(s, 24).wrapping_mul(9);
+    let g = fetch64(s, len - 8);
+    let h = fetch64(s, len - 16).wrapping_mul(mul);
+    let u =
+        rotate(a.wrapping_add(g), 43).wrapping_add(rotate(b, 30).wrapping_add(c).wrapping_mul(9));
+    let v = (a.wrapping_add(g) ^ d).wrapping_add(f).wrapping_add(1);
+    let w = u
+        .wrapping_add(v)
+        .wrapping_mul(mul)
+        .swap_bytes()
+        .wrapping_add(h);
+    let x = rotate(e.wrapping_add(f), 42).wrapping_add(c);
+    let y = v
+        .wrapping_add(w)
+        .wrapping_mul(mul)
+        .swap_bytes()
+        .wrapping_add(g)
+        .wrapping_mul(mul);
+    let z = e.wrapping_add(f).wrapping_add(c);
+    let a = x
+        .wrapping_add(z)
+        .wrapping_mul(mul)
+        .wrapping_add(y)
+        .swap_bytes()
+        .wrapping_add(b);
+    let b = shift_mix(
+        z.wrapping_add(a)
+            .wrapping_mul(mul)
+            .wrapping_add(d)
+            .wrapping_add(h),
+    )
+    .wrapping_mul(mul);
+    b.wrapping_add(x)
+}
+
+fn weak_hash_len32_with_seeds(s: &[u8], offset: usize, a: u64, b: u64) -> (u64, u64) {
+    let w = fetch64(s, offset);
+    let x = fetch64(s, offset + 8);
+    let y = fetch64(s, offset + 16);
+    let z = fetch64(s, offset + 24);
+    let mut a = a.wrapping_add(w);
+    let mut b = rotate(b.wrapping_add(a).wrapping_add(z), 21);
+    let c = a;
+    a = a.wrapping_add(x).wrapping_add(y);
+    b = b.wrapping_add(rotate(a, 44));
+    (a.wrapping_add(z), b.wrapping_add(c))
+}
+
+pub fn city_hash64(s: &[u8]) -> u64 {
+    let len = s.len();
+    if len <= 16 {
+        return hash_len0_to16(s);
+    }
+    if len <= 32 {
+        return hash_len17_to32(s);
+    }
+    if len <= 64 {
+        return hash_len33_to64(s);
+    }
+
+    let mut x = fetch64(s, len - 40);
+    let mut y = fetch64(s, len - 16).wrapping_add(fetch64(s, len - 56));
+    let mut z = hash_len16(
+        fetch64(s, len - 48).wrapping_add(len as u64),
+        fetch64(s, len - 24),
+    );
+    let mut v = weak_hash_len32_with_seeds(s, len - 64, len as u64, z);
+    let mut w = weak_hash_len32_with_seeds(s, len - 32, y.wrapping_add(K1), x);
+    x = x.wrapping_mul(K1).wrapping_add(fetch64(s, 0));
+
+    let mut offset = 0usize;
+    let mut remaining = (len - 1) & !63;
+    loop {
+        x = rotate(
+            x.wrapping_add(y)
+                .wrapping_add(v.0)
+                .wrapping_add(fetch64(s, offset + 8)),
+            37,
+        )
+        .wrapping_mul(K1);
+        y = rotate(
+            y.wrapping_add(v.1).wrapping_add(fetch64(s, offset + 48)),
+            42,
+        )
+        .wrapping_mul(K1);
+        x ^= w.1;
+        y = y.wrapping_add(v.0).wrapping_add(fetch64(s, offset + 40));
+        z = rotate(z.wrapping_add(w.0), 33).wrapping_mul(K1);
+        v = weak_hash_len32_with_seeds(s, offset, v.1.wrapping_mul(K1), x.wrapping_add(w.0));
+        w = weak_hash_len32_with_seeds(
+            s,
+            offset + 32,
+            z.wrapping_add(w.1),
+            y.wrapping_add(fetch64(s, offset + 16)),
+        );
+        std::mem::swap(&mut z, &mut x);
+        offset += 64;
+        remaining -= 64;
+        if remaining == 0 {
+            break;
+        }
+    }
+
+    hash_len16(
+        hash_len16(v.0, w.0)
+            .wrapping_add(shift_mix(y).wrapping_mul(K1))
+            .wrapping_add(z),
+        hash_len16(v.1, w.1).wrapping_add(x),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::Compression;
+    use flate2::write::ZlibEncoder;
+    use std::io::Write;
+
+    fn deflate(bytes: &[u8]) -> Vec<u8> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(bytes).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    /// Builds a version 1 archive from `(path, content, is_directory)` entries.
+    fn build_v1(entries: &[(&str, Vec<u8>, bool)]) -> Vec<u8> {
+        let mut body = Vec::new();
+        let mut table = Vec::new();
+        for (path, content, directory) in entries {
+            let stored = deflate(content);
+            let offset = 20 + body.len();
+            body.extend_from_slice(&stored);
+            table.extend_from_slice(&hash_path(path).to_le_bytes());
+            table.extend_from_slice(&(offset as u64).to_le_bytes());
+            let flags = V1_FLAG_COMPRESSED | if *directory { V1_FLAG_DIRECTORY } else { 0 };
+            table.extend_from_slice(&flags.to_le_bytes());
+            table.extend_from_slice(&0u32.to_le_bytes());
+            table.extend_from_slice(&(content.len() as u32).to_le_bytes());
+            table.extend_from_slice(&(stored.len() as u32).to_le_bytes());
+        }
+        let mut archive = Vec::new();
+        archive.extend_from_slice(&HASHFS_MAGIC.to_le_bytes());
+        archive.extend_from_slice(&1u16.to_le_bytes());
+        archive.extend_from_slice(&0u16.to_le_bytes());
+        archive.extend_from_slice(&HASH_METHOD_CITY.to_le_bytes());
+        archive.extend_from_slice(&(entries.len() as u32).to_le_bytes());
+        archive.extend_from_slice(&((20 + body.len()) as u32).to_le_bytes());
+        archive.extend_from_slice(&body);
+        archive.extend_from_slice(&table);
+        archive
+    }
+
+    #[test]
+    fn empty_path_hashes_to_the_root_seed() {
+        assert_eq!(city_hash64(b""), K2);
+        assert_eq!(hash_path("/"), hash_path(""));
+        // Every length branch must be deterministic and distinct.
+        let long = "def/vehicle/truck/scania.s_2016/accessory/cabin_addon/very_long_name.sii";
+        assert!(long.len() > 64);
+        let hashes = ["abc", "def/city", "def/vehicle/truck/", &long[..40], long]
+            .iter()
+            .map(|value| city_hash64(value.as_bytes()))
+            .collect::<std::collections::HashSet<_>>();
+        assert_eq!(hashes.len(), 5);
+    }
+
+    #[test]
+    fn lists_and_reads_a_v1_archive() {
+        let archive = build_v1(&[
+            ("", b"*def\nmanifest.sii\n".to_vec(), true),
+            ("def", b"cargo.sii\n".to_vec(), true),
+            ("def/cargo.sii", b"SiiNunit {}".to_vec(), false),
+            ("manifest.sii", b"mod_package: .package {}".to_vec(), false),
+        ]);
+        let path = std::env::temp_dir().join(format!("hashfs-{}.scs", uuid::Uuid::new_v4()));
+        std::fs::write(&path, archive).unwrap();
+
+        assert!(is_hashfs_archive(&path));
+        let reader = HashFsArchive::open(&path).unwrap();
+        assert_eq!(
+            reader.list_files().unwrap(),
+            vec!["/def/cargo.sii".to_string(), "/manifest.sii".to_string()]
+        );
+        assert_eq!(
+            reader.read_file("def/cargo.sii").unwrap().as_deref(),
+            Some(&b"SiiNunit {}"[..])
+        );
+        assert!(reader.read_file("def/missing.sii").unwrap().is_none());
+
+        drop(reader);
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn keeps_the_listed_case_so_entries_stay_readable() {
+        let archive = build_v1(&[
+            ("", b"*def\n".to_vec(), true),
+            ("def", b"Cargo.SII\n".to_vec(), true),
+            ("def/Cargo.SII", b"SiiNunit {}".to_vec(), false),
+        ]);
+        let path = std::env::temp_dir().join(format!("hashfs-{}.scs", uuid::Uuid::new_v4()));
+        std::fs::write(&path, archive).unwrap();
+
+        let reader = HashFsArchive::open(&path).unwrap();
+        let files = reader.list_files().unwrap();
+        assert_eq!(files, vec!["/def/Cargo.SII".to_string()]);
+        for file in &files {
+            assert!(reader.read_file(file).unwrap().is_some());
+        }
+        assert!(reader.read_file("def/cargo.sii").unwrap().is_none());
+
+        drop(reader);
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn parses_v2_directory_listing() {
+        let mut listing = 2u32.to_le_bytes().to_vec();
+        listing.extend_from_slice(&[4, 9]);
+        listing.extend_from_slice(b"/defcargo.sii");
+        assert_eq!(
+            parse_v2_listing(&listing).unwrap(),
+            vec![("def".to_string(), true), ("cargo.sii".to_string(), false)]
+        );
+        assert!(parse_v2_listing(&listing[..6]).is_err());
+    }
+}
@@ -4,6 +4,7 @@ pub mod ets2data;
 pub mod extract;
 pub mod extract_save_name;
 pub mod game_loader;
+pub mod hashfs;
 pub mod hex_float;
 pub mod logs;
 pub mod models;
@@ -36,6 +36,11 @@ pub fn mod_directory_path(game: &str) -> Option<PathBuf> {
     get_base_path(game).map(|base_path| base_path.join("mod"))
 }
 
+/// Ablage für deaktivierte Mods (wird vom Spiel nicht geladen)
+pub fn disabled_mod_directory_path(game: &str) -> Option<PathBuf> {
+    get_base_path(game).map(|base_path| base_path.join("mod_disabled"))
+}
+
 pub fn autosave_path(profile_path: &str) -> PathBuf {
     Path::new(profile_path)
         .join("save")
@@ -290,6 +290,10 @@ impl Default for HubState {
 pub struct CareerRuntime {
     pub stop_all: AtomicBool,
     pub telemetry_stop: AtomicBool,
+    /// Stops a running replay without touching the live reader's `telemetry_stop`.
+    pub replay_stop: AtomicBool,
+    /// Set while a replay owns the telemetry pipeline; the live reader stays parked until it ends.
+    pub replay_running: AtomicBool,
     pub telemetry_running: AtomicBool,
     pub trip_start_blocked: AtomicBool,
     pub ets2_running: AtomicBool,
@@ -301,6 +305,11 @@ pub struct CareerRuntime {
     pub last_telemetry: Mutex<Option<LiveTelemetryState>>,
     pub active_job: Mutex<Option<ActiveJobState>>,
     pub active_trip: Mutex<Option<ActiveTripState>>,
+    pub trip_track: Mutex<Option<TrackDownsampler>>,
+    /// Loaded from `tachograph_state` on the first sample of a session.
+    pub tachograph: Mutex<Option<TachographState>>,
+    /// Last in-game day seen this session; the bank calendar persists its own.
+    pub last_game_day: Mutex<Option<i64>>,
     pub db_path: Mutex<Option<PathBuf>>,
 }
 
@@ -309,6 +318,8 @@ impl Default for CareerRuntime {
         Self {
             stop_all: AtomicBool::new(false),
             telemetry_stop: AtomicBool::new(false),
+            replay_stop: AtomicBool::new(false),
+            replay_running: AtomicBool::new(false),
             telemetry_running: AtomicBool::new(false),
             trip_start_blocked: AtomicBool::new(false),
             ets2_running: AtomicBool::new(false),
@@ -320,11 +331,68 @@ impl Default for CareerRuntime {
             last_telemetry: Mutex::new(None),
             active_job: Mutex::new(None),
             active_trip: Mutex::new(None),
+            trip_track: Mutex::new(None),
+            tachograph: Mutex::new(None),
+            last_game_day: Mutex::new(None),
             db_path: Mutex::new(None),
         }
     }
 }
 
+/// One recorded route position of a logbook trip, in world metres.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackPoint {
+    pub seq: i64,
+    pub segment: i64,
+    /// Telemetry timestamp of the sample, so replays keep their original timing.
+    pub recorded_at_ms: i64,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub heading_deg: f64,
+    pub speed_kph: f64,
+}
+
+/// Decides which telemetry positions of a single trip end up in the database.
+/// The downsampling itself lives in `career::trip_track`.
+#[derive(Debug, Clone)]
+pub struct TrackDownsampler {
+    pub trip_id: i64,
+    pub next_seq: i64,
+    pub segment: i64,
+    pub last_kept: Option<TrackPoint>,
+    /// Latest position and whether it was stored.
+    pub last_seen: Option<(TrackPoint, bool)>,
+}
+
+/// Hours-of-service counters of the career driver, persisted across sessions.
+/// The rules live in `career::tachograph`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TachographState {
+    pub last_game_time_min: Option<u32>,
+    pub stationary_min: u32,
+    pub continuous_drive_min: u32,
+    pub daily_drive_min: u32,
+    pub weekly_drive_min: u32,
+    pub duty_started_game_min: Option<u32>,
+    pub day_extended: bool,
+    pub extended_days_used: u8,
+    pub reduced_rests_used: u8,
+    pub continuous_flagged: bool,
+    pub daily_flagged: bool,
+    pub weekly_flagged: bool,
+    pub duty_flagged: bool,
+    /// Whether the counters currently run on the sample clock because the
+    /// telemetry source reports no game time.
+    pub clock_from_samples: bool,
+    /// Game minutes derived from unpaused wall time between samples.
+    pub sample_clock_min: f64,
+    #[serde(skip)]
+    pub last_sample_ms: Option<i64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct LiveTelemetryState {
@@ -388,6 +456,56 @@ pub struct ActiveTripState {
     pub fuel_used_liters: f64,
     pub last_fuel_liters: f32,
     pub last_speed_kph: f32,
+    pub driving: DrivingBehaviourState,
+    pub score_thresholds: DrivingScoreThresholds,
+    /// Tachograph violations recorded while this trip was active.
+    pub hos_violations: i64,
+    /// Highest cargo damage telemetry reported during the trip, in percent.
+    pub cargo_damage_percent: f64,
+}
+
+/// Limits used to score one logbook trip. They are loaded when the trip
+/// starts, so changing them never rescores a trip halfway through.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DrivingScoreThresholds {
+    pub harsh_acceleration_kph_per_s: f64,
+    pub harsh_braking_kph_per_s: f64,
+    pub over_rev_rpm: f64,
+    pub lugging_rpm: f64,
+    pub idle_speed_kph: f64,
+    /// Idling per stop that is tolerated before it counts against the trip.
+    pub idle_grace_seconds: f64,
+    pub target_fuel_liters_per_100km: f64,
+}
+
+impl Default for DrivingScoreThresholds {
+    fn default() -> Self {
+        Self {
+            harsh_acceleration_kph_per_s: 8.0,
+            harsh_braking_kph_per_s: 12.0,
+            over_rev_rpm: 2_000.0,
+            lugging_rpm: 900.0,
+            idle_speed_kph: 1.0,
+            idle_grace_seconds: 120.0,
+            target_fuel_liters_per_100km: 38.0,
+        }
+    }
+}
+
+/// Driving behaviour counters collected over one logbook trip.
+#[derive(Debug, Clone, Default)]
+pub struct DrivingBehaviourState {
+    pub driving_seconds: f64,
+    pub idle_seconds: f64,
+    pub idle_stop_seconds: f64,
+    pub over_rev_seconds: f64,
+    pub lugging_seconds: f64,
+    pub harsh_acceleration_events: i64,
+    pub harsh_braking_events: i64,
+    pub in_harsh_acceleration: bool,
+    pub in_harsh_braking: bool,
+    pub planned_distance_km: Option<f64>,
 }
 
 pub struct CareerState {